@@ -0,0 +1,60 @@
+use anyhow::{bail, Result};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Keccak-256 digest, as used for Ethereum addresses and signatures
+pub(crate) fn keccak(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut output = [0u8; 32];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// EIP-55 mixed-case encoding of an address
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lower: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = keccak(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// Checks a wallet address typed by a user and returns it lowercased, the form the APIs and
+/// local data use. Mixed-case input must carry a valid EIP-55 checksum, so a mistyped
+/// character is caught here instead of surfacing as a wallet with no trades
+pub fn validate_address(input: &str) -> Result<String> {
+    let input = input.trim();
+    let Some(hex) = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) else {
+        bail!("Invalid wallet address {}: must start with 0x", input);
+    };
+    if hex.len() != 40 {
+        bail!("Invalid wallet address {}: expected 40 hex digits after 0x, got {}", input, hex.len());
+    }
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        bail!("Invalid wallet address {}: '{}' is not a hex digit", input, c);
+    }
+
+    let mut bytes = [0u8; 20];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+    }
+
+    let mixed_case = hex.chars().any(|c| c.is_ascii_uppercase()) && hex.chars().any(|c| c.is_ascii_lowercase());
+    if mixed_case {
+        let expected = checksum_address(&bytes);
+        if expected[2..] != *hex {
+            bail!(
+                "Invalid wallet address {}: checksum doesn't match (expected {}), so a character is probably mistyped",
+                input, expected
+            );
+        }
+    }
+
+    Ok(format!("0x{}", hex.to_ascii_lowercase()))
+}
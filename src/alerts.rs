@@ -0,0 +1,399 @@
+use crate::config::AlertsConfig;
+use crate::copy_trading::CopySignal;
+use crate::digest::Digest;
+use crate::models::{
+    ArbitrageKind, ArbitrageOpportunity, EventArbitrageOpportunity, LinkArbitrageOpportunity, Market, SuspicionReport,
+    Trade, WalletPerformance,
+};
+use crate::monitor::{MonitorReading, MonitorThresholds};
+use crate::priority::BookCheck;
+use crate::watchlist::TradeContext;
+use anyhow::Result;
+use serde_json::json;
+
+/// Where alerts are delivered
+#[derive(Debug, Clone)]
+pub enum AlertDestination {
+    /// Generic webhook receiving the alert as JSON
+    Webhook(String),
+    /// Discord channel webhook URL
+    Discord(String),
+    /// Telegram bot posting to a chat
+    Telegram { bot_token: String, chat_id: String },
+}
+
+impl AlertDestination {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertDestination::Webhook(_) => "webhook",
+            AlertDestination::Discord(_) => "discord",
+            AlertDestination::Telegram { .. } => "telegram",
+        }
+    }
+
+    /// Lower is louder
+    fn loudness_rank(&self) -> u8 {
+        match self {
+            AlertDestination::Telegram { .. } => 0,
+            AlertDestination::Discord(_) => 1,
+            AlertDestination::Webhook(_) => 2,
+        }
+    }
+}
+
+/// A notification about something the scanners found
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub title: String,
+    pub message: String,
+    /// Structured data for generic webhooks
+    pub data: serde_json::Value,
+}
+
+impl Alert {
+    /// Builds an alert for an arbitrage opportunity
+    pub fn arbitrage(opp: &ArbitrageOpportunity) -> Self {
+        Self {
+            title: format!("Arbitrage ({}): {:.2}% profit", opp.kind.label().to_lowercase(), opp.profit_percent),
+            message: format!(
+                "{}\n{} ${:.4} + {} ${:.4} = ${:.4}\nNet profit ${:.4} per $1 (gross ${:.4}) | Liquidity ${:.0}",
+                opp.question, opp.outcomes[0], opp.yes_price, opp.outcomes[1], opp.no_price, opp.total_cost,
+                opp.profit_per_dollar, opp.gross_profit_per_dollar, opp.liquidity
+            ),
+            data: json!({
+                "type": "arbitrage",
+                "kind": opp.kind.label(),
+                "question": opp.question,
+                "condition_id": opp.condition_id,
+                "outcomes": opp.outcomes,
+                "yes_price": opp.yes_price,
+                "no_price": opp.no_price,
+                "total_cost": opp.total_cost,
+                "gross_profit_per_dollar": opp.gross_profit_per_dollar,
+                "fees_per_dollar": opp.fees_per_dollar,
+                "slippage_per_dollar": opp.slippage_per_dollar,
+                "profit_per_dollar": opp.profit_per_dollar,
+                "profit_percent": opp.profit_percent,
+                "expected_profit": opp.expected_profit,
+                "volume": opp.volume,
+                "liquidity": opp.liquidity,
+            }),
+        }
+    }
+
+    /// Builds a high-confidence alert for an arbitrage that cleared the volume and liquidity
+    /// floors and was confirmed against both order books
+    pub fn priority_arbitrage(opp: &ArbitrageOpportunity, check: &BookCheck) -> Self {
+        let mut alert = Self::arbitrage(opp);
+        alert.title = format!("🚨 HIGH-CONFIDENCE arbitrage ({}): {:.2}% profit", opp.kind.label().to_lowercase(), opp.profit_percent);
+        alert.message = format!(
+            "{}\nBook: {} ${:.4} + {} ${:.4} for {:.0} shares | Net ${:.2} after fees\n24h volume ${:.0} | Liquidity ${:.0}",
+            alert.message, opp.outcomes[0], check.yes_price, opp.outcomes[1], check.no_price, check.shares, check.net_profit, opp.volume_24h, opp.liquidity
+        );
+        alert.data["type"] = json!("priority_arbitrage");
+        alert.data["volume_24h"] = json!(opp.volume_24h);
+        alert.data["book_check"] = json!(check);
+        alert
+    }
+
+    /// Builds an alert for a negative-risk event arbitrage
+    pub fn event_arbitrage(opp: &EventArbitrageOpportunity) -> Self {
+        Self {
+            title: format!("Event arbitrage: {:.2}% profit", opp.profit_percent),
+            message: format!(
+                "{}\n{} across {} markets: cost ${:.4} for ${:.2} payout\nThinnest leg liquidity ${:.0}",
+                opp.title, opp.kind.label(), opp.legs, opp.total_cost, opp.payout, opp.min_liquidity
+            ),
+            data: json!({
+                "type": "event_arbitrage",
+                "event_id": opp.event_id,
+                "title": opp.title,
+                "kind": opp.kind.label(),
+                "legs": opp.legs,
+                "total_cost": opp.total_cost,
+                "payout": opp.payout,
+                "profit_per_dollar": opp.profit_per_dollar,
+                "profit_percent": opp.profit_percent,
+                "expected_profit": opp.expected_profit,
+                "min_liquidity": opp.min_liquidity,
+            }),
+        }
+    }
+
+    /// Builds an alert for a linked pair priced against its implication
+    pub fn link_arbitrage(opp: &LinkArbitrageOpportunity) -> Self {
+        let pair = match &opp.label {
+            Some(label) => label.clone(),
+            None => format!("{} => {}", opp.implying_question, opp.implied_question),
+        };
+        Self {
+            title: format!("Linked-market arbitrage: {:.2}% profit", opp.profit_percent),
+            message: format!(
+                "{}
+Implying YES ${:.4} above implied YES ${:.4}
+Buy implied YES + implying NO for ${:.4} | Thinner leg liquidity ${:.0}",
+                pair, opp.implying_yes, opp.implied_yes, opp.total_cost, opp.min_liquidity
+            ),
+            data: json!({
+                "type": "link_arbitrage",
+                "label": opp.label,
+                "implying_condition_id": opp.implying_condition_id,
+                "implying_question": opp.implying_question,
+                "implied_condition_id": opp.implied_condition_id,
+                "implied_question": opp.implied_question,
+                "implying_yes": opp.implying_yes,
+                "implied_yes": opp.implied_yes,
+                "total_cost": opp.total_cost,
+                "profit_per_dollar": opp.profit_per_dollar,
+                "profit_percent": opp.profit_percent,
+                "expected_profit": opp.expected_profit,
+                "min_liquidity": opp.min_liquidity,
+            }),
+        }
+    }
+
+    /// Builds an alert for a monitored market whose YES + NO total crossed the arbitrage threshold
+    pub fn monitored_market(
+        market: &Market,
+        kind: ArbitrageKind,
+        reading: &MonitorReading,
+        thresholds: &MonitorThresholds,
+    ) -> Self {
+        let (total, threshold) = match kind {
+            ArbitrageKind::BuyBoth => (reading.ask_total(), thresholds.buy_below),
+            ArbitrageKind::SellBoth => (reading.bid_total(), thresholds.sell_above),
+        };
+        let total = total.unwrap_or_default();
+        Self {
+            title: format!("Monitored market crossed ({}): ${:.4} vs ${:.4}", kind.label().to_lowercase(), total, threshold),
+            message: format!(
+                "{}\n{} bid/ask ${:.3}/${:.3} | {} bid/ask ${:.3}/${:.3}",
+                market.question,
+                market.outcome_name(0),
+                reading.yes.bid.unwrap_or_default(),
+                reading.yes.ask.unwrap_or_default(),
+                market.outcome_name(1),
+                reading.no.bid.unwrap_or_default(),
+                reading.no.ask.unwrap_or_default()
+            ),
+            data: json!({
+                "type": "monitored_market",
+                "kind": kind.label(),
+                "question": market.question,
+                "condition_id": market.condition_id,
+                "total": total,
+                "threshold": threshold,
+                "yes_bid": reading.yes.bid,
+                "yes_ask": reading.yes.ask,
+                "no_bid": reading.no.bid,
+                "no_ask": reading.no.ask,
+                "yes_ask_depth": reading.yes.ask_depth,
+                "no_ask_depth": reading.no.ask_depth,
+                "yes_bid_depth": reading.yes.bid_depth,
+                "no_bid_depth": reading.no.bid_depth,
+            }),
+        }
+    }
+
+    /// Builds an alert carrying a periodic digest
+    pub fn digest(digest: &Digest) -> Self {
+        let day = chrono::DateTime::from_timestamp(digest.until, 0)
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        Self {
+            title: format!(
+                "Digest {}: {} opportunities, {} new insiders",
+                day,
+                digest.opportunities,
+                digest.new_insiders.len()
+            ),
+            message: digest.summary_lines().join("\n"),
+            data: json!({
+                "type": "digest",
+                "digest": digest,
+            }),
+        }
+    }
+
+    /// Builds an alert for a newly flagged insider wallet
+    pub fn insider_wallet(
+        wallet: &str,
+        username: Option<&str>,
+        performance: &WalletPerformance,
+        report: &SuspicionReport,
+    ) -> Self {
+        let name = match username {
+            Some(user) => format!("{} (@{})", wallet, user),
+            None => wallet.to_string(),
+        };
+
+        Self {
+            title: format!("Suspicious wallet flagged (score {:.0}/100)", report.score),
+            message: format!(
+                "{}\nWin rate {:.1}% | ROI {:.1}% | Net profit ${:.2}\n{}",
+                name,
+                performance.win_rate,
+                performance.roi,
+                performance.net_profit,
+                report.reasons.iter().map(|r| format!("• {}", r)).collect::<Vec<_>>().join("\n")
+            ),
+            data: json!({
+                "type": "insider_wallet",
+                "wallet": wallet,
+                "username": username,
+                "win_rate": performance.win_rate,
+                "roi": performance.roi,
+                "net_profit": performance.net_profit,
+                "resolved_positions": performance.resolved_positions,
+                "score": report.score,
+                "suspicion": report,
+            }),
+        }
+    }
+
+    /// Builds an alert for a watched wallet opening a position, with context on the trade
+    pub fn watched_trade(wallet_name: &str, trade: &Trade, context: &TradeContext) -> Self {
+        let title = trade.title.as_deref().unwrap_or(&trade.condition_id);
+
+        Self {
+            title: format!("Watched wallet opened a position: {}", wallet_name),
+            message: format!(
+                "{}\n{} {} @ ${:.3} | {:.2} shares | ${:.2}\n{}",
+                title,
+                trade.side,
+                trade.outcome,
+                trade.price,
+                trade.size,
+                trade.price * trade.size,
+                context.summary_lines().join("\n")
+            ),
+            data: json!({
+                "type": "watched_trade",
+                "wallet": trade.proxy_wallet,
+                "wallet_name": wallet_name,
+                "condition_id": trade.condition_id,
+                "title": trade.title,
+                "side": trade.side,
+                "outcome": trade.outcome,
+                "outcome_index": trade.outcome_index,
+                "price": trade.price,
+                "size": trade.size,
+                "timestamp": trade.timestamp,
+                "context": context,
+            }),
+        }
+    }
+
+    /// Builds an alert for a copy-trading signal; webhooks receive the full signal
+    pub fn copy_signal(signal: &CopySignal) -> Self {
+        let title = signal.title.as_deref().unwrap_or(&signal.condition_id);
+
+        Self {
+            title: format!("Copy signal: {} {} {}", signal.wallet_name, signal.side, signal.outcome),
+            message: format!(
+                "{}\n{} {} @ ${:.3} | stake ${:.2} ({:.1}x the wallet's typical size)",
+                title, signal.side, signal.outcome, signal.entry_price, signal.suggested_stake, signal.size_proportion
+            ),
+            data: json!({
+                "type": "copy_signal",
+                "signal": signal,
+            }),
+        }
+    }
+}
+
+/// Sends alerts to every configured destination
+#[derive(Clone)]
+pub struct AlertDispatcher {
+    client: reqwest::Client,
+    destinations: Vec<AlertDestination>,
+}
+
+impl AlertDispatcher {
+    /// Creates a dispatcher for the given destinations
+    pub fn new(destinations: Vec<AlertDestination>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap(),
+            destinations,
+        }
+    }
+
+    /// Creates a dispatcher for the destinations set in the config
+    pub fn from_config(config: &AlertsConfig) -> Self {
+        let mut destinations = Vec::new();
+
+        if let Some(url) = &config.webhook_url {
+            destinations.push(AlertDestination::Webhook(url.clone()));
+        }
+        if let Some(url) = &config.discord_webhook_url {
+            destinations.push(AlertDestination::Discord(url.clone()));
+        }
+        if let (Some(bot_token), Some(chat_id)) = (&config.telegram_bot_token, &config.telegram_chat_id) {
+            destinations.push(AlertDestination::Telegram {
+                bot_token: bot_token.clone(),
+                chat_id: chat_id.clone(),
+            });
+        }
+
+        Self::new(destinations)
+    }
+
+    /// A dispatcher sending only to the named destination ("telegram", "discord" or
+    /// "webhook"), or without a name to the loudest one configured: Telegram pushes to
+    /// phones, Discord pings a channel, a webhook reaches whatever sits behind it
+    pub fn single_channel(&self, channel: Option<&str>) -> Result<Self> {
+        let destination = match channel {
+            Some(name) => self
+                .destinations
+                .iter()
+                .find(|d| d.name().eq_ignore_ascii_case(name))
+                .ok_or_else(|| anyhow::anyhow!("Alert channel {} is not configured", name))?,
+            None => match self.destinations.iter().min_by_key(|d| d.loudness_rank()) {
+                Some(destination) => destination,
+                None => return Ok(Self::new(Vec::new())),
+            },
+        };
+        Ok(Self::new(vec![destination.clone()]))
+    }
+
+    /// Whether any destination is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.destinations.is_empty()
+    }
+
+    /// Sends an alert everywhere; delivery failures are logged, not propagated
+    pub async fn send(&self, alert: &Alert) {
+        for destination in &self.destinations {
+            if let Err(e) = self.send_to(destination, alert).await {
+                eoutln!("Warning: Failed to deliver alert: {}", e);
+            }
+        }
+    }
+
+    /// Sends an alert to a single destination
+    async fn send_to(&self, destination: &AlertDestination, alert: &Alert) -> Result<()> {
+        let request = match destination {
+            AlertDestination::Webhook(url) => self.client.post(url).json(&json!({
+                "title": alert.title,
+                "message": alert.message,
+                "data": alert.data,
+            })),
+            AlertDestination::Discord(url) => self.client.post(url).json(&json!({
+                "content": format!("**{}**\n{}", alert.title, alert.message),
+            })),
+            AlertDestination::Telegram { bot_token, chat_id } => self
+                .client
+                .post(format!("https://api.telegram.org/bot{}/sendMessage", bot_token))
+                .json(&json!({
+                    "chat_id": chat_id,
+                    "text": format!("{}\n{}", alert.title, alert.message),
+                })),
+        };
+
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
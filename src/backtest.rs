@@ -0,0 +1,499 @@
+use crate::client::PolymarketClient;
+use crate::models::{ArbitrageKind, ArbitrageOpportunity, EventRef, Market, PricePoint, PriceSeries, Tag};
+use crate::scanner::ArbitrageScanner;
+use crate::store::Store;
+use anyhow::Result;
+use chrono::DateTime;
+use futures::StreamExt;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Price histories recorded by the last `backtest` fetch, one market per line
+const HISTORY_FILE: &str = "backtest_prices.jsonl";
+
+/// Polling intervals the configured one is compared with, in seconds
+pub const COMPARED_INTERVALS: [i64; 6] = [10, 30, 60, 300, 900, 3600];
+
+/// A binary market and its YES and NO price history, as recorded for replay
+/// Kept separate from `Market` so API-facing serde attributes can't break old recordings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedMarket {
+    pub question: String,
+    pub condition_id: String,
+    pub categories: Vec<String>,
+    pub event: Option<EventRef>,
+    /// Volume, liquidity and fees when the history was recorded; the replay uses them throughout
+    pub volume: f64,
+    pub volume_24hr: f64,
+    pub liquidity: f64,
+    pub tick_size: Option<f64>,
+    pub min_order_size: Option<f64>,
+    pub taker_fee_bps: Option<f64>,
+    pub maker_fee_bps: Option<f64>,
+    pub neg_risk: Option<bool>,
+    pub yes: Vec<PricePoint>,
+    pub no: Vec<PricePoint>,
+}
+
+impl RecordedMarket {
+    /// Pairs a binary market with its outcomes' price series (None for other markets)
+    pub fn new(market: &Market, series: Vec<PriceSeries>) -> Option<Self> {
+        let condition_id = market.condition_id.clone()?;
+        let [yes, no]: [PriceSeries; 2] = series.try_into().ok()?;
+        Some(Self {
+            question: market.question.clone(),
+            condition_id,
+            categories: market.categories(),
+            event: market.event().cloned(),
+            volume: market.parsed_volume(),
+            volume_24hr: market.volume_24hr.unwrap_or(0.0),
+            liquidity: market.parsed_liquidity(),
+            tick_size: market.order_price_min_tick_size,
+            min_order_size: market.order_min_size,
+            taker_fee_bps: market.taker_base_fee,
+            maker_fee_bps: market.maker_base_fee,
+            neg_risk: market.neg_risk,
+            yes: yes.points,
+            no: no.points,
+        })
+    }
+
+    /// Price points across both outcomes
+    pub fn points(&self) -> usize {
+        self.yes.len() + self.no.len()
+    }
+
+    /// The market as the scanner would have seen it quoting these YES and NO prices
+    fn market_at(&self, yes: f64, no: f64) -> Market {
+        Market {
+            question: self.question.clone(),
+            slug: None,
+            outcome_prices: Some(format!("[\"{}\",\"{}\"]", yes, no)),
+            volume: Some(self.volume.to_string()),
+            volume_24hr: Some(self.volume_24hr),
+            liquidity: Some(self.liquidity.to_string()),
+            condition_id: Some(self.condition_id.clone()),
+            closed: Some(false),
+            outcomes: None,
+            start_date: None,
+            end_date: None,
+            closed_time: None,
+            category: None,
+            clob_token_ids: None,
+            tags: self
+                .categories
+                .iter()
+                .map(|category| Tag { label: Some(category.clone()), slug: None })
+                .collect(),
+            order_price_min_tick_size: self.tick_size,
+            order_min_size: self.min_order_size,
+            taker_base_fee: self.taker_fee_bps,
+            maker_base_fee: self.maker_fee_bps,
+            neg_risk: self.neg_risk,
+            resolution_source: None,
+            uma_resolution_status: None,
+            uma_resolution_statuses: None,
+            events: self.event.iter().cloned().collect(),
+            best_bid: None,
+            best_ask: None,
+            one_day_price_change: None,
+            rewards_min_size: None,
+            rewards_max_spread: None,
+            clob_rewards: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Every change of the YES or NO price as (timestamp, yes, no), from the first moment
+    /// both are known; each price holds until its next point
+    fn timeline(&self) -> Vec<(i64, f64, f64)> {
+        let mut changes: Vec<(i64, Option<f64>, Option<f64>)> = self
+            .yes
+            .iter()
+            .map(|point| (point.timestamp, Some(point.price), None))
+            .chain(self.no.iter().map(|point| (point.timestamp, None, Some(point.price))))
+            .collect();
+        changes.sort_by_key(|(timestamp, _, _)| *timestamp);
+
+        let (mut yes, mut no) = (None, None);
+        let mut timeline: Vec<(i64, f64, f64)> = Vec::new();
+        for (timestamp, yes_change, no_change) in changes {
+            yes = yes_change.or(yes);
+            no = no_change.or(no);
+            if let (Some(yes), Some(no)) = (yes, no) {
+                match timeline.last_mut() {
+                    Some(last) if last.0 == timestamp => *last = (timestamp, yes, no),
+                    _ => timeline.push((timestamp, yes, no)),
+                }
+            }
+        }
+        timeline
+    }
+}
+
+/// Fetches price histories for a backtest and keeps them in the store, so later runs can
+/// replay the same data with other settings without refetching
+pub struct PriceRecorder {
+    client: PolymarketClient,
+    store: Store,
+}
+
+impl PriceRecorder {
+    pub fn new(client: PolymarketClient, store: Store) -> Self {
+        Self { client, store }
+    }
+
+    /// Fetches each market's YES and NO price history between two timestamps and replaces
+    /// the recorded histories with them; markets whose history can't be fetched are skipped
+    pub async fn record(
+        &self,
+        markets: &[Market],
+        start_ts: i64,
+        end_ts: i64,
+        fidelity_minutes: u32,
+        max_concurrent: usize,
+    ) -> Result<Vec<RecordedMarket>> {
+        let recorded: Vec<RecordedMarket> = futures::stream::iter(markets)
+            .map(|market| async move {
+                let series = self.client.fetch_market_price_history(market, start_ts, end_ts, fidelity_minutes).await;
+                (market, series)
+            })
+            .buffered(max_concurrent.max(1))
+            .filter_map(|(market, series)| async move {
+                match series {
+                    Ok(series) => RecordedMarket::new(market, series),
+                    Err(e) => {
+                        eoutln!("Warning: Failed to fetch price history for {}: {}", market.question, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await;
+
+        let mut lines = String::new();
+        for market in &recorded {
+            lines.push_str(&serde_json::to_string(market)?);
+            lines.push('\n');
+        }
+        self.store.save_bytes(HISTORY_FILE, lines.as_bytes())?;
+
+        Ok(recorded)
+    }
+
+    /// Histories saved by the last `record` (empty if there are none)
+    pub fn load(&self) -> Result<Vec<RecordedMarket>> {
+        self.store.read_jsonl(HISTORY_FILE)
+    }
+}
+
+/// What trading an opportunity at one moment would have returned, at the scanner's trade size
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HypotheticalTrade {
+    pub at: i64,
+    pub yes_price: f64,
+    pub no_price: f64,
+    pub profit_percent: f64,
+    /// Net of fees and expected slippage, in dollars
+    pub profit: f64,
+    /// Cost of the pairs bought (with costs), or the dollars split into pairs to sell
+    pub capital: f64,
+}
+
+impl HypotheticalTrade {
+    fn new(at: i64, opportunity: &ArbitrageOpportunity) -> Self {
+        let per_pair = match opportunity.kind {
+            ArbitrageKind::BuyBoth => {
+                opportunity.total_cost + opportunity.fees_per_dollar + opportunity.slippage_per_dollar
+            }
+            ArbitrageKind::SellBoth => 1.0,
+        };
+        Self {
+            at,
+            yes_price: opportunity.yes_price,
+            no_price: opportunity.no_price,
+            profit_percent: opportunity.profit_percent,
+            profit: opportunity.profit_per_dollar * opportunity.trade_size,
+            capital: per_pair * opportunity.trade_size,
+        }
+    }
+}
+
+/// A stretch of time a market was reported as an opportunity without interruption
+#[derive(Debug, Clone, Serialize)]
+pub struct OpportunityWindow {
+    pub condition_id: String,
+    pub question: String,
+    pub kind: ArbitrageKind,
+    pub opened_at: i64,
+    pub closed_at: i64,
+    /// Still open when the history ended, so the duration is a lower bound
+    pub still_open: bool,
+    pub best_profit_percent: f64,
+    /// The trade made at the first poll inside the window, for each replayed interval
+    /// (in the report's order); None if every poll missed it
+    pub trades: Vec<Option<HypotheticalTrade>>,
+}
+
+impl OpportunityWindow {
+    pub fn duration_secs(&self) -> i64 {
+        self.closed_at - self.opened_at
+    }
+}
+
+/// Opportunities caught and their returns when polling at one interval
+#[derive(Debug, Clone, Serialize)]
+pub struct IntervalResult {
+    pub interval_secs: i64,
+    pub caught: usize,
+    /// Caught opportunities that still made money after fees and slippage
+    pub profitable: usize,
+    pub profit: f64,
+    pub capital: f64,
+}
+
+/// Outcome of replaying recorded price histories through the scanner
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestReport {
+    pub markets: usize,
+    pub price_points: usize,
+    pub start: i64,
+    pub end: i64,
+    /// One per replayed polling interval; the first is the configured one
+    pub results: Vec<IntervalResult>,
+    /// Oldest first
+    pub windows: Vec<OpportunityWindow>,
+}
+
+/// Replays recorded price histories through an `ArbitrageScanner`, finding every window in
+/// which the scanner would have reported a market and which of them a scanner polling at
+/// a fixed interval would have caught
+pub struct Backtester {
+    scanner: ArbitrageScanner,
+    poll_interval_secs: i64,
+}
+
+impl Backtester {
+    pub fn new(scanner: ArbitrageScanner, poll_interval_secs: u64) -> Self {
+        Self {
+            scanner,
+            poll_interval_secs: (poll_interval_secs as i64).max(1),
+        }
+    }
+
+    pub fn run(&self, markets: &[RecordedMarket]) -> BacktestReport {
+        let mut intervals = vec![self.poll_interval_secs];
+        intervals.extend(COMPARED_INTERVALS.iter().filter(|interval| **interval != self.poll_interval_secs));
+
+        let timelines: Vec<Vec<(i64, f64, f64)>> = markets.par_iter().map(RecordedMarket::timeline).collect();
+        let start = timelines.iter().filter_map(|timeline| timeline.first()).map(|t| t.0).min().unwrap_or(0);
+        let end = timelines.iter().filter_map(|timeline| timeline.last()).map(|t| t.0).max().unwrap_or(start);
+
+        let mut windows: Vec<OpportunityWindow> = markets
+            .par_iter()
+            .zip(timelines.par_iter())
+            .flat_map_iter(|(market, timeline)| self.replay(market, timeline, start, end, &intervals))
+            .collect();
+        windows.sort_by_key(|window| window.opened_at);
+
+        let results = intervals
+            .iter()
+            .enumerate()
+            .map(|(index, interval)| {
+                let trades: Vec<&HypotheticalTrade> = windows.iter().filter_map(|w| w.trades[index].as_ref()).collect();
+                IntervalResult {
+                    interval_secs: *interval,
+                    caught: trades.len(),
+                    profitable: trades.iter().filter(|trade| trade.profit > 0.0).count(),
+                    profit: trades.iter().map(|trade| trade.profit).sum(),
+                    capital: trades.iter().map(|trade| trade.capital).sum(),
+                }
+            })
+            .collect();
+
+        BacktestReport {
+            markets: markets.len(),
+            price_points: markets.iter().map(RecordedMarket::points).sum(),
+            start,
+            end,
+            results,
+            windows,
+        }
+    }
+
+    /// Scans one market at every price change and groups the results into windows
+    fn replay(
+        &self,
+        market: &RecordedMarket,
+        timeline: &[(i64, f64, f64)],
+        start: i64,
+        end: i64,
+        intervals: &[i64],
+    ) -> Vec<OpportunityWindow> {
+        let states: Vec<(i64, Option<(ArbitrageKind, HypotheticalTrade)>)> = timeline
+            .iter()
+            .map(|(timestamp, yes, no)| {
+                let opportunity = self.scanner.scan_market(&market.market_at(*yes, *no));
+                (*timestamp, opportunity.map(|opp| (opp.kind, HypotheticalTrade::new(*timestamp, &opp))))
+            })
+            .collect();
+
+        let mut windows = Vec::new();
+        let mut index = 0;
+        while index < states.len() {
+            let Some((kind, _)) = states[index].1 else {
+                index += 1;
+                continue;
+            };
+            let first = index;
+            while index < states.len() && states[index].1.map(|(k, _)| k) == Some(kind) {
+                index += 1;
+            }
+            let open_states = &states[first..index];
+            let opened_at = open_states[0].0;
+            let still_open = index == states.len();
+            let closed_at = if still_open { end } else { states[index].0 };
+
+            let trades = intervals
+                .iter()
+                .map(|interval| {
+                    // First poll at or after the window opened, on a grid starting with the history
+                    let poll = start + (opened_at - start + interval - 1).div_euclid(*interval) * interval;
+                    if poll >= closed_at && !(still_open && poll == closed_at) {
+                        return None;
+                    }
+                    let seen = open_states.partition_point(|(timestamp, _)| *timestamp <= poll);
+                    open_states[seen - 1].1.map(|(_, trade)| HypotheticalTrade { at: poll, ..trade })
+                })
+                .collect();
+
+            windows.push(OpportunityWindow {
+                condition_id: market.condition_id.clone(),
+                question: market.question.clone(),
+                kind,
+                opened_at,
+                closed_at,
+                still_open,
+                best_profit_percent: open_states
+                    .iter()
+                    .filter_map(|(_, state)| state.map(|(_, trade)| trade.profit_percent))
+                    .fold(f64::NEG_INFINITY, f64::max),
+                trades,
+            });
+        }
+        windows
+    }
+}
+
+impl BacktestReport {
+    /// Prints the windows found, their durations and what polling at each interval returned
+    pub fn print(&self, top: usize) {
+        outln!(
+            "Replayed {} markets from {} to {} ({} price points)\n",
+            self.markets,
+            date_time_of(self.start),
+            date_time_of(self.end),
+            self.price_points
+        );
+
+        if self.windows.is_empty() {
+            outln!("No opportunities at any point in the replayed history.");
+            return;
+        }
+
+        let buy_both = self.windows.iter().filter(|w| w.kind == ArbitrageKind::BuyBoth).count();
+        let still_open = self.windows.iter().filter(|w| w.still_open).count();
+        let mut durations: Vec<i64> = self.windows.iter().map(OpportunityWindow::duration_secs).collect();
+        durations.sort_unstable();
+        let mut edges: Vec<f64> = self.windows.iter().map(|w| w.best_profit_percent).collect();
+        edges.sort_by(f64::total_cmp);
+
+        outln!("{:<22} {} ({} buy both, {} sell both)", "Opportunities:", self.windows.len(), buy_both,
+            self.windows.len() - buy_both);
+        if still_open > 0 {
+            outln!("{:<22} {} (durations are lower bounds)", "Open at the end:", still_open);
+        }
+        outln!(
+            "{:<22} median {}, mean {}, longest {}",
+            "Duration:",
+            duration(durations[durations.len() / 2]),
+            duration(durations.iter().sum::<i64>() / durations.len() as i64),
+            duration(durations[durations.len() - 1])
+        );
+        outln!(
+            "{:<22} median {:.2}%, best {:.2}%",
+            "Peak net edge:",
+            edges[edges.len() / 2],
+            edges[edges.len() - 1]
+        );
+
+        let configured = &self.results[0];
+        outln!("\nPolling every {} (configured):", duration(configured.interval_secs));
+        outln!(
+            "{:<22} {} of {} ({:.0}%)",
+            "Caught:",
+            configured.caught,
+            self.windows.len(),
+            configured.caught as f64 / self.windows.len() as f64 * 100.0
+        );
+        outln!("{:<22} ${:.2} on ${:.2} traded", "Net profit:", configured.profit, configured.capital);
+        if configured.profitable < configured.caught {
+            outln!(
+                "{:<22} {} caught opportunities lost money after fees and slippage",
+                "Unprofitable:",
+                configured.caught - configured.profitable
+            );
+        }
+
+        outln!("\nBy polling interval:");
+        outln!("  {:>9}  {:>9}  {:>12}  {:>10}", "Interval", "Caught", "Net profit", "Return");
+        let mut results: Vec<&IntervalResult> = self.results.iter().collect();
+        results.sort_by_key(|result| result.interval_secs);
+        for result in results {
+            let ret = if result.capital > 0.0 { result.profit / result.capital * 100.0 } else { 0.0 };
+            outln!(
+                "  {:>9}  {:>9}  {:>12}  {:>9.2}%",
+                duration(result.interval_secs),
+                format!("{}/{}", result.caught, self.windows.len()),
+                format!("${:.2}", result.profit),
+                ret
+            );
+        }
+
+        let mut best: Vec<&OpportunityWindow> = self.windows.iter().collect();
+        best.sort_by(|a, b| b.best_profit_percent.total_cmp(&a.best_profit_percent));
+        outln!("\nLargest opportunities:");
+        for window in best.into_iter().take(top) {
+            let caught = if window.trades[0].is_some() { "" } else { " (missed)" };
+            outln!(
+                "  {}  {:>8}  {:>6.2}%  {:<9}  {}{}",
+                date_time_of(window.opened_at),
+                duration(window.duration_secs()),
+                window.best_profit_percent,
+                window.kind.label(),
+                window.question.chars().take(60).collect::<String>(),
+                caught
+            );
+        }
+    }
+}
+
+fn date_time_of(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else if seconds < 86400 {
+        format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+    } else {
+        format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600)
+    }
+}
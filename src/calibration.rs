@@ -0,0 +1,318 @@
+use crate::client::PolymarketClient;
+use crate::export::{Column, ColumnType, CsvExport};
+use crate::models::Market;
+use crate::store::Store;
+use anyhow::Result;
+use chrono::Utc;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Latest calibration report, reused as the baseline for skill scoring
+const CALIBRATION_FILE: &str = "calibration.json";
+/// Cached pre-resolution outcome prices, keyed by condition ID and horizon
+const CALIBRATION_PRICES_FILE: &str = "calibration_prices.json";
+/// Width of each price band
+const BAND_WIDTH: f64 = 0.1;
+/// Label for markets without a category
+const UNCATEGORIZED: &str = "uncategorized";
+/// Label for the venue-wide buckets
+const ALL_CATEGORIES: &str = "all";
+
+/// One outcome of a resolved market: what the market priced it at, and whether it won
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationSample {
+    pub condition_id: String,
+    pub category: String,
+    pub outcome_index: usize,
+    pub price: f64,
+    pub won: bool,
+}
+
+/// Calibration of outcomes in one category and price band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationBucket {
+    pub category: String,
+    pub band_low: f64,
+    pub band_high: f64,
+    pub samples: usize,
+    pub wins: usize,
+    /// Average price of the outcomes in the bucket (the implied probability)
+    pub mean_price: f64,
+    /// Share of the outcomes that won (the realized probability)
+    pub win_rate: f64,
+}
+
+impl CalibrationBucket {
+    /// How much more often outcomes won than priced (positive = the market underpriced them)
+    pub fn edge(&self) -> f64 {
+        self.win_rate - self.mean_price
+    }
+}
+
+/// Venue-wide calibration of implied probabilities, by category and price band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub generated_at: i64,
+    /// Prices were taken this many hours before each market resolved
+    pub horizon_hours: f64,
+    pub markets: usize,
+    pub samples: usize,
+    /// Mean squared error of price vs outcome; 0 is perfect, 0.25 is a coin flip at 50c
+    pub brier_score: f64,
+    /// Category "all" first, then each category, bands in ascending order
+    pub buckets: Vec<CalibrationBucket>,
+}
+
+impl CalibrationReport {
+    /// Buckets samples by price band, venue-wide and per category
+    pub fn build(samples: &[CalibrationSample], horizon_hours: f64) -> Self {
+        // (category, band index) -> (samples, wins, price sum)
+        let mut totals: BTreeMap<(String, usize), (usize, usize, f64)> = BTreeMap::new();
+        for sample in samples {
+            let band = band_index(sample.price);
+            for category in [ALL_CATEGORIES, sample.category.as_str()] {
+                let entry = totals.entry((category.to_string(), band)).or_default();
+                entry.0 += 1;
+                entry.1 += sample.won as usize;
+                entry.2 += sample.price;
+            }
+        }
+
+        let mut buckets: Vec<CalibrationBucket> = totals
+            .into_iter()
+            .map(|((category, band), (count, wins, price_sum))| CalibrationBucket {
+                category,
+                band_low: band as f64 * BAND_WIDTH,
+                band_high: (band + 1) as f64 * BAND_WIDTH,
+                samples: count,
+                wins,
+                mean_price: price_sum / count as f64,
+                win_rate: wins as f64 / count as f64,
+            })
+            .collect();
+        buckets.sort_by_key(|b| (b.category != ALL_CATEGORIES, b.category.clone()));
+
+        let mut markets: Vec<&str> = samples.iter().map(|s| s.condition_id.as_str()).collect();
+        markets.sort();
+        markets.dedup();
+
+        let brier_score = if samples.is_empty() {
+            0.0
+        } else {
+            samples
+                .iter()
+                .map(|s| (s.price - if s.won { 1.0 } else { 0.0 }).powi(2))
+                .sum::<f64>()
+                / samples.len() as f64
+        };
+
+        Self {
+            generated_at: Utc::now().timestamp(),
+            horizon_hours,
+            markets: markets.len(),
+            samples: samples.len(),
+            brier_score,
+            buckets,
+        }
+    }
+
+    /// Loads the last saved report, if any
+    pub fn load(store: &Store) -> Result<Option<Self>> {
+        store.load_json(CALIBRATION_FILE)
+    }
+
+    /// Saves the report as the baseline for later runs
+    pub fn save(&self, store: &Store) -> Result<()> {
+        store.save_json(CALIBRATION_FILE, self)
+    }
+
+    /// How often outcomes priced at `price` actually won, using the category's bucket
+    /// when it has at least `min_samples`, else the venue-wide one
+    pub fn expected_win_rate(&self, price: f64, category: Option<&str>, min_samples: usize) -> Option<f64> {
+        let band = band_index(price);
+        let lookup = |category: &str| {
+            self.buckets
+                .iter()
+                .find(|b| b.category == category && band_index(b.band_low + BAND_WIDTH / 2.0) == band)
+                .filter(|b| b.samples >= min_samples)
+                .map(|b| b.win_rate)
+        };
+
+        category
+            .and_then(|c| lookup(&c.to_lowercase()))
+            .or_else(|| lookup(ALL_CATEGORIES))
+    }
+
+    /// Builds a CSV export with one row per category and price band
+    pub fn to_csv(&self, decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("category", ColumnType::String, "Market category, or \"all\" for the whole venue"),
+                Column::new("band_low", ColumnType::Decimal, "Lower bound of the price band (inclusive)").unit("usd"),
+                Column::new("band_high", ColumnType::Decimal, "Upper bound of the price band (exclusive)").unit("usd"),
+                Column::new("samples", ColumnType::Integer, "Outcomes priced in the band"),
+                Column::new("wins", ColumnType::Integer, "Outcomes in the band that won"),
+                Column::new("mean_price", ColumnType::Decimal, "Average price (implied probability)").unit("fraction"),
+                Column::new("win_rate", ColumnType::Decimal, "Share that won (realized probability)").unit("fraction"),
+                Column::new("edge", ColumnType::Decimal, "Win rate minus mean price").unit("fraction"),
+            ],
+            decimal_places,
+        );
+
+        for bucket in &self.buckets {
+            export.push_row(vec![
+                bucket.category.as_str().into(),
+                bucket.band_low.into(),
+                bucket.band_high.into(),
+                bucket.samples.into(),
+                bucket.wins.into(),
+                bucket.mean_price.into(),
+                bucket.win_rate.into(),
+                bucket.edge().into(),
+            ]);
+        }
+
+        export
+    }
+
+    /// Prints the venue-wide calibration table, then each category with enough samples
+    pub fn print(&self, min_category_samples: usize) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("CALIBRATION REPORT ({:.0}h before resolution)", self.horizon_hours);
+        outln!("{}", "=".repeat(80));
+        outln!("Markets:              {}", self.markets);
+        outln!("Outcomes:             {}", self.samples);
+        outln!("Brier Score:          {:.4}", self.brier_score);
+
+        let mut categories: Vec<(&str, usize)> = Vec::new();
+        for bucket in &self.buckets {
+            match categories.last_mut() {
+                Some((category, samples)) if *category == bucket.category => *samples += bucket.samples,
+                _ => categories.push((&bucket.category, bucket.samples)),
+            }
+        }
+
+        for (category, samples) in categories {
+            if category != ALL_CATEGORIES && samples < min_category_samples {
+                continue;
+            }
+
+            outln!("\n--- {} ({} outcomes) ---", category, samples);
+            outln!("{:<12} {:>8} {:>11} {:>10} {:>8}", "Price Band", "Outcomes", "Avg Price", "Won", "Edge");
+            for bucket in self.buckets.iter().filter(|b| b.category == category) {
+                outln!(
+                    "{:<12} {:>8} {:>10.1}% {:>9.1}% {:>+7.1}%",
+                    format!("{:.0}-{:.0}c", bucket.band_low * 100.0, bucket.band_high * 100.0),
+                    bucket.samples,
+                    bucket.mean_price * 100.0,
+                    bucket.win_rate * 100.0,
+                    bucket.edge() * 100.0
+                );
+            }
+        }
+
+        outln!("{}", "=".repeat(80));
+    }
+}
+
+/// Collects calibration samples from resolved markets by looking up each outcome's
+/// price a fixed time before resolution; prices are cached, since they never change
+pub struct CalibrationSampler {
+    client: PolymarketClient,
+    store: Store,
+    horizon_secs: i64,
+    max_concurrent: usize,
+}
+
+impl CalibrationSampler {
+    pub fn new(client: PolymarketClient, store: Store, horizon_secs: i64, max_concurrent: usize) -> Self {
+        Self {
+            client,
+            store,
+            horizon_secs,
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    /// Samples the `limit` most recently resolved markets with a single winner and at least `min_volume` traded
+    pub async fn collect(&self, resolved_markets: &[Market], limit: usize, min_volume: f64) -> Result<Vec<CalibrationSample>> {
+        let mut eligible: Vec<(&Market, i64, usize, Vec<String>)> = resolved_markets
+            .iter()
+            .filter(|m| m.condition_id.is_some() && m.parsed_volume() >= min_volume)
+            .filter_map(|m| {
+                Some((m, m.resolution_timestamp()?, m.winning_outcome_index()?, m.parsed_clob_token_ids()?))
+            })
+            .collect();
+        eligible.sort_by_key(|(_, resolved_at, _, _)| std::cmp::Reverse(*resolved_at));
+        eligible.truncate(limit);
+
+        // Condition ID@horizon -> price of each outcome (None if it didn't trade)
+        let mut cache: HashMap<String, Vec<Option<f64>>> =
+            self.store.load_json(CALIBRATION_PRICES_FILE)?.unwrap_or_default();
+        let cache_key = |condition_id: &str| format!("{}@{}", condition_id, self.horizon_secs);
+
+        let missing: Vec<&(&Market, i64, usize, Vec<String>)> = eligible
+            .iter()
+            .filter(|(m, ..)| !cache.contains_key(&cache_key(m.condition_id.as_deref().unwrap_or_default())))
+            .collect();
+        eoutln!("  {} cached, {} to fetch", eligible.len() - missing.len(), missing.len());
+
+        let fetched: Vec<(String, Vec<Option<f64>>)> = futures::stream::iter(missing)
+            .map(|(market, resolved_at, _, token_ids)| async move {
+                let at = resolved_at - self.horizon_secs;
+                let mut prices = Vec::with_capacity(token_ids.len());
+                for token_id in token_ids {
+                    prices.push(self.client.fetch_price_at(token_id, at).await?);
+                }
+                Ok::<_, anyhow::Error>((market.condition_id.clone().unwrap_or_default(), prices))
+            })
+            .buffer_unordered(self.max_concurrent)
+            .filter_map(|result| async move {
+                match result {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        eoutln!("Warning: Failed to fetch price history: {}", e);
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await;
+
+        for (condition_id, prices) in fetched {
+            cache.insert(cache_key(&condition_id), prices);
+        }
+        self.store.save_json(CALIBRATION_PRICES_FILE, &cache)?;
+
+        let mut samples = Vec::new();
+        for (market, _, winner, _) in &eligible {
+            let condition_id = market.condition_id.clone().unwrap_or_default();
+            let Some(prices) = cache.get(&cache_key(&condition_id)) else { continue };
+            let category = market
+                .category
+                .as_deref()
+                .map(str::to_lowercase)
+                .unwrap_or_else(|| UNCATEGORIZED.to_string());
+
+            for (outcome_index, price) in prices.iter().enumerate() {
+                let Some(price) = price else { continue };
+                samples.push(CalibrationSample {
+                    condition_id: condition_id.clone(),
+                    category: category.clone(),
+                    outcome_index,
+                    price: *price,
+                    won: outcome_index == *winner,
+                });
+            }
+        }
+
+        Ok(samples)
+    }
+}
+
+/// Index of the price band containing `price` (a price of exactly 1 falls in the top band)
+fn band_index(price: f64) -> usize {
+    let bands = (1.0 / BAND_WIDTH).round() as usize;
+    ((price.clamp(0.0, 1.0) / BAND_WIDTH) as usize).min(bands - 1)
+}
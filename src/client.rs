@@ -1,28 +1,316 @@
-use anyhow::Result;
-use crate::models::{Market, Trade};
-use futures::stream::{FuturesUnordered, StreamExt};
+use anyhow::{Context, Result};
+use crate::address::validate_address;
+use crate::config::ApiConfig;
+use crate::failover::{same_host, Endpoint, HostPool, HostStatus};
+use crate::rate_budget::RateBudget;
+use crate::shutdown::{is_cancelled, CancellationToken, Cancelled};
+use crate::models::{
+    Event, LeaderboardRanking, LeaderboardWindow, Market, OrderBook, Profile, PriceHistory, PriceInterval, PricePoint, PriceSeries,
+    TokenPrices, TokenQuote, Trade, TradeCursor, WalletActivity, WalletPosition,
+};
+use futures::stream::{Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
 use tokio::sync::Semaphore;
+use tokio::task::{JoinError, JoinSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
-const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com/markets";
-const TRADES_API_URL: &str = "https://data-api.polymarket.com/trades";
-const MAX_CONCURRENT_REQUESTS: usize = 20;
+const CONDITION_IDS_PER_REQUEST: usize = 50;
+/// Most pages of recently closed markets fetched per refresh
+const RECENTLY_RESOLVED_MAX_PAGES: usize = 50;
+/// Trades per page when paging through a wallet's history
+pub const WALLET_TRADES_PAGE_SIZE: usize = 1000;
+/// Positions per page, the most the positions endpoint returns at once
+const WALLET_POSITIONS_PAGE_SIZE: usize = 500;
+/// Outcome tokens quoted per CLOB prices request (each asks for both sides)
+const TOKENS_PER_PRICES_REQUEST: usize = 250;
+/// Most wallets the leaderboard returns per request
+const LEADERBOARD_PAGE_SIZE: usize = 50;
+/// Minutes between price history points, unless a caller asks for finer data
+const HOURLY_FIDELITY: u32 = 60;
 
 /// Client for interacting with the Polymarket API
 #[derive(Clone)]
 pub struct PolymarketClient {
     client: reqwest::Client,
+    /// Gamma markets and events, and Data API trades and leaderboard, each failing over across mirrors
+    gamma: Endpoint,
+    events: Endpoint,
+    trades: Endpoint,
+    activity: Endpoint,
+    positions: Endpoint,
+    leaderboard: Endpoint,
+    profile: Endpoint,
+    search: Endpoint,
+    clob_url: String,
+    /// Requests-per-second limit shared by every endpoint and clone of the client
+    budget: RateBudget,
+    max_concurrent_requests: usize,
+    resolved_max_concurrent_requests: usize,
+    resolved_market_limit: usize,
+    page_retries: u32,
+    sequential_fallback: bool,
+    /// Once cancelled, requests in flight are dropped and paginations stop with [`Cancelled`]
+    cancel: CancellationToken,
+}
+
+/// Configures the endpoints, limits and HTTP transport of a [`PolymarketClient`]
+/// A home connection and a datacenter IP need very different settings to avoid bans
+#[derive(Debug, Clone)]
+pub struct PolymarketClientBuilder {
+    config: ApiConfig,
+}
+
+impl PolymarketClientBuilder {
+    /// Starts from the default settings
+    pub fn new() -> Self {
+        Self::from_config(&ApiConfig::default())
+    }
+
+    /// Starts from the `[api]` section of the config
+    pub fn from_config(config: &ApiConfig) -> Self {
+        Self { config: config.clone() }
+    }
+
+    /// Total time allowed for each request, including reading the body
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout_secs = timeout.as_secs().max(1);
+        self
+    }
+
+    /// Time allowed to establish each connection
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout_secs = timeout.as_secs().max(1);
+        self
+    }
+
+    /// Requests in flight at once when paginating
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.config.max_concurrent_requests = max;
+        self
+    }
+
+    /// Requests per second across every call the client makes (0 = unlimited)
+    pub fn with_requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.config.requests_per_second = requests_per_second.max(0.0);
+        self
+    }
+
+    /// Requests in flight at once when paginating resolved markets
+    pub fn with_resolved_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.config.resolved_max_concurrent_requests = max;
+        self
+    }
+
+    /// Times a failed market page is retried after concurrent pagination, and whether every
+    /// page is refetched one at a time if some are still missing
+    pub fn with_page_recovery(mut self, retries: u32, sequential_fallback: bool) -> Self {
+        self.config.page_retries = retries;
+        self.config.sequential_fallback = sequential_fallback;
+        self
+    }
+
+    /// TCP keep-alive probe interval; None disables keep-alive probes
+    pub fn with_keep_alive(mut self, interval: Option<Duration>) -> Self {
+        self.config.tcp_keepalive_secs = interval.map_or(0, |d| d.as_secs().max(1));
+        self
+    }
+
+    /// How long idle pooled connections are kept, and how many per host
+    pub fn with_pool(mut self, idle_timeout: Duration, max_idle_per_host: usize) -> Self {
+        self.config.pool_idle_timeout_secs = idle_timeout.as_secs();
+        self.config.pool_max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// Sends every request through this proxy (http://, https:// or socks5:// URL)
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.config.proxy = Some(url.into());
+        self
+    }
+
+    /// Replaces the User-Agent header sent with every request
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Never negotiates HTTP/2, for proxies that mishandle it
+    pub fn with_http1_only(mut self, http1_only: bool) -> Self {
+        self.config.http1_only = http1_only;
+        self
+    }
+
+    /// HTTP/2 ping interval, which keeps long-lived connections from being dropped; None disables pings
+    pub fn with_http2_keep_alive(mut self, interval: Option<Duration>) -> Self {
+        self.config.http2_keep_alive_secs = interval.map_or(0, |d| d.as_secs().max(1));
+        self
+    }
+
+    /// Builds the underlying HTTP client with these transport settings
+    pub fn http_client(&self) -> Result<reqwest::Client> {
+        let config = &self.config;
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .tcp_keepalive((config.tcp_keepalive_secs > 0).then(|| Duration::from_secs(config.tcp_keepalive_secs)));
+
+        if let Some(proxy) = &config.proxy {
+            let proxy = reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy URL: {}", proxy))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(user_agent) = &config.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if config.http1_only {
+            builder = builder.http1_only();
+        } else if config.http2_keep_alive_secs > 0 {
+            builder = builder
+                .http2_keep_alive_interval(Duration::from_secs(config.http2_keep_alive_secs))
+                .http2_keep_alive_while_idle(true)
+                .http2_adaptive_window(true);
+        }
+
+        builder.build().context("Failed to build HTTP client")
+    }
+
+    pub fn build(self) -> Result<PolymarketClient> {
+        let client = self.http_client()?;
+        let config = self.config;
+        let failover = |pool: HostPool| pool.with_failover(config.failover_threshold, config.failover_cooldown_secs as i64);
+        let gamma_pool = failover(HostPool::new(&config.gamma_url, &config.gamma_fallback_hosts)?);
+        // Events share the markets' host health unless they're served from elsewhere
+        let events_pool = if same_host(&config.events_url, &config.gamma_url) {
+            gamma_pool.clone()
+        } else {
+            failover(HostPool::new(&config.events_url, &config.gamma_fallback_hosts)?)
+        };
+        // Profiles and search are Gamma endpoints too
+        let profile_pool = if same_host(&config.profile_url, &config.gamma_url) {
+            gamma_pool.clone()
+        } else {
+            failover(HostPool::new(&config.profile_url, &config.gamma_fallback_hosts)?)
+        };
+        let search_pool = if same_host(&config.search_url, &config.gamma_url) {
+            gamma_pool.clone()
+        } else {
+            failover(HostPool::new(&config.search_url, &config.gamma_fallback_hosts)?)
+        };
+        let data_pool = failover(HostPool::new(&config.trades_url, &config.data_fallback_hosts)?);
+        let activity_pool = if same_host(&config.activity_url, &config.trades_url) {
+            data_pool.clone()
+        } else {
+            failover(HostPool::new(&config.activity_url, &config.data_fallback_hosts)?)
+        };
+        let positions_pool = if same_host(&config.positions_url, &config.trades_url) {
+            data_pool.clone()
+        } else {
+            failover(HostPool::new(&config.positions_url, &config.data_fallback_hosts)?)
+        };
+        let leaderboard_pool = if same_host(&config.leaderboard_url, &config.trades_url) {
+            data_pool.clone()
+        } else {
+            failover(HostPool::new(&config.leaderboard_url, &config.data_fallback_hosts)?)
+        };
+        let budget = RateBudget::new(config.requests_per_second);
+        let endpoint = |pool: HostPool, url: &str| Endpoint::new(pool, url).map(|e| e.with_budget(budget.clone()));
+        Ok(PolymarketClient {
+            client,
+            clob_url: config.clob_url.trim_end_matches('/').to_string(),
+            gamma: endpoint(gamma_pool, &config.gamma_url)?,
+            events: endpoint(events_pool, &config.events_url)?,
+            profile: endpoint(profile_pool, &config.profile_url)?,
+            search: endpoint(search_pool, &config.search_url)?,
+            trades: endpoint(data_pool, &config.trades_url)?,
+            activity: endpoint(activity_pool, &config.activity_url)?,
+            positions: endpoint(positions_pool, &config.positions_url)?,
+            leaderboard: endpoint(leaderboard_pool, &config.leaderboard_url)?,
+            budget,
+            max_concurrent_requests: config.max_concurrent_requests.max(1),
+            resolved_max_concurrent_requests: config.resolved_max_concurrent_requests.max(1),
+            resolved_market_limit: config.resolved_market_limit,
+            page_retries: config.page_retries,
+            sequential_fallback: config.sequential_fallback,
+            cancel: CancellationToken::new(),
+        })
+    }
+}
+
+impl Default for PolymarketClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PolymarketClient {
-    /// Creates a new Polymarket API client
+    /// Creates a new Polymarket API client with default settings
     pub fn new() -> Self {
-        Self {
-            client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .unwrap(),
+        Self::builder().build().expect("default client settings are valid")
+    }
+
+    /// Starts configuring a client, from the default settings
+    pub fn builder() -> PolymarketClientBuilder {
+        PolymarketClientBuilder::new()
+    }
+
+    /// Creates a client using the endpoints, limits and transport settings from the config
+    pub fn with_config(config: &ApiConfig) -> Result<Self> {
+        PolymarketClientBuilder::from_config(config).build()
+    }
+
+    /// Stops requests and paginations (including their spawned page fetches) when the token is
+    /// cancelled, failing them with [`Cancelled`]
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// The token that cancels this client's requests
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Requests kept in flight at once by concurrent fetches
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
+    /// Runs a request unless, or until, the client is cancelled
+    async fn cancellable<T>(&self, request: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        tokio::select! {
+            _ = self.cancel.cancelled() => Err(Cancelled.into()),
+            result = request => result,
+        }
+    }
+
+    /// Waits for the next spawned page fetch to finish, failing with [`Cancelled`] if the client
+    /// is cancelled first; dropping the set then aborts the fetches still running
+    async fn join_next<T: 'static>(&self, tasks: &mut JoinSet<T>) -> Result<Option<Result<T, JoinError>>> {
+        tokio::select! {
+            _ = self.cancel.cancelled() => Err(Cancelled.into()),
+            result = tasks.join_next() => Ok(result),
+        }
+    }
+
+    /// Request counts, failures and failover state of every Gamma and Data API host
+    pub fn host_status(&self) -> Vec<HostStatus> {
+        let mut status = self.gamma.pool().status();
+        if !self.events.pool().same_hosts(self.gamma.pool()) {
+            status.extend(self.events.pool().status());
+        }
+        status.extend(self.trades.pool().status());
+        if !self.leaderboard.pool().same_hosts(self.trades.pool()) {
+            status.extend(self.leaderboard.pool().status());
+        }
+        for endpoint in [&self.profile, &self.search] {
+            if !endpoint.pool().same_hosts(self.gamma.pool()) {
+                status.extend(endpoint.pool().status());
+            }
         }
+        status
     }
 
     /// Fetches all active markets from Polymarket using concurrent pagination
@@ -31,43 +319,44 @@ impl PolymarketClient {
 
         // Fetch first page to check if pagination is needed
         let first_page = self.fetch_page(0, limit).await?;
-        let first_page_count = first_page.len();
 
         // If first page is partial, we're done
-        if first_page_count < limit {
-            return Ok(first_page);
+        if first_page.received < limit {
+            return Ok(first_page.records);
         }
 
         // Initialize for concurrent fetching
-        let mut all_markets = first_page;
-        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
-        let mut futures = FuturesUnordered::new();
+        let mut pages = PageSet::default();
+        pages.insert(0, first_page);
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent_requests));
+        let mut tasks = JoinSet::new();
         let mut next_offset = limit;
         let mut spawned_offsets = std::collections::HashSet::new();
 
         // Spawn initial batch of concurrent requests
-        for i in 0..MAX_CONCURRENT_REQUESTS {
+        for i in 0..self.max_concurrent_requests {
             let offset = next_offset + (i * limit);
             spawned_offsets.insert(offset);
 
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let client = self.client.clone();
+            let endpoint = self.gamma.clone();
 
-            futures.push(tokio::spawn(async move {
-                let result = fetch_page_internal(&client, offset, limit).await;
+            tasks.spawn(async move {
+                let result = fetch_page_internal(&client, &endpoint, offset, limit).await;
                 drop(permit);
                 (offset, result)
-            }));
+            });
         }
 
-        next_offset += MAX_CONCURRENT_REQUESTS * limit;
+        next_offset += self.max_concurrent_requests * limit;
 
         // Process results and spawn new requests dynamically
-        while let Some(result) = futures.next().await {
+        while let Some(result) = self.join_next(&mut tasks).await? {
             match result {
-                Ok((_offset, Ok(markets))) => {
-                    let page_count = markets.len();
-                    all_markets.extend(markets);
+                Ok((offset, Ok(page))) => {
+                    let page_count = page.received;
+                    pages.insert(offset, page);
 
                     // If page is full, spawn next request
                     if page_count == limit && !spawned_offsets.contains(&next_offset) {
@@ -75,66 +364,247 @@ impl PolymarketClient {
 
                         let permit = semaphore.clone().acquire_owned().await.unwrap();
                         let client = self.client.clone();
+                        let endpoint = self.gamma.clone();
                         let offset = next_offset;
 
-                        futures.push(tokio::spawn(async move {
-                            let result = fetch_page_internal(&client, offset, limit).await;
+                        tasks.spawn(async move {
+                            let result = fetch_page_internal(&client, &endpoint, offset, limit).await;
                             drop(permit);
                             (offset, result)
-                        }));
+                        });
 
                         next_offset += limit;
                     }
                 }
                 Ok((offset, Err(e))) => {
-                    eprintln!("Warning: Failed to fetch page at offset {}: {}", offset, e);
-                    // Continue with other pages
+                    eoutln!("Warning: Failed to fetch page at offset {}: {}", offset, e);
+                    pages.fail(offset);
                 }
                 Err(e) => {
-                    eprintln!("Warning: Task failed: {}", e);
-                    // Continue with other pages
+                    // The offset is recovered below as a gap
+                    eoutln!("Warning: Task failed: {}", e);
                 }
             }
         }
 
-        Ok(all_markets)
+        pages.mark_gaps(&spawned_offsets);
+        self.recover_pages(&mut pages, "active markets", limit, None, |offset| self.fetch_page(offset, limit)).await
+    }
+
+    /// Fetches all active events, with their markets embedded
+    pub async fn fetch_active_events(&self) -> Result<Vec<Event>> {
+        self.fetch_events(false, None).await
+    }
+
+    /// Fetches events with their markets embedded: open ones, or closed ones (newest first),
+    /// up to `max_events`
+    pub async fn fetch_events(&self, closed: bool, max_events: Option<usize>) -> Result<Vec<Event>> {
+        let mut all_events = Vec::new();
+        let limit = 100;
+        let mut offset = 0;
+
+        while max_events.is_none_or(|max| all_events.len() < max) {
+            let fetch_limit = max_events.map_or(limit, |max| limit.min(max - all_events.len()));
+            let request = self.events
+                .get(&self.client, |request| {
+                    let request = request.query(&[
+                        ("closed", closed.to_string()),
+                        ("limit", fetch_limit.to_string()),
+                        ("offset", offset.to_string()),
+                    ]);
+                    if closed {
+                        request.query(&[("order", "id"), ("ascending", "false")])
+                    } else {
+                        request.query(&[("active", "true")])
+                    }
+                });
+            let events: Vec<Event> = self
+                .cancellable(async { Ok(request.await?.error_for_status()?.json().await?) })
+                .await?;
+
+            let count = events.len();
+            all_events.extend(events);
+
+            if count < fetch_limit {
+                break;
+            }
+
+            offset += fetch_limit;
+        }
+
+        Ok(all_events)
     }
 
     /// Fetches a single page of markets
-    async fn fetch_page(&self, offset: usize, limit: usize) -> Result<Vec<Market>> {
-        fetch_page_internal(&self.client, offset, limit).await
+    async fn fetch_page(&self, offset: usize, limit: usize) -> Result<Page<Market>> {
+        self.cancellable(fetch_page_internal(&self.client, &self.gamma, offset, limit)).await
     }
 
-    /// Fetches all trades for a specific wallet address
+    /// Fetches all trades for a specific wallet address, including fills where it was the maker
     pub async fn fetch_wallet_trades(&self, wallet_address: &str) -> Result<Vec<Trade>> {
+        self.fetch_wallet_trades_paged(wallet_address, false).await
+    }
+
+    /// Fetches only the wallet's trades where it took liquidity
+    pub async fn fetch_wallet_taker_trades(&self, wallet_address: &str) -> Result<Vec<Trade>> {
+        self.fetch_wallet_trades_paged(wallet_address, true).await
+    }
+
+    /// Pages through a wallet's trades
+    async fn fetch_wallet_trades_paged(&self, wallet_address: &str, taker_only: bool) -> Result<Vec<Trade>> {
         let mut all_trades = Vec::new();
-        let limit = 1000;
         let mut offset = 0;
 
         loop {
-            let trades: Vec<Trade> = self.client
-                .get(TRADES_API_URL)
-                .query(&[
+            let page = self.fetch_wallet_trades_page(wallet_address, taker_only, offset).await?;
+            all_trades.extend(page.records);
+
+            if page.received < WALLET_TRADES_PAGE_SIZE {
+                break;
+            }
+
+            offset += WALLET_TRADES_PAGE_SIZE;
+        }
+
+        Ok(all_trades)
+    }
+
+    /// Fetches only a wallet's trades newer than the cursor, including fills where it was the maker
+    /// Pages newest first and stops at the first page reaching the cursor, so a wallet with a long
+    /// history costs one request when little has changed
+    pub async fn fetch_wallet_trades_since(&self, wallet_address: &str, cursor: &TradeCursor) -> Result<Vec<Trade>> {
+        let mut new_trades = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = self.fetch_wallet_trades_page(wallet_address, false, offset).await?;
+            let reached_cursor = page.records.iter().any(|trade| trade.timestamp < cursor.timestamp);
+            new_trades.extend(page.records.into_iter().filter(|trade| !cursor.covers(trade)));
+
+            if reached_cursor || page.received < WALLET_TRADES_PAGE_SIZE {
+                break;
+            }
+
+            offset += WALLET_TRADES_PAGE_SIZE;
+        }
+
+        Ok(new_trades)
+    }
+
+    /// Streams a wallet's trades one page at a time, newest first
+    /// The next page is fetched in the background while the caller processes the current one,
+    /// and at most one page is buffered ahead, so memory stays bounded however many trades there are
+    pub fn wallet_trade_pages(&self, wallet_address: &str) -> impl Stream<Item = Result<Vec<Trade>>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(1);
+        let client = self.clone();
+        let wallet_address = wallet_address.to_string();
+
+        tokio::spawn(async move {
+            let mut offset = 0;
+            loop {
+                let page = client.fetch_wallet_trades_page(&wallet_address, false, offset).await;
+                let last = page.as_ref().map_or(true, |page| page.received < WALLET_TRADES_PAGE_SIZE);
+                // Stop early if the stream was dropped
+                if sender.send(page.map(|page| page.records)).await.is_err() || last {
+                    break;
+                }
+                offset += WALLET_TRADES_PAGE_SIZE;
+            }
+        });
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|page| (page, receiver))
+        })
+    }
+
+    /// Fetches one page of a wallet's trades
+    async fn fetch_wallet_trades_page(&self, wallet_address: &str, taker_only: bool, offset: usize) -> Result<Page<Trade>> {
+        let request = self.trades.get(&self.client, |request| {
+            request.query(&[
+                ("user", wallet_address),
+                ("takerOnly", &taker_only.to_string()),
+                ("limit", &WALLET_TRADES_PAGE_SIZE.to_string()),
+                ("offset", &offset.to_string()),
+            ])
+        });
+        let body = self.cancellable(async { Ok(request.await?.text().await?) }).await?;
+
+        parse_page(&body, "trades")
+    }
+
+    /// Fetches a wallet's splits, merges, redemptions, conversions and rewards from its
+    /// activity feed; trades are left to the trades endpoint
+    pub async fn fetch_wallet_activity(&self, wallet_address: &str) -> Result<Vec<WalletActivity>> {
+        let mut all_activity = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let request = self.activity.get(&self.client, |request| {
+                request.query(&[
                     ("user", wallet_address),
-                    ("limit", &limit.to_string()),
+                    ("type", "SPLIT,MERGE,REDEEM,CONVERSION,REWARD"),
+                    ("limit", &WALLET_TRADES_PAGE_SIZE.to_string()),
                     ("offset", &offset.to_string()),
                 ])
-                .send()
-                .await?
-                .json()
-                .await?;
+            });
+            let body = self.cancellable(async { Ok(request.await?.text().await?) }).await?;
+            let page: Page<WalletActivity> = parse_page(&body, "activity records")?;
+            all_activity.extend(page.records);
 
-            let count = trades.len();
-            all_trades.extend(trades);
+            if page.received < WALLET_TRADES_PAGE_SIZE {
+                break;
+            }
 
-            if count < limit {
+            offset += WALLET_TRADES_PAGE_SIZE;
+        }
+
+        Ok(all_activity)
+    }
+
+    /// Fetches a wallet's current positions with the API's own cost basis, mark price and P&L,
+    /// including resolved ones not yet redeemed
+    pub async fn fetch_wallet_positions(&self, wallet_address: &str) -> Result<Vec<WalletPosition>> {
+        let mut all_positions = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let request = self.positions.get(&self.client, |request| {
+                request.query(&[
+                    ("user", wallet_address),
+                    ("sizeThreshold", "0"),
+                    ("limit", &WALLET_POSITIONS_PAGE_SIZE.to_string()),
+                    ("offset", &offset.to_string()),
+                ])
+            });
+            let body = self.cancellable(async { Ok(request.await?.text().await?) }).await?;
+            let page: Page<WalletPosition> = parse_page(&body, "positions")?;
+            all_positions.extend(page.records);
+
+            if page.received < WALLET_POSITIONS_PAGE_SIZE {
                 break;
             }
 
-            offset += limit;
+            offset += WALLET_POSITIONS_PAGE_SIZE;
         }
 
-        Ok(all_trades)
+        Ok(all_positions)
+    }
+
+    /// Fetches the most recent trades for a wallet (a single page, newest first)
+    pub async fn fetch_latest_wallet_trades(&self, wallet_address: &str, limit: usize) -> Result<Vec<Trade>> {
+        let body = self.trades
+            .get(&self.client, |request| {
+                request.query(&[
+                    ("user", wallet_address),
+                    ("takerOnly", "false"),
+                    ("limit", &limit.to_string()),
+                ])
+            })
+            .await?
+            .text()
+            .await?;
+
+        Ok(parse_page(&body, "trades")?.records)
     }
 
     /// Fetches recent trades (no wallet filter) to discover active wallets
@@ -146,61 +616,294 @@ impl PolymarketClient {
         while all_trades.len() < limit {
             let fetch_limit = std::cmp::min(page_limit, limit - all_trades.len());
 
-            let trades: Vec<Trade> = self.client
-                .get(TRADES_API_URL)
-                .query(&[
-                    ("limit", &fetch_limit.to_string()),
-                    ("offset", &offset.to_string()),
-                ])
-                .send()
+            let page = self.fetch_recent_trades_page(offset, fetch_limit).await?;
+            all_trades.extend(page.records);
+
+            if page.received < fetch_limit {
+                break;
+            }
+
+            offset += fetch_limit;
+        }
+
+        Ok(all_trades)
+    }
+
+    /// Fetches recent trades across all markets newer than the cursor, at most `limit`
+    pub async fn fetch_recent_trades_since(&self, cursor: &TradeCursor, limit: usize) -> Result<Vec<Trade>> {
+        let mut new_trades = Vec::new();
+        let page_limit = 1000;
+        let mut offset = 0;
+
+        while new_trades.len() < limit {
+            let page = self.fetch_recent_trades_page(offset, page_limit).await?;
+            let reached_cursor = page.records.iter().any(|trade| trade.timestamp < cursor.timestamp);
+            new_trades.extend(page.records.into_iter().filter(|trade| !cursor.covers(trade)));
+
+            if reached_cursor || page.received < page_limit {
+                break;
+            }
+
+            offset += page_limit;
+        }
+
+        new_trades.truncate(limit);
+        Ok(new_trades)
+    }
+
+    /// Fetches one page of recent trades across all markets, newest first
+    pub async fn fetch_recent_trades_page(&self, offset: usize, limit: usize) -> Result<Page<Trade>> {
+        let request = self.trades.get(&self.client, |request| {
+            request.query(&[
+                ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
+            ])
+        });
+        let body = self
+            .cancellable(async { Ok(request.await?.error_for_status()?.text().await?) })
+            .await?;
+
+        parse_page(&body, "trades")
+    }
+
+    /// Fetches recent trades in the given markets, at most `limit`
+    /// Markets are queried in batches of condition IDs, each batch getting an equal share of the limit
+    pub async fn fetch_market_trades(&self, condition_ids: &[String], limit: usize) -> Result<Vec<Trade>> {
+        let batches = condition_ids.len().div_ceil(CONDITION_IDS_PER_REQUEST).max(1);
+        let batch_limit = limit.div_ceil(batches);
+        let page_limit = 1000;
+        let mut all_trades = Vec::new();
+
+        for batch in condition_ids.chunks(CONDITION_IDS_PER_REQUEST) {
+            let markets = batch.join(",");
+            let mut fetched = 0;
+
+            while fetched < batch_limit && all_trades.len() < limit {
+                let fetch_limit = page_limit.min(batch_limit - fetched).min(limit - all_trades.len());
+                let request = self.trades.get(&self.client, |request| {
+                    request.query(&[
+                        ("market", markets.as_str()),
+                        ("limit", &fetch_limit.to_string()),
+                        ("offset", &fetched.to_string()),
+                    ])
+                });
+                let body = self
+                    .cancellable(async { Ok(request.await?.error_for_status()?.text().await?) })
+                    .await?;
+                let page = parse_page::<Trade>(&body, "trades")?;
+                all_trades.extend(page.records);
+                fetched += fetch_limit;
+
+                if page.received < fetch_limit {
+                    break;
+                }
+            }
+        }
+
+        Ok(all_trades)
+    }
+
+    /// Fetches when each market last traded, keyed by condition ID (None for markets that
+    /// never have), one request per market
+    pub async fn fetch_last_trade_times(&self, condition_ids: &[String]) -> Result<HashMap<String, Option<i64>>> {
+        futures::stream::iter(condition_ids.iter().cloned())
+            .map(|condition_id| async move {
+                let request = self.trades.get(&self.client, |request| {
+                    request.query(&[("market", condition_id.as_str()), ("limit", "1")])
+                });
+                let body = self
+                    .cancellable(async { Ok(request.await?.error_for_status()?.text().await?) })
+                    .await?;
+                let page = parse_page::<Trade>(&body, "trades")?;
+                Ok::<_, anyhow::Error>((condition_id, page.records.first().map(|trade| trade.timestamp)))
+            })
+            .buffer_unordered(self.max_concurrent_requests)
+            .try_collect()
+            .await
+    }
+
+    /// Fetches the most profitable wallets over a window from Polymarket's leaderboard,
+    /// optionally limited to one category (e.g. "politics"), best first
+    pub async fn fetch_leaderboard(
+        &self,
+        window: LeaderboardWindow,
+        category: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<LeaderboardRanking>> {
+        let mut rankings: Vec<LeaderboardRanking> = Vec::new();
+
+        while rankings.len() < limit {
+            let fetch_limit = std::cmp::min(LEADERBOARD_PAGE_SIZE, limit - rankings.len());
+            let offset = rankings.len();
+            let page: Vec<LeaderboardRanking> = self.leaderboard
+                .get(&self.client, |request| {
+                    let request = request.query(&[
+                        ("timePeriod", window.as_str()),
+                        ("orderBy", "PNL"),
+                        ("limit", &fetch_limit.to_string()),
+                        ("offset", &offset.to_string()),
+                    ]);
+                    match category {
+                        Some(category) => request.query(&[("category", category.to_uppercase())]),
+                        None => request,
+                    }
+                })
                 .await?
+                .error_for_status()
+                .context("Leaderboard request failed")?
                 .json()
-                .await?;
-
-            let count = trades.len();
-            all_trades.extend(trades);
+                .await
+                .context("Failed to parse leaderboard")?;
 
+            let count = page.len();
+            rankings.extend(page);
             if count < fetch_limit {
                 break;
             }
+        }
 
-            offset += fetch_limit;
+        Ok(rankings)
+    }
+
+    /// Fetches a wallet's public profile (None if the wallet has never set one up)
+    pub async fn fetch_profile(&self, wallet_address: &str) -> Result<Option<Profile>> {
+        let response = self.profile
+            .get(&self.client, |request| request.query(&[("address", wallet_address)]))
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+        let profile = response
+            .error_for_status()
+            .context("Profile request failed")?
+            .json()
+            .await
+            .context("Failed to parse profile")?;
+        Ok(Some(profile))
+    }
 
-        Ok(all_trades)
+    /// Searches user profiles by name
+    pub async fn search_profiles(&self, query: &str) -> Result<Vec<Profile>> {
+        #[derive(serde::Deserialize)]
+        struct SearchResults {
+            #[serde(default)]
+            profiles: Option<Vec<Profile>>,
+        }
+
+        let results: SearchResults = self.search
+            .get(&self.client, |request| {
+                request.query(&[
+                    ("q", query),
+                    ("search_profiles", "true"),
+                    ("limit_per_type", "20"),
+                ])
+            })
+            .await?
+            .error_for_status()
+            .context("Profile search failed")?
+            .json()
+            .await
+            .context("Failed to parse profile search results")?;
+        Ok(results.profiles.unwrap_or_default())
+    }
+
+    /// Resolves a wallet given as an address (checked with `validate_address`) or as a
+    /// Polymarket username, with or without a leading `@`, to its lowercase proxy wallet address
+    pub async fn resolve_wallet(&self, wallet: &str) -> Result<String> {
+        if wallet.starts_with("0x") || wallet.starts_with("0X") {
+            return validate_address(wallet);
+        }
+
+        let name = wallet.trim_start_matches('@');
+        let profiles = self.search_profiles(name).await?;
+        if let Some(address) = profiles
+            .iter()
+            .filter(|profile| profile.is_named(name))
+            .find_map(|profile| profile.proxy_wallet.as_deref())
+        {
+            return validate_address(address);
+        }
+
+        let similar: Vec<&str> = profiles.iter().filter_map(Profile::display_name).take(5).collect();
+        if similar.is_empty() {
+            anyhow::bail!("No Polymarket user named {}", name);
+        }
+        anyhow::bail!("No Polymarket user named {} (similar: {})", name, similar.join(", "))
+    }
+
+    /// Fetches one page of closed markets as the API returns them (every field, tags included),
+    /// oldest first by ID so offsets stay stable as new markets close
+    pub async fn fetch_closed_markets_raw(&self, offset: usize, limit: usize) -> Result<Vec<serde_json::Value>> {
+        let markets: Vec<serde_json::Value> = self.gamma
+            .get(&self.client, |request| {
+                request.query(&[
+                    ("closed", "true"),
+                    ("include_tag", "true"),
+                    ("order", "id"),
+                    ("ascending", "true"),
+                    ("limit", &limit.to_string()),
+                    ("offset", &offset.to_string()),
+                ])
+            })
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(markets)
+    }
+
+    /// Fetches one page of a wallet's trades as the API returns them, newest first
+    /// Pages hold `WALLET_TRADES_PAGE_SIZE` trades; a shorter page is the last
+    pub async fn fetch_wallet_trades_raw(&self, wallet_address: &str, offset: usize) -> Result<Vec<serde_json::Value>> {
+        let trades: Vec<serde_json::Value> = self.trades
+            .get(&self.client, |request| {
+                request.query(&[
+                    ("user", wallet_address),
+                    ("takerOnly", "false"),
+                    ("limit", &WALLET_TRADES_PAGE_SIZE.to_string()),
+                    ("offset", &offset.to_string()),
+                ])
+            })
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(trades)
     }
 
     /// Fetches resolved markets with optional limit
     pub async fn fetch_resolved_markets_limited(&self, max_markets: Option<usize>) -> Result<Vec<Market>> {
         let limit = 100;
-        let max_concurrent = 10; // Reduced concurrency to avoid rate limits
+        let max_concurrent = self.resolved_max_concurrent_requests; // Reduced concurrency to avoid rate limits
 
         // Fetch first page to check if pagination is needed
         let first_page = self.fetch_markets_page(0, limit, true).await?;
-        let first_page_count = first_page.len();
 
         // If first page is partial, we're done
-        if first_page_count < limit {
-            return Ok(first_page);
+        if first_page.received < limit {
+            return Ok(first_page.records);
         }
 
         // Check if we've already hit the limit
         if let Some(max) = max_markets {
-            if first_page_count >= max {
-                return Ok(first_page.into_iter().take(max).collect());
+            if first_page.records.len() >= max {
+                return Ok(first_page.records.into_iter().take(max).collect());
             }
         }
 
         // Initialize for concurrent fetching
-        let mut all_markets = first_page;
+        let mut pages = PageSet::default();
+        pages.insert(0, first_page);
         let semaphore = Arc::new(Semaphore::new(max_concurrent));
-        let mut futures = FuturesUnordered::new();
+        let mut tasks = JoinSet::new();
         let mut next_offset = limit;
         let mut spawned_offsets = std::collections::HashSet::new();
         let mut consecutive_empty_pages = 0;
         let max_consecutive_empty = 10; // Stop after 10 consecutive empty pages
 
-        eprint!("  Loading markets");
+        eout!("  Loading markets");
 
         // Spawn initial batch of concurrent requests
         for i in 0..max_concurrent {
@@ -209,23 +912,25 @@ impl PolymarketClient {
 
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let client = self.client.clone();
+            let endpoint = self.gamma.clone();
 
-            futures.push(tokio::spawn(async move {
-                let result = fetch_resolved_markets_page(&client, offset, limit).await;
+            tasks.spawn(async move {
+                let result = fetch_resolved_markets_page(&client, &endpoint, offset, limit).await;
                 drop(permit);
                 (offset, result)
-            }));
+            });
         }
 
         next_offset += max_concurrent * limit;
 
         // Process results and spawn new requests dynamically
-        while let Some(result) = futures.next().await {
+        while let Some(result) = self.join_next(&mut tasks).await? {
             match result {
-                Ok((_offset, Ok(markets))) => {
-                    let page_count = markets.len();
+                Ok((offset, Ok(page))) => {
+                    let page_count = page.received;
 
                     if page_count == 0 {
+                        pages.insert(offset, page);
                         consecutive_empty_pages += 1;
                         if consecutive_empty_pages >= max_consecutive_empty {
                             // Stop spawning new requests, but let existing ones finish
@@ -233,18 +938,19 @@ impl PolymarketClient {
                         }
                     } else {
                         consecutive_empty_pages = 0; // Reset counter
-                        all_markets.extend(markets);
+                        pages.insert(offset, page);
+                        let loaded = pages.market_count();
 
                         // Show progress - update every 500 markets or show dots
-                        if all_markets.len() % 500 == 0 {
-                            eprint!("\r  Loaded {} markets...", all_markets.len());
-                        } else if all_markets.len() % 100 == 0 {
-                            eprint!(".");
+                        if loaded % 500 == 0 {
+                            eout!("\r  Loaded {} markets...", loaded);
+                        } else if loaded % 100 == 0 {
+                            eout!(".");
                         }
 
                         // Check if we've reached the limit
                         if let Some(max) = max_markets {
-                            if all_markets.len() >= max {
+                            if loaded >= max {
                                 break; // Stop fetching
                             }
                         }
@@ -252,7 +958,7 @@ impl PolymarketClient {
 
                     // If page is full, spawn next request (and we haven't hit limit)
                     let should_continue = if let Some(max) = max_markets {
-                        all_markets.len() < max
+                        pages.market_count() < max
                     } else {
                         true
                     };
@@ -262,107 +968,602 @@ impl PolymarketClient {
 
                         let permit = semaphore.clone().acquire_owned().await.unwrap();
                         let client = self.client.clone();
+                        let endpoint = self.gamma.clone();
                         let offset = next_offset;
 
-                        futures.push(tokio::spawn(async move {
-                            let result = fetch_resolved_markets_page(&client, offset, limit).await;
+                        tasks.spawn(async move {
+                            let result = fetch_resolved_markets_page(&client, &endpoint, offset, limit).await;
                             drop(permit);
                             (offset, result)
-                        }));
+                        });
 
                         next_offset += limit;
                     }
                 }
                 Ok((offset, Err(e))) => {
-                    eprintln!("\nWarning: Failed to fetch page at offset {}: {}", offset, e);
-                    consecutive_empty_pages += 1;
+                    eoutln!("\nWarning: Failed to fetch page at offset {}: {}", offset, e);
+                    pages.fail(offset);
                 }
                 Err(e) => {
-                    eprintln!("\nWarning: Task failed: {}", e);
+                    // The offset is recovered below as a gap
+                    eoutln!("\nWarning: Task failed: {}", e);
                 }
             }
         }
 
-        eprintln!(); // New line after progress indicator
+        eoutln!(); // New line after progress indicator
+
+        pages.mark_gaps(&spawned_offsets);
+        let markets = self
+            .recover_pages(&mut pages, "resolved markets", limit, max_markets, |offset| {
+                self.fetch_markets_page(offset, limit, true)
+            })
+            .await?;
 
         // Trim to max if we over-fetched
         if let Some(max) = max_markets {
-            Ok(all_markets.into_iter().take(max).collect())
+            Ok(markets.into_iter().take(max).collect())
         } else {
-            Ok(all_markets)
+            Ok(markets)
+        }
+    }
+
+    /// Fills the gaps left by a concurrent pagination: retries each failed page one at a time,
+    /// keeps paging if the last page was full, then either refetches everything sequentially
+    /// or reports the pages still missing
+    async fn recover_pages<F, Fut>(
+        &self,
+        pages: &mut PageSet,
+        what: &str,
+        limit: usize,
+        max_markets: Option<usize>,
+        fetch: F,
+    ) -> Result<Vec<Market>>
+    where
+        F: Fn(usize) -> Fut,
+        Fut: std::future::Future<Output = Result<Page<Market>>>,
+    {
+        let under_max = |pages: &PageSet| max_markets.is_none_or(|max| pages.market_count() < max);
+
+        for offset in pages.failed.clone() {
+            match self.fetch_with_retries(offset, &fetch).await {
+                Ok(page) => pages.insert(offset, page),
+                Err(e) if is_cancelled(&e) => return Err(e),
+                Err(e) => eoutln!("Warning: Page at offset {} failed after {} retries: {}", offset, self.page_retries, e),
+            }
+        }
+
+        // A failed final page stops the concurrent fetch from spawning its successors
+        while pages.failed.is_empty() && under_max(pages) {
+            let Some(offset) = pages.next_offset(limit) else { break };
+            match self.fetch_with_retries(offset, &fetch).await {
+                Ok(page) => pages.insert(offset, page),
+                Err(e) if is_cancelled(&e) => return Err(e),
+                Err(e) => {
+                    eoutln!("Warning: Page at offset {} failed after {} retries: {}", offset, self.page_retries, e);
+                    pages.fail(offset);
+                }
+            }
+        }
+
+        if pages.failed.is_empty() {
+            return Ok(std::mem::take(pages).into_markets());
+        }
+
+        let missing = pages.failed.iter().map(|offset| offset.to_string()).collect::<Vec<_>>().join(", ");
+        if !self.sequential_fallback {
+            eoutln!(
+                "Warning: {} page(s) of {} still missing (offsets {}); results are incomplete. \
+                 Set sequential_fallback = true in [api] to refetch them one page at a time",
+                pages.failed.len(), what, missing
+            );
+            return Ok(std::mem::take(pages).into_markets());
+        }
+
+        eoutln!("Warning: {} page(s) of {} missing (offsets {}); refetching sequentially", pages.failed.len(), what, missing);
+        let mut sequential = PageSet::default();
+        let mut offset = 0;
+        while under_max(&sequential) {
+            let page = self
+                .fetch_with_retries(offset, &fetch)
+                .await
+                .with_context(|| format!("Sequential fetch of {} failed at offset {}", what, offset))?;
+            let count = page.received;
+            sequential.insert(offset, page);
+            if count < limit {
+                break;
+            }
+            offset += limit;
+        }
+        Ok(sequential.into_markets())
+    }
+
+    /// Fetches one page, retrying with exponential backoff
+    async fn fetch_with_retries<F, Fut>(&self, offset: usize, fetch: &F) -> Result<Page<Market>>
+    where
+        F: Fn(usize) -> Fut,
+        Fut: std::future::Future<Output = Result<Page<Market>>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match fetch(offset).await {
+                Ok(page) => return Ok(page),
+                Err(e) if attempt >= self.page_retries || is_cancelled(&e) => return Err(e),
+                Err(_) => {
+                    self.cancellable(async {
+                        tokio::time::sleep(Duration::from_secs(1 << attempt.min(5))).await;
+                        Ok(())
+                    })
+                    .await?;
+                    attempt += 1;
+                }
+            }
         }
     }
 
     /// Fetches all closed/resolved markets
     pub async fn fetch_resolved_markets(&self) -> Result<Vec<Market>> {
         // Fetch most recent 15,000 markets by default (sufficient for most analysis)
-        self.fetch_resolved_markets_limited(Some(15000)).await
+        self.fetch_resolved_markets_limited(Some(self.resolved_market_limit)).await
+    }
+
+    /// Fetches markets closed at or after `since` (unix seconds), most recently closed first
+    /// Used to top up a resolved-market set without refetching all of it
+    pub async fn fetch_recently_resolved_markets(&self, since: i64) -> Result<Vec<Market>> {
+        let limit = 100;
+        let mut markets = Vec::new();
+
+        for page in 0..RECENTLY_RESOLVED_MAX_PAGES {
+            let request = self.gamma.get(&self.client, |request| {
+                request.query(&[
+                    ("closed", "true"),
+                    ("order", "closedTime"),
+                    ("ascending", "false"),
+                    ("limit", &limit.to_string()),
+                    ("offset", &(page * limit).to_string()),
+                ])
+            });
+            let body = self
+                .cancellable(async { Ok(request.await?.error_for_status()?.text().await?) })
+                .await?;
+            let page = parse_page::<Market>(&body, "markets")?;
+
+            let count = page.received;
+            let batch = page.records;
+            // Markets without a close time can't be placed in the window, so they're kept
+            let reached_older = batch
+                .iter()
+                .any(|m| m.resolution_timestamp().is_some_and(|closed_at| closed_at < since));
+            markets.extend(
+                batch
+                    .into_iter()
+                    .filter(|m| m.resolution_timestamp().is_none_or(|closed_at| closed_at >= since)),
+            );
+
+            if count < limit || reached_older {
+                break;
+            }
+        }
+
+        Ok(markets)
+    }
+
+    /// Fetches markets (open or closed) for a list of condition IDs
+    /// IDs are queried in batches, with batches fetched concurrently
+    pub async fn fetch_markets_by_condition_ids(&self, condition_ids: &[String]) -> Result<Vec<Market>> {
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent_requests));
+        let mut tasks = JoinSet::new();
+
+        for batch in condition_ids.chunks(CONDITION_IDS_PER_REQUEST) {
+            let permit = self.cancellable(async { Ok(semaphore.clone().acquire_owned().await?) }).await?;
+            let client = self.client.clone();
+            let endpoint = self.gamma.clone();
+            let batch = batch.to_vec();
+
+            tasks.spawn(async move {
+                let result = fetch_markets_by_condition_ids_internal(&client, &endpoint, &batch).await;
+                drop(permit);
+                result
+            });
+        }
+
+        let mut all_markets = Vec::new();
+        while let Some(result) = self.join_next(&mut tasks).await? {
+            all_markets.extend(result??);
+        }
+
+        Ok(all_markets)
+    }
+
+    /// Fetches a market by its URL slug (the last part of its polymarket.com address)
+    pub async fn fetch_market_by_slug(&self, slug: &str) -> Result<Option<Market>> {
+        let markets: Vec<Market> = self.gamma
+            .get(&self.client, |request| request.query(&[("slug", slug)]))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(markets.into_iter().next())
+    }
+
+    /// Finds markets (open or closed) by question text or slug
+    /// An exact slug match comes first, then markets whose question or slug contains every
+    /// word of the query, then the rest of the matching events' markets, each most liquid first
+    pub async fn search_markets(&self, query: &str) -> Result<Vec<Market>> {
+        #[derive(serde::Deserialize)]
+        struct SearchResults {
+            #[serde(default)]
+            events: Option<Vec<Event>>,
+        }
+
+        let query = query.trim();
+        let exact = if query.is_empty() || query.contains(char::is_whitespace) {
+            None
+        } else {
+            self.fetch_market_by_slug(query).await?
+        };
+
+        let results: SearchResults = self.search
+            .get(&self.client, |request| {
+                request.query(&[
+                    ("q", query),
+                    ("keep_closed_markets", "1"),
+                    ("limit_per_type", "20"),
+                ])
+            })
+            .await?
+            .error_for_status()
+            .context("Market search failed")?
+            .json()
+            .await
+            .context("Failed to parse market search results")?;
+
+        let mut found: Vec<Market> = results
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|event| event.markets)
+            .collect();
+        found.sort_by(|a, b| {
+            b.matches_query(query)
+                .cmp(&a.matches_query(query))
+                .then_with(|| b.parsed_liquidity().total_cmp(&a.parsed_liquidity()))
+        });
+
+        let mut seen = HashSet::new();
+        Ok(exact
+            .into_iter()
+            .chain(found)
+            .filter(|market| seen.insert(market.condition_id.clone().unwrap_or_else(|| market.question.clone())))
+            .collect())
+    }
+
+    /// Fetches the current CLOB order book for an outcome token
+    pub async fn fetch_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.budget.acquire().await;
+        let book: OrderBook = self.client
+            .get(format!("{}/book", self.clob_url))
+            .query(&[("token_id", token_id)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(book)
+    }
+
+    /// Fetches the current best bid and ask of each outcome token from the CLOB, keyed by
+    /// token ID; a side with no orders is None
+    /// Tokens are quoted in batches, one request per batch, so this is far cheaper than
+    /// fetching each token's order book
+    pub async fn fetch_token_quotes(&self, token_ids: &[String]) -> Result<HashMap<String, TokenQuote>> {
+        let mut quotes = HashMap::with_capacity(token_ids.len());
+        for batch in token_ids.chunks(TOKENS_PER_PRICES_REQUEST) {
+            let body: Vec<serde_json::Value> = batch
+                .iter()
+                .flat_map(|token_id| {
+                    ["BUY", "SELL"].map(|side| serde_json::json!({ "token_id": token_id, "side": side }))
+                })
+                .collect();
+            let request = self.client.post(format!("{}/prices", self.clob_url)).json(&body).send();
+            let prices: HashMap<String, TokenPrices> = self
+                .cancellable(async {
+                    self.budget.acquire().await;
+                    Ok(request.await?.error_for_status()?.json().await?)
+                })
+                .await?;
+
+            for token_id in batch {
+                let token_prices = prices.get(token_id);
+                let quote = TokenQuote::from_prices(token_id, token_prices.unwrap_or(&TokenPrices::default()));
+                quotes.insert(token_id.clone(), quote);
+            }
+        }
+
+        Ok(quotes)
+    }
+
+    /// Fetches the current CLOB quote of each of a market's outcomes, in outcome order
+    pub async fn fetch_market_quotes(&self, market: &Market) -> Result<Vec<TokenQuote>> {
+        let condition_id = market.condition_id.as_deref().unwrap_or(&market.question);
+        let token_ids = market
+            .parsed_clob_token_ids()
+            .with_context(|| format!("Market {} has no CLOB token IDs", condition_id))?;
+        let mut quotes = self.fetch_token_quotes(&token_ids).await?;
+
+        Ok(token_ids
+            .iter()
+            .map(|token_id| {
+                quotes.remove(token_id).unwrap_or_else(|| TokenQuote::from_prices(token_id, &TokenPrices::default()))
+            })
+            .collect())
+    }
+
+    /// Fetches price history for every outcome of a market over a trailing interval
+    pub async fn fetch_price_history(&self, condition_id: &str, interval: PriceInterval) -> Result<Vec<PriceSeries>> {
+        let market = self
+            .fetch_markets_by_condition_ids(&[condition_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("No market found for condition ID {}", condition_id))?;
+
+        self.fetch_outcome_series(&market, HOURLY_FIDELITY, &[("interval", interval.as_str())]).await
+    }
+
+    /// Fetches every outcome's price history for a market between two timestamps, one point
+    /// per `fidelity_minutes`
+    pub async fn fetch_market_price_history(
+        &self,
+        market: &Market,
+        start_ts: i64,
+        end_ts: i64,
+        fidelity_minutes: u32,
+    ) -> Result<Vec<PriceSeries>> {
+        let range = [("startTs", start_ts.to_string()), ("endTs", end_ts.to_string())];
+        let range: Vec<(&str, &str)> = range.iter().map(|(key, value)| (*key, value.as_str())).collect();
+        self.fetch_outcome_series(market, fidelity_minutes, &range).await
+    }
+
+    /// Queries the price history of each of a market's outcome tokens
+    async fn fetch_outcome_series(
+        &self,
+        market: &Market,
+        fidelity_minutes: u32,
+        range: &[(&str, &str)],
+    ) -> Result<Vec<PriceSeries>> {
+        let condition_id = market.condition_id.as_deref().unwrap_or(&market.question);
+        let token_ids = market
+            .parsed_clob_token_ids()
+            .with_context(|| format!("Market {} has no CLOB token IDs", condition_id))?;
+        let outcomes = market.parsed_outcomes().unwrap_or_default();
+
+        let mut series = Vec::with_capacity(token_ids.len());
+        for (index, token_id) in token_ids.into_iter().enumerate() {
+            let points = self.fetch_token_price_history(&token_id, fidelity_minutes, range).await?;
+            series.push(PriceSeries {
+                outcome: outcomes.get(index).cloned().unwrap_or_else(|| format!("Outcome {}", index)),
+                token_id,
+                points,
+            });
+        }
+
+        Ok(series)
+    }
+
+    /// Fetches an outcome token's price history between two timestamps
+    pub async fn fetch_token_price_history_range(&self, token_id: &str, start_ts: i64, end_ts: i64) -> Result<Vec<PricePoint>> {
+        self.fetch_token_price_history(
+            token_id,
+            HOURLY_FIDELITY,
+            &[("startTs", &start_ts.to_string()), ("endTs", &end_ts.to_string())],
+        )
+        .await
+    }
+
+    /// Queries the CLOB price history endpoint for one outcome token
+    async fn fetch_token_price_history(
+        &self,
+        token_id: &str,
+        fidelity_minutes: u32,
+        range: &[(&str, &str)],
+    ) -> Result<Vec<PricePoint>> {
+        self.budget.acquire().await;
+        let history: PriceHistory = self.client
+            .get(format!("{}/prices-history", self.clob_url))
+            .query(&[("market", token_id), ("fidelity", &fidelity_minutes.to_string())])
+            .query(range)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(history.history)
+    }
+
+    /// Returns an outcome token's last traded price at or before a timestamp
+    /// (looking back up to a day), or None if it didn't trade in that window
+    pub async fn fetch_price_at(&self, token_id: &str, timestamp: i64) -> Result<Option<f64>> {
+        let history = self.fetch_token_price_history_range(token_id, timestamp - 24 * 3600, timestamp).await?;
+
+        Ok(history
+            .iter()
+            .filter(|point| point.timestamp <= timestamp)
+            .max_by_key(|point| point.timestamp)
+            .map(|point| point.price))
     }
 
     /// Fetches a single page of markets with optional closed filter
-    async fn fetch_markets_page(&self, offset: usize, limit: usize, _closed: bool) -> Result<Vec<Market>> {
-        fetch_resolved_markets_page(&self.client, offset, limit).await
+    async fn fetch_markets_page(&self, offset: usize, limit: usize, _closed: bool) -> Result<Page<Market>> {
+        self.cancellable(fetch_resolved_markets_page(&self.client, &self.gamma, offset, limit)).await
     }
 }
 
+/// Pages of a concurrent pagination keyed by offset, so markets come back in API order
+/// however the requests complete, along with the offsets that failed
+#[derive(Default)]
+struct PageSet {
+    pages: BTreeMap<usize, Page<Market>>,
+    failed: BTreeSet<usize>,
+}
+
+impl PageSet {
+    fn insert(&mut self, offset: usize, page: Page<Market>) {
+        self.failed.remove(&offset);
+        self.pages.insert(offset, page);
+    }
+
+    fn fail(&mut self, offset: usize) {
+        if !self.pages.contains_key(&offset) {
+            self.failed.insert(offset);
+        }
+    }
+
+    /// Marks requested pages below the last one received that never came back (e.g. a
+    /// panicked task) as failed; requests past it were abandoned, not lost
+    fn mark_gaps(&mut self, requested: &std::collections::HashSet<usize>) {
+        let Some(&last) = self.pages.keys().next_back() else { return };
+        for &offset in requested.iter().filter(|&&offset| offset < last) {
+            self.fail(offset);
+        }
+    }
+
+    fn market_count(&self) -> usize {
+        self.pages.values().map(|page| page.records.len()).sum()
+    }
+
+    /// Offset after the last page, if that page was full and so isn't the end of the data
+    fn next_offset(&self, limit: usize) -> Option<usize> {
+        let (&offset, page) = self.pages.iter().next_back()?;
+        (page.received == limit).then_some(offset + limit)
+    }
+
+    fn into_markets(self) -> Vec<Market> {
+        self.pages.into_values().flat_map(|page| page.records).collect()
+    }
+}
+
+/// One page of a list endpoint, parsed record by record
+pub struct Page<T> {
+    pub records: Vec<T>,
+    /// Records the API returned, including any that didn't parse, so a page is only
+    /// taken for the last one when the API really ran out of data
+    pub received: usize,
+    /// Why each skipped record didn't parse
+    pub rejected: Vec<String>,
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Parses a JSON list, skipping records that don't fit the model instead of losing the page
+    /// An empty body is an empty page; a body that isn't a list is an error
+    pub fn parse(body: &str) -> Result<Self> {
+        if body.trim().is_empty() {
+            return Ok(Self { records: Vec::new(), received: 0, rejected: Vec::new() });
+        }
+
+        let items: Vec<serde_json::Value> = serde_json::from_str(body).context("Response is not a JSON list")?;
+        let received = items.len();
+        let mut records = Vec::with_capacity(received);
+        let mut rejected = Vec::new();
+        for item in items {
+            match serde_json::from_value(item) {
+                Ok(record) => records.push(record),
+                Err(e) => rejected.push(e.to_string()),
+            }
+        }
+
+        Ok(Self { records, received, rejected })
+    }
+}
+
+/// Parses a page, warning about the records it had to skip
+fn parse_page<T: DeserializeOwned>(body: &str, what: &str) -> Result<Page<T>> {
+    let page = Page::parse(body)?;
+    if let Some(first) = page.rejected.first() {
+        eoutln!(
+            "Warning: Skipped {} of {} {} that no longer match the model ({}); run doctor --fields",
+            page.rejected.len(),
+            page.received,
+            what,
+            first
+        );
+    }
+    Ok(page)
+}
+
 /// Helper function to fetch a single page
 async fn fetch_page_internal(
     client: &reqwest::Client,
+    endpoint: &Endpoint,
     offset: usize,
     limit: usize,
-) -> Result<Vec<Market>> {
-    let markets: Vec<Market> = client
-        .get(GAMMA_API_URL)
-        .query(&[
-            ("active", "true"),
-            ("closed", "false"),
-            ("limit", &limit.to_string()),
-            ("offset", &offset.to_string()),
-        ])
-        .send()
+) -> Result<Page<Market>> {
+    let body = endpoint
+        .get(client, |request| {
+            request.query(&[
+                ("active", "true"),
+                ("closed", "false"),
+                ("include_tag", "true"),
+                ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
+            ])
+        })
         .await?
-        .json()
+        .text()
         .await?;
 
-    Ok(markets)
+    parse_page(&body, "markets")
 }
 
 /// Helper function to fetch a single page of resolved markets
 async fn fetch_resolved_markets_page(
     client: &reqwest::Client,
+    endpoint: &Endpoint,
     offset: usize,
     limit: usize,
-) -> Result<Vec<Market>> {
-    let response = client
-        .get(GAMMA_API_URL)
-        .query(&[
-            ("closed", "true"),
-            ("limit", &limit.to_string()),
-            ("offset", &offset.to_string()),
-        ])
-        .send()
+) -> Result<Page<Market>> {
+    let response = endpoint
+        .get(client, |request| {
+            request.query(&[
+                ("closed", "true"),
+                ("limit", &limit.to_string()),
+                ("offset", &offset.to_string()),
+            ])
+        })
         .await?;
 
-    // Check HTTP status
-    if !response.status().is_success() {
-        return Ok(Vec::new()); // Return empty vec for non-success status
-    }
+    // A rate limit or server error is a failed page, not the end of the data
+    let response = response.error_for_status()?;
 
-    // Get response text first to check if empty
+    // A body that isn't a list is a failed page too; markets that don't parse are skipped
     let text = response.text().await?;
-    if text.trim().is_empty() {
-        return Ok(Vec::new()); // Return empty vec for empty responses
-    }
+    parse_page(&text, "markets").with_context(|| format!("Unreadable page at offset {}", offset))
+}
 
-    // Try to parse JSON
-    match serde_json::from_str::<Vec<Market>>(&text) {
-        Ok(markets) => Ok(markets),
-        Err(e) => {
-            // If JSON parsing fails, treat as end of data
-            eprintln!("JSON decode error at offset {}: {} (treating as end of data)", offset, e);
-            Ok(Vec::new())
-        }
-    }
+/// Helper function to fetch one batch of markets by condition ID
+async fn fetch_markets_by_condition_ids_internal(
+    client: &reqwest::Client,
+    endpoint: &Endpoint,
+    condition_ids: &[String],
+) -> Result<Vec<Market>> {
+    let mut query: Vec<(&str, &str)> = condition_ids
+        .iter()
+        .map(|id| ("condition_ids", id.as_str()))
+        .collect();
+    let limit = condition_ids.len().to_string();
+    query.push(("limit", &limit));
+
+    let body = endpoint
+        .get(client, |request| request.query(&query))
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(parse_page(&body, "markets")?.records)
 }
 
 impl Default for PolymarketClient {
@@ -0,0 +1,253 @@
+use crate::export::{Column, ColumnType, CsvExport};
+use crate::models::{Trade, WalletPerformance};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Directory in the store holding exported cluster reports
+const CLUSTER_REPORTS_DIR: &str = "cluster_reports";
+
+/// A market traded by more than one wallet in a cluster
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedMarket {
+    pub condition_id: String,
+    pub title: Option<String>,
+    /// Members that traded the market, in order of their first trade
+    pub wallets: Vec<String>,
+    /// First trade in the market by any member (unix seconds)
+    pub first_trade_at: i64,
+}
+
+/// One wallet's contribution to a cluster
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterMember {
+    pub wallet: String,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub total_invested: f64,
+    pub net_profit: f64,
+    pub roi: f64,
+    pub unrealized_pnl: f64,
+}
+
+/// Aggregate performance of a group of wallets investigated as one trader
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterReport {
+    pub id: String,
+    pub generated_at: i64,
+    pub wallets: Vec<String>,
+    pub total_invested: f64,
+    pub total_payout: f64,
+    pub net_profit: f64,
+    pub roi: f64,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    pub losses: usize,
+    /// Pooled across members: total wins / total resolved positions
+    pub win_rate: f64,
+    pub unrealized_pnl: f64,
+    /// Markets traded by two or more members, earliest first
+    pub shared_markets: Vec<SharedMarket>,
+    pub members: Vec<ClusterMember>,
+}
+
+impl ClusterReport {
+    /// Builds the report from each member's trades and analyzed performance
+    pub fn build(id: &str, members: &[(Vec<Trade>, WalletPerformance)]) -> Self {
+        let sum = |f: fn(&WalletPerformance) -> f64| members.iter().map(|(_, p)| f(p)).sum::<f64>();
+        let total_invested = sum(|p| p.total_invested);
+        let total_payout = sum(|p| p.total_payout);
+        let net_profit = sum(|p| p.net_profit);
+        let unrealized_pnl = sum(|p| p.unrealized_pnl);
+        let resolved_positions: usize = members.iter().map(|(_, p)| p.resolved_positions).sum();
+        let wins: usize = members.iter().map(|(_, p)| p.wins).sum();
+        let losses: usize = members.iter().map(|(_, p)| p.losses).sum();
+
+        let win_rate = if resolved_positions > 0 {
+            wins as f64 / resolved_positions as f64 * 100.0
+        } else {
+            0.0
+        };
+        let roi = if total_invested > 0.0 {
+            net_profit / total_invested * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            id: id.to_string(),
+            generated_at: Utc::now().timestamp(),
+            wallets: members.iter().map(|(_, p)| p.wallet_address.clone()).collect(),
+            total_invested,
+            total_payout,
+            net_profit,
+            roi,
+            resolved_positions,
+            wins,
+            losses,
+            win_rate,
+            unrealized_pnl,
+            shared_markets: shared_markets(members),
+            members: members
+                .iter()
+                .map(|(_, p)| ClusterMember {
+                    wallet: p.wallet_address.clone(),
+                    resolved_positions: p.resolved_positions,
+                    wins: p.wins,
+                    win_rate: p.win_rate,
+                    total_invested: p.total_invested,
+                    net_profit: p.net_profit,
+                    roi: p.roi,
+                    unrealized_pnl: p.unrealized_pnl,
+                })
+                .collect(),
+        }
+    }
+
+    /// The first market two or more members traded, where coordination could have started
+    pub fn earliest_common_market(&self) -> Option<&SharedMarket> {
+        self.shared_markets.first()
+    }
+
+    /// Saves the report as JSON under `cluster_reports/`, returning its path
+    pub fn save(&self, store: &Store) -> Result<PathBuf> {
+        let dir = store.path(CLUSTER_REPORTS_DIR);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let name = format!("{}/{}.json", CLUSTER_REPORTS_DIR, self.id);
+        store.save_json(&name, self)?;
+        Ok(store.path(&name))
+    }
+
+    /// Builds a CSV export with one row per member
+    pub fn members_csv(&self, decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("cluster_id", ColumnType::String, "Cluster the wallet belongs to"),
+                Column::new("wallet", ColumnType::String, "Wallet address"),
+                Column::new("resolved_positions", ColumnType::Integer, "Positions held to resolution or exited"),
+                Column::new("wins", ColumnType::Integer, "Winning resolved positions"),
+                Column::new("win_rate", ColumnType::Decimal, "Wins / resolved positions").unit("percent"),
+                Column::new("total_invested", ColumnType::Decimal, "Cost basis of resolved positions").unit("usd"),
+                Column::new("net_profit", ColumnType::Decimal, "Realized profit on resolved positions").unit("usd"),
+                Column::new("roi", ColumnType::Decimal, "Net profit / total invested").unit("percent"),
+                Column::new("unrealized_pnl", ColumnType::Decimal, "Open positions marked to market").unit("usd"),
+                Column::new("share_of_profit", ColumnType::Decimal, "Member's share of the cluster's net profit").unit("fraction"),
+            ],
+            decimal_places,
+        );
+
+        for member in &self.members {
+            let share = (self.net_profit != 0.0).then(|| member.net_profit / self.net_profit);
+            export.push_row(vec![
+                self.id.as_str().into(),
+                member.wallet.as_str().into(),
+                member.resolved_positions.into(),
+                member.wins.into(),
+                member.win_rate.into(),
+                member.total_invested.into(),
+                member.net_profit.into(),
+                member.roi.into(),
+                member.unrealized_pnl.into(),
+                share.into(),
+            ]);
+        }
+
+        export
+    }
+
+    /// Prints the cluster's aggregate performance and shared markets
+    pub fn print(&self) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("CLUSTER REPORT: {}", self.id);
+        outln!("{}", "=".repeat(80));
+
+        outln!("\n--- Combined Performance ({} wallets) ---", self.wallets.len());
+        outln!("Resolved Positions:   {}", self.resolved_positions);
+        outln!("Wins / Losses:        {} / {}", self.wins, self.losses);
+        outln!("Win Rate:             {:.1}%", self.win_rate);
+        outln!("Total Invested:       ${:.2}", self.total_invested);
+        outln!("Total Payout:         ${:.2}", self.total_payout);
+        outln!("Net Profit:           ${:.2}", self.net_profit);
+        outln!("ROI:                  {:.1}%", self.roi);
+        outln!("Unrealized P&L:       ${:.2}", self.unrealized_pnl);
+
+        outln!("\n--- Members ---");
+        for member in &self.members {
+            outln!(
+                "{}  {:>4} resolved | Win {:>5.1}% | Invested ${:>10.2} | Profit ${:>10.2}",
+                member.wallet, member.resolved_positions, member.win_rate, member.total_invested, member.net_profit
+            );
+        }
+
+        outln!("\n--- Shared Markets ({}) ---", self.shared_markets.len());
+        match self.earliest_common_market() {
+            Some(market) => outln!(
+                "Earliest Common:      {} ({}, {} wallets)",
+                market.title.as_deref().unwrap_or(&market.condition_id),
+                format_time(market.first_trade_at),
+                market.wallets.len()
+            ),
+            None => outln!("No market was traded by more than one member."),
+        }
+        for market in self.shared_markets.iter().skip(1).take(9) {
+            outln!(
+                "  {} ({}, {} wallets)",
+                market.title.as_deref().unwrap_or(&market.condition_id),
+                format_time(market.first_trade_at),
+                market.wallets.len()
+            );
+        }
+        if self.shared_markets.len() > 10 {
+            outln!("  ... and {} more", self.shared_markets.len() - 10);
+        }
+
+        outln!("{}", "=".repeat(80));
+    }
+}
+
+/// Finds markets traded by two or more members, earliest first
+fn shared_markets(members: &[(Vec<Trade>, WalletPerformance)]) -> Vec<SharedMarket> {
+    // Condition ID -> (title, wallet -> first trade timestamp)
+    let mut markets: HashMap<&str, (Option<&String>, HashMap<&str, i64>)> = HashMap::new();
+    for (trades, performance) in members {
+        for trade in trades {
+            let (title, wallets) = markets.entry(trade.condition_id.as_str()).or_default();
+            if title.is_none() {
+                *title = trade.title.as_ref();
+            }
+            let first = wallets.entry(performance.wallet_address.as_str()).or_insert(trade.timestamp);
+            *first = (*first).min(trade.timestamp);
+        }
+    }
+
+    let mut shared: Vec<SharedMarket> = markets
+        .into_iter()
+        .filter(|(_, (_, wallets))| wallets.len() >= 2)
+        .map(|(condition_id, (title, wallets))| {
+            let mut wallets: Vec<(&str, i64)> = wallets.into_iter().collect();
+            wallets.sort_by_key(|(_, first)| *first);
+            SharedMarket {
+                condition_id: condition_id.to_string(),
+                title: title.cloned(),
+                first_trade_at: wallets[0].1,
+                wallets: wallets.into_iter().map(|(wallet, _)| wallet.to_string()).collect(),
+            }
+        })
+        .collect();
+
+    shared.sort_by_key(|market| market.first_trade_at);
+    shared
+}
+
+/// Formats unix seconds as a UTC date
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
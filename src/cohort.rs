@@ -0,0 +1,276 @@
+use crate::export::{Column, ColumnType, CsvExport};
+use crate::models::{Market, Trade, WalletPerformance};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Label for positions in markets without a category
+const UNCATEGORIZED: &str = "uncategorized";
+/// Categories listed per member in the printed report
+const TOP_CATEGORIES: usize = 3;
+
+/// One wallet's figures in a cohort comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct CohortMember {
+    /// 1 = most consistent
+    pub rank: usize,
+    pub wallet: String,
+    pub total_trades: usize,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub total_invested: f64,
+    pub net_profit: f64,
+    pub roi: f64,
+    pub unrealized_pnl: f64,
+    /// Mean per-position return divided by its standard deviation (a Sharpe ratio
+    /// without a risk-free rate); None with fewer than two resolved positions
+    pub consistency: Option<f64>,
+    /// Share of the amount invested in each category, largest first
+    pub categories: Vec<(String, f64)>,
+}
+
+/// How similar two members' trading is
+#[derive(Debug, Clone, Serialize)]
+pub struct CohortOverlap {
+    pub wallet_a: String,
+    pub wallet_b: String,
+    /// Overlap of the two category mixes by amount invested: 1.0 = identical mix, 0.0 = disjoint
+    pub category_overlap: f64,
+    /// Markets both wallets traded
+    pub shared_markets: usize,
+}
+
+/// Side-by-side comparison of a handful of wallets, ranked by consistency
+#[derive(Debug, Clone, Serialize)]
+pub struct CohortReport {
+    /// Ordered by rank
+    pub members: Vec<CohortMember>,
+    /// Every pair of members, most similar first
+    pub overlaps: Vec<CohortOverlap>,
+}
+
+impl CohortReport {
+    /// Builds the comparison from each wallet's trades and analyzed performance
+    /// `resolved_markets` supplies the category of each resolved position
+    pub fn build(members: &[(Vec<Trade>, WalletPerformance)], resolved_markets: &[Market]) -> Self {
+        let categories: HashMap<&str, String> = resolved_markets
+            .iter()
+            .filter_map(|m| Some((m.condition_id.as_deref()?, m.primary_category()?)))
+            .collect();
+
+        let mut cohort: Vec<CohortMember> = members
+            .iter()
+            .map(|(_, performance)| CohortMember {
+                rank: 0,
+                wallet: performance.wallet_address.clone(),
+                total_trades: performance.total_trades,
+                resolved_positions: performance.resolved_positions,
+                wins: performance.wins,
+                win_rate: performance.win_rate,
+                total_invested: performance.total_invested,
+                net_profit: performance.net_profit,
+                roi: performance.roi,
+                unrealized_pnl: performance.unrealized_pnl,
+                consistency: consistency(performance),
+                categories: category_mix(performance, &categories),
+            })
+            .collect();
+
+        // Wallets without enough history to measure consistency rank last, by ROI
+        cohort.sort_by(|a, b| match (a.consistency, b.consistency) {
+            (Some(x), Some(y)) => y.total_cmp(&x),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.roi.total_cmp(&a.roi),
+        });
+        for (i, member) in cohort.iter_mut().enumerate() {
+            member.rank = i + 1;
+        }
+
+        let markets: HashMap<&str, HashSet<&str>> = members
+            .iter()
+            .map(|(trades, p)| {
+                (p.wallet_address.as_str(), trades.iter().map(|t| t.condition_id.as_str()).collect())
+            })
+            .collect();
+
+        let mut overlaps = Vec::new();
+        for (i, a) in cohort.iter().enumerate() {
+            for b in &cohort[i + 1..] {
+                let shared_markets = match (markets.get(a.wallet.as_str()), markets.get(b.wallet.as_str())) {
+                    (Some(x), Some(y)) => x.intersection(y).count(),
+                    _ => 0,
+                };
+                overlaps.push(CohortOverlap {
+                    wallet_a: a.wallet.clone(),
+                    wallet_b: b.wallet.clone(),
+                    category_overlap: category_overlap(&a.categories, &b.categories),
+                    shared_markets,
+                });
+            }
+        }
+        overlaps.sort_by(|a, b| {
+            b.category_overlap
+                .total_cmp(&a.category_overlap)
+                .then(b.shared_markets.cmp(&a.shared_markets))
+        });
+
+        Self { members: cohort, overlaps }
+    }
+
+    /// Builds a CSV export with one row per member, in rank order
+    pub fn members_csv(&self, decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("rank", ColumnType::Integer, "Rank by consistency (1 = most consistent)"),
+                Column::new("wallet", ColumnType::String, "Wallet address"),
+                Column::new("total_trades", ColumnType::Integer, "Trades in the wallet's history"),
+                Column::new("resolved_positions", ColumnType::Integer, "Positions held to resolution or exited"),
+                Column::new("wins", ColumnType::Integer, "Winning resolved positions"),
+                Column::new("win_rate", ColumnType::Decimal, "Wins / resolved positions").unit("percent"),
+                Column::new("total_invested", ColumnType::Decimal, "Cost basis of resolved positions").unit("usd"),
+                Column::new("net_profit", ColumnType::Decimal, "Realized profit on resolved positions").unit("usd"),
+                Column::new("roi", ColumnType::Decimal, "Net profit / total invested").unit("percent"),
+                Column::new("unrealized_pnl", ColumnType::Decimal, "Open positions marked to market").unit("usd"),
+                Column::new("consistency", ColumnType::Decimal, "Mean / standard deviation of per-position returns"),
+                Column::new("top_category", ColumnType::String, "Category with the most invested"),
+                Column::new("top_category_share", ColumnType::Decimal, "Share invested in the top category").unit("fraction"),
+            ],
+            decimal_places,
+        );
+
+        for member in &self.members {
+            let top = member.categories.first();
+            export.push_row(vec![
+                member.rank.into(),
+                member.wallet.as_str().into(),
+                member.total_trades.into(),
+                member.resolved_positions.into(),
+                member.wins.into(),
+                member.win_rate.into(),
+                member.total_invested.into(),
+                member.net_profit.into(),
+                member.roi.into(),
+                member.unrealized_pnl.into(),
+                member.consistency.into(),
+                top.map(|(category, _)| category.as_str()).into(),
+                top.map(|(_, share)| *share).into(),
+            ]);
+        }
+
+        export
+    }
+
+    /// Prints the members side by side, then how much each pair overlaps
+    pub fn print(&self) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WALLET COHORT COMPARISON ({} wallets)", self.members.len());
+        outln!("{}", "=".repeat(80));
+
+        outln!(
+            "\n{:<4} {:<12} {:>8} {:>8} {:>9} {:>12} {:>11}",
+            "Rank", "Wallet", "Resolved", "Win %", "ROI %", "Net Profit", "Consistency"
+        );
+        for member in &self.members {
+            outln!(
+                "{:<4} {:<12} {:>8} {:>7.1}% {:>8.1}% {:>12.2} {:>11}",
+                member.rank,
+                short_wallet(&member.wallet),
+                member.resolved_positions,
+                member.win_rate,
+                member.roi,
+                member.net_profit,
+                member
+                    .consistency
+                    .map(|c| format!("{:.2}", c))
+                    .unwrap_or_else(|| "-".to_string())
+            );
+        }
+
+        outln!("\n--- Categories (share of amount invested) ---");
+        for member in &self.members {
+            let top: Vec<String> = member
+                .categories
+                .iter()
+                .take(TOP_CATEGORIES)
+                .map(|(category, share)| format!("{} {:.0}%", category, share * 100.0))
+                .collect();
+            outln!(
+                "{:<12} {}",
+                short_wallet(&member.wallet),
+                if top.is_empty() { "-".to_string() } else { top.join(", ") }
+            );
+        }
+
+        outln!("\n--- Overlap ---");
+        for overlap in &self.overlaps {
+            outln!(
+                "{} ~ {}  categories {:>5.1}% | {} shared markets",
+                short_wallet(&overlap.wallet_a),
+                short_wallet(&overlap.wallet_b),
+                overlap.category_overlap * 100.0,
+                overlap.shared_markets
+            );
+        }
+
+        outln!("{}", "=".repeat(80));
+    }
+}
+
+/// Mean over standard deviation of per-position returns (profit / amount invested)
+fn consistency(performance: &WalletPerformance) -> Option<f64> {
+    let returns: Vec<f64> = performance
+        .positions
+        .iter()
+        .filter(|p| p.total_invested > 0.0)
+        .map(|p| p.profit / p.total_invested)
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    (std_dev > 0.0).then(|| mean / std_dev)
+}
+
+/// Share of the amount invested in each category, largest first
+fn category_mix(performance: &WalletPerformance, categories: &HashMap<&str, String>) -> Vec<(String, f64)> {
+    let mut invested: BTreeMap<&str, f64> = BTreeMap::new();
+    for position in &performance.positions {
+        let category = categories
+            .get(position.condition_id.as_str())
+            .map(String::as_str)
+            .unwrap_or(UNCATEGORIZED);
+        *invested.entry(category).or_default() += position.total_invested;
+    }
+
+    let total: f64 = invested.values().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut mix: Vec<(String, f64)> = invested
+        .into_iter()
+        .map(|(category, amount)| (category.to_string(), amount / total))
+        .collect();
+    mix.sort_by(|a, b| b.1.total_cmp(&a.1));
+    mix
+}
+
+/// Histogram intersection of two category mixes
+fn category_overlap(a: &[(String, f64)], b: &[(String, f64)]) -> f64 {
+    let b: HashMap<&str, f64> = b.iter().map(|(category, share)| (category.as_str(), *share)).collect();
+    a.iter()
+        .filter_map(|(category, share)| Some(share.min(*b.get(category.as_str())?)))
+        .sum()
+}
+
+/// First and last characters of an address, for tables
+fn short_wallet(wallet: &str) -> String {
+    if wallet.len() <= 12 {
+        return wallet.to_string();
+    }
+    format!("{}..{}", &wallet[..6], &wallet[wallet.len() - 4..])
+}
@@ -0,0 +1,805 @@
+use crate::alerts::{Alert, AlertDispatcher};
+use crate::backtest::{Backtester, PriceRecorder};
+use crate::config::{split_list, ArbitrageConfig};
+use crate::daemon::Daemon;
+use crate::dashboard::{Dashboard, DashboardState};
+use crate::exchange::ExchangeClient;
+use crate::executor::ClobExecutor;
+use crate::fees::TradingCosts;
+use crate::guardrails::Guardrails;
+use crate::manifold::ManifoldClient;
+use crate::metrics::{EfficiencyLog, OpportunityWindowLog};
+use crate::models::{EventArbitrageKind, LinkArbitrageOpportunity, SnapshotVerdict};
+use crate::paper::{PaperLedger, PaperTrader};
+use crate::polling::AdaptivePolling;
+use crate::priority::PriorityAlerts;
+use crate::scanner::{CategoryFilter, ScanPass};
+use crate::shutdown;
+use crate::signals::{ArbitrageSignalTracker, SignalLog};
+use crate::snapshots::{self, OrderBookSnapshotter};
+use crate::store::Store;
+use crate::trade_history::TradeHistoryCache;
+use crate::{
+    ArbitrageOpportunity, ArbitrageScanner, Config, EventArbitrageOpportunity, Market, PolymarketClient, WalletAnalyzer,
+};
+use super::args::{flag_value, parse_age_secs, resolving_within_hours};
+use super::wallets::analyze_exchange_wallet;
+use super::{format_timestamp, write_json};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Run a single scan iteration, returning the binary, event and linked-pair opportunities it found
+async fn run_single_scan(
+    client: &impl ExchangeClient,
+    scanner: &ArbitrageScanner,
+    efficiency_log: Option<&EfficiencyLog>,
+) -> Result<(Vec<ArbitrageOpportunity>, Vec<EventArbitrageOpportunity>, Vec<LinkArbitrageOpportunity>)> {
+    let total_start = Instant::now();
+
+    // Fetch all active markets and events (for multi-market arbitrage) and scan them
+    let ScanPass {
+        markets,
+        events,
+        opportunities,
+        event_opportunities,
+        link_opportunities,
+        stale_suppressed,
+        fetch_duration,
+        scan_duration,
+    } = scanner.fetch_and_scan(client).await?;
+
+    outln!("✓ Fetched {} markets and {} events in {:.2}s (concurrent pagination)\n",
+        markets.len(),
+        events.len(),
+        fetch_duration.as_secs_f64()
+    );
+    outln!("✓ Scanned markets in {:.3}s (parallel processing)\n",
+        scan_duration.as_secs_f64()
+    );
+    if let Some(hours) = scanner.max_trade_age_hours() {
+        let flagged = opportunities.iter().filter(|o| o.stale).count();
+        if stale_suppressed > 0 {
+            outln!("⏸️  Skipped {} opportunities in markets without a trade in the last {}h\n", stale_suppressed, hours);
+        }
+        if flagged > 0 {
+            outln!("⚠️  {} opportunities are in markets without a trade in the last {}h\n", flagged, hours);
+        }
+    }
+
+    // Track how efficiently the venue is pricing binary markets over time
+    if let Some(efficiency) = scanner.efficiency(&markets) {
+        outln!("📊 Market efficiency index: {:.4} (volume-weighted |YES+NO-1| across {} markets)\n",
+            efficiency.index,
+            efficiency.markets
+        );
+        if let Some(Err(e)) = efficiency_log.map(|log| log.record(&efficiency)) {
+            eoutln!("Warning: Failed to record market efficiency: {}", e);
+        }
+    }
+
+    // Display results
+    if opportunities.is_empty() {
+        match (scanner.threshold(), scanner.overpriced_threshold()) {
+            (Some(threshold), Some(upper)) => outln!(
+                "No arbitrage opportunities found (threshold: total < ${:.3} or > ${:.3})",
+                threshold, upper
+            ),
+            (Some(threshold), None) => outln!("No arbitrage opportunities found (threshold: total < ${:.3})", threshold),
+            (None, _) => outln!("No arbitrage opportunities found (threshold: per-market break-even after fees)"),
+        }
+        outln!("\nThis is normal - efficient markets eliminate arbitrage quickly.");
+        outln!("Run this periodically to catch fleeting opportunities.");
+    } else {
+        outln!("Found {} arbitrage opportunities:\n", opportunities.len());
+        outln!("{}", "=".repeat(80));
+
+        for (i, opp) in opportunities.iter().enumerate() {
+            opp.print(i + 1);
+        }
+    }
+
+    if !event_opportunities.is_empty() {
+        outln!("\nFound {} negative-risk event arbitrage opportunities:\n", event_opportunities.len());
+        outln!("{}", "=".repeat(80));
+
+        for (i, opp) in event_opportunities.iter().enumerate() {
+            opp.print(i + 1);
+        }
+    }
+
+    if !scanner.links().is_empty() {
+        let missing = scanner.missing_links(&markets);
+        for link in &missing {
+            eoutln!("Warning: Linked pair {} => {} isn't among the active markets, skipping", link.implying, link.implied);
+        }
+        outln!(
+            "\nChecked {} linked market pairs: {} priced against their implication",
+            scanner.links().len() - missing.len(),
+            link_opportunities.len()
+        );
+        if !link_opportunities.is_empty() {
+            outln!("{}", "=".repeat(80));
+            for (i, opp) in link_opportunities.iter().enumerate() {
+                opp.print(i + 1);
+            }
+        }
+    }
+
+    let total_elapsed = total_start.elapsed();
+    outln!("\n[{}] Scan completed - Total: {:.2}s | Fetch: {:.2}s | Scan: {:.3}s",
+        Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+        total_elapsed.as_secs_f64(),
+        fetch_duration.as_secs_f64(),
+        scan_duration.as_secs_f64()
+    );
+
+    Ok((opportunities, event_opportunities, link_opportunities))
+}
+
+/// When the arbitrage scanner stops on its own, from the config and command line
+struct StopConditions {
+    max_hits: Option<usize>,
+    max_duration: Option<Duration>,
+}
+
+impl StopConditions {
+    /// `--run-forever` clears both limits; otherwise `--stop-after-n-hits` and
+    /// `--stop-after-duration` replace the config's. Paper trading and live execution keep
+    /// scanning past opportunities unless a hit limit is given on the command line
+    fn from_args(config: &ArbitrageConfig, args: &[String], trading: bool) -> Result<Self> {
+        if args.iter().any(|arg| arg == "--run-forever") {
+            return Ok(Self { max_hits: None, max_duration: None });
+        }
+
+        let max_hits = match flag_value(args, "--stop-after-n-hits") {
+            Some(n) => Some(n.parse::<usize>().context("--stop-after-n-hits must be a number of opportunities")?),
+            None if trading => None,
+            None => Some(config.stop_after_hits),
+        };
+        let max_secs = match flag_value(args, "--stop-after-duration") {
+            Some(value) => value
+                .parse::<u64>()
+                .ok()
+                .or_else(|| parse_age_secs(value).map(|secs| secs.max(0) as u64))
+                .ok_or_else(|| anyhow::anyhow!("Invalid duration: {} (use seconds or an age like 30m, 6h or 2d)", value))?,
+            None => config.stop_after_secs,
+        };
+
+        Ok(Self {
+            max_hits: max_hits.filter(|&hits| hits > 0),
+            max_duration: (max_secs > 0).then(|| Duration::from_secs(max_secs)),
+        })
+    }
+
+    fn describe(&self) -> String {
+        let hits = self.max_hits.map(|hits| match hits {
+            1 => "the first new opportunity".to_string(),
+            n => format!("{} new opportunities", n),
+        });
+        let duration = self.max_duration.map(|d| format!("{} minutes", d.as_secs().div_ceil(60)));
+        match (hits, duration) {
+            (None, None) => "Running until stopped (Ctrl+C)".to_string(),
+            (Some(hits), None) => format!("Stopping after {}", hits),
+            (None, Some(duration)) => format!("Stopping after {}", duration),
+            (Some(hits), Some(duration)) => format!("Stopping after {} or {}, whichever comes first", hits, duration),
+        }
+    }
+
+    /// Why the scanner should stop now, if a limit has been reached
+    fn reached(&self, hits: usize, elapsed: Duration) -> Option<String> {
+        if self.max_hits.is_some_and(|max| hits >= max) {
+            return Some(match hits {
+                1 => "Arbitrage opportunity found!".to_string(),
+                n => format!("{} new arbitrage opportunities found.", n),
+            });
+        }
+        if self.max_duration.is_some_and(|max| elapsed >= max) {
+            return Some(format!("Ran for {} minutes.", elapsed.as_secs() / 60));
+        }
+        None
+    }
+}
+
+/// Runs the arbitrage scanner until a stop condition or Ctrl+C, alerting on, snapshotting,
+/// paper trading and executing each new opportunity
+pub async fn run_scanner(config: &Config, args: &[String]) -> Result<()> {
+    outln!("Running arbitrage scanner...\n");
+
+    // Category filters on the command line replace those in the config
+    let include = flag_value(args, "--include")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.include_categories.clone());
+    let exclude = flag_value(args, "--exclude")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.exclude_categories.clone());
+    if !include.is_empty() {
+        outln!("Only scanning categories: {}", include.join(", "));
+    }
+    if !exclude.is_empty() {
+        outln!("Excluding categories: {}", exclude.join(", "));
+    }
+    let horizon = resolving_within_hours(&config.arbitrage, args)?;
+    if horizon > 0 {
+        outln!("Only scanning markets resolving within {} hours", horizon);
+    }
+
+    // Ctrl+C cancels the scan in flight, aborting its page fetches, rather than waiting it out
+    let shutdown = shutdown::ctrl_c_token();
+
+    // Create API client and scanner (reused across iterations)
+    let client = PolymarketClient::with_config(&config.api)?.with_cancellation(shutdown.clone());
+    let scanner = ArbitrageScanner::from_config(&config.arbitrage)
+        .with_categories(CategoryFilter::new(&include, &exclude))
+        .with_resolving_within(horizon);
+    let store = Store::open(&config.data_dir)?;
+    let signal_log = SignalLog::new(store.clone());
+    let efficiency_log = EfficiencyLog::new(store.clone());
+    let window_log = OpportunityWindowLog::new(store.clone());
+    let snapshotter = config
+        .snapshots
+        .enabled
+        .then(|| OrderBookSnapshotter::new(client.clone(), store.clone()));
+    let paper_trader = (config.paper.enabled || args.iter().any(|arg| arg == "--paper")).then(|| {
+        PaperTrader::new(client.clone(), store, TradingCosts::from_config(&config.arbitrage), &config.paper)
+    });
+    if paper_trader.is_some() {
+        outln!("Paper trading: simulating {} shares per leg against the order book\n", config.paper.shares);
+    }
+
+    // Live trading only with the explicit flag, and always behind the guardrails
+    let executor = if args.iter().any(|arg| arg == "--execute") {
+        let guardrails = Guardrails::new(config.executor.clone(), Store::open(&config.data_dir)?);
+        let executor = ClobExecutor::connect(
+            &config.executor,
+            &config.api,
+            guardrails,
+            TradingCosts::from_config(&config.arbitrage),
+        )
+        .await?;
+        outln!(
+            "⚠️  LIVE TRADING as {}: buying {} shares per leg of each new opportunity\n",
+            executor.address(),
+            config.executor.order_shares
+        );
+        Some(executor)
+    } else {
+        None
+    };
+    let mut arb_signals = ArbitrageSignalTracker::new().with_window_log(window_log);
+    let mut live_events: HashSet<(String, EventArbitrageKind)> = HashSet::new();
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+    let priority_alerts =
+        PriorityAlerts::from_config(client.clone(), &config.alerts, TradingCosts::from_config(&config.arbitrage))?;
+    if priority_alerts.is_some() {
+        outln!(
+            "Priority alerts: markets with ${:.0}+ 24h volume and ${:.0}+ liquidity, verified against the book\n",
+            config.alerts.priority.min_volume_24h, config.alerts.priority.min_liquidity
+        );
+    }
+
+    let stop = StopConditions::from_args(&config.arbitrage, args, paper_trader.is_some() || executor.is_some())?;
+    outln!("{}\n", stop.describe());
+    let started = Instant::now();
+    let mut hits = 0usize;
+
+    // Polling interval (10 seconds by default), or one that follows how often opportunities appear
+    let poll_interval = match flag_value(args, "--interval") {
+        Some(secs) => secs.parse::<u64>().context("--interval must be a number of seconds")?,
+        None => config.arbitrage.poll_interval_secs,
+    }
+    .max(1);
+    let mut adaptive = (config.arbitrage.adaptive_polling || args.iter().any(|arg| arg == "--adaptive")).then(|| {
+        let arbitrage = ArbitrageConfig { poll_interval_secs: poll_interval, ..config.arbitrage.clone() };
+        let history = signal_log.load().unwrap_or_else(|e| {
+            eoutln!("Warning: Failed to load past signals for adaptive polling: {}", e);
+            Vec::new()
+        });
+        AdaptivePolling::from_config(&arbitrage).with_history(&history, Utc::now().timestamp())
+    });
+    match &adaptive {
+        Some(adaptive) => outln!(
+            "Adaptive polling: {}s to {}s, resting at {}s this hour\n",
+            config.arbitrage.min_poll_interval_secs.max(1),
+            config.arbitrage.max_poll_interval_secs.max(poll_interval),
+            adaptive.resting_interval(Utc::now().timestamp()).as_secs()
+        ),
+        None => outln!("Polling every {}s\n", poll_interval),
+    }
+    let mut next_scan = tokio::time::Instant::now();
+    let mut scan_count = 0u32;
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_scan) => {
+                let scan_started = tokio::time::Instant::now();
+                let mut found = None;
+                scan_count += 1;
+                outln!("[{}] Scan #{} starting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), scan_count);
+
+                // Run scan with error handling
+                match run_single_scan(&client, &scanner, Some(&efficiency_log)).await {
+                    Ok((opportunities, event_opportunities, link_opportunities)) => {
+                        found = Some(opportunities.len() + event_opportunities.len() + link_opportunities.len());
+
+                        // Alert once per event arbitrage, when it first appears
+                        let current_events: HashSet<(String, EventArbitrageKind)> = event_opportunities
+                            .iter()
+                            .map(|o| (o.event_id.clone(), o.kind))
+                            .collect();
+                        for opp in &event_opportunities {
+                            if !live_events.contains(&(opp.event_id.clone(), opp.kind)) {
+                                alerts.send(&Alert::event_arbitrage(opp)).await;
+                            }
+                        }
+                        live_events = current_events;
+
+                        // Everything downstream works from the scan itself: a signal log that can't be
+                        // written is reported but holds up no alert or order
+                        let new_opportunities = arb_signals.new_opportunities(&opportunities);
+                        let new_links = arb_signals.new_links(&link_opportunities);
+                        if let Err(e) = arb_signals.observe(&signal_log, &opportunities, &link_opportunities) {
+                            eoutln!("Warning: Failed to record signals: {}", e);
+                        }
+
+                        // Alert once per opportunity, when it first appears
+                        hits += new_opportunities.len() + new_links.len();
+                        for opp in &new_opportunities {
+                            alerts.send(&Alert::arbitrage(opp)).await;
+                        }
+                        for opp in &new_links {
+                            alerts.send(&Alert::link_arbitrage(opp)).await;
+                        }
+                        if let Some(priority_alerts) = &priority_alerts {
+                            priority_alerts.process(&new_opportunities).await;
+                        }
+
+                        // Keep the books behind each new opportunity for later audit, as they
+                        // stood before any of our own orders took liquidity from them
+                        if let Some(snapshotter) = &snapshotter {
+                            snapshotter.capture_opportunities(&new_opportunities).await;
+                        }
+
+                        // Measure how much of each new edge survives the book and fees
+                        if let Some(paper_trader) = &paper_trader {
+                            paper_trader.execute_flagged(&new_opportunities).await;
+                        }
+                        if let Some(executor) = &executor {
+                            executor.execute_flagged(&new_opportunities).await;
+                        }
+
+                    }
+                    Err(e) if shutdown::is_cancelled(&e) => {
+                        outln!("\n[{}] Shutdown signal received, scan #{} cancelled, exiting...",
+                            Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+                            scan_count
+                        );
+                        outln!("Goodbye!");
+                        break;
+                    }
+                    Err(e) => {
+                        outln!("[{}] ERROR (Scan #{}): {}",
+                            Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+                            scan_count,
+                            e
+                        );
+                    }
+                }
+
+                // Positions in markets that have resolved no longer count toward the exposure limit
+                if let Some(executor) = &executor {
+                    executor.settle_resolved().await;
+                }
+
+                // A failed scan says nothing about how busy the markets are, so it keeps the interval
+                let wait = match (&mut adaptive, found) {
+                    (Some(adaptive), Some(found)) => adaptive.observe(found, Utc::now().timestamp()),
+                    (Some(adaptive), None) => adaptive.current(),
+                    (None, _) => Duration::from_secs(poll_interval),
+                };
+                next_scan = scan_started + wait;
+                if found.is_none() {
+                    outln!("Retrying in {} seconds...\n", wait.as_secs());
+                }
+
+                if let Some(reason) = stop.reached(hits, started.elapsed()) {
+                    outln!("\n[{}] {} Stopping scanner.", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), reason);
+
+                    // No next scan will grade the latest opportunities, so recheck their markets
+                    // when it would have run
+                    let pending = arb_signals.pending_rechecks();
+                    if pending > 0 {
+                        outln!("Rechecking {} new opportunities in {}s to grade them...", pending, wait.as_secs());
+                        tokio::select! {
+                            _ = tokio::time::sleep_until(next_scan) => {
+                                match arb_signals.recheck(&signal_log, &client, &scanner).await {
+                                    Ok(graded) => outln!("Graded {} signals", graded),
+                                    Err(e) => eoutln!("Warning: Failed to grade signals: {}", e),
+                                }
+                            }
+                            _ = shutdown.cancelled() => {}
+                        }
+                    }
+                    break;
+                }
+            }
+            _ = shutdown.cancelled() => {
+                outln!("\n[{}] Shutdown signal received, exiting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
+                outln!("Goodbye!");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the arbitrage scanner behind a full-screen dashboard, redrawn every second
+/// and after each scan; alerts are sent as in the scrolling scanner
+pub async fn run_dashboard(config: &Config, args: &[String]) -> Result<()> {
+    let include = flag_value(args, "--include")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.include_categories.clone());
+    let exclude = flag_value(args, "--exclude")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.exclude_categories.clone());
+    let poll_interval = match flag_value(args, "--interval") {
+        Some(secs) => secs.parse::<u64>().context("--interval must be a number of seconds")?,
+        None => config.arbitrage.poll_interval_secs,
+    }
+    .max(1);
+
+    let shutdown = shutdown::ctrl_c_token();
+    let client = PolymarketClient::with_config(&config.api)?.with_cancellation(shutdown.clone());
+    let scanner = ArbitrageScanner::from_config(&config.arbitrage)
+        .with_categories(CategoryFilter::new(&include, &exclude))
+        .with_resolving_within(resolving_within_hours(&config.arbitrage, args)?);
+    let store = Store::open(&config.data_dir)?;
+    let signal_log = SignalLog::new(store.clone());
+    let efficiency_log = EfficiencyLog::new(store.clone());
+    let window_log = OpportunityWindowLog::new(store.clone());
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+    let priority_alerts =
+        PriorityAlerts::from_config(client.clone(), &config.alerts, TradingCosts::from_config(&config.arbitrage))?;
+    let mut arb_signals = ArbitrageSignalTracker::new().with_window_log(window_log);
+    let mut live_events: HashSet<(String, EventArbitrageKind)> = HashSet::new();
+
+    let mut state = DashboardState::new(poll_interval);
+    state.refresh_from_store(&store, &signal_log);
+    let mut dashboard = Dashboard::enter(shutdown.clone())?;
+
+    let mut scan_interval = tokio::time::interval(Duration::from_secs(poll_interval));
+    let mut redraw_interval = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        tokio::select! {
+            _ = scan_interval.tick() => {
+                state.scans += 1;
+                outln!("[{}] Scan #{} starting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), state.scans);
+                let scan_start = Instant::now();
+
+                match run_single_scan(&client, &scanner, Some(&efficiency_log)).await {
+                    Ok((opportunities, event_opportunities, link_opportunities)) => {
+                        // Alert once per event arbitrage, when it first appears
+                        for opp in &event_opportunities {
+                            if !live_events.contains(&(opp.event_id.clone(), opp.kind)) {
+                                alerts.send(&Alert::event_arbitrage(opp)).await;
+                            }
+                        }
+                        live_events = event_opportunities.iter().map(|o| (o.event_id.clone(), o.kind)).collect();
+
+                        // Alert once per opportunity, when it first appears, even if its signal can't be logged
+                        let new_opportunities = arb_signals.new_opportunities(&opportunities);
+                        let new_links = arb_signals.new_links(&link_opportunities);
+                        if let Err(e) = arb_signals.observe(&signal_log, &opportunities, &link_opportunities) {
+                            eoutln!("Warning: Failed to record signals: {}", e);
+                        }
+                        for opp in &new_opportunities {
+                            alerts.send(&Alert::arbitrage(opp)).await;
+                        }
+                        for opp in &new_links {
+                            alerts.send(&Alert::link_arbitrage(opp)).await;
+                        }
+                        if let Some(priority_alerts) = &priority_alerts {
+                            priority_alerts.process(&new_opportunities).await;
+                        }
+                        state.new_opportunities += new_opportunities.len() + new_links.len();
+
+                        state.opportunities = opportunities;
+                        state.event_opportunities = event_opportunities;
+                        state.last_error = None;
+                    }
+                    Err(e) if shutdown::is_cancelled(&e) => break,
+                    Err(e) => {
+                        outln!("[{}] ERROR (Scan #{}): {}", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), state.scans, e);
+                        state.failed_scans += 1;
+                        state.last_error = Some(e.to_string());
+                    }
+                }
+
+                state.last_scan_at = Some(Utc::now().timestamp());
+                state.last_scan_secs = Some(scan_start.elapsed().as_secs_f64());
+                state.efficiency = efficiency_log.load_since(1).ok().and_then(|readings| readings.into_iter().last());
+                state.refresh_from_store(&store, &signal_log);
+                dashboard.draw(&state)?;
+            }
+            _ = redraw_interval.tick() => {
+                dashboard.draw(&state)?;
+            }
+            _ = dashboard.redraw_requested() => {
+                dashboard.draw(&state)?;
+            }
+            _ = shutdown.cancelled() => {
+                break;
+            }
+        }
+    }
+
+    drop(dashboard);
+    outln!("Dashboard closed after {} scans ({} new opportunities)", state.scans, state.new_opportunities);
+    Ok(())
+}
+
+/// Runs the arbitrage scanner in the background behind an HTTP API until Ctrl+C
+pub async fn run_daemon(config: &Config, args: &[String]) -> Result<()> {
+    let include = flag_value(args, "--include")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.include_categories.clone());
+    let exclude = flag_value(args, "--exclude")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.exclude_categories.clone());
+    let poll_interval = match flag_value(args, "--interval") {
+        Some(secs) => secs.parse::<u64>().context("--interval must be a number of seconds")?,
+        None => config.arbitrage.poll_interval_secs,
+    };
+    let bind = flag_value(args, "--bind").unwrap_or(&config.daemon.bind);
+
+    outln!("Polymarket Scanner Daemon");
+    outln!("=========================\n");
+
+    let shutdown = shutdown::ctrl_c_token();
+    let client = PolymarketClient::with_config(&config.api)?.with_cancellation(shutdown.clone());
+    let scanner = ArbitrageScanner::from_config(&config.arbitrage)
+        .with_categories(CategoryFilter::new(&include, &exclude))
+        .with_resolving_within(resolving_within_hours(&config.arbitrage, args)?);
+    let analyzer = WalletAnalyzer::new()
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days);
+
+    let store = Store::open(&config.data_dir)?;
+    let mut daemon = Daemon::new(client, scanner, analyzer, store.clone())
+        .with_poll_interval(poll_interval)
+        .with_wallet_activity(config.insider.wallet_activity);
+    if config.arbitrage.adaptive_polling || args.iter().any(|arg| arg == "--adaptive") {
+        let arbitrage = ArbitrageConfig { poll_interval_secs: poll_interval, ..config.arbitrage.clone() };
+        let history = SignalLog::new(store.clone()).load().unwrap_or_default();
+        daemon = daemon
+            .with_adaptive_polling(AdaptivePolling::from_config(&arbitrage).with_history(&history, Utc::now().timestamp()));
+    }
+    if config.cache.trade_histories {
+        daemon = daemon.with_trade_history(
+            TradeHistoryCache::new(store).with_max_age_secs(config.cache.trade_histories_max_age_secs),
+        );
+    }
+    daemon.run(bind, shutdown).await
+}
+
+/// Scans Manifold Markets for arbitrage, or analyzes a Manifold user's bets
+pub async fn manifold(config: &Config, args: &[String]) -> Result<()> {
+    let client = ManifoldClient::with_config(&config.api, &config.manifold)?;
+
+    match args.get(2).map(String::as_str) {
+        None | Some("scan") => {
+            outln!("Manifold Arbitrage Scanner");
+            outln!("==========================\n");
+            // Manifold's prices are probabilities, not quotes, so readings stay out of the Polymarket efficiency log
+            // Linked pairs name Polymarket markets
+            let scanner = ArbitrageScanner::from_config(&config.arbitrage).with_links(&[]);
+            run_single_scan(&client, &scanner, None).await?;
+            Ok(())
+        }
+        Some(username) => {
+            let detailed = args.iter().any(|arg| arg == "--detailed");
+            let user_id = client.resolve_user(username).await?;
+            analyze_exchange_wallet(config, &client, &user_id, detailed).await
+        }
+    }
+}
+
+/// Replays price history through the arbitrage scanner: how many opportunities existed,
+/// how long they lasted and what polling at the configured interval would have caught
+pub async fn run_backtest(config: &Config, args: &[String]) -> Result<()> {
+    outln!("Polymarket Arbitrage Backtest");
+    outln!("=============================\n");
+
+    // Settings on the command line replace those in the config, so thresholds can be compared
+    let mut arbitrage = config.arbitrage.clone();
+    if let Some(threshold) = flag_value(args, "--threshold") {
+        arbitrage.threshold = Some(threshold.parse().context("--threshold must be a YES + NO total like 0.99")?);
+    }
+    if let Some(include) = flag_value(args, "--include") {
+        arbitrage.include_categories = split_list(include);
+    }
+    if let Some(exclude) = flag_value(args, "--exclude") {
+        arbitrage.exclude_categories = split_list(exclude);
+    }
+    let poll_interval = match flag_value(args, "--interval") {
+        Some(secs) => secs.parse::<u64>().context("--interval must be a number of seconds")?,
+        None => arbitrage.poll_interval_secs,
+    };
+    let scanner = ArbitrageScanner::from_config(&arbitrage);
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let recorder = PriceRecorder::new(client.clone(), Store::open(&config.data_dir)?);
+    let markets = if args.iter().any(|arg| arg == "--cached") {
+        let markets = recorder.load()?;
+        if markets.is_empty() {
+            outln!("No recorded price history yet; run backtest without --cached first.");
+            return Ok(());
+        }
+        outln!("📂 Loaded recorded price history for {} markets", markets.len());
+        markets
+    } else {
+        let market_count = match flag_value(args, "--markets") {
+            Some(n) => n.parse::<usize>().context("--markets must be a number of markets")?,
+            None => config.backtest.markets,
+        };
+        let days = match flag_value(args, "--days") {
+            Some(n) => n.parse::<u32>().context("--days must be a number of days")?,
+            None => config.backtest.days,
+        };
+        let fidelity = match flag_value(args, "--fidelity") {
+            Some(n) => n.parse::<u32>().context("--fidelity must be a number of minutes")?,
+            None => config.backtest.fidelity_minutes,
+        }
+        .max(1);
+
+        outln!("📥 Fetching active markets...");
+        let mut markets: Vec<Market> = client
+            .fetch_all_active_markets()
+            .await?
+            .into_iter()
+            .filter(|market| market.condition_id.is_some())
+            .filter(|market| market.parsed_clob_token_ids().is_some_and(|ids| ids.len() == 2))
+            .filter(|market| scanner.categories().is_empty() || scanner.categories().allows(&market.categories()))
+            .collect();
+        markets.sort_by(|a, b| b.volume_24hr.unwrap_or(0.0).total_cmp(&a.volume_24hr.unwrap_or(0.0)));
+        markets.truncate(market_count);
+
+        let end = Utc::now().timestamp();
+        let start = end - i64::from(days) * 86400;
+        outln!(
+            "📈 Fetching {} days of price history for the {} busiest markets ({}-minute points)...",
+            days,
+            markets.len(),
+            fidelity
+        );
+        recorder
+            .record(&markets, start, end, fidelity, config.api.max_concurrent_requests)
+            .await?
+    };
+
+    let thresholds = match scanner.threshold() {
+        Some(threshold) => format!("fixed threshold {:.3}", threshold),
+        None => "fee-derived thresholds".to_string(),
+    };
+    outln!("⏪ Replaying through the scanner ({}, polling every {}s)...\n", thresholds, poll_interval);
+    let report = Backtester::new(scanner, poll_interval).run(&markets);
+    report.print(10);
+
+    if let Some(path) = flag_value(args, "--json") {
+        write_json(&report, path)?;
+    }
+
+    Ok(())
+}
+
+/// Manages the executor kill switch (halt/resume/status)
+pub async fn manage_executor(config: &Config, args: &[String]) -> Result<()> {
+    let guardrails = Guardrails::new(config.executor.clone(), Store::open(&config.data_dir)?);
+
+    match args.get(2).map(|a| a.as_str()) {
+        Some("halt") => {
+            let reason = flag_value(args, "--reason").unwrap_or("manual halt");
+            guardrails.halt(reason)?;
+            outln!("🛑 Executor halted: {}", reason);
+        }
+        Some("resume") => {
+            guardrails.resume()?;
+            outln!("✓ Executor resumed");
+        }
+        _ => {
+            // Exposure in markets that resolved since the scanner last ran is no longer at risk
+            let client = PolymarketClient::with_config(&config.api)?;
+            if let Err(e) = guardrails.settle_resolved(&client).await {
+                eoutln!("Warning: Failed to settle resolved positions: {}", e);
+            }
+            guardrails.print_status(&guardrails.state()?)
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists captured order book snapshots, newest first, with whether each flagged
+/// arbitrage was really on the book
+pub fn snapshot_report(config: &Config, args: &[String]) -> Result<()> {
+    let hours: i64 = flag_value(args, "--hours").and_then(|h| h.parse().ok()).unwrap_or(24 * 7);
+    let limit: usize = flag_value(args, "--limit").and_then(|l| l.parse().ok()).unwrap_or(20);
+    let market = flag_value(args, "--market");
+    let since = Utc::now().timestamp() - hours * 3600;
+
+    let mut snapshots: Vec<_> = snapshots::load_snapshots(&Store::open(&config.data_dir)?)?
+        .into_iter()
+        .filter(|s| s.captured_at >= since)
+        .filter(|s| market.is_none_or(|id| s.condition_id == id))
+        .collect();
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.captured_at));
+
+    outln!("Order Book Snapshots (last {}h)", hours);
+    outln!("==============================\n");
+    if snapshots.is_empty() {
+        outln!("No snapshots captured. Enable [snapshots] and run the arbitrage scanner or --scan.");
+        return Ok(());
+    }
+
+    let verdicts: Vec<SnapshotVerdict> = snapshots.iter().filter_map(|s| s.verdict()).collect();
+    let count = |verdict| verdicts.iter().filter(|v| **v == verdict).count();
+    outln!(
+        "{} snapshots, {} of flagged arbitrage: {} executable, {} stale, {} without a book\n",
+        snapshots.len(),
+        verdicts.len(),
+        count(SnapshotVerdict::Executable),
+        count(SnapshotVerdict::Stale),
+        count(SnapshotVerdict::NoBook)
+    );
+
+    for snapshot in snapshots.iter().take(limit) {
+        outln!("[{}] {}", format_timestamp(snapshot.captured_at), snapshot.condition_id);
+        snapshot.print_summary();
+        if let Some(market) = &snapshot.market {
+            outln!(
+                "   Liquidity ${:.0} | 24h volume ${:.0} | tick {} | min size {} | ends {}",
+                market.liquidity,
+                market.volume_24hr.unwrap_or(0.0),
+                market.order_price_min_tick_size.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
+                market.order_min_size.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+                market.end_date.as_deref().unwrap_or("-")
+            );
+        }
+        outln!();
+    }
+    if snapshots.len() > limit {
+        outln!("... and {} more (raise --limit to see them)", snapshots.len() - limit);
+    }
+
+    Ok(())
+}
+
+/// Prints how long arbitrage opportunities stayed open over the last `--hours` (a week by default)
+pub fn durations_report(config: &Config, args: &[String]) -> Result<()> {
+    let hours: i64 = flag_value(args, "--hours").and_then(|h| h.parse().ok()).unwrap_or(24 * 7);
+    let log = OpportunityWindowLog::new(Store::open(&config.data_dir)?);
+    log.print_report(&log.load_since(hours)?, hours);
+    Ok(())
+}
+
+/// Prints the market efficiency index over the last `--hours` (a week by default)
+pub fn efficiency_report(config: &Config, args: &[String]) -> Result<()> {
+    let hours: i64 = flag_value(args, "--hours").and_then(|h| h.parse().ok()).unwrap_or(24 * 7);
+    let log = EfficiencyLog::new(Store::open(&config.data_dir)?);
+    log.print_report(&log.load_since(hours)?, hours);
+    Ok(())
+}
+
+/// Prints the simulated executions of the last `--days` (30 by default)
+pub fn paper_report(config: &Config, args: &[String]) -> Result<()> {
+    let days: i64 = flag_value(args, "--days").and_then(|d| d.parse().ok()).unwrap_or(30);
+    let ledger = PaperLedger::new(Store::open(&config.data_dir)?);
+    ledger.print_report(&ledger.load_since(days)?, days);
+    Ok(())
+}
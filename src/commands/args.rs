@@ -0,0 +1,88 @@
+use crate::config::ArbitrageConfig;
+use crate::models::parse_api_timestamp;
+use crate::{Config, PolymarketClient};
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+/// Returns the value following a `--flag` argument, if present
+pub fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| value.as_str())
+}
+
+/// Removes a flag and its value from the arguments wherever it appears, parsing the value
+pub fn take_flag<T: std::str::FromStr>(args: &mut Vec<String>, flag: &str, what: &str) -> Result<Option<T>> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else { return Ok(None) };
+    let value = args.get(index + 1).with_context(|| format!("{} needs {}", flag, what))?;
+    let parsed = value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid {} value {}: expected {}", flag, value, what))?;
+    args.drain(index..index + 2);
+    Ok(Some(parsed))
+}
+
+/// Parses a point in time given as unix seconds, a date/time like `2024-11-05T12:00:00Z`,
+/// or an age like `30m`, `6h` or `2d`
+pub fn parse_since(value: &str) -> Result<i64> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Some(timestamp) = parse_api_timestamp(value) {
+        return Ok(timestamp);
+    }
+
+    let age = parse_age_secs(value)
+        .ok_or_else(|| anyhow::anyhow!("Invalid time: {} (use unix seconds, a date, or an age like 6h)", value))?;
+    Ok(Utc::now().timestamp() - age)
+}
+
+/// Parses a duration like `30m`, `6h` or `2d` into seconds
+pub fn parse_age_secs(value: &str) -> Option<i64> {
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let unit_secs = match unit {
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    amount.parse::<i64>().ok().map(|amount| amount * unit_secs)
+}
+
+/// Hours markets must end within to be scanned: `--resolving-within` as hours or a duration
+/// like 48h or 2d, else the config's (0 = any end date)
+pub fn resolving_within_hours(config: &ArbitrageConfig, args: &[String]) -> Result<u64> {
+    match flag_value(args, "--resolving-within") {
+        Some(value) => value
+            .parse::<u64>()
+            .ok()
+            .or_else(|| parse_age_secs(value).map(|secs| (secs.max(0) as u64).div_ceil(3600)))
+            .ok_or_else(|| anyhow::anyhow!("Invalid horizon: {} (use hours or a duration like 48h or 2d)", value)),
+        None => Ok(config.resolving_within_hours),
+    }
+}
+
+/// Replaces every `@username` argument with the proxy wallet address of that Polymarket user
+/// (values of `--flags` are left alone)
+pub async fn resolve_usernames(config: &Config, args: &mut [String]) -> Result<()> {
+    let is_username = |arg: &str| {
+        arg.len() > 1
+            && arg.starts_with('@')
+            && arg[1..].chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    };
+    let positions: Vec<usize> = (1..args.len())
+        .filter(|&i| is_username(&args[i]) && !args[i - 1].starts_with("--"))
+        .collect();
+    if positions.is_empty() {
+        return Ok(());
+    }
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    for i in positions {
+        let address = client.resolve_wallet(&args[i]).await?;
+        outln!("Resolved {} to {}", args[i], address);
+        args[i] = address;
+    }
+    Ok(())
+}
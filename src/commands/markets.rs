@@ -0,0 +1,372 @@
+use crate::alerts::{Alert, AlertDispatcher};
+use crate::config::split_list;
+use crate::fees::{FeeSchedule, TradingCosts, BREAK_EVEN_SIZES};
+use crate::models::{ArbitrageKind, PriceInterval};
+use crate::monitor::{self, BookTop, MarketMonitor, MonitorReading, MonitorThresholds};
+use crate::resolutions::ResolutionChecker;
+use crate::rewards::RewardScanner;
+use crate::scanner::CategoryFilter;
+use crate::schedule::ResolutionCalendar;
+use crate::shutdown;
+use crate::store::Store;
+use crate::{Config, Market, PolymarketClient};
+use super::args::flag_value;
+use super::{format_timestamp, write_csv};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Finds markets by question text or slug and prints what other commands need to target them
+pub async fn search_markets(config: &Config, args: &[String]) -> Result<()> {
+    let limit = match flag_value(args, "--limit") {
+        Some(n) => n.parse::<usize>().context("--limit must be a number of markets")?,
+        None => 10,
+    };
+    let open_only = args.iter().any(|arg| arg == "--open");
+    let mut words = Vec::new();
+    let mut rest = args.iter().skip(2);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--limit" => {
+                rest.next();
+            }
+            "--open" => {}
+            _ => words.push(arg.as_str()),
+        }
+    }
+    let query = words.join(" ");
+    if query.is_empty() {
+        outln!("Usage: cargo run -- search <query|slug> [--open] [--limit <n>]");
+        return Ok(());
+    }
+
+    outln!("Polymarket Market Search");
+    outln!("========================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let markets: Vec<_> = client
+        .search_markets(&query)
+        .await?
+        .into_iter()
+        .filter(|market| !open_only || market.closed != Some(true))
+        .collect();
+    if markets.is_empty() {
+        outln!("No markets found for \"{}\".", query);
+        return Ok(());
+    }
+
+    outln!("Found {} markets for \"{}\" (showing {}):\n", markets.len(), query, markets.len().min(limit));
+    for (i, market) in markets.iter().take(limit).enumerate() {
+        let status = if market.closed == Some(true) { "closed" } else { "open" };
+        outln!("{}. {} [{}]", i + 1, market.question, status);
+        if let Some(slug) = &market.slug {
+            outln!("   Slug:         {}", slug);
+        }
+        outln!("   Condition ID: {}", market.condition_id.as_deref().unwrap_or("-"));
+        if let Some(prices) = market.parsed_outcome_prices() {
+            let outcomes = market.parsed_outcomes().unwrap_or_default();
+            let prices: Vec<String> = prices
+                .iter()
+                .enumerate()
+                .map(|(j, price)| format!("{} ${:.3}", outcomes.get(j).map_or("?", String::as_str), price))
+                .collect();
+            outln!("   Prices:       {}", prices.join(" | "));
+        }
+        outln!("   Liquidity:    ${:.0} | Volume: ${:.0}", market.parsed_liquidity(), market.parsed_volume());
+        outln!();
+    }
+
+    Ok(())
+}
+
+/// Stakes out one market, printing its order books whenever they change and alerting
+/// when its YES + NO total crosses the arbitrage threshold
+pub async fn monitor_market(config: &Config, args: &[String]) -> Result<()> {
+    let Some(target) = args.get(2).filter(|arg| !arg.starts_with("--")) else {
+        outln!("Usage: cargo run -- monitor <condition_id|slug> [--interval <secs>]");
+        return Ok(());
+    };
+    let poll_interval = match flag_value(args, "--interval") {
+        Some(secs) => secs.parse::<u64>().context("--interval must be a number of seconds")?,
+        None => config.monitor.poll_interval_secs,
+    };
+
+    let shutdown = shutdown::ctrl_c_token();
+    let client = PolymarketClient::with_config(&config.api)?.with_cancellation(shutdown.clone());
+    let monitor = MarketMonitor::new(client, &config.arbitrage);
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+    let market = monitor.resolve(target).await?;
+
+    outln!("Polymarket Market Monitor");
+    outln!("=========================\n");
+    outln!("{}", market.question);
+    if let Some(condition_id) = &market.condition_id {
+        outln!("Condition ID: {}", condition_id);
+    }
+    outln!("Polling every {}s - Press Ctrl+C to stop", poll_interval.max(1));
+    outln!("Depth is the shares resting within {:.0}¢ of each best price\n", monitor::DEPTH_WINDOW * 100.0);
+
+    let mut interval = tokio::time::interval(Duration::from_secs(poll_interval.max(1)));
+    let mut last: Option<MonitorReading> = None;
+    let mut crossed: Option<ArbitrageKind> = None;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let reading = match monitor.read(&market).await {
+                    Ok(reading) => reading,
+                    Err(e) => {
+                        eoutln!("Warning: Failed to fetch order books: {}", e);
+                        continue;
+                    }
+                };
+                if last.is_some_and(|last| !reading.changed_from(&last)) {
+                    continue;
+                }
+
+                let thresholds = monitor.thresholds(&market, &reading);
+                print_monitor_reading(&market, &reading, last.as_ref(), &thresholds);
+
+                let crossing = monitor.crossing(&reading, &thresholds);
+                if crossing != crossed {
+                    match crossing {
+                        Some(kind) => {
+                            outln!("🚨 {} arbitrage: totals crossed the threshold", kind.label());
+                            alerts.send(&Alert::monitored_market(&market, kind, &reading, &thresholds)).await;
+                        }
+                        None => outln!("   Back inside the thresholds"),
+                    }
+                    crossed = crossing;
+                }
+                last = Some(reading);
+            }
+            _ = shutdown.cancelled() => break,
+        }
+    }
+
+    outln!("\nStopped monitoring {}", market.question);
+    Ok(())
+}
+
+/// Prints one line per changed monitor reading: each outcome's bid/ask and depth, and the
+/// YES + NO totals with how they moved since the last line
+fn print_monitor_reading(
+    market: &Market,
+    reading: &MonitorReading,
+    last: Option<&MonitorReading>,
+    thresholds: &MonitorThresholds,
+) {
+    let price = |price: Option<f64>| price.map(|p| format!("{:.3}", p)).unwrap_or_else(|| "-".to_string());
+    let total = |total: Option<f64>, previous: Option<f64>| match (total, previous) {
+        (Some(total), Some(previous)) if (total - previous).abs() > 1e-9 => format!("{:.3} ({:+.3})", total, total - previous),
+        (total, _) => price(total),
+    };
+    let side = |top: &BookTop| {
+        format!(
+            "{}/{} ({:.0}|{:.0})",
+            price(top.bid),
+            price(top.ask),
+            top.bid_depth,
+            top.ask_depth
+        )
+    };
+
+    outln!(
+        "[{}] {} {}  {} {}  asks {} < {:.3}?  bids {} > {:.3}?",
+        chrono::DateTime::from_timestamp(reading.timestamp, 0)
+            .map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_default(),
+        market.outcome_name(0),
+        side(&reading.yes),
+        market.outcome_name(1),
+        side(&reading.no),
+        total(reading.ask_total(), last.and_then(MonitorReading::ask_total)),
+        thresholds.buy_below,
+        total(reading.bid_total(), last.and_then(MonitorReading::bid_total)),
+        thresholds.sell_above
+    );
+}
+
+/// Prints a market's price history per outcome
+pub async fn price_history(config: &Config, condition_id: &str, interval: PriceInterval) -> Result<()> {
+    outln!("Polymarket Price History");
+    outln!("========================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let series = client.fetch_price_history(condition_id, interval).await?;
+
+    for outcome in &series {
+        outln!("\n--- {} ({} points, {}) ---", outcome.outcome, outcome.points.len(), interval.as_str());
+
+        let (Some(first), Some(last)) = (outcome.points.first(), outcome.points.last()) else {
+            outln!("No price history.");
+            continue;
+        };
+        let low = outcome.points.iter().map(|p| p.price).fold(f64::INFINITY, f64::min);
+        let high = outcome.points.iter().map(|p| p.price).fold(f64::NEG_INFINITY, f64::max);
+
+        outln!("First:                ${:.3} ({})", first.price, format_timestamp(first.timestamp));
+        outln!("Last:                 ${:.3} ({})", last.price, format_timestamp(last.timestamp));
+        outln!("Low / High:           ${:.3} / ${:.3}", low, high);
+        if let Some((at, change)) = outcome.largest_move() {
+            outln!("Largest Move:         {:+.3} at {}", change, format_timestamp(at));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints a market's fee structure and the YES + NO spread needed to break even at several sizes
+pub async fn fee_report(config: &Config, condition_id: &str) -> Result<()> {
+    outln!("Polymarket Fee Report");
+    outln!("=====================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let market = client
+        .fetch_markets_by_condition_ids(&[condition_id.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Market {} not found", condition_id))?;
+    let token_ids = market
+        .parsed_clob_token_ids()
+        .filter(|ids| ids.len() == 2)
+        .ok_or_else(|| anyhow::anyhow!("Market {} is not a binary market with CLOB tokens", condition_id))?;
+
+    let fees = FeeSchedule::from_market(&market);
+    let costs = TradingCosts::from_config(&config.arbitrage);
+
+    outln!("{}\n", market.question);
+    outln!("Taker Fee:            {:.0} bps", fees.taker_fee_bps);
+    outln!("Maker Fee:            {:.0} bps", fees.maker_fee_bps);
+    outln!("Tick Size:            ${}", fees.tick_size);
+    outln!("Min Order Size:       {} shares", fees.min_order_size);
+    outln!("Fixed Cost:           ${:.2} per trade", costs.fixed_cost_per_trade);
+    outln!("Min Edge:             ${:.4} per $1", costs.min_edge);
+
+    let (yes_book, no_book) = tokio::join!(
+        client.fetch_order_book(&token_ids[0]),
+        client.fetch_order_book(&token_ids[1])
+    );
+    let (yes_book, no_book) = (yes_book?, no_book?);
+
+    outln!("\n{:>10} {:>10} {:>10} {:>10} {:>12} {:>10} {:>10}",
+        "Shares", "YES Ask", "NO Ask", "Total", "Break-Even", "Min Spread", "Profit");
+    for shares in BREAK_EVEN_SIZES {
+        // Fall back to Gamma prices when the book is too thin to fill this size
+        let book_prices = yes_book.average_ask_price(shares).zip(no_book.average_ask_price(shares));
+        let (yes_price, no_price) = match book_prices.or_else(|| {
+            market.parsed_outcome_prices().filter(|p| p.len() == 2).map(|p| (p[0], p[1]))
+        }) {
+            Some(prices) => prices,
+            None => continue,
+        };
+
+        let legs = [(fees, yes_price), (fees, no_price)];
+        let total = yes_price + no_price;
+        let break_even = costs.break_even_ratio(&legs, 1.0, shares);
+        let profit = (break_even - total) * shares;
+        outln!("{:>10.0} {:>10.4} {:>10.4} {:>10.4} {:>12.4} {:>10.4} {:>10.2}{}",
+            shares, yes_price, no_price, total, break_even,
+            costs.min_spread(&legs, 1.0, shares), profit,
+            if book_prices.is_none() { "  (not enough depth, Gamma prices)" } else { "" });
+    }
+
+    match config.arbitrage.threshold {
+        Some(threshold) => outln!("\nScanner uses a fixed threshold: YES + NO < ${:.3}", threshold),
+        None => {
+            let prices = market.parsed_outcome_prices().filter(|p| p.len() == 2).unwrap_or(vec![0.5, 0.5]);
+            outln!("\nScanner threshold at {:.0} shares: YES + NO < ${:.4}",
+                costs.trade_size,
+                costs.threshold(&[(fees, prices[0]), (fees, prices[1])], 1.0));
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists markets whose liquidity rewards should outweigh the cost of quoting them
+pub async fn scan_rewards(config: &Config, args: &[String]) -> Result<()> {
+    outln!("Polymarket Liquidity Rewards Scanner");
+    outln!("====================================\n");
+
+    let include = flag_value(args, "--include")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.include_categories.clone());
+    let exclude = flag_value(args, "--exclude")
+        .map(split_list)
+        .unwrap_or_else(|| config.arbitrage.exclude_categories.clone());
+    let mut scanner = RewardScanner::from_config(&config.rewards).with_categories(CategoryFilter::new(&include, &exclude));
+    if let Some(size) = flag_value(args, "--size") {
+        scanner = scanner.with_quote_size(size.parse().context("--size must be a dollar amount per side")?);
+    }
+    if let Some(percent) = flag_value(args, "--min-yield") {
+        let percent: f64 = percent.parse().context("--min-yield must be a percentage per day")?;
+        scanner = scanner.with_min_daily_yield(percent / 100.0);
+    }
+    let limit = match flag_value(args, "--limit") {
+        Some(n) => n.parse::<usize>().context("--limit must be a number of markets")?,
+        None => 20,
+    };
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let markets = client.fetch_all_active_markets().await?;
+    let now = Utc::now().timestamp();
+    let rewarded = markets.iter().filter(|market| market.daily_reward_rate(now) > 0.0).count();
+    outln!("✓ Fetched {} markets, {} with active liquidity rewards\n", markets.len(), rewarded);
+
+    let opportunities = scanner.scan(&markets, now);
+    if opportunities.is_empty() {
+        outln!("No rewarded markets expected to pay more than they cost to quote.");
+        return Ok(());
+    }
+
+    outln!("Found {} markets worth quoting for rewards (showing {}):\n", opportunities.len(), opportunities.len().min(limit));
+    outln!("{}", "=".repeat(80));
+    for (i, opportunity) in opportunities.iter().take(limit).enumerate() {
+        opportunity.print(i + 1);
+    }
+
+    Ok(())
+}
+
+/// Reports resolution status for a list of condition IDs read from a file
+pub async fn check_resolutions(config: &Config, ids_path: &str, csv_path: Option<&str>) -> Result<()> {
+    outln!("Polymarket Resolution Checker");
+    outln!("=============================\n");
+
+    let ids = ResolutionChecker::read_ids_file(std::path::Path::new(ids_path))?;
+    if ids.is_empty() {
+        outln!("No condition IDs found in {}", ids_path);
+        return Ok(());
+    }
+
+    outln!("🔍 Checking {} condition IDs...", ids.len());
+    let start = Instant::now();
+    let checker = ResolutionChecker::new(
+        PolymarketClient::with_config(&config.api)?,
+        Store::open(&config.data_dir)?,
+    );
+    let resolutions = checker.check(&ids).await?;
+    outln!("✓ Checked {} markets in {:.2}s", resolutions.len(), start.elapsed().as_secs_f64());
+
+    checker.print_report(&resolutions);
+
+    if let Some(path) = csv_path {
+        let export = ResolutionChecker::to_csv(&resolutions, config.export.decimal_places);
+        write_csv(&export, path)?;
+    }
+
+    Ok(())
+}
+
+/// Prints how many markets end in each of the next `--hours` (48 by default) and the bursts among them
+pub async fn calendar_report(config: &Config, args: &[String]) -> Result<()> {
+    let hours: i64 = flag_value(args, "--hours").and_then(|h| h.parse().ok()).unwrap_or(48);
+    let client = PolymarketClient::with_config(&config.api)?;
+    outln!("📅 Reading end dates of active markets...");
+    let calendar = ResolutionCalendar::fetch(&client).await?;
+    calendar.print_upcoming(hours, config.schedule.burst_min_markets);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! The CLI's commands, one function per subcommand, grouped by what they work on.
+//!
+//! The binary only parses arguments and dispatches to these; each takes the loaded config
+//! and the raw arguments, and prints its own output.
+
+pub mod arbitrage;
+pub mod args;
+pub mod markets;
+pub mod research;
+pub mod tracking;
+pub mod wallets;
+
+use crate::export::CsvExport;
+use crate::leaderboard;
+use crate::models::{SuspicionReport, WalletPerformance};
+use crate::report;
+use anyhow::Result;
+
+/// Writes a CSV export and its schema sidecar
+pub(crate) fn write_csv(export: &CsvExport, path: &str) -> Result<()> {
+    let schema_path = export.write(std::path::Path::new(path))?;
+    outln!("\n✓ Exported CSV to {} (schema: {})", path, schema_path.display());
+    Ok(())
+}
+
+/// Writes a JSON export in the field layout of Polymarket's data API
+pub(crate) fn write_json<T: serde::Serialize>(value: &T, path: &str) -> Result<()> {
+    leaderboard::write_json(value, std::path::Path::new(path))?;
+    outln!("\n✓ Exported JSON to {}", path);
+    Ok(())
+}
+
+/// Writes wallet analyses as a standalone HTML report
+pub(crate) fn write_html(
+    title: &str,
+    wallets: &[(String, Option<String>, WalletPerformance, SuspicionReport)],
+    path: &str,
+) -> Result<()> {
+    report::write_html(title, wallets, std::path::Path::new(path))?;
+    outln!("\n✓ Wrote HTML report to {}", path);
+    Ok(())
+}
+
+/// Writes a Parquet export and its schema sidecar
+#[cfg(feature = "parquet")]
+pub(crate) fn write_parquet(export: &CsvExport, path: &str) -> Result<()> {
+    let schema_path = export.write_parquet(std::path::Path::new(path))?;
+    outln!("\n✓ Exported Parquet to {} (schema: {})", path, schema_path.display());
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub(crate) fn write_parquet(_export: &CsvExport, _path: &str) -> Result<()> {
+    anyhow::bail!("Parquet support isn't compiled in; rebuild with --features parquet")
+}
+
+/// Formats unix seconds as a UTC date and time
+pub(crate) fn format_timestamp(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,134 @@
+use crate::calibration::{CalibrationReport, CalibrationSampler};
+use crate::dataset::DatasetBuilder;
+use crate::doctor::{self, Doctor};
+use crate::market_cache::ResolvedMarketCache;
+use crate::store::Store;
+use crate::{Config, PolymarketClient};
+use super::args::{flag_value, parse_age_secs};
+use super::{format_timestamp, write_csv};
+use anyhow::Result;
+
+/// Builds the venue-wide calibration report from recently resolved markets
+pub async fn calibration_report(config: &Config, args: &[String]) -> Result<()> {
+    let sample_size = match flag_value(args, "--sample") {
+        Some(value) => value.parse().map_err(|_| anyhow::anyhow!("Invalid --sample: {}", value))?,
+        None => config.calibration.sample_size,
+    };
+    let horizon_secs = match flag_value(args, "--horizon") {
+        Some(value) => parse_age_secs(value)
+            .ok_or_else(|| anyhow::anyhow!("Invalid --horizon: {} (use e.g. 6h or 2d)", value))?,
+        None => (config.calibration.horizon_hours * 3600.0) as i64,
+    };
+
+    outln!("Polymarket Calibration Report");
+    outln!("=============================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let store = Store::open(&config.data_dir)?;
+
+    outln!("📚 Loading resolved markets...");
+    let cache = ResolvedMarketCache::new(store.clone(), config.cache.resolved_markets_max_age_secs());
+    let resolved_markets = cache.load_or_fetch_all(&client).await?;
+
+    outln!(
+        "📈 Fetching prices {:.0}h before resolution for up to {} markets...",
+        horizon_secs as f64 / 3600.0,
+        sample_size
+    );
+    let sampler = CalibrationSampler::new(client, store.clone(), horizon_secs, config.api.max_concurrent_requests);
+    let samples = sampler
+        .collect(&resolved_markets, sample_size, config.calibration.min_volume)
+        .await?;
+
+    let report = CalibrationReport::build(&samples, horizon_secs as f64 / 3600.0);
+    report.print(config.calibration.min_category_samples);
+    report.save(&store)?;
+
+    if let Some(path) = flag_value(args, "--csv") {
+        write_csv(&report.to_csv(config.export.decimal_places), path)?;
+    }
+
+    Ok(())
+}
+
+/// Checks every API endpoint and prints a pass/fail report
+pub async fn run_doctor(config: &Config, args: &[String]) -> Result<()> {
+    outln!("Polymarket Scanner Doctor");
+    outln!("=========================\n");
+
+    if args.iter().any(|a| a == "--fields") {
+        outln!("Sampling markets and trades for fields the models don't know...");
+        let reports = Doctor::new(&config.api)?.field_reports().await?;
+        doctor::print_field_reports(&reports);
+        return Ok(());
+    }
+
+    outln!("Sending test requests to every endpoint...");
+
+    let results = Doctor::new(&config.api)?.run().await;
+    let failed = doctor::print_report(&results);
+    if failed > 0 {
+        anyhow::bail!("{} health check(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+/// Builds (or resumes) the offline research dataset, or shows its progress
+pub async fn dataset_command(config: &Config, args: &[String]) -> Result<()> {
+    let mut dataset_config = config.dataset.clone();
+    if let Some(dir) = flag_value(args, "--out") {
+        dataset_config.dir = dir.to_string();
+    }
+    if let Some(value) = flag_value(args, "--wallets") {
+        dataset_config.wallets = value.parse().map_err(|_| anyhow::anyhow!("Invalid --wallets: {}", value))?;
+    }
+    if let Some(value) = flag_value(args, "--rate") {
+        dataset_config.requests_per_minute = value.parse().map_err(|_| anyhow::anyhow!("Invalid --rate: {}", value))?;
+    }
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let fresh = args.iter().any(|a| a == "--fresh");
+    let dir = dataset_config.dir.clone();
+    let rate = dataset_config.requests_per_minute;
+    let mut builder = DatasetBuilder::open(client, dataset_config, fresh)?;
+
+    match args.get(2).map(|a| a.as_str()) {
+        Some("build") => {
+            outln!("Polymarket Dataset Build");
+            outln!("========================\n");
+            outln!("Writing to {} at {} requests/minute - Press Ctrl+C to pause, rerun to resume\n", dir, rate);
+
+            tokio::select! {
+                result = builder.build() => {
+                    result?;
+                    outln!("\n✓ Dataset complete in {} (see README.md and manifest.json there)", dir);
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    outln!("\nPaused; progress is saved. Rerun `dataset build` to resume.");
+                }
+            }
+        }
+        Some("status") => {
+            let checkpoint = builder.checkpoint();
+            outln!("Dataset:              {}", dir);
+            if checkpoint.requests == 0 {
+                outln!("Not started.");
+                return Ok(());
+            }
+            outln!("Started:              {}", format_timestamp(checkpoint.started_at));
+            outln!("Last Progress:        {}", format_timestamp(checkpoint.updated_at));
+            outln!(
+                "Markets:              {}{}",
+                checkpoint.markets,
+                if checkpoint.markets_complete { " (complete)" } else { "" }
+            );
+            outln!("Wallets:              {} / {}", checkpoint.wallets_done, checkpoint.wallets.len());
+            outln!("Trades:               {}", checkpoint.trades);
+            outln!("Requests:             {}", checkpoint.requests);
+        }
+        _ => outln!("Usage: cargo run -- dataset <build|status> [--out <dir>] [--wallets <n>] [--rate <per minute>] [--fresh]"),
+    }
+
+    Ok(())
+}
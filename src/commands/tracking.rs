@@ -0,0 +1,519 @@
+use crate::address::validate_address;
+use crate::alerts::{Alert, AlertDispatcher};
+use crate::copy_trading::{CopySignalGenerator, CopySignalLog, COPY_CURSORS_FILE};
+use crate::digest::{self, Digest, DigestLog};
+use crate::knowledge::{BlacklistEntry, WalletKnowledgeBase, WalletNote, WalletQuery};
+use crate::models::TradeCursor;
+use crate::resolutions::ResolutionChecker;
+use crate::shutdown;
+use crate::signals::{smart_money_signals, SignalLog};
+use crate::store::Store;
+use crate::watchlist::{
+    self, MarketMetadata, TradeContext, TradeContextBuilder, TradeTail, WatchActivityLog, WatchCursors, Watchlist,
+    WatchlistEntry,
+};
+use crate::{Config, PolymarketClient};
+use super::args::{flag_value, parse_since};
+use super::format_timestamp;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Manages the wallet watchlist (add/remove/list/activity)
+pub async fn manage_watchlist(config: &Config, args: &[String]) -> Result<()> {
+    let store = Store::open(&config.data_dir)?;
+    let mut watchlist = Watchlist::load(&store)?;
+
+    match args.get(2).map(|a| a.as_str()) {
+        Some("add") => {
+            let Some(address) = args.get(3) else {
+                outln!("Usage: cargo run -- watchlist add <wallet_address> [--label <name>] [--categories <a,b>]");
+                return Ok(());
+            };
+            let address = validate_address(address)?;
+            let categories = flag_value(args, "--categories")
+                .map(|list| {
+                    list.split(',')
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            watchlist.add(WatchlistEntry {
+                address: address.clone(),
+                label: flag_value(args, "--label").map(String::from),
+                categories,
+            });
+            watchlist.save(&store)?;
+            outln!("✓ Added {} to the watchlist", address);
+        }
+        Some("remove") => {
+            let Some(address) = args.get(3) else {
+                outln!("Usage: cargo run -- watchlist remove <wallet_address>");
+                return Ok(());
+            };
+            let address = &validate_address(address)?;
+            if watchlist.remove(address) {
+                watchlist.save(&store)?;
+                outln!("✓ Removed {} from the watchlist", address);
+            } else {
+                outln!("{} is not on the watchlist", address);
+            }
+        }
+        Some("activity") => {
+            let hours: i64 = flag_value(args, "--hours").and_then(|h| h.parse().ok()).unwrap_or(24);
+            watchlist_activity(config, &watchlist, hours).await?;
+        }
+        _ => watchlist.print(),
+    }
+
+    Ok(())
+}
+
+/// Shows recent trades of watched wallets, applying each entry's category filter
+async fn watchlist_activity(config: &Config, watchlist: &Watchlist, hours: i64) -> Result<()> {
+    outln!("Watched Wallet Activity (last {}h)", hours);
+    outln!("==================================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let mut metadata = MarketMetadata::new(client.clone());
+    let since = Utc::now().timestamp() - hours * 3600;
+
+    for entry in &watchlist.entries {
+        let trades = client.fetch_wallet_trades_since(&entry.address, &TradeCursor::since(since)).await?;
+
+        let trades = watchlist::alertable_trades(entry, trades, &mut metadata).await?;
+        for trade in &trades {
+            watchlist::print_trade(entry, trade, metadata.get(&trade.condition_id));
+        }
+        if trades.is_empty() {
+            outln!("\n{}: no matching trades", entry.display_name());
+        }
+    }
+
+    Ok(())
+}
+
+/// Queries and annotates the wallet knowledge base (show/search/label/tag/note/blacklist)
+pub fn manage_knowledge(config: &Config, args: &[String]) -> Result<()> {
+    let store = Store::open(&config.data_dir)?;
+    let command = args.get(2).map(|a| a.as_str());
+    let wallet = match command {
+        Some("search") => None,
+        _ => args.get(3).filter(|a| !a.starts_with("--")).map(|a| validate_address(a)).transpose()?,
+    };
+    // Free text after the wallet, up to the first flag
+    let text: Vec<String> = args.iter().skip(4).take_while(|a| !a.starts_with("--")).cloned().collect();
+
+    match (command, wallet.as_ref()) {
+        (Some("show"), Some(wallet)) => WalletKnowledgeBase::load(&store)?.print_record(wallet),
+        (Some("search"), _) => {
+            let query = WalletQuery::parse(&args[3..])?;
+            let knowledge = WalletKnowledgeBase::load(&store)?;
+            knowledge.print_search(&query, &knowledge.search(&query));
+        }
+        (Some("label"), Some(wallet)) => {
+            let label = text.join(" ");
+            WalletKnowledgeBase::update(&store, |knowledge| {
+                knowledge.record_mut(wallet).label = Some(label.clone()).filter(|l| !l.is_empty());
+            })?;
+            if label.is_empty() {
+                outln!("✓ Cleared the label of {}", wallet);
+            } else {
+                outln!("✓ Labelled {} as {}", wallet, label);
+            }
+        }
+        (Some("tag"), Some(wallet)) if !text.is_empty() => {
+            WalletKnowledgeBase::update(&store, |knowledge| {
+                let tags = &mut knowledge.record_mut(wallet).tags;
+                for tag in &text {
+                    if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                        tags.push(tag.clone());
+                    }
+                }
+            })?;
+            outln!("✓ Tagged {}: {}", wallet, text.join(", "));
+        }
+        (Some("untag"), Some(wallet)) if !text.is_empty() => {
+            WalletKnowledgeBase::update(&store, |knowledge| {
+                knowledge
+                    .record_mut(wallet)
+                    .tags
+                    .retain(|t| !text.iter().any(|tag| t.eq_ignore_ascii_case(tag)));
+            })?;
+            outln!("✓ Removed tags from {}: {}", wallet, text.join(", "));
+        }
+        (Some("note"), Some(wallet)) if !text.is_empty() => {
+            let note = WalletNote { at: Utc::now().timestamp(), text: text.join(" ") };
+            WalletKnowledgeBase::update(&store, |knowledge| knowledge.record_mut(wallet).notes.push(note))?;
+            outln!("✓ Added a note to {}", wallet);
+        }
+        (Some("blacklist"), Some(wallet)) => {
+            let entry = BlacklistEntry {
+                at: Utc::now().timestamp(),
+                reason: flag_value(args, "--reason").map(String::from),
+            };
+            WalletKnowledgeBase::update(&store, |knowledge| knowledge.record_mut(wallet).blacklist = Some(entry))?;
+            outln!("✓ Blacklisted {}: insider scans, watch and copy will skip it", wallet);
+        }
+        (Some("unblacklist"), Some(wallet)) => {
+            let removed = WalletKnowledgeBase::update(&store, |knowledge| {
+                knowledge.record_mut(wallet).blacklist.take().is_some()
+            })?;
+            if removed {
+                outln!("✓ Removed {} from the blacklist", wallet);
+            } else {
+                outln!("{} is not blacklisted", wallet);
+            }
+        }
+        _ => {
+            outln!("Usage:");
+            outln!("  cargo run -- kb show <wallet_address>");
+            outln!("  cargo run -- kb search [<term>...]          e.g. roi>50 score>=40 flagged !bot tag=whale");
+            outln!("  cargo run -- kb label <wallet_address> [<label>]");
+            outln!("  cargo run -- kb tag|untag <wallet_address> <tag>...");
+            outln!("  cargo run -- kb note <wallet_address> <text>");
+            outln!("  cargo run -- kb blacklist <wallet_address> [--reason <text>]");
+            outln!("  cargo run -- kb unblacklist <wallet_address>");
+        }
+    }
+
+    Ok(())
+}
+
+/// Tails watched wallets, printing new trades and alerting when one opens a position
+/// Wallets come from the command line, or else the watchlist, `[watch] wallets`
+/// and (with `--flagged`) wallets flagged by insider scans
+pub async fn watch_wallets(config: &Config, args: &[String]) -> Result<()> {
+    let store = Store::open(&config.data_dir)?;
+    let entries = tracked_wallets(&store, args, &config.watch.wallets)?;
+    if entries.is_empty() {
+        outln!("No wallets to watch.");
+        outln!("Pass addresses, add them with `watchlist add`, set `[watch] wallets`, or use --flagged.");
+        return Ok(());
+    }
+
+    outln!("Polymarket Wallet Watcher");
+    outln!("=========================\n");
+    outln!("Watching {} wallets - Press Ctrl+C to stop", entries.len());
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+    let mut metadata = MarketMetadata::new(client.clone());
+    let mut contexts = TradeContextBuilder::new(client.clone());
+    let activity = WatchActivityLog::new(store.clone());
+
+    // Resume each wallet from its last notified trade, unless replaying from a given time
+    let mut cursors = WatchCursors::load(&store)?;
+    let replay_since = flag_value(args, "--replay-since").map(parse_since).transpose()?;
+    if let Some(since) = replay_since {
+        outln!("Replaying trades since {}", format_timestamp(since));
+    }
+    let mut tails: Vec<TradeTail> = entries
+        .iter()
+        .map(|entry| match (replay_since, cursors.get(&entry.address)) {
+            (Some(since), _) => TradeTail::with_cursor(TradeCursor::since(since)),
+            (None, Some(cursor)) => TradeTail::with_cursor(cursor.clone()),
+            (None, None) => TradeTail::new(),
+        })
+        .collect();
+
+    let poll_interval = config.watch.poll_interval_secs.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(poll_interval));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for (entry, tail) in entries.iter().zip(tails.iter_mut()) {
+                    let trades = match tail.poll(&client, &entry.address).await {
+                        Ok(trades) => trades,
+                        Err(e) => {
+                            eoutln!("Warning: Failed to fetch trades for {}: {}", entry.display_name(), e);
+                            continue;
+                        }
+                    };
+
+                    for tailed in tail.new_trades(trades) {
+                        let alertable = match watchlist::alertable_trades(entry, vec![tailed.trade], &mut metadata).await {
+                            Ok(trades) => trades,
+                            Err(e) => {
+                                eoutln!("Warning: Failed to load market metadata: {}", e);
+                                continue;
+                            }
+                        };
+                        for trade in &alertable {
+                            let context = if tailed.opened_position {
+                                match contexts.context(trade, tail.recent(), &mut metadata).await {
+                                    Ok(context) => Some(context),
+                                    Err(e) => {
+                                        eoutln!("Warning: Failed to build trade context: {}", e);
+                                        Some(TradeContext {
+                                            notional: trade.size * trade.price,
+                                            ..TradeContext::default()
+                                        })
+                                    }
+                                }
+                            } else {
+                                None
+                            };
+
+                            if let Some(context) = &context {
+                                outln!("\n🆕 {} opened a new position", entry.display_name());
+                                alerts.send(&Alert::watched_trade(entry.display_name(), trade, context)).await;
+                            }
+                            watchlist::print_trade(entry, trade, metadata.get(&trade.condition_id));
+                            for line in context.iter().flat_map(TradeContext::summary_lines) {
+                                outln!("   {}", line);
+                            }
+                            if let Err(e) = activity.record(&entry.address, trade, context.is_some(), Utc::now().timestamp()) {
+                                eoutln!("Warning: Failed to record watched trade: {}", e);
+                            }
+                        }
+                    }
+
+                    if let Some(cursor) = tail.cursor() {
+                        cursors.set(&entry.address, cursor.clone());
+                    }
+                }
+
+                if let Err(e) = cursors.save(&store) {
+                    eoutln!("Warning: Failed to save watch cursors: {}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                outln!("\n[{}] Shutdown signal received, exiting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Wallets to tail: addresses given on the command line, or else the watchlist, the
+/// configured wallets and (with `--flagged`) wallets flagged by insider scans
+fn tracked_wallets(store: &Store, args: &[String], configured: &[String]) -> Result<Vec<WatchlistEntry>> {
+    let explicit: Vec<String> = args
+        .iter()
+        .skip(2)
+        .filter(|a| a.starts_with("0x"))
+        .map(|a| validate_address(a))
+        .collect::<Result<_>>()?;
+
+    let plain_entry = |address: &str| WatchlistEntry {
+        address: address.to_string(),
+        label: None,
+        categories: Vec::new(),
+    };
+
+    let mut entries: Vec<WatchlistEntry> = if explicit.is_empty() {
+        let knowledge = WalletKnowledgeBase::load(store)?;
+        let mut entries = knowledge.watchlist().entries;
+        entries.extend(configured.iter().map(|a| plain_entry(a)));
+        if args.iter().any(|a| a == "--flagged") {
+            entries.extend(knowledge.flagged_wallets().iter().map(|a| plain_entry(a)));
+        }
+
+        // Blacklisted wallets are only tracked when named explicitly
+        let before = entries.len();
+        entries.retain(|e| !knowledge.is_blacklisted(&e.address));
+        if entries.len() < before {
+            outln!("Skipping {} blacklisted wallet(s)", before - entries.len());
+        }
+        entries
+    } else {
+        explicit.iter().map(|a| plain_entry(a)).collect()
+    };
+
+    // Keep the first entry per wallet (watchlist entries carry labels and filters)
+    let mut seen = HashSet::new();
+    entries.retain(|e| seen.insert(e.address.to_lowercase()));
+
+    Ok(entries)
+}
+
+/// Tails tracked wallets and emits their new trades as copy-trading signals
+pub async fn copy_signals(config: &Config, args: &[String]) -> Result<()> {
+    let store = Store::open(&config.data_dir)?;
+    let entries = tracked_wallets(&store, args, &config.copy.wallets)?;
+    if entries.is_empty() {
+        outln!("No wallets to copy.");
+        outln!("Pass addresses, add them with `watchlist add`, set `[copy] wallets`, or use --flagged.");
+        return Ok(());
+    }
+
+    // With --jsonl, stdout carries only the signals so it can be piped
+    let jsonl = args.iter().any(|a| a == "--jsonl");
+    if !jsonl {
+        outln!("Polymarket Copy-Trading Signals");
+        outln!("===============================\n");
+        outln!("Tracking {} wallets - Press Ctrl+C to stop", entries.len());
+    }
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+    let generator = CopySignalGenerator::new(config.copy.clone());
+    let copy_log = CopySignalLog::new(store.clone());
+    let signal_log = SignalLog::new(store.clone());
+    let mut metadata = MarketMetadata::new(client.clone());
+
+    // Resume each wallet from its last signalled trade; wallets never tracked start from now
+    let mut cursors = WatchCursors::load_from(&store, COPY_CURSORS_FILE)?;
+    let mut tails: Vec<TradeTail> = entries
+        .iter()
+        .map(|entry| match cursors.get(&entry.address) {
+            Some(cursor) => TradeTail::with_cursor(cursor.clone()),
+            None => TradeTail::new(),
+        })
+        .collect();
+
+    let poll_interval = config.watch.poll_interval_secs.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(poll_interval));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for (entry, tail) in entries.iter().zip(tails.iter_mut()) {
+                    let trades = match tail.poll(&client, &entry.address).await {
+                        Ok(trades) => trades,
+                        Err(e) => {
+                            eoutln!("Warning: Failed to fetch trades for {}: {}", entry.display_name(), e);
+                            continue;
+                        }
+                    };
+
+                    let mut new_trades = Vec::new();
+                    for tailed in tail.new_trades(trades) {
+                        match watchlist::alertable_trades(entry, vec![tailed.trade.clone()], &mut metadata).await {
+                            Ok(alertable) if !alertable.is_empty() => new_trades.push(tailed),
+                            Ok(_) => {}
+                            Err(e) => eoutln!("Warning: Failed to load market metadata: {}", e),
+                        }
+                    }
+
+                    let signals = generator.signals(entry.display_name(), &new_trades, tail.recent());
+                    if let Err(e) = copy_log.record(&signals) {
+                        eoutln!("Warning: Failed to record copy signals: {}", e);
+                    }
+                    if let Err(e) = signal_log.record(&smart_money_signals(&signals)) {
+                        eoutln!("Warning: Failed to record signals: {}", e);
+                    }
+                    for signal in &signals {
+                        if jsonl {
+                            outln!("{}", serde_json::to_string(signal)?);
+                        } else {
+                            outln!(
+                                "\n[{}] {} {} {} @ ${:.3} - {}",
+                                format_timestamp(signal.trade_timestamp),
+                                signal.wallet_name,
+                                signal.side,
+                                signal.outcome,
+                                signal.entry_price,
+                                signal.title.as_deref().unwrap_or(&signal.condition_id)
+                            );
+                            outln!(
+                                "   Suggested stake ${:.2} ({:.1}x the wallet's typical size, wallet traded ${:.2})",
+                                signal.suggested_stake, signal.size_proportion, signal.wallet_notional
+                            );
+                        }
+                        alerts.send(&Alert::copy_signal(signal)).await;
+                    }
+
+                    if let Some(cursor) = tail.cursor() {
+                        cursors.set(&entry.address, cursor.clone());
+                    }
+                }
+
+                if let Err(e) = cursors.save_to(&store, COPY_CURSORS_FILE) {
+                    eoutln!("Warning: Failed to save copy cursors: {}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                eoutln!("\n[{}] Shutdown signal received, exiting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Grades pending signals and prints the signal accuracy report
+pub async fn signal_report(config: &Config, grade: bool) -> Result<()> {
+    outln!("Polymarket Signal Grader");
+    outln!("========================\n");
+
+    let store = Store::open(&config.data_dir)?;
+    let log = SignalLog::new(store.clone());
+
+    if grade {
+        outln!("🔍 Grading signals against market resolutions...");
+        let checker = ResolutionChecker::new(PolymarketClient::with_config(&config.api)?, store);
+        let graded = log.grade_resolved(&checker).await?;
+        outln!("✓ Graded {} signals", graded);
+    }
+
+    log.print_report(&log.load()?);
+
+    Ok(())
+}
+
+/// Summarizes what the scanners recorded over the last day (or `--hours`), optionally
+/// sending it through the alert destinations; `--daily` keeps running and sends one a day
+pub async fn digest_command(config: &Config, args: &[String]) -> Result<()> {
+    let store = Store::open(&config.data_dir)?;
+    let log = DigestLog::new(store.clone());
+    let alerts = AlertDispatcher::from_config(&config.alerts);
+
+    if args.iter().any(|arg| arg == "--daily") {
+        outln!("Polymarket Daily Digest");
+        outln!("=======================\n");
+        if !alerts.is_enabled() {
+            outln!("No alert destinations configured under [alerts]; digests will only be printed and saved.");
+        }
+
+        let shutdown = shutdown::ctrl_c_token();
+        loop {
+            let next = digest::next_run(Utc::now().timestamp(), config.digest.hour_utc);
+            outln!("Next digest at {} - Press Ctrl+C to stop", format_timestamp(next));
+            let wait = Duration::from_secs((next - Utc::now().timestamp()).max(0) as u64);
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = shutdown.cancelled() => break,
+            }
+
+            // Pick up where the last digest ended, unless that was over a week ago
+            let since = log
+                .last()?
+                .map(|last| last.until)
+                .filter(|&until| next - until <= 7 * 86_400)
+                .unwrap_or(next - 86_400);
+            let digest = Digest::build(&store, since, next)?;
+            digest.print();
+            log.record(&digest)?;
+            alerts.send(&Alert::digest(&digest)).await;
+        }
+        return Ok(());
+    }
+
+    let hours = match flag_value(args, "--hours") {
+        Some(hours) => hours.parse::<i64>().context("--hours must be a number of hours")?,
+        None => 24,
+    };
+    let until = Utc::now().timestamp();
+    let digest = Digest::build(&store, until - hours * 3600, until)?;
+    digest.print();
+    log.record(&digest)?;
+
+    if args.iter().any(|arg| arg == "--send") {
+        if !alerts.is_enabled() {
+            anyhow::bail!("No alert destinations configured under [alerts]");
+        }
+        alerts.send(&Alert::digest(&digest)).await;
+        outln!("\n✓ Sent digest");
+    }
+
+    Ok(())
+}
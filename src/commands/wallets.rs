@@ -0,0 +1,660 @@
+use crate::address::validate_address;
+use crate::alerts::AlertDispatcher;
+use crate::clusters::ClusterReport;
+use crate::cohort::CohortReport;
+use crate::exchange::ExchangeClient;
+use crate::fees::TradingCosts;
+use crate::hedging::{self, HedgePlanner};
+use crate::identities::IdentityHistory;
+use crate::knowledge::{ScanSummary, WalletKnowledgeBase};
+use crate::leaderboard::LeaderboardEntry;
+use crate::market_cache::ResolvedMarketCache;
+use crate::models::{LeaderboardWindow, PricePoint};
+use crate::positions;
+use crate::shutdown;
+use crate::signals::SignalLog;
+use crate::snapshots::OrderBookSnapshotter;
+use crate::store::Store;
+use crate::trade_history::TradeHistoryCache;
+use crate::wallet_analyzer::PositionLedger;
+use crate::wallet_pipeline::{WalletAnalysis, WalletPipeline};
+use crate::wallet_scanner::{DiscoveryStrategy, ScanState};
+use crate::{Config, Market, PolymarketClient, Trade, WalletAnalyzer, WalletPerformance, WalletScanner};
+use super::args::flag_value;
+use super::{write_csv, write_html, write_json, write_parquet};
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
+use tokio::time::Instant;
+
+/// Analyzes a wallet's trading performance
+pub async fn analyze_wallet(config: &Config, wallet_address: &str, args: &[String]) -> Result<()> {
+    let detailed = args.iter().any(|arg| arg == "--detailed");
+    let attribution = args.iter().any(|arg| arg == "--attribution");
+    let csv_path = flag_value(args, "--csv");
+    let json_path = flag_value(args, "--json");
+    let html_path = flag_value(args, "--html");
+    let timeline_path = flag_value(args, "--timeline");
+
+    outln!("Polymarket Wallet Analyzer");
+    outln!("==========================\n");
+    outln!("Analyzing wallet: {}\n", wallet_address);
+    if config.insider.resolved_within_days > 0 {
+        outln!("Only counting markets resolved in the last {} days\n", config.insider.resolved_within_days);
+    }
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let analyzer = WalletAnalyzer::new()
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days);
+
+    // Resolved markets come from the local cache when it's fresh
+    let store = Store::open(&config.data_dir)?;
+    let cache = ResolvedMarketCache::new(store.clone(), config.cache.resolved_markets_max_age_secs());
+    let trade_history = config.cache.trade_histories.then(|| TradeHistoryCache::new(store));
+    let mut pipeline = WalletPipeline::new(&client, &analyzer)
+        .with_trade_history(trade_history.as_ref())
+        .with_resolved_cache(&cache)
+        .with_wallet_activity(config.insider.wallet_activity)
+        .with_liquidity_roles(true)
+        .with_progress(true);
+    if attribution {
+        pipeline = pipeline.with_attribution(config.api.max_concurrent_requests);
+    }
+
+    let analysis = pipeline.run(wallet_address).await?;
+    let Some(WalletAnalysis { trades, resolved_markets, performance, report, username }) = analysis else {
+        outln!("\nNo trades found for this wallet.");
+        return Ok(());
+    };
+
+    // Print results
+    analyzer.print_performance(&performance, detailed);
+
+    if let Some(path) = csv_path {
+        let export = analyzer.positions_csv(&performance, config.export.decimal_places);
+        write_csv(&export, path)?;
+    }
+
+    // Keep the latest results in the wallet knowledge base
+    let summary = ScanSummary::new(username.as_deref(), &performance, Some(&report));
+    if let Err(e) = WalletKnowledgeBase::update(&Store::open(&config.data_dir)?, |knowledge| {
+        knowledge.record_scan(wallet_address, summary)
+    }) {
+        eoutln!("Warning: Failed to update the wallet knowledge base: {}", e);
+    }
+
+    if let Some(path) = json_path {
+        write_json(&LeaderboardEntry::new(username.as_deref(), &performance, &report), path)?;
+    }
+
+    if let Some(path) = html_path {
+        let title = format!("Wallet analysis: {}", username.as_deref().unwrap_or(wallet_address));
+        write_html(&title, &[(wallet_address.to_string(), username, performance, report)], path)?;
+    }
+
+    if let Some(path) = timeline_path {
+        write_timeline(&client, &analyzer, config, wallet_address, &trades, &resolved_markets, path).await?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the price history of every outcome a wallet traded and writes its trading timeline as JSON
+async fn write_timeline(
+    client: &PolymarketClient,
+    analyzer: &WalletAnalyzer,
+    config: &Config,
+    wallet_address: &str,
+    trades: &[Trade],
+    resolved_markets: &[Market],
+    path: &str,
+) -> Result<()> {
+    outln!("\n🕒 Fetching price history for the trading timeline...");
+    let timeline_start = Instant::now();
+
+    // Markets still open aren't in the resolved set, so their token IDs are looked up
+    let traded: HashSet<&str> = trades.iter().map(|t| t.condition_id.as_str()).collect();
+    let mut markets: Vec<Market> = resolved_markets
+        .iter()
+        .filter(|m| m.condition_id.as_deref().is_some_and(|id| traded.contains(id)))
+        .cloned()
+        .collect();
+    let resolved: HashSet<&str> = markets.iter().filter_map(|m| m.condition_id.as_deref()).collect();
+    let open_ids: Vec<String> = traded.iter().filter(|id| !resolved.contains(*id)).map(|id| id.to_string()).collect();
+    let open_markets = if open_ids.is_empty() {
+        Vec::new()
+    } else {
+        client.fetch_markets_by_condition_ids(&open_ids).await.unwrap_or_else(|e| {
+            eoutln!("Warning: Failed to fetch open markets, leaving them off the timeline: {}", e);
+            Vec::new()
+        })
+    };
+    markets.extend(open_markets);
+
+    let queries = analyzer.timeline_queries(trades, &markets);
+    let histories: HashMap<(String, usize), Vec<PricePoint>> = futures::stream::iter(&queries)
+        .map(|query| async move {
+            let history = client.fetch_token_price_history_range(&query.token_id, query.start, query.end).await;
+            (query, history)
+        })
+        .buffer_unordered(config.api.max_concurrent_requests.max(1))
+        .filter_map(|(query, history)| async move {
+            match history {
+                Ok(points) => Some(((query.condition_id.clone(), query.outcome_index), points)),
+                Err(e) => {
+                    eoutln!("Warning: Failed to fetch price history for {}: {}", query.condition_id, e);
+                    None
+                }
+            }
+        })
+        .collect()
+        .await;
+
+    let timeline = analyzer.timeline(wallet_address, trades, &queries, &histories);
+    outln!(
+        "✓ Placed {} trades against {} price histories in {:.2}s",
+        timeline.events.len(),
+        timeline.series.len(),
+        timeline_start.elapsed().as_secs_f64()
+    );
+    match timeline.mean_change_after_entry {
+        Some(mean) => outln!(
+            "  {} of {} priced buys were followed by a jump of {:.0}¢+ within {}h (mean move {:+.1}¢)",
+            timeline.entries_before_jumps,
+            timeline.priced_entries,
+            timeline.jump_threshold * 100.0,
+            timeline.lookahead_hours,
+            mean * 100.0
+        ),
+        None => outln!("  No buys had price history on both sides of the lookahead window"),
+    }
+
+    write_json(&timeline, path)
+}
+
+/// Analyzes a wallet on any venue from its trades and the markets it traded
+/// (without the Polymarket-only extras: caches, taker fills and attribution)
+pub(crate) async fn analyze_exchange_wallet(config: &Config, client: &impl ExchangeClient, wallet: &str, detailed: bool) -> Result<()> {
+    let title = format!("{} Wallet Analyzer", client.venue());
+    outln!("{}", title);
+    outln!("{}\n", "=".repeat(title.chars().count()));
+    outln!("Analyzing: {}\n", wallet);
+
+    outln!("📊 Fetching trade history...");
+    let fetch_start = Instant::now();
+    let mut trades = client.fetch_wallet_trades(wallet).await?;
+    outln!("✓ Fetched {} trades in {:.2}s", trades.len(), fetch_start.elapsed().as_secs_f64());
+    if trades.is_empty() {
+        outln!("\nNo trades found for this wallet.");
+        return Ok(());
+    }
+
+    let mut ids: Vec<String> = trades.iter().map(|t| t.condition_id.clone()).collect();
+    ids.sort();
+    ids.dedup();
+    let markets = client.fetch_markets_by_ids(&ids).await?;
+    outln!("✓ Fetched {} of {} traded markets\n", markets.len(), ids.len());
+
+    let titles: HashMap<&str, &str> = markets
+        .iter()
+        .filter_map(|m| Some((m.condition_id.as_deref()?, m.question.as_str())))
+        .collect();
+    for trade in trades.iter_mut().filter(|t| t.title.is_none()) {
+        trade.title = titles.get(trade.condition_id.as_str()).map(|title| title.to_string());
+    }
+    let (resolved_markets, open_markets): (Vec<Market>, Vec<Market>) =
+        markets.into_iter().partition(|m| m.closed == Some(true));
+
+    let analyzer = WalletAnalyzer::new()
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days);
+    let mut performance = analyzer.analyze(&trades, &resolved_markets);
+    let open_positions = analyzer.open_positions(&trades, &resolved_markets);
+    analyzer.value_open_positions(&mut performance, &open_positions, &open_markets);
+    analyzer.print_performance(&performance, detailed);
+
+    Ok(())
+}
+
+/// Auto-scan mode: Find and analyze active wallets for insider patterns
+pub async fn auto_scan_for_insiders(
+    config: &Config,
+    sample_size: usize,
+    max_wallets: usize,
+    continuous: bool,
+    fresh: bool,
+    json_path: Option<&str>,
+    html_path: Option<&str>,
+) -> Result<()> {
+    let discovery: DiscoveryStrategy = config.scan.discovery.parse()?;
+
+    outln!("Polymarket Insider Scanner");
+    outln!("==========================\n");
+
+    if continuous {
+        outln!("Running in CONTINUOUS mode - Press Ctrl+C to stop");
+        outln!("Will keep scanning for profitable wallets and accumulate results...\n");
+        if config.schedule.enabled {
+            outln!("Scheduling scans around resolution bursts ({}+ markets per hour)\n", config.schedule.burst_min_markets);
+        }
+    } else {
+        outln!("Automatically finding and analyzing wallets for insider patterns...\n");
+    }
+
+    // Ctrl+C stops the batch in flight and keeps (and reports) the wallets analyzed so far
+    let client = PolymarketClient::with_config(&config.api)?.with_cancellation(shutdown::ctrl_c_token());
+    let store = Store::open(&config.data_dir)?;
+    let mut scanner = insider_scanner(config, client, &store).with_discovery(discovery);
+
+    if continuous {
+        if fresh {
+            ScanState::clear(&store)?;
+        }
+        scanner = scanner
+            .with_state_store(store.clone())
+            .with_resolved_refresh(config.scan.resolved_refresh_iterations)
+            .with_schedule(config.schedule.clone());
+        scanner.continuous_scan(sample_size, max_wallets).await?;
+
+        let state = ScanState::load(&store)?;
+        if let Some(path) = json_path {
+            write_json(&LeaderboardEntry::ranked(&state.profitable_wallets), path)?;
+        }
+        if let Some(path) = html_path {
+            write_html("Insider scan (continuous)", &state.profitable_wallets, path)?;
+        }
+    } else {
+        scanner = scanner.with_coordination(config.coordination.clone());
+
+        // Step 1: Find active wallets
+        let wallets = scanner.find_active_wallets(sample_size, max_wallets).await?;
+
+        if wallets.is_empty() {
+            outln!("No active wallets found.");
+            return Ok(());
+        }
+
+        // Step 2: Analyze them for insider patterns
+        let profitable_wallets = scanner.scan_for_insiders(&wallets).await?;
+
+        if let Some(path) = json_path {
+            write_json(&LeaderboardEntry::ranked(&profitable_wallets), path)?;
+        }
+        if let Some(path) = html_path {
+            write_html("Insider scan", &profitable_wallets, path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a wallet scanner with the insider criteria, alerts and caches from the config
+fn insider_scanner(config: &Config, client: PolymarketClient, store: &Store) -> WalletScanner {
+    let mut scanner = WalletScanner::new()
+        .with_client(client.clone())
+        .with_criteria(config.insider.clone())
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days)
+        .with_signal_log(SignalLog::new(store.clone()))
+        .with_alerts(AlertDispatcher::from_config(&config.alerts))
+        .with_knowledge_base(store.clone())
+        .with_market_cache(ResolvedMarketCache::new(
+            store.clone(),
+            config.cache.resolved_markets_max_age_secs(),
+        ));
+    if config.cache.trade_histories {
+        scanner = scanner.with_trade_history(
+            TradeHistoryCache::new(store.clone()).with_max_age_secs(config.cache.trade_histories_max_age_secs),
+        );
+    }
+    if config.snapshots.enabled {
+        scanner = scanner.with_snapshots(OrderBookSnapshotter::new(client, store.clone()));
+    }
+    scanner
+}
+
+/// Ranks wallets by profit from Polymarket's leaderboard, optionally running them
+/// through the insider analyzer
+pub async fn scan_leaderboard(config: &Config, args: &[String]) -> Result<()> {
+    let window: LeaderboardWindow = flag_value(args, "--window").unwrap_or("week").parse()?;
+    let limit = match flag_value(args, "--limit") {
+        Some(n) => n.parse::<usize>().context("--limit must be a number of wallets")?,
+        None => 50,
+    };
+    let category = flag_value(args, "--category");
+    let analyze = args.iter().any(|arg| arg == "--analyze");
+
+    outln!("Polymarket Profit Leaderboard");
+    outln!("=============================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let rankings = client.fetch_leaderboard(window, category, limit).await?;
+    if rankings.is_empty() {
+        outln!("No wallets on the leaderboard for the {}.", window.label());
+        return Ok(());
+    }
+
+    outln!("Top {} wallets by profit, {}{}:\n", rankings.len(), window.label(),
+        category.map(|c| format!(" ({})", c)).unwrap_or_default());
+    outln!("{:>4}  {:<44}  {:<20}  {:>14}  {:>14}", "Rank", "Wallet", "Name", "Profit", "Volume");
+    outln!("{}", "-".repeat(104));
+    for (i, ranking) in rankings.iter().enumerate() {
+        let name = ranking.user_name.as_deref().filter(|name| !name.is_empty()).unwrap_or("-");
+        outln!("{:>4}  {:<44}  {:<20}  {:>14}  {:>14}",
+            i + 1,
+            ranking.proxy_wallet,
+            name.chars().take(20).collect::<String>(),
+            format!("${:.2}", ranking.pnl),
+            format!("${:.2}", ranking.vol));
+    }
+
+    if !analyze {
+        return Ok(());
+    }
+
+    outln!("\nAnalyzing {} leaderboard wallets for insider patterns...\n", rankings.len());
+    let store = Store::open(&config.data_dir)?;
+    let client = client.with_cancellation(shutdown::ctrl_c_token());
+    let scanner = insider_scanner(config, client, &store)
+        .with_coordination(config.coordination.clone());
+    let wallets: Vec<String> = rankings.into_iter().map(|r| r.proxy_wallet).collect();
+    let profitable_wallets = scanner.scan_for_insiders(&wallets).await?;
+
+    if let Some(path) = flag_value(args, "--json") {
+        write_json(&LeaderboardEntry::ranked(&profitable_wallets), path)?;
+    }
+    if let Some(path) = flag_value(args, "--html") {
+        let title = format!("Leaderboard insider scan, {}", window.label());
+        write_html(&title, &profitable_wallets, path)?;
+    }
+
+    Ok(())
+}
+
+/// Exports the profitable wallets accumulated by continuous scans as leaderboard JSON
+pub fn export_leaderboard(config: &Config, args: &[String]) -> Result<()> {
+    let state = ScanState::load(&Store::open(&config.data_dir)?)?;
+    let entries = LeaderboardEntry::ranked(&state.profitable_wallets);
+
+    match flag_value(args, "--out") {
+        Some(path) => write_json(&entries, path),
+        None => {
+            outln!("{}", serde_json::to_string_pretty(&entries)?);
+            Ok(())
+        }
+    }
+}
+
+/// Fetches and analyzes each wallet's full history, marking open positions to market
+async fn analyze_wallets(
+    client: &PolymarketClient,
+    analyzer: &WalletAnalyzer,
+    wallets: &[String],
+    resolved_markets: &[Market],
+) -> Result<Vec<(Vec<Trade>, WalletPerformance)>> {
+    let mut members = Vec::new();
+    for wallet in wallets {
+        outln!("📊 Analyzing {}...", wallet);
+        let trades = client.fetch_wallet_trades(wallet).await?;
+        let mut performance = analyzer.analyze(&trades, resolved_markets);
+        performance.wallet_address = wallet.clone();
+
+        let open_positions = analyzer.open_positions(&trades, resolved_markets);
+        if !open_positions.is_empty() {
+            let mut condition_ids: Vec<String> = open_positions.iter().map(|p| p.condition_id.clone()).collect();
+            condition_ids.sort();
+            condition_ids.dedup();
+            let current_markets = client
+                .fetch_markets_by_condition_ids(&condition_ids)
+                .await
+                .unwrap_or_else(|e| {
+                    eoutln!("Warning: Failed to fetch current prices for open positions: {}", e);
+                    Vec::new()
+                });
+            analyzer.value_open_positions(&mut performance, &open_positions, &current_markets);
+        }
+
+        members.push((trades, performance));
+    }
+
+    Ok(members)
+}
+
+/// Analyzes a group of wallets as one trader and exports the cluster report
+pub async fn cluster_report(config: &Config, args: &[String]) -> Result<()> {
+    let mut wallets: Vec<String> = args
+        .iter()
+        .skip(2)
+        .filter(|a| a.starts_with("0x"))
+        .map(|a| validate_address(a))
+        .collect::<Result<_>>()?;
+    let mut seen = HashSet::new();
+    wallets.retain(|w| seen.insert(w.clone()));
+    if wallets.len() < 2 {
+        outln!("Usage: cargo run -- cluster <wallet_address> <wallet_address>... [--name <id>] [--csv <file>]");
+        return Ok(());
+    }
+    let id = flag_value(args, "--name")
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("cluster-{}", wallets[0]));
+
+    outln!("Polymarket Cluster Report");
+    outln!("=========================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let store = Store::open(&config.data_dir)?;
+    let analyzer = WalletAnalyzer::new()
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days);
+
+    outln!("📚 Loading resolved markets...");
+    let cache = ResolvedMarketCache::new(store.clone(), config.cache.resolved_markets_max_age_secs());
+    let resolved_markets = cache.load_or_fetch_all(&client).await?;
+
+    let members = analyze_wallets(&client, &analyzer, &wallets, &resolved_markets).await?;
+
+    let report = ClusterReport::build(&id, &members);
+    report.print();
+
+    let path = report.save(&store)?;
+    outln!("\n✓ Saved cluster report to {}", path.display());
+
+    if let Some(path) = flag_value(args, "--csv") {
+        write_csv(&report.members_csv(config.export.decimal_places), path)?;
+    }
+
+    Ok(())
+}
+
+/// Compares a handful of wallets side by side and ranks them by consistency
+pub async fn compare_wallets(config: &Config, args: &[String]) -> Result<()> {
+    let mut wallets: Vec<String> = args
+        .iter()
+        .skip(2)
+        .filter(|a| a.starts_with("0x"))
+        .map(|a| validate_address(a))
+        .collect::<Result<_>>()?;
+    let mut seen = HashSet::new();
+    wallets.retain(|w| seen.insert(w.clone()));
+    if wallets.len() < 2 {
+        outln!("Usage: cargo run -- compare <wallet_address> <wallet_address>... [--csv <file>]");
+        return Ok(());
+    }
+
+    outln!("Polymarket Wallet Comparison");
+    outln!("============================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    let store = Store::open(&config.data_dir)?;
+    let analyzer = WalletAnalyzer::new()
+        .with_suspicion(config.suspicion.clone())
+        .with_min_position_invested(config.insider.min_position_invested)
+        .with_period_days(config.insider.period_days)
+        .with_resolved_within_days(config.insider.resolved_within_days);
+
+    outln!("📚 Loading resolved markets...");
+    let cache = ResolvedMarketCache::new(store, config.cache.resolved_markets_max_age_secs());
+    let resolved_markets = cache.load_or_fetch_all(&client).await?;
+
+    let members = analyze_wallets(&client, &analyzer, &wallets, &resolved_markets).await?;
+    let report = CohortReport::build(&members, &resolved_markets);
+    report.print();
+
+    if let Some(path) = flag_value(args, "--csv") {
+        write_csv(&report.members_csv(config.export.decimal_places), path)?;
+    }
+
+    Ok(())
+}
+
+/// Suggests hedges for a wallet's open positions: buying the other side where that
+/// locks in profit or caps a loss at current order book prices
+pub async fn hedge_positions(config: &Config, wallet: &str) -> Result<()> {
+    outln!("Polymarket Hedge Planner");
+    outln!("========================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    outln!("📊 Fetching trade history for {}...", wallet);
+    let trades = client.fetch_wallet_trades(wallet).await?;
+    let positions: Vec<_> = PositionLedger::from_trades(&trades)
+        .positions()
+        .into_iter()
+        .filter(|p| p.net_shares > 0.0)
+        .collect();
+
+    let mut condition_ids: Vec<String> = positions.iter().map(|p| p.condition_id.clone()).collect();
+    condition_ids.sort();
+    condition_ids.dedup();
+    outln!("💹 Pricing hedges for {} positions against the order book...", positions.len());
+    let current_markets = client.fetch_markets_by_condition_ids(&condition_ids).await?;
+    let trading: HashSet<&str> = current_markets
+        .iter()
+        .filter(|m| m.closed != Some(true))
+        .filter_map(|m| m.condition_id.as_deref())
+        .collect();
+    let open = positions.iter().filter(|p| trading.contains(p.condition_id.as_str())).count();
+
+    let planner = HedgePlanner::new(client, TradingCosts::from_config(&config.arbitrage));
+    let suggestions = planner.plan(&positions, &current_markets).await;
+    hedging::print_suggestions(wallet, open, &suggestions);
+
+    Ok(())
+}
+
+/// Lists a wallet's current positions from the positions API; with `check`, also rebuilds
+/// them from trade history and reports where the analyzer's positions disagree
+pub async fn wallet_positions(config: &Config, wallet: &str, check: bool) -> Result<()> {
+    outln!("Polymarket Wallet Positions");
+    outln!("===========================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    outln!("📊 Fetching positions for {}...", wallet);
+    let api_positions = client.fetch_wallet_positions(wallet).await?;
+    positions::print_positions(wallet, &api_positions);
+    if !check {
+        return Ok(());
+    }
+
+    outln!("\n📊 Fetching trade history for {}...", wallet);
+    let (trades, activity) = tokio::join!(client.fetch_wallet_trades(wallet), async {
+        if config.insider.wallet_activity {
+            client.fetch_wallet_activity(wallet).await.ok()
+        } else {
+            None
+        }
+    });
+    let mut ledger = PositionLedger::from_trades(&trades?);
+    if let Some(activity) = &activity {
+        ledger.extend_activity(activity);
+    }
+
+    let mut condition_ids: Vec<String> = ledger
+        .positions()
+        .into_iter()
+        .filter(|p| p.net_shares > 0.0)
+        .map(|p| p.condition_id)
+        .collect();
+    condition_ids.sort();
+    condition_ids.dedup();
+    let markets = client.fetch_markets_by_condition_ids(&condition_ids).await?;
+    ledger.label_outcomes(&markets);
+    let resolved_markets: Vec<Market> = markets.into_iter().filter(|m| m.closed == Some(true)).collect();
+    let ledger_positions = WalletAnalyzer::new().ledger_open_positions(&ledger, &resolved_markets);
+
+    let mismatches = positions::reconcile(&api_positions, &ledger_positions);
+    positions::print_reconciliation(&api_positions, &ledger_positions, &mismatches);
+    Ok(())
+}
+
+/// Downloads a wallet's full trade history and writes it as CSV or Parquet
+pub async fn export_trades(config: &Config, args: &[String]) -> Result<()> {
+    let Some(wallet) = args.get(2).filter(|a| a.starts_with("0x")) else {
+        outln!("Usage: cargo run -- export <wallet_address> [--out <file>] [--format csv|parquet]");
+        return Ok(());
+    };
+    let wallet = validate_address(wallet)?;
+
+    // The format follows --format, then the output file's extension
+    let out = flag_value(args, "--out");
+    let format = match flag_value(args, "--format") {
+        Some(format) => format.to_lowercase(),
+        None if out.is_some_and(|path| path.ends_with(".parquet")) => "parquet".to_string(),
+        None => "csv".to_string(),
+    };
+    let path = out
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{}_trades.{}", wallet, format));
+
+    outln!("Polymarket Trade Export");
+    outln!("=======================\n");
+
+    let client = PolymarketClient::with_config(&config.api)?;
+    outln!("📥 Fetching trade history for {}...", wallet);
+    let trades = client.fetch_wallet_trades(&wallet).await?;
+    outln!("✓ Fetched {} trades", trades.len());
+
+    let export = WalletAnalyzer::new().trades_export(&trades, config.export.decimal_places);
+    match format.as_str() {
+        "csv" => write_csv(&export, &path),
+        "parquet" => write_parquet(&export, &path),
+        other => anyhow::bail!("Unknown export format: {} (use csv or parquet)", other),
+    }
+}
+
+/// Looks up who's behind a wallet, or which wallet a username belongs to
+pub async fn whois(config: &Config, wallet: &str) -> Result<()> {
+    let client = PolymarketClient::with_config(&config.api)?;
+    let address = client.resolve_wallet(wallet).await?;
+    let profile = client.fetch_profile(&address).await?;
+
+    outln!("{:<22}{}", "Wallet:", address);
+    match profile.as_ref().and_then(|p| p.name.as_deref()).filter(|name| !name.is_empty()) {
+        Some(name) => outln!("{:<22}{}", "Username:", name),
+        None => outln!("{:<22}(none set)", "Username:"),
+    }
+    if let Some(pseudonym) = profile.as_ref().and_then(|p| p.pseudonym.as_deref()) {
+        outln!("{:<22}{}", "Pseudonym:", pseudonym);
+    }
+    if let Some(record) = WalletKnowledgeBase::load(&Store::open(&config.data_dir)?)?.get(&address) {
+        if let Some(label) = &record.label {
+            outln!("{:<22}{}", "Label:", label);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the wallets seen under more than one display name, or one wallet's name history
+pub fn identities_report(config: &Config, args: &[String]) -> Result<()> {
+    let history = IdentityHistory::load(&Store::open(&config.data_dir)?)?;
+    history.print_report(args.get(2).map(|a| a.as_str()).filter(|a| a.starts_with("0x")));
+    Ok(())
+}
@@ -0,0 +1,1063 @@
+use crate::models::WalletPerformance;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Config file loaded when `SCANNER_CONFIG` isn't set
+const DEFAULT_CONFIG_FILE: &str = "scanner.toml";
+
+/// Scanner configuration loaded from `scanner.toml`, with env-var overrides
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Directory for caches and persisted state
+    pub data_dir: String,
+    pub arbitrage: ArbitrageConfig,
+    pub rewards: RewardsConfig,
+    pub api: ApiConfig,
+    pub insider: InsiderCriteria,
+    pub suspicion: SuspicionConfig,
+    pub scan: ScanConfig,
+    pub schedule: ScheduleConfig,
+    pub coordination: CoordinationConfig,
+    pub alerts: AlertsConfig,
+    pub snapshots: SnapshotConfig,
+    pub paper: PaperConfig,
+    pub export: ExportConfig,
+    pub watch: WatchConfig,
+    pub monitor: MonitorConfig,
+    pub digest: DigestConfig,
+    pub copy: CopyConfig,
+    pub calibration: CalibrationConfig,
+    pub dataset: DatasetConfig,
+    pub backtest: BacktestConfig,
+    pub executor: ExecutorConfig,
+    pub cache: CacheConfig,
+    pub manifold: ManifoldConfig,
+    pub daemon: DaemonConfig,
+}
+
+/// Arbitrage scanner settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ArbitrageConfig {
+    /// Fixed YES + NO total to report below; when unset it's derived per market from fees
+    pub threshold: Option<f64>,
+    /// Also report markets where YES + NO is above $1 (sell both sides)
+    pub detect_overpriced: bool,
+    /// Fixed YES + NO total to report above; when unset it's derived per market from fees
+    pub overpriced_threshold: Option<f64>,
+    /// Shares per leg the fee-derived threshold is computed for
+    pub trade_size: f64,
+    /// Fixed cost per arbitrage (e.g. gas to merge or redeem), in dollars
+    pub fixed_cost_per_trade: f64,
+    /// Margin per $1 required beyond break-even
+    pub min_edge: f64,
+    /// Slippage assumed on every fill at quoted prices, in basis points of the price
+    pub slippage_bps: f64,
+    /// Extra slippage per unit of order notional / market liquidity
+    pub slippage_impact: f64,
+    /// Only scan markets with one of these categories or tags (empty = all)
+    pub include_categories: Vec<String>,
+    /// Skip markets with any of these categories or tags
+    pub exclude_categories: Vec<String>,
+    /// Skip markets with less order book liquidity than this, in dollars
+    pub min_liquidity: f64,
+    /// Skip markets with less traded volume than this, in dollars
+    pub min_volume: f64,
+    /// Most dollars committed to one opportunity when estimating its dollar profit
+    pub max_position: f64,
+    /// Skip opportunities expected to make less than this, in dollars
+    pub min_expected_profit: f64,
+    /// Seconds between arbitrage scans
+    pub poll_interval_secs: u64,
+    /// Skip markets that traded less than this in the last 24 hours, in dollars
+    pub min_volume_24h: f64,
+    /// Opportunities in markets without a trade in this many hours are stale (0 = don't check)
+    pub max_trade_age_hours: f64,
+    /// Drop stale opportunities; when false they're reported with a warning
+    pub suppress_stale: bool,
+    /// Speed scans up after they find opportunities and slow them down in hours that rarely see any
+    pub adaptive_polling: bool,
+    /// Fastest adaptive interval, used right after a scan finds opportunities
+    pub min_poll_interval_secs: u64,
+    /// Slowest adaptive interval, used in historically quiet hours
+    pub max_poll_interval_secs: u64,
+    /// New opportunities found before the scanner stops (0 = never)
+    pub stop_after_hits: usize,
+    /// Seconds the scanner runs before stopping (0 = never)
+    pub stop_after_secs: u64,
+    /// Overrides for markets in particular categories or tags, keyed by name
+    pub category_thresholds: BTreeMap<String, CategoryThresholds>,
+    /// Pairs of markets where one resolving YES forces the other to
+    pub linked_markets: Vec<MarketLink>,
+    /// Only scan markets scheduled to end within this many hours (0 = any end date)
+    pub resolving_within_hours: u64,
+}
+
+/// Two markets where the first resolving YES means the second does too, e.g. "X wins the
+/// election" implies "X wins the nomination", so the first can't be worth more than the second
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketLink {
+    /// Slug or condition ID of the market whose YES implies the other's
+    pub implying: String,
+    /// Slug or condition ID of the implied market
+    pub implied: String,
+    /// Name shown in reports instead of the two questions
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Liquidity reward scanner settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RewardsConfig {
+    /// Dollars quoted on each side of a market (raised to the program's minimum size)
+    pub quote_size: f64,
+    /// Net reward per day, as a fraction of the capital quoted, below which markets aren't reported
+    pub min_daily_yield: f64,
+}
+
+/// Arbitrage thresholds for one category; unset values fall back to `[arbitrage]`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CategoryThresholds {
+    /// Fixed YES + NO total to report below
+    pub threshold: Option<f64>,
+    /// Margin per $1 required beyond break-even (with fee-derived thresholds)
+    pub min_edge: Option<f64>,
+    pub min_liquidity: Option<f64>,
+    pub min_volume: Option<f64>,
+}
+
+/// API endpoints and request limits
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub gamma_url: String,
+    pub events_url: String,
+    pub trades_url: String,
+    /// Data API activity feed: a wallet's splits, merges, redemptions and rewards
+    pub activity_url: String,
+    /// Data API positions: a wallet's current holdings, marked to market
+    pub positions_url: String,
+    /// Data API leaderboard of wallets ranked by profit or volume
+    pub leaderboard_url: String,
+    /// Gamma public profile of a wallet (display name and proxy wallet)
+    pub profile_url: String,
+    /// Gamma search, used to look wallets up by username and markets by question
+    pub search_url: String,
+    pub clob_url: String,
+    /// Mirrors of the Gamma API (markets and events) tried when the primary host fails
+    pub gamma_fallback_hosts: Vec<String>,
+    /// Mirrors of the Data API (trades)
+    pub data_fallback_hosts: Vec<String>,
+    /// Consecutive failures before a host is skipped in favor of the next mirror
+    pub failover_threshold: u32,
+    /// Seconds a failing host is skipped before it's tried again
+    pub failover_cooldown_secs: u64,
+    pub timeout_secs: u64,
+    pub max_concurrent_requests: usize,
+    /// Requests per second across every API call the client makes, however many run
+    /// concurrently (0 = unlimited)
+    pub requests_per_second: f64,
+    /// Concurrency for resolved-market pagination (kept lower to avoid rate limits)
+    pub resolved_max_concurrent_requests: usize,
+    /// Number of most recent resolved markets loaded for wallet analysis
+    pub resolved_market_limit: usize,
+    /// Times a market page that failed during concurrent pagination is retried, one at a time
+    pub page_retries: u32,
+    /// Refetch every page sequentially when some are still missing after their retries,
+    /// instead of returning an incomplete market set
+    pub sequential_fallback: bool,
+    pub connect_timeout_secs: u64,
+    /// Seconds an idle pooled connection is kept open
+    pub pool_idle_timeout_secs: u64,
+    pub pool_max_idle_per_host: usize,
+    /// TCP keep-alive probe interval (0 disables)
+    pub tcp_keepalive_secs: u64,
+    /// HTTP/2 ping interval for idle connections (0 disables)
+    pub http2_keep_alive_secs: u64,
+    /// Never negotiate HTTP/2, for proxies that mishandle it
+    pub http1_only: bool,
+    /// Proxy URL every request is sent through (http://, https:// or socks5://)
+    pub proxy: Option<String>,
+    /// User-Agent header sent with every request
+    pub user_agent: Option<String>,
+}
+
+/// Criteria a wallet must meet to be reported as profitable
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct InsiderCriteria {
+    pub min_positions: usize,
+    pub min_roi: f64,
+    pub min_profit: f64,
+    /// Win rate (%) a wallet needs over its resolved positions (0 = any)
+    pub min_win_rate: f64,
+    /// Positions with less invested than this (dollars) are ignored when analyzing a wallet
+    pub min_position_invested: f64,
+    /// Days per period in the wallet report's performance breakdown (0 = calendar months)
+    pub period_days: u32,
+    /// Only count positions in markets resolved within this many days (0 = all)
+    pub resolved_within_days: u32,
+    /// Leave likely market makers out of scan results instead of labelling them
+    pub exclude_market_makers: bool,
+    /// Fetch each wallet's activity feed so splits, merges, redemptions and rewards are
+    /// accounted for (one more request per wallet)
+    pub wallet_activity: bool,
+}
+
+/// Thresholds and weights for the 0-100 insider score
+/// Each sub-score ramps linearly from 0 at its floor to 100 at its ceiling
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SuspicionConfig {
+    /// Wallets scoring at least this are flagged as suspicious
+    pub flag_score: f64,
+    /// Resolved positions needed for full confidence; scores are scaled down below this
+    pub min_resolved_positions: usize,
+    /// Win rate (%) range of the win-rate sub-score
+    pub win_rate_floor: f64,
+    pub win_rate_ceiling: f64,
+    /// Judge the win rate against the one the entry prices implied rather than 50%: winning
+    /// 70% of bets on favorites bought at $0.90 is unremarkable
+    pub price_implied_baseline: bool,
+    /// One-sided binomial p-value below which the win rate counts; above it the win rate is
+    /// put down to chance and left out of the score (1 = always count it)
+    pub win_rate_significance: f64,
+    /// ROI (%) at which the ROI sub-score maxes out
+    pub roi_ceiling: f64,
+    /// Capital invested ($) before the ROI sub-score counts in full
+    pub roi_full_capital: f64,
+    /// Late low-price winning buys at which the timing sub-score maxes out
+    pub late_buys_ceiling: f64,
+    /// Share of winnings from the top 3 positions above which concentration counts
+    pub concentration_floor: f64,
+    /// Share of winnings from one category or topic above which domain concentration counts
+    pub domain_concentration_floor: f64,
+    /// Winning positions a category or topic needs before its share counts
+    pub domain_min_positions: usize,
+    /// Average winning stake / average losing stake at which size asymmetry maxes out
+    pub size_ratio_ceiling: f64,
+    /// Gaps between trades needed before a wallet can be classified as a bot
+    pub bot_min_intervals: usize,
+    /// Share of gaps matching the typical gap at which a wallet is classified as a bot
+    pub bot_regularity: f64,
+    /// Seconds either side of the typical gap that still count as matching it
+    pub bot_tolerance_secs: f64,
+    /// Trades needed before a wallet can be classified as a market maker
+    pub market_maker_min_trades: usize,
+    /// Average trade ($) above which a wallet isn't classified as a market maker
+    pub market_maker_max_avg_trade: f64,
+    /// Share of markets traded on both sides (both outcomes, or buying and selling one) at
+    /// which a wallet can be classified as a market maker
+    pub market_maker_min_two_sided: f64,
+    /// Net one-sided dollars over dollars traded below which a wallet can be classified as a
+    /// market maker
+    pub market_maker_max_net_exposure: f64,
+    /// Share of dollars traded on the sell side needed, and on the buy side, for balanced flow
+    pub market_maker_min_sell_share: f64,
+    /// Smallest stake ($) that can count as an all-in bet
+    pub all_in_min_stake: f64,
+    /// Stake over the wallet's estimated prior balance at which a bet counts as all-in
+    pub all_in_balance_multiple: f64,
+    /// Lifetime market volume ($) at or below which a market counts as thinly traded
+    pub all_in_max_market_volume: f64,
+    /// Earlier bets needed before a bet's size can be judged against the wallet's balance
+    pub all_in_min_prior_bets: usize,
+    /// Winning all-in bets at which the size asymmetry sub-score maxes out
+    pub all_in_ceiling: f64,
+    /// Days after its first trade during which a wallet counts as fresh
+    pub fresh_wallet_days: f64,
+    /// Smallest stake ($) on one market that counts as a fresh wallet's big win
+    pub fresh_wallet_min_stake: f64,
+    /// Fresh-wallet big wins at which the timing sub-score maxes out
+    pub fresh_wallet_ceiling: f64,
+    pub weights: SuspicionWeights,
+}
+
+/// Relative weight of each sub-score in the insider score (normalized by their sum)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SuspicionWeights {
+    pub win_rate: f64,
+    pub roi: f64,
+    pub timing: f64,
+    pub concentration: f64,
+    pub size_asymmetry: f64,
+}
+
+/// Defaults for the `--scan` command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    pub sample_size: usize,
+    pub max_wallets: usize,
+    /// Continuous scans fetch newly resolved markets every this many iterations (0 = never)
+    pub resolved_refresh_iterations: usize,
+    /// How wallets are picked from recent trades: count, notional, category:<name> or resolving:<days>
+    pub discovery: String,
+}
+
+/// When continuous insider scans spend their API requests, based on the resolution calendar
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    /// Scan intensively after resolution bursts and slowly in between (off = back-to-back scans)
+    pub enabled: bool,
+    /// Markets resolving within one hour that count as a burst
+    pub burst_min_markets: usize,
+    /// Hours after a burst's hour during which scans run back-to-back
+    pub intensive_hours: u64,
+    /// Wallets per iteration during a burst, as a multiple of max_wallets
+    pub intensive_wallet_multiplier: usize,
+    /// Seconds between iterations outside bursts
+    pub idle_interval_secs: u64,
+    /// Hours before the calendar of upcoming resolutions is rebuilt
+    pub calendar_refresh_hours: u64,
+}
+
+/// Thresholds for detecting wallets that trade in coordination
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CoordinationConfig {
+    /// Buys of the same outcome by two wallets this close together count as coordinated
+    pub window_minutes: u64,
+    /// Distinct markets two wallets must have traded in coordination to be linked
+    pub min_shared_markets: usize,
+    /// Share of the less active wallet's markets that must be coordinated (0 to 1)
+    pub min_overlap: f64,
+}
+
+/// Settings for the `watch` command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Wallets tailed in addition to the watchlist
+    pub wallets: Vec<String>,
+    /// Seconds between polls of each watched wallet
+    pub poll_interval_secs: u64,
+}
+
+/// Settings for the `monitor` command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    /// Seconds between polls of the monitored market's order books
+    pub poll_interval_secs: u64,
+}
+
+/// Settings for the `digest` command
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Hour of the day (UTC) at which `digest --daily` builds and sends each digest
+    pub hour_utc: u32,
+}
+
+/// Settings for the `copy` command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CopyConfig {
+    /// Wallets tracked in addition to the watchlist
+    pub wallets: Vec<String>,
+    /// Suggested stake, in dollars, for copying a trade of the wallet's typical size
+    pub base_stake: f64,
+    /// Cap on the suggested stake of a single signal, in dollars
+    pub max_stake: f64,
+    /// Wallet trades smaller than this many dollars don't produce signals
+    pub min_notional: f64,
+    /// Emit signals for sells as well as buys
+    pub include_sells: bool,
+}
+
+/// Settings for the `calibration` report
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CalibrationConfig {
+    /// Most recently resolved markets sampled
+    pub sample_size: usize,
+    /// Outcome prices are taken this many hours before resolution
+    pub horizon_hours: f64,
+    /// Markets that traded less than this many dollars are left out
+    pub min_volume: f64,
+    /// Categories with fewer sampled outcomes aren't printed (they're still exported)
+    pub min_category_samples: usize,
+}
+
+/// Settings for `dataset build`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DatasetConfig {
+    /// Directory the dataset is written to
+    pub dir: String,
+    /// Requests per minute; requests are made one at a time, spaced evenly
+    pub requests_per_minute: u32,
+    /// Trade histories downloaded for this many of the most active wallets (0 = none)
+    pub wallets: usize,
+    /// Recent trades sampled to pick the most active wallets
+    pub wallet_sample: usize,
+    /// Attempts per request before the build stops (progress is kept)
+    pub max_retries: u32,
+}
+
+/// Settings for `backtest`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BacktestConfig {
+    /// Active binary markets replayed, by 24h volume
+    pub markets: usize,
+    /// Days of price history replayed
+    pub days: u32,
+    /// Minutes between price history points; finer data shows shorter-lived opportunities
+    pub fidelity_minutes: u32,
+}
+
+/// Local caches of API data
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Hours before the resolved-market cache is refetched
+    pub resolved_markets_max_age_hours: u64,
+    /// Keep each analyzed wallet's trade history locally and fetch only newer trades
+    pub trade_histories: bool,
+    /// Seconds after a wallet's history is brought up to date during which scans reuse it
+    /// without checking for new trades (0 = always check)
+    pub trade_histories_max_age_secs: u64,
+}
+
+/// Manifold Markets, the second venue the `manifold` command scans and analyzes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ManifoldConfig {
+    pub api_url: String,
+    /// Most open markets fetched per scan, most liquid first
+    pub max_markets: usize,
+}
+
+/// The `daemon` command's HTTP API
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Address and port the API listens on
+    pub bind: String,
+}
+
+/// Guardrails bounding automated order execution
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExecutorConfig {
+    /// Maximum total notional placed per UTC day, in dollars
+    pub max_daily_notional: f64,
+    /// Maximum cost of positions open at once, in dollars
+    pub max_open_exposure: f64,
+    /// Fill slippage (fraction of expected price) that trips the kill switch
+    pub max_slippage: f64,
+    /// Shares bought per leg when `--execute` trades an arbitrage
+    pub order_shares: f64,
+    /// Hex private key of the signing wallet; required for `--execute`
+    pub private_key: Option<String>,
+    /// Address holding the funds, when it differs from the signer (e.g. a Polymarket proxy wallet)
+    pub funder: Option<String>,
+    /// 0 = EOA, 1 = Polymarket proxy, 2 = Gnosis Safe
+    pub signature_type: u8,
+    /// CLOB API credentials; derived from the private key when unset
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub api_passphrase: Option<String>,
+    pub chain_id: u64,
+}
+
+/// Order book snapshots taken when a market is flagged
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    pub enabled: bool,
+}
+
+/// Simulated execution of arbitrage opportunities against the live order book
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PaperConfig {
+    /// Paper trade each new opportunity and keep scanning instead of stopping at the first
+    pub enabled: bool,
+    /// Shares per leg to buy, reduced when the book can't fill them profitably
+    pub shares: f64,
+}
+
+/// Numeric format for CSV exports
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Fixed number of decimal places for decimal columns
+    pub decimal_places: usize,
+}
+
+/// Alert destinations (all optional)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    pub webhook_url: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub priority: PriorityAlertConfig,
+}
+
+/// High-confidence arbitrage alerts: only opportunities in busy, deep markets whose
+/// edge survives both order books, sent to a single destination
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PriorityAlertConfig {
+    pub enabled: bool,
+    /// Minimum 24h traded volume of the market, in dollars
+    pub min_volume_24h: f64,
+    /// Minimum order book liquidity of the market, in dollars
+    pub min_liquidity: f64,
+    /// "telegram", "discord" or "webhook"; defaults to the loudest one configured
+    pub channel: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_dir: "data".to_string(),
+            arbitrage: ArbitrageConfig::default(),
+            rewards: RewardsConfig::default(),
+            api: ApiConfig::default(),
+            insider: InsiderCriteria::default(),
+            suspicion: SuspicionConfig::default(),
+            scan: ScanConfig::default(),
+            schedule: ScheduleConfig::default(),
+            coordination: CoordinationConfig::default(),
+            alerts: AlertsConfig::default(),
+            snapshots: SnapshotConfig::default(),
+            paper: PaperConfig::default(),
+            export: ExportConfig::default(),
+            watch: WatchConfig::default(),
+            monitor: MonitorConfig::default(),
+            digest: DigestConfig::default(),
+            copy: CopyConfig::default(),
+            calibration: CalibrationConfig::default(),
+            dataset: DatasetConfig::default(),
+            backtest: BacktestConfig::default(),
+            executor: ExecutorConfig::default(),
+            cache: CacheConfig::default(),
+            manifold: ManifoldConfig::default(),
+            daemon: DaemonConfig::default(),
+        }
+    }
+}
+
+impl Default for PriorityAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_volume_24h: 50_000.0,
+            min_liquidity: 10_000.0,
+            channel: None,
+        }
+    }
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            threshold: None,
+            detect_overpriced: true,
+            overpriced_threshold: None,
+            trade_size: 100.0,
+            fixed_cost_per_trade: 0.02,
+            // Half a cent per $1 of margin, as the old fixed 0.995 threshold had
+            min_edge: 0.005,
+            slippage_bps: 10.0,
+            slippage_impact: 0.5,
+            include_categories: Vec::new(),
+            exclude_categories: Vec::new(),
+            min_liquidity: 0.0,
+            min_volume: 0.0,
+            max_position: 1000.0,
+            min_expected_profit: 0.0,
+            poll_interval_secs: 10,
+            min_volume_24h: 0.0,
+            max_trade_age_hours: 24.0,
+            suppress_stale: true,
+            adaptive_polling: false,
+            min_poll_interval_secs: 3,
+            max_poll_interval_secs: 60,
+            stop_after_hits: 1,
+            stop_after_secs: 0,
+            category_thresholds: BTreeMap::new(),
+            linked_markets: Vec::new(),
+            resolving_within_hours: 0,
+        }
+    }
+}
+
+impl Default for RewardsConfig {
+    fn default() -> Self {
+        Self {
+            quote_size: 100.0,
+            min_daily_yield: 0.001,
+        }
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            gamma_url: "https://gamma-api.polymarket.com/markets".to_string(),
+            events_url: "https://gamma-api.polymarket.com/events".to_string(),
+            trades_url: "https://data-api.polymarket.com/trades".to_string(),
+            activity_url: "https://data-api.polymarket.com/activity".to_string(),
+            positions_url: "https://data-api.polymarket.com/positions".to_string(),
+            leaderboard_url: "https://data-api.polymarket.com/v1/leaderboard".to_string(),
+            profile_url: "https://gamma-api.polymarket.com/public-profile".to_string(),
+            search_url: "https://gamma-api.polymarket.com/public-search".to_string(),
+            clob_url: "https://clob.polymarket.com".to_string(),
+            gamma_fallback_hosts: Vec::new(),
+            data_fallback_hosts: Vec::new(),
+            failover_threshold: 3,
+            failover_cooldown_secs: 60,
+            timeout_secs: 30,
+            max_concurrent_requests: 20,
+            requests_per_second: 10.0,
+            resolved_max_concurrent_requests: 10,
+            resolved_market_limit: 15000,
+            page_retries: 3,
+            sequential_fallback: false,
+            connect_timeout_secs: 10,
+            pool_idle_timeout_secs: 90,
+            pool_max_idle_per_host: 32,
+            tcp_keepalive_secs: 60,
+            http2_keep_alive_secs: 0,
+            http1_only: false,
+            proxy: None,
+            user_agent: None,
+        }
+    }
+}
+
+impl Default for InsiderCriteria {
+    fn default() -> Self {
+        // Require: 10+ resolved positions, ROI > 10%, net profit > $50
+        Self {
+            min_positions: 10,
+            min_roi: 10.0,
+            min_profit: 50.0,
+            min_win_rate: 0.0,
+            min_position_invested: 0.0,
+            period_days: 0,
+            resolved_within_days: 0,
+            exclude_market_makers: true,
+            wallet_activity: true,
+        }
+    }
+}
+
+impl Default for SuspicionConfig {
+    fn default() -> Self {
+        Self {
+            flag_score: 50.0,
+            min_resolved_positions: 10,
+            // Normal traders win ~50-60% of the time
+            win_rate_floor: 55.0,
+            win_rate_ceiling: 85.0,
+            price_implied_baseline: true,
+            win_rate_significance: 0.05,
+            roi_ceiling: 100.0,
+            roi_full_capital: 1000.0,
+            late_buys_ceiling: 5.0,
+            concentration_floor: 0.5,
+            domain_concentration_floor: 0.7,
+            domain_min_positions: 3,
+            size_ratio_ceiling: 3.0,
+            bot_min_intervals: 20,
+            bot_regularity: 0.5,
+            bot_tolerance_secs: 2.0,
+            market_maker_min_trades: 1000,
+            market_maker_max_avg_trade: 50.0,
+            market_maker_min_two_sided: 0.5,
+            market_maker_max_net_exposure: 0.2,
+            market_maker_min_sell_share: 0.3,
+            all_in_min_stake: 500.0,
+            all_in_balance_multiple: 1.0,
+            all_in_max_market_volume: 100_000.0,
+            all_in_min_prior_bets: 5,
+            all_in_ceiling: 2.0,
+            fresh_wallet_days: 7.0,
+            fresh_wallet_min_stake: 1000.0,
+            fresh_wallet_ceiling: 1.0,
+            weights: SuspicionWeights::default(),
+        }
+    }
+}
+
+impl Default for SuspicionWeights {
+    fn default() -> Self {
+        Self {
+            win_rate: 0.30,
+            roi: 0.20,
+            timing: 0.25,
+            concentration: 0.10,
+            size_asymmetry: 0.15,
+        }
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 5000,
+            max_wallets: 30,
+            resolved_refresh_iterations: 5,
+            discovery: "count".to_string(),
+        }
+    }
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            burst_min_markets: 25,
+            intensive_hours: 3,
+            intensive_wallet_multiplier: 2,
+            idle_interval_secs: 900,
+            calendar_refresh_hours: 6,
+        }
+    }
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            wallets: Vec::new(),
+            poll_interval_secs: 30,
+        }
+    }
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self { poll_interval_secs: 2 }
+    }
+}
+
+impl Default for CopyConfig {
+    fn default() -> Self {
+        Self {
+            wallets: Vec::new(),
+            base_stake: 10.0,
+            max_stake: 100.0,
+            min_notional: 10.0,
+            include_sells: true,
+        }
+    }
+}
+
+impl Default for CoordinationConfig {
+    fn default() -> Self {
+        Self {
+            window_minutes: 60,
+            min_shared_markets: 3,
+            min_overlap: 0.2,
+        }
+    }
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 1000,
+            horizon_hours: 24.0,
+            min_volume: 1000.0,
+            min_category_samples: 50,
+        }
+    }
+}
+
+impl Default for DatasetConfig {
+    fn default() -> Self {
+        Self {
+            dir: "data/dataset".to_string(),
+            requests_per_minute: 30,
+            wallets: 0,
+            wallet_sample: 5000,
+            max_retries: 8,
+        }
+    }
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            markets: 50,
+            days: 7,
+            fidelity_minutes: 1,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            resolved_markets_max_age_hours: 6,
+            trade_histories: true,
+            trade_histories_max_age_secs: 600,
+        }
+    }
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self { bind: "127.0.0.1:8787".to_string() }
+    }
+}
+
+impl Default for ManifoldConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "https://api.manifold.markets/v0".to_string(),
+            max_markets: 5000,
+        }
+    }
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            max_daily_notional: 500.0,
+            max_open_exposure: 1000.0,
+            max_slippage: 0.02,
+            order_shares: 10.0,
+            private_key: None,
+            funder: None,
+            signature_type: 0,
+            api_key: None,
+            api_secret: None,
+            api_passphrase: None,
+            chain_id: 137,
+        }
+    }
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self { enabled: false, shares: 100.0 }
+    }
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self { decimal_places: 6 }
+    }
+}
+
+impl InsiderCriteria {
+    /// Whether a wallet's performance meets the profitability criteria
+    /// Likely market makers never do when they're excluded: their profit is spread capture
+    pub fn matches(&self, performance: &WalletPerformance) -> bool {
+        performance.resolved_positions >= self.min_positions
+            && performance.roi > self.min_roi
+            && performance.net_profit > self.min_profit
+            && performance.win_rate >= self.min_win_rate
+            && !self.excludes(performance)
+    }
+
+    /// Whether a wallet is left out of results as a likely market maker
+    pub fn excludes(&self, performance: &WalletPerformance) -> bool {
+        self.exclude_market_makers && performance.market_making.as_ref().is_some_and(|m| m.likely_market_maker)
+    }
+}
+
+impl CacheConfig {
+    /// Maximum age of the resolved-market cache, in seconds
+    pub fn resolved_markets_max_age_secs(&self) -> i64 {
+        (self.resolved_markets_max_age_hours * 3600) as i64
+    }
+}
+
+impl Config {
+    /// Loads `scanner.toml` (or the file named by `SCANNER_CONFIG`) and applies env overrides
+    /// A missing default config file is not an error; defaults are used instead
+    pub fn load() -> Result<Self> {
+        let explicit_path = std::env::var("SCANNER_CONFIG").ok();
+        let path = explicit_path.as_deref().unwrap_or(DEFAULT_CONFIG_FILE);
+
+        let mut config = if Path::new(path).exists() || explicit_path.is_some() {
+            Self::from_file(Path::new(path))?
+        } else {
+            Self::default()
+        };
+
+        config.apply_env_overrides()?;
+        Ok(config)
+    }
+
+    /// Parses a config file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Overrides config values from `SCANNER_*` / `ALERT_*` environment variables
+    fn apply_env_overrides(&mut self) -> Result<()> {
+        override_from_env("SCANNER_DATA_DIR", &mut self.data_dir)?;
+        override_optional_from_env("SCANNER_ARBITRAGE_THRESHOLD", &mut self.arbitrage.threshold)?;
+        override_from_env("SCANNER_DETECT_OVERPRICED", &mut self.arbitrage.detect_overpriced)?;
+        override_optional_from_env("SCANNER_ARBITRAGE_OVERPRICED_THRESHOLD", &mut self.arbitrage.overpriced_threshold)?;
+        override_from_env("SCANNER_ARBITRAGE_TRADE_SIZE", &mut self.arbitrage.trade_size)?;
+        override_from_env("SCANNER_ARBITRAGE_FIXED_COST", &mut self.arbitrage.fixed_cost_per_trade)?;
+        override_from_env("SCANNER_ARBITRAGE_MIN_EDGE", &mut self.arbitrage.min_edge)?;
+        override_from_env("SCANNER_ARBITRAGE_SLIPPAGE_BPS", &mut self.arbitrage.slippage_bps)?;
+        override_from_env("SCANNER_ARBITRAGE_SLIPPAGE_IMPACT", &mut self.arbitrage.slippage_impact)?;
+        override_list_from_env("SCANNER_INCLUDE_CATEGORIES", &mut self.arbitrage.include_categories);
+        override_list_from_env("SCANNER_EXCLUDE_CATEGORIES", &mut self.arbitrage.exclude_categories);
+        override_from_env("SCANNER_RESOLVING_WITHIN_HOURS", &mut self.arbitrage.resolving_within_hours)?;
+        override_from_env("SCANNER_MIN_LIQUIDITY", &mut self.arbitrage.min_liquidity)?;
+        override_from_env("SCANNER_MIN_VOLUME", &mut self.arbitrage.min_volume)?;
+        override_from_env("SCANNER_ARBITRAGE_MAX_POSITION", &mut self.arbitrage.max_position)?;
+        override_from_env("SCANNER_ARBITRAGE_MIN_EXPECTED_PROFIT", &mut self.arbitrage.min_expected_profit)?;
+        override_from_env("SCANNER_POLL_INTERVAL_SECS", &mut self.arbitrage.poll_interval_secs)?;
+        override_from_env("SCANNER_MIN_VOLUME_24H", &mut self.arbitrage.min_volume_24h)?;
+        override_from_env("SCANNER_MAX_TRADE_AGE_HOURS", &mut self.arbitrage.max_trade_age_hours)?;
+        override_from_env("SCANNER_SUPPRESS_STALE", &mut self.arbitrage.suppress_stale)?;
+        override_from_env("SCANNER_ADAPTIVE_POLLING", &mut self.arbitrage.adaptive_polling)?;
+        override_from_env("SCANNER_MIN_POLL_INTERVAL_SECS", &mut self.arbitrage.min_poll_interval_secs)?;
+        override_from_env("SCANNER_MAX_POLL_INTERVAL_SECS", &mut self.arbitrage.max_poll_interval_secs)?;
+        override_from_env("SCANNER_STOP_AFTER_HITS", &mut self.arbitrage.stop_after_hits)?;
+        override_from_env("SCANNER_STOP_AFTER_SECS", &mut self.arbitrage.stop_after_secs)?;
+        override_from_env("SCANNER_REWARDS_QUOTE_SIZE", &mut self.rewards.quote_size)?;
+        override_from_env("SCANNER_REWARDS_MIN_DAILY_YIELD", &mut self.rewards.min_daily_yield)?;
+        override_from_env("SCANNER_GAMMA_API_URL", &mut self.api.gamma_url)?;
+        override_from_env("SCANNER_EVENTS_API_URL", &mut self.api.events_url)?;
+        override_from_env("SCANNER_TRADES_API_URL", &mut self.api.trades_url)?;
+        override_from_env("SCANNER_ACTIVITY_API_URL", &mut self.api.activity_url)?;
+        override_from_env("SCANNER_POSITIONS_API_URL", &mut self.api.positions_url)?;
+        override_from_env("SCANNER_LEADERBOARD_API_URL", &mut self.api.leaderboard_url)?;
+        override_from_env("SCANNER_PROFILE_API_URL", &mut self.api.profile_url)?;
+        override_from_env("SCANNER_SEARCH_API_URL", &mut self.api.search_url)?;
+        override_from_env("SCANNER_CLOB_API_URL", &mut self.api.clob_url)?;
+        override_from_env("SCANNER_MANIFOLD_API_URL", &mut self.manifold.api_url)?;
+        override_from_env("SCANNER_MANIFOLD_MAX_MARKETS", &mut self.manifold.max_markets)?;
+        override_from_env("SCANNER_DAEMON_BIND", &mut self.daemon.bind)?;
+        override_list_from_env("SCANNER_GAMMA_FALLBACK_HOSTS", &mut self.api.gamma_fallback_hosts);
+        override_list_from_env("SCANNER_DATA_FALLBACK_HOSTS", &mut self.api.data_fallback_hosts);
+        override_from_env("SCANNER_TIMEOUT_SECS", &mut self.api.timeout_secs)?;
+        override_from_env("SCANNER_MAX_CONCURRENT_REQUESTS", &mut self.api.max_concurrent_requests)?;
+        override_from_env("SCANNER_REQUESTS_PER_SECOND", &mut self.api.requests_per_second)?;
+        override_from_env("SCANNER_RESOLVED_MARKET_LIMIT", &mut self.api.resolved_market_limit)?;
+        override_from_env("SCANNER_PAGE_RETRIES", &mut self.api.page_retries)?;
+        override_from_env("SCANNER_SEQUENTIAL_FALLBACK", &mut self.api.sequential_fallback)?;
+        override_from_env("SCANNER_CONNECT_TIMEOUT_SECS", &mut self.api.connect_timeout_secs)?;
+        override_optional_from_env("SCANNER_PROXY", &mut self.api.proxy)?;
+        override_optional_from_env("SCANNER_USER_AGENT", &mut self.api.user_agent)?;
+        override_from_env("SCANNER_RESOLVED_CACHE_MAX_AGE_HOURS", &mut self.cache.resolved_markets_max_age_hours)?;
+        override_from_env("SCANNER_CACHE_TRADE_HISTORIES", &mut self.cache.trade_histories)?;
+        override_from_env("SCANNER_TRADE_HISTORIES_MAX_AGE_SECS", &mut self.cache.trade_histories_max_age_secs)?;
+        override_from_env("SCANNER_RESOLVED_REFRESH_ITERATIONS", &mut self.scan.resolved_refresh_iterations)?;
+        override_from_env("SCANNER_DISCOVERY", &mut self.scan.discovery)?;
+        override_from_env("SCANNER_SCHEDULE_BURSTS", &mut self.schedule.enabled)?;
+        override_from_env("SCANNER_BURST_MIN_MARKETS", &mut self.schedule.burst_min_markets)?;
+        override_from_env("SCANNER_IDLE_INTERVAL_SECS", &mut self.schedule.idle_interval_secs)?;
+        override_from_env("SCANNER_MIN_POSITIONS", &mut self.insider.min_positions)?;
+        override_from_env("SCANNER_MIN_ROI", &mut self.insider.min_roi)?;
+        override_from_env("SCANNER_MIN_PROFIT", &mut self.insider.min_profit)?;
+        override_from_env("SCANNER_MIN_WIN_RATE", &mut self.insider.min_win_rate)?;
+        override_from_env("SCANNER_MIN_POSITION_INVESTED", &mut self.insider.min_position_invested)?;
+        override_from_env("SCANNER_PERIOD_DAYS", &mut self.insider.period_days)?;
+        override_from_env("SCANNER_RESOLVED_WITHIN_DAYS", &mut self.insider.resolved_within_days)?;
+        override_from_env("SCANNER_EXCLUDE_MARKET_MAKERS", &mut self.insider.exclude_market_makers)?;
+        override_from_env("SCANNER_WALLET_ACTIVITY", &mut self.insider.wallet_activity)?;
+        override_from_env("SCANNER_FLAG_SCORE", &mut self.suspicion.flag_score)?;
+        override_from_env("SCANNER_WATCH_POLL_INTERVAL_SECS", &mut self.watch.poll_interval_secs)?;
+        override_from_env("SCANNER_MONITOR_POLL_INTERVAL_SECS", &mut self.monitor.poll_interval_secs)?;
+        override_from_env("SCANNER_DIGEST_HOUR_UTC", &mut self.digest.hour_utc)?;
+        override_from_env("SCANNER_COPY_BASE_STAKE", &mut self.copy.base_stake)?;
+        override_from_env("SCANNER_COPY_MAX_STAKE", &mut self.copy.max_stake)?;
+        override_from_env("SCANNER_COPY_MIN_NOTIONAL", &mut self.copy.min_notional)?;
+        override_from_env("SCANNER_COORDINATION_WINDOW_MINUTES", &mut self.coordination.window_minutes)?;
+        override_from_env("SCANNER_COORDINATION_MIN_SHARED_MARKETS", &mut self.coordination.min_shared_markets)?;
+        override_from_env("SCANNER_CALIBRATION_SAMPLE_SIZE", &mut self.calibration.sample_size)?;
+        override_from_env("SCANNER_CALIBRATION_HORIZON_HOURS", &mut self.calibration.horizon_hours)?;
+        override_from_env("SCANNER_DATASET_DIR", &mut self.dataset.dir)?;
+        override_from_env("SCANNER_DATASET_REQUESTS_PER_MINUTE", &mut self.dataset.requests_per_minute)?;
+        override_from_env("SCANNER_DATASET_WALLETS", &mut self.dataset.wallets)?;
+        override_from_env("SCANNER_BACKTEST_MARKETS", &mut self.backtest.markets)?;
+        override_from_env("SCANNER_BACKTEST_DAYS", &mut self.backtest.days)?;
+        override_from_env("SCANNER_BACKTEST_FIDELITY_MINUTES", &mut self.backtest.fidelity_minutes)?;
+        override_from_env("SCANNER_MAX_DAILY_NOTIONAL", &mut self.executor.max_daily_notional)?;
+        override_from_env("SCANNER_MAX_OPEN_EXPOSURE", &mut self.executor.max_open_exposure)?;
+        override_from_env("SCANNER_MAX_SLIPPAGE", &mut self.executor.max_slippage)?;
+        override_from_env("SCANNER_ORDER_SHARES", &mut self.executor.order_shares)?;
+        override_optional_from_env("SCANNER_PRIVATE_KEY", &mut self.executor.private_key)?;
+        override_optional_from_env("SCANNER_FUNDER", &mut self.executor.funder)?;
+        override_optional_from_env("SCANNER_CLOB_API_KEY", &mut self.executor.api_key)?;
+        override_optional_from_env("SCANNER_CLOB_API_SECRET", &mut self.executor.api_secret)?;
+        override_optional_from_env("SCANNER_CLOB_API_PASSPHRASE", &mut self.executor.api_passphrase)?;
+        override_from_env("SCANNER_PAPER_TRADING", &mut self.paper.enabled)?;
+        override_from_env("SCANNER_PAPER_SHARES", &mut self.paper.shares)?;
+        override_from_env("SCANNER_EXPORT_DECIMAL_PLACES", &mut self.export.decimal_places)?;
+        override_optional_from_env("ALERT_WEBHOOK_URL", &mut self.alerts.webhook_url)?;
+        override_optional_from_env("ALERT_DISCORD_WEBHOOK_URL", &mut self.alerts.discord_webhook_url)?;
+        override_optional_from_env("ALERT_TELEGRAM_BOT_TOKEN", &mut self.alerts.telegram_bot_token)?;
+        override_optional_from_env("ALERT_TELEGRAM_CHAT_ID", &mut self.alerts.telegram_chat_id)?;
+        override_from_env("ALERT_PRIORITY_ENABLED", &mut self.alerts.priority.enabled)?;
+        override_from_env("ALERT_PRIORITY_MIN_VOLUME_24H", &mut self.alerts.priority.min_volume_24h)?;
+        override_from_env("ALERT_PRIORITY_MIN_LIQUIDITY", &mut self.alerts.priority.min_liquidity)?;
+        override_optional_from_env("ALERT_PRIORITY_CHANNEL", &mut self.alerts.priority.channel)?;
+        Ok(())
+    }
+}
+
+/// Replaces a value with the parsed contents of an env var, if set
+fn override_from_env<T: std::str::FromStr>(name: &str, value: &mut T) -> Result<()>
+where
+    T::Err: std::fmt::Display,
+{
+    if let Ok(raw) = std::env::var(name) {
+        *value = raw
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid value for {}: {} ({})", name, raw, e))?;
+    }
+    Ok(())
+}
+
+/// Sets an optional value from the parsed contents of an env var, if set
+fn override_optional_from_env<T: std::str::FromStr>(name: &str, value: &mut Option<T>) -> Result<()>
+where
+    T::Err: std::fmt::Display,
+{
+    if let Ok(raw) = std::env::var(name) {
+        *value = Some(
+            raw.parse()
+                .map_err(|e| anyhow::anyhow!("Invalid value for {}: {} ({})", name, raw, e))?,
+        );
+    }
+    Ok(())
+}
+
+/// Replaces a list with the comma-separated contents of an env var, if set
+fn override_list_from_env(name: &str, value: &mut Vec<String>) {
+    if let Ok(raw) = std::env::var(name) {
+        *value = split_list(&raw);
+    }
+}
+
+/// Splits a comma-separated list, dropping empty items
+pub fn split_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
@@ -0,0 +1,122 @@
+use crate::config::CopyConfig;
+use crate::models::Trade;
+use crate::store::Store;
+use crate::watchlist::{typical_notional, TailedTrade};
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Append-only log of emitted copy-trading signals
+pub const COPY_SIGNALS_FILE: &str = "copy_signals.jsonl";
+/// Notification cursors of the `copy` command, kept apart from `watch`'s
+pub const COPY_CURSORS_FILE: &str = "copy_cursors.json";
+
+/// A tracked wallet's trade turned into an order to mirror
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopySignal {
+    /// Stable ID (wallet and trade), so consumers can deduplicate
+    pub id: String,
+    pub generated_at: i64,
+    pub wallet: String,
+    pub wallet_name: String,
+    pub condition_id: String,
+    pub title: Option<String>,
+    pub outcome: String,
+    pub outcome_index: usize,
+    /// "BUY" or "SELL"
+    pub side: String,
+    /// Price the wallet traded at; the copier's limit price
+    pub entry_price: f64,
+    /// Shares the wallet traded
+    pub wallet_size: f64,
+    pub wallet_notional: f64,
+    /// Trade size relative to the wallet's typical recent trade (1.0 = typical, 3.0 = three times)
+    pub size_proportion: f64,
+    /// Dollars to stake: the configured base stake scaled by `size_proportion`, capped
+    pub suggested_stake: f64,
+    /// Buy into a market outcome the wallet didn't hold before
+    pub opened_position: bool,
+    pub trade_timestamp: i64,
+    pub transaction_hash: Option<String>,
+}
+
+/// Converts tracked wallets' new trades into copy-trading signals
+pub struct CopySignalGenerator {
+    config: CopyConfig,
+}
+
+impl CopySignalGenerator {
+    pub fn new(config: CopyConfig) -> Self {
+        Self { config }
+    }
+
+    /// Builds signals for a wallet's new trades, sized against its `recent` trades
+    /// (the latest page, which includes the new ones)
+    pub fn signals(&self, wallet_name: &str, new_trades: &[TailedTrade], recent: &[Trade]) -> Vec<CopySignal> {
+        let typical_notional = typical_notional(recent);
+        let now = Utc::now().timestamp();
+
+        new_trades
+            .iter()
+            .filter(|tailed| tailed.trade.side == "BUY" || (self.config.include_sells && tailed.trade.side == "SELL"))
+            .filter(|tailed| tailed.trade.size * tailed.trade.price >= self.config.min_notional)
+            .map(|tailed| {
+                let trade = &tailed.trade;
+                let notional = trade.size * trade.price;
+                let size_proportion = match typical_notional {
+                    Some(typical) if typical > 0.0 => notional / typical,
+                    _ => 1.0,
+                };
+
+                CopySignal {
+                    id: format!(
+                        "copy:{}:{}:{}:{}:{}",
+                        trade.proxy_wallet.to_lowercase(),
+                        trade.transaction_hash.as_deref().unwrap_or(""),
+                        trade.condition_id,
+                        trade.outcome_index,
+                        trade.timestamp
+                    ),
+                    generated_at: now,
+                    wallet: trade.proxy_wallet.clone(),
+                    wallet_name: wallet_name.to_string(),
+                    condition_id: trade.condition_id.clone(),
+                    title: trade.title.clone(),
+                    outcome: trade.outcome.clone(),
+                    outcome_index: trade.outcome_index,
+                    side: trade.side.clone(),
+                    entry_price: trade.price,
+                    wallet_size: trade.size,
+                    wallet_notional: notional,
+                    size_proportion,
+                    suggested_stake: (self.config.base_stake * size_proportion).min(self.config.max_stake),
+                    opened_position: tailed.opened_position,
+                    trade_timestamp: trade.timestamp,
+                    transaction_hash: trade.transaction_hash.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Persists emitted copy-trading signals as JSON lines
+#[derive(Clone)]
+pub struct CopySignalLog {
+    store: Store,
+}
+
+impl CopySignalLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Appends signals to `copy_signals.jsonl`
+    pub fn record(&self, signals: &[CopySignal]) -> Result<()> {
+        self.store.append_jsonl(COPY_SIGNALS_FILE, signals)
+    }
+
+    /// Loads every signal emitted so far
+    pub fn load(&self) -> Result<Vec<CopySignal>> {
+        self.store.read_jsonl(COPY_SIGNALS_FILE)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::address::validate_address;
+use crate::client::PolymarketClient;
+use crate::leaderboard::LeaderboardEntry;
+use crate::models::{ArbitrageOpportunity, EventArbitrageOpportunity, LinkArbitrageOpportunity};
+use crate::polling::AdaptivePolling;
+use crate::scanner::ArbitrageScanner;
+use crate::shutdown::is_cancelled;
+use crate::store::Store;
+use crate::trade_history::TradeHistoryCache;
+use crate::wallet_analyzer::WalletAnalyzer;
+use crate::wallet_pipeline::{WalletAnalysis, WalletPipeline};
+use crate::watchlist::{Watchlist, WatchlistEntry};
+use anyhow::{Context, Result};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Results of the latest arbitrage scan, and how scanning has gone so far
+#[derive(Default)]
+struct ScanResults {
+    scanned_at: Option<i64>,
+    scans: usize,
+    failed_scans: usize,
+    last_error: Option<String>,
+    opportunities: Vec<ArbitrageOpportunity>,
+    event_opportunities: Vec<EventArbitrageOpportunity>,
+    link_opportunities: Vec<LinkArbitrageOpportunity>,
+}
+
+/// Body of `GET /opportunities`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OpportunitiesResponse<'a> {
+    scanned_at: Option<i64>,
+    opportunities: &'a [ArbitrageOpportunity],
+    event_opportunities: &'a [EventArbitrageOpportunity],
+    link_opportunities: &'a [LinkArbitrageOpportunity],
+}
+
+/// Body of `GET /health`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HealthResponse<'a> {
+    status: &'static str,
+    scanned_at: Option<i64>,
+    scans: usize,
+    failed_scans: usize,
+    last_error: Option<&'a str>,
+}
+
+/// An error sent to the caller as `{"error": "..."}` with a status code
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl ToString) -> Self {
+        Self { status, message: message.to_string() }
+    }
+}
+
+/// Failures reaching the Polymarket APIs are the upstream's fault, not the caller's
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(StatusCode::BAD_GATEWAY, format!("{:#}", error))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(serde_json::json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Shared by the scan loop and every request handler
+#[derive(Clone)]
+struct DaemonState {
+    client: PolymarketClient,
+    scanner: Arc<ArbitrageScanner>,
+    analyzer: Arc<WalletAnalyzer>,
+    store: Store,
+    trade_history: Option<TradeHistoryCache>,
+    wallet_activity: bool,
+    results: Arc<RwLock<ScanResults>>,
+    /// Held across each watchlist load-modify-save, so concurrent requests don't drop entries
+    watchlist: Arc<tokio::sync::Mutex<()>>,
+}
+
+/// Runs the arbitrage scanner in the background and serves its results, wallet analysis
+/// and the watchlist over a small JSON API, so other programs can build on the scanner
+pub struct Daemon {
+    state: DaemonState,
+    poll_interval: Duration,
+    adaptive: Option<AdaptivePolling>,
+}
+
+impl Daemon {
+    pub fn new(client: PolymarketClient, scanner: ArbitrageScanner, analyzer: WalletAnalyzer, store: Store) -> Self {
+        Self {
+            state: DaemonState {
+                client,
+                scanner: Arc::new(scanner),
+                analyzer: Arc::new(analyzer),
+                store,
+                trade_history: None,
+                wallet_activity: false,
+                results: Arc::new(RwLock::new(ScanResults::default())),
+                watchlist: Arc::new(tokio::sync::Mutex::new(())),
+            },
+            poll_interval: Duration::from_secs(10),
+            adaptive: None,
+        }
+    }
+
+    /// Seconds between arbitrage scans
+    pub fn with_poll_interval(mut self, secs: u64) -> Self {
+        self.poll_interval = Duration::from_secs(secs.max(1));
+        self
+    }
+
+    /// Chooses the wait before each scan from what recent scans found instead of polling evenly
+    pub fn with_adaptive_polling(mut self, adaptive: AdaptivePolling) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Fetches each analyzed wallet's activity feed, so splits, merges and redemptions count
+    pub fn with_wallet_activity(mut self, enabled: bool) -> Self {
+        self.state.wallet_activity = enabled;
+        self
+    }
+
+    /// Reads wallet trades through the given cache, so wallets requested again only fetch new trades
+    pub fn with_trade_history(mut self, cache: TradeHistoryCache) -> Self {
+        self.state.trade_history = Some(cache);
+        self
+    }
+
+    /// Serves the API on `bind` (e.g. 127.0.0.1:8787) until `shutdown` is cancelled
+    pub async fn run(self, bind: &str, shutdown: CancellationToken) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(bind)
+            .await
+            .with_context(|| format!("Failed to listen on {}", bind))?;
+        outln!("Serving the API on http://{}", listener.local_addr()?);
+
+        let scan_loop = tokio::spawn(scan_loop(self.state.clone(), self.poll_interval, self.adaptive, shutdown.clone()));
+
+        let app = Router::new()
+            .route("/health", get(health))
+            .route("/opportunities", get(opportunities))
+            .route("/wallets/{address}/performance", get(wallet_performance))
+            .route("/watchlist", post(add_to_watchlist))
+            .with_state(self.state);
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown.clone().cancelled_owned())
+            .await
+            .context("API server failed")?;
+
+        shutdown.cancel();
+        scan_loop.await.ok();
+        Ok(())
+    }
+}
+
+/// Scans every `poll_interval`, or as `adaptive` decides, and swaps the results in for the
+/// handlers to read
+async fn scan_loop(
+    state: DaemonState,
+    poll_interval: Duration,
+    mut adaptive: Option<AdaptivePolling>,
+    shutdown: CancellationToken,
+) {
+    let mut next_scan = tokio::time::Instant::now();
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_scan) => {}
+            _ = shutdown.cancelled() => return,
+        }
+        let scan_started = tokio::time::Instant::now();
+        next_scan = scan_started + adaptive.as_ref().map_or(poll_interval, AdaptivePolling::current);
+
+        let pass = match state.scanner.fetch_and_scan(&state.client).await {
+            Ok(pass) => pass,
+            Err(e) if is_cancelled(&e) => return,
+            Err(e) => {
+                eoutln!("Warning: Scan failed: {}", e);
+                let mut results = state.results.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+                results.scans += 1;
+                results.failed_scans += 1;
+                results.last_error = Some(e.to_string());
+                continue;
+            }
+        };
+        if let Some(adaptive) = &mut adaptive {
+            next_scan = scan_started + adaptive.observe(pass.found(), Utc::now().timestamp());
+        }
+
+        let mut results = state.results.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        results.scanned_at = Some(Utc::now().timestamp());
+        results.scans += 1;
+        results.last_error = None;
+        results.opportunities = pass.opportunities;
+        results.event_opportunities = pass.event_opportunities;
+        results.link_opportunities = pass.link_opportunities;
+    }
+}
+
+/// `GET /health`: whether the daemon is up and how its scans are going
+async fn health(State(state): State<DaemonState>) -> Response {
+    let results = state.results.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    Json(HealthResponse {
+        status: "ok",
+        scanned_at: results.scanned_at,
+        scans: results.scans,
+        failed_scans: results.failed_scans,
+        last_error: results.last_error.as_deref(),
+    })
+    .into_response()
+}
+
+/// `GET /opportunities`: what the latest scan found, most expected profit first
+async fn opportunities(State(state): State<DaemonState>) -> Response {
+    let results = state.results.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    Json(OpportunitiesResponse {
+        scanned_at: results.scanned_at,
+        opportunities: &results.opportunities,
+        event_opportunities: &results.event_opportunities,
+        link_opportunities: &results.link_opportunities,
+    })
+    .into_response()
+}
+
+/// `GET /wallets/{address}/performance`: the wallet analyzed as in the wallet report, laid
+/// out like the leaderboard export. Only the markets the wallet traded are fetched
+async fn wallet_performance(
+    State(state): State<DaemonState>,
+    Path(address): Path<String>,
+) -> Result<Json<LeaderboardEntry>, ApiError> {
+    let address = validate_address(&address).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+
+    let analysis = WalletPipeline::new(&state.client, &state.analyzer)
+        .with_trade_history(state.trade_history.as_ref())
+        .with_wallet_activity(state.wallet_activity)
+        .run(&address)
+        .await?;
+    let Some(WalletAnalysis { performance, report, username, .. }) = analysis else {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, format!("No trades found for {}", address)));
+    };
+
+    Ok(Json(LeaderboardEntry::new(username.as_deref(), &performance, &report)))
+}
+
+/// `POST /watchlist`: adds (or replaces) a watchlist entry, sent as
+/// `{"address": "0x...", "label": "...", "categories": [...]}`; returns the whole watchlist
+async fn add_to_watchlist(
+    State(state): State<DaemonState>,
+    Json(mut entry): Json<WatchlistEntry>,
+) -> Result<Json<Watchlist>, ApiError> {
+    entry.address = validate_address(&entry.address).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+    entry.categories.retain(|category| !category.trim().is_empty());
+
+    let _guard = state.watchlist.lock().await;
+    let mut watchlist = Watchlist::load(&state.store).map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    watchlist.add(entry);
+    watchlist
+        .save(&state.store)
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(Json(watchlist))
+}
@@ -0,0 +1,397 @@
+use crate::models::{ArbitrageOpportunity, EventArbitrageOpportunity, MarketEfficiency, Signal};
+use crate::output::Console;
+use crate::signals::SignalLog;
+use crate::store::Store;
+use crate::watchlist::{WatchCursors, Watchlist};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use futures::StreamExt;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table};
+use ratatui::{Frame, Terminal};
+use std::io::Stdout;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Signals shown in the recent alerts pane
+const RECENT_ALERTS: usize = 50;
+
+/// Width of the scan statistics pane
+const STATS_WIDTH: u16 = 36;
+
+/// Pane borders in plain mode
+const PLAIN_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// A watched wallet and the last trade the watcher notified about
+#[derive(Debug, Clone)]
+pub struct WatchedWallet {
+    pub name: String,
+    pub address: String,
+    pub last_trade_at: Option<i64>,
+}
+
+/// Everything the dashboard shows, updated by the scan loop between redraws
+#[derive(Debug)]
+pub struct DashboardState {
+    pub started_at: i64,
+    pub interval_secs: u64,
+    pub scans: u32,
+    pub failed_scans: u32,
+    pub last_scan_at: Option<i64>,
+    pub last_scan_secs: Option<f64>,
+    pub last_error: Option<String>,
+    /// Opportunities found by the latest successful scan
+    pub opportunities: Vec<ArbitrageOpportunity>,
+    pub event_opportunities: Vec<EventArbitrageOpportunity>,
+    /// Opportunities seen for the first time since the dashboard started
+    pub new_opportunities: usize,
+    pub efficiency: Option<MarketEfficiency>,
+    pub watched: Vec<WatchedWallet>,
+    /// Latest signals from every scanner writing to the data directory, newest first
+    pub alerts: Vec<Signal>,
+}
+
+impl DashboardState {
+    pub fn new(interval_secs: u64) -> Self {
+        Self {
+            started_at: Utc::now().timestamp(),
+            interval_secs,
+            scans: 0,
+            failed_scans: 0,
+            last_scan_at: None,
+            last_scan_secs: None,
+            last_error: None,
+            opportunities: Vec::new(),
+            event_opportunities: Vec::new(),
+            new_opportunities: 0,
+            efficiency: None,
+            watched: Vec::new(),
+            alerts: Vec::new(),
+        }
+    }
+
+    /// Reloads the watchlist, the watcher's cursors and the signal log, so wallets
+    /// tailed and signals emitted by other processes show up too
+    pub fn refresh_from_store(&mut self, store: &Store, signal_log: &SignalLog) {
+        match (Watchlist::load(store), WatchCursors::load(store)) {
+            (Ok(watchlist), Ok(cursors)) => {
+                self.watched = watchlist
+                    .entries
+                    .iter()
+                    .map(|entry| WatchedWallet {
+                        name: entry.display_name().to_string(),
+                        address: entry.address.clone(),
+                        last_trade_at: cursors.get(&entry.address).map(|cursor| cursor.timestamp),
+                    })
+                    .collect();
+            }
+            (Err(e), _) | (_, Err(e)) => eoutln!("Warning: Failed to load the watchlist: {}", e),
+        }
+
+        match signal_log.load() {
+            Ok(mut signals) => {
+                signals.sort_by_key(|s| std::cmp::Reverse(s.emitted_at));
+                signals.truncate(RECENT_ALERTS);
+                self.alerts = signals;
+            }
+            Err(e) => eoutln!("Warning: Failed to load signals: {}", e),
+        }
+    }
+
+    /// Seconds until the next scan is due
+    fn next_scan_in(&self, now: i64) -> i64 {
+        self.last_scan_at
+            .map(|last| (last + self.interval_secs as i64 - now).max(0))
+            .unwrap_or(0)
+    }
+}
+
+/// Full-screen view of a running scan: arbitrage opportunities, scan statistics,
+/// recent alerts, watched wallets and the scan log in separate panes
+/// While it's open, printed output is captured into the log pane instead of
+/// scrolling the terminal; dropping it restores the terminal
+pub struct Dashboard {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    redraw: Arc<Notify>,
+    input: JoinHandle<()>,
+}
+
+impl Dashboard {
+    /// Switches to the terminal's alternate screen in raw mode and starts capturing output
+    /// Pressing q, Esc or Ctrl+C cancels `shutdown`; resizing the terminal asks for a redraw
+    pub fn enter(shutdown: CancellationToken) -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = std::io::stdout();
+        if let Err(e) = execute!(stdout, EnterAlternateScreen) {
+            terminal::disable_raw_mode().ok();
+            return Err(e.into());
+        }
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        terminal.hide_cursor()?;
+        Console::global().start_capture();
+
+        let redraw = Arc::new(Notify::new());
+        let input = tokio::spawn(read_input(shutdown, redraw.clone()));
+        Ok(Self { terminal, redraw, input })
+    }
+
+    /// Waits until the terminal has been resized
+    pub async fn redraw_requested(&self) {
+        self.redraw.notified().await
+    }
+
+    /// Redraws every pane at the terminal's current size
+    pub fn draw(&mut self, state: &DashboardState) -> Result<()> {
+        self.terminal.draw(|frame| render(frame, state))?;
+        Ok(())
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.input.abort();
+        Console::global().stop_capture();
+        terminal::disable_raw_mode().ok();
+        execute!(self.terminal.backend_mut(), LeaveAlternateScreen).ok();
+        self.terminal.show_cursor().ok();
+    }
+}
+
+/// Turns key presses and resizes into shutdown and redraw requests until input ends
+async fn read_input(shutdown: CancellationToken, redraw: Arc<Notify>) {
+    let mut events = EventStream::new();
+    while let Some(Ok(event)) = events.next().await {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                    shutdown.cancel();
+                    return;
+                }
+            }
+            Event::Resize(_, _) => redraw.notify_one(),
+            _ => {}
+        }
+    }
+}
+
+/// Lays the panes out over the whole frame
+fn render(frame: &mut Frame, state: &DashboardState) {
+    let now = Utc::now().timestamp();
+    let [header, top, middle, bottom] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Percentage(40),
+        Constraint::Percentage(30),
+        Constraint::Min(3),
+    ])
+    .areas(frame.area());
+    let [opportunities, stats] = Layout::horizontal([Constraint::Min(0), Constraint::Length(STATS_WIDTH)]).areas(top);
+    let [alerts, watched] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(middle);
+
+    let title = format!(
+        " PREDICTION MARKET SCANNER  |  {}  |  next scan in {}s  |  q to quit",
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        state.next_scan_in(now)
+    );
+    frame.render_widget(
+        Paragraph::new(text(&title)).style(Style::new().add_modifier(Modifier::REVERSED)),
+        header,
+    );
+
+    frame.render_widget(opportunity_table(state), opportunities);
+    frame.render_widget(lines_pane("Scan Statistics", &stats_lines(state, now)), stats);
+    frame.render_widget(lines_pane("Recent Alerts", &alert_lines(state)), alerts);
+    frame.render_widget(lines_pane("Watched Wallets", &watched_lines(state)), watched);
+
+    // The newest log lines that fit inside the pane's borders
+    let log = Console::global().captured_lines(bottom.height.saturating_sub(2) as usize);
+    frame.render_widget(lines_pane("Log", &log), bottom);
+}
+
+/// A bordered pane, drawn in ASCII in plain mode
+fn pane(title: &str) -> Block<'static> {
+    let block = Block::bordered().title(text(&format!(" {} ", title)));
+    if Console::global().is_plain() {
+        block.border_set(PLAIN_BORDER)
+    } else {
+        block
+    }
+}
+
+/// A pane showing as many of `lines` as fit, cut off at its right edge
+fn lines_pane(title: &str, lines: &[String]) -> Paragraph<'static> {
+    let lines: Vec<Line> = lines.iter().map(|line| Line::from(text(line))).collect();
+    Paragraph::new(lines).block(pane(title))
+}
+
+fn opportunity_table(state: &DashboardState) -> Table<'static> {
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(10),
+    ];
+    let rows: Vec<Row> = opportunity_lines(state)
+        .into_iter()
+        .map(|cells| Row::new(cells.into_iter().map(|cell| Cell::from(text(&cell)))))
+        .collect();
+    let header = Row::new(["Expected", "Profit", "Total", "Liquidity", "Vol 24h", "Market"])
+        .style(Style::new().add_modifier(Modifier::BOLD));
+
+    if rows.is_empty() {
+        let message = if state.scans == 0 { "Waiting for the first scan..." } else { "No opportunities in the last scan" };
+        return Table::new([Row::new([message])], [Constraint::Percentage(100)]).block(pane("Arbitrage Opportunities"));
+    }
+    Table::new(rows, widths).header(header).block(pane("Arbitrage Opportunities"))
+}
+
+/// Text as the console would print it (ASCII-safe in plain mode)
+fn text(line: &str) -> String {
+    Console::global().render(line).into_owned()
+}
+
+/// One row of cells per opportunity: expected profit, profit, total, liquidity, 24h volume, market
+fn opportunity_lines(state: &DashboardState) -> Vec<[String; 6]> {
+    let binary = state.opportunities.iter().map(|o| {
+        [
+            dollars(o.expected_profit),
+            format!("{:.2}%", o.profit_percent),
+            format!("{:.4}", o.total_cost),
+            dollars(o.liquidity),
+            dollars(o.volume_24h),
+            format!("{} [{}]", o.question, o.kind.label()),
+        ]
+    });
+    let events = state.event_opportunities.iter().map(|o| {
+        [
+            dollars(o.expected_profit),
+            format!("{:.2}%", o.profit_percent),
+            format!("{:.4}", o.total_cost),
+            dollars(o.min_liquidity),
+            "-".to_string(),
+            format!("[EVENT] {} ({} legs)", o.title, o.legs),
+        ]
+    });
+    binary.chain(events).collect()
+}
+
+fn stats_lines(state: &DashboardState, now: i64) -> Vec<String> {
+    let last_scan = match (state.last_scan_at, state.last_scan_secs) {
+        (Some(at), Some(secs)) => format!("{} ({:.2}s)", time_of(at), secs),
+        _ => "-".to_string(),
+    };
+    let efficiency = state
+        .efficiency
+        .as_ref()
+        .map(|e| format!("{:.4} ({} mkts)", e.index, e.markets))
+        .unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![
+        format!("Uptime:        {}", duration(now - state.started_at)),
+        format!("Scans:         {} ({} failed)", state.scans, state.failed_scans),
+        format!("Last scan:     {}", last_scan),
+        format!("Interval:      {}s", state.interval_secs),
+        format!("Opportunities: {}", state.opportunities.len()),
+        format!("Event arbs:    {}", state.event_opportunities.len()),
+        format!("New (session): {}", state.new_opportunities),
+        format!("Efficiency:    {}", efficiency),
+        format!("Watched:       {}", state.watched.len()),
+    ];
+    if let Some(error) = &state.last_error {
+        lines.push(format!("Last error:    {}", error));
+    }
+    lines
+}
+
+fn alert_lines(state: &DashboardState) -> Vec<String> {
+    if state.alerts.is_empty() {
+        return vec!["No signals recorded yet".to_string()];
+    }
+    state
+        .alerts
+        .iter()
+        .map(|signal| {
+            let wallet = signal.wallet.as_deref().map(|w| format!(" {}", short_address(w))).unwrap_or_default();
+            format!(
+                "{}  {:<13}{}  {}",
+                date_time_of(signal.emitted_at),
+                signal.kind.label(),
+                wallet,
+                signal.question
+            )
+        })
+        .collect()
+}
+
+fn watched_lines(state: &DashboardState) -> Vec<String> {
+    if state.watched.is_empty() {
+        return vec!["Watchlist is empty".to_string()];
+    }
+    state
+        .watched
+        .iter()
+        .map(|wallet| {
+            let last = wallet
+                .last_trade_at
+                .map(|at| format!("last trade {}", date_time_of(at)))
+                .unwrap_or_else(|| "no trades seen".to_string());
+            let name = if wallet.name == wallet.address { short_address(&wallet.address) } else { wallet.name.clone() };
+            format!("{:<16}  {}", name, last)
+        })
+        .collect()
+}
+
+fn dollars(amount: f64) -> String {
+    if amount >= 1_000_000.0 {
+        format!("${:.1}M", amount / 1_000_000.0)
+    } else if amount >= 1_000.0 {
+        format!("${:.1}K", amount / 1_000.0)
+    } else {
+        format!("${:.0}", amount)
+    }
+}
+
+fn short_address(address: &str) -> String {
+    if address.len() <= 12 || !address.is_ascii() {
+        return address.to_string();
+    }
+    format!("{}..{}", &address[..6], &address[address.len() - 4..])
+}
+
+fn time_of(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn date_time_of(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!("{}h {:02}m {:02}s", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
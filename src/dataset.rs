@@ -0,0 +1,357 @@
+use crate::client::{PolymarketClient, WALLET_TRADES_PAGE_SIZE};
+use crate::config::DatasetConfig;
+use crate::store::Store;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::Write;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Progress of the build, saved after every request
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+/// Every closed market, one API object per line
+const MARKETS_FILE: &str = "markets.jsonl";
+/// Wallets whose trade histories are downloaded, most active first
+const WALLETS_FILE: &str = "wallets.json";
+/// Directory of per-wallet trade histories
+const TRADES_DIR: &str = "trades";
+/// Counts and file descriptions of the finished dataset
+const MANIFEST_FILE: &str = "manifest.json";
+/// Human-readable description of the dataset directory
+const README_FILE: &str = "README.md";
+/// Markets per request
+const MARKETS_PAGE_SIZE: usize = 100;
+/// Recent trades per request when sampling active wallets
+const SAMPLE_PAGE_SIZE: usize = 1000;
+/// First wait after a failed request; doubled on each retry
+const INITIAL_BACKOFF_SECS: u64 = 30;
+const MAX_BACKOFF_SECS: u64 = 600;
+
+/// Where a dataset build got to, so an interrupted build resumes instead of restarting
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatasetCheckpoint {
+    pub started_at: i64,
+    pub updated_at: i64,
+    /// Offset of the next page of closed markets
+    pub market_offset: usize,
+    pub markets: usize,
+    /// Length of `markets.jsonl` at the last checkpoint; anything past it is a partial write
+    pub markets_bytes: u64,
+    pub markets_complete: bool,
+    /// Chosen once, on the first build that downloads trades
+    pub wallets: Vec<String>,
+    /// Wallets whose trade histories are complete
+    pub wallets_done: usize,
+    pub trades: usize,
+    pub requests: u64,
+}
+
+/// Describes one file of the dataset
+#[derive(Debug, Clone, Serialize)]
+struct DatasetFile {
+    path: &'static str,
+    format: &'static str,
+    description: &'static str,
+}
+
+/// Counts and layout of a dataset, written when a build finishes
+#[derive(Debug, Clone, Serialize)]
+struct DatasetManifest {
+    generated_at: i64,
+    started_at: i64,
+    markets: usize,
+    wallets: usize,
+    trades: usize,
+    requests: u64,
+    files: Vec<DatasetFile>,
+}
+
+/// Spaces requests evenly so the build never exceeds the configured rate
+struct RateLimiter {
+    spacing: Duration,
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        Self {
+            spacing: Duration::from_secs_f64(60.0 / requests_per_minute.max(1) as f64),
+            next: None,
+        }
+    }
+
+    /// Waits until the next request is allowed
+    async fn wait(&mut self) {
+        if let Some(next) = self.next {
+            tokio::time::sleep_until(next).await;
+        }
+        self.next = Some(Instant::now() + self.spacing);
+    }
+}
+
+/// Downloads a research dataset slowly and resumably: every closed market, and
+/// optionally the full trade histories of the most active wallets
+pub struct DatasetBuilder {
+    client: PolymarketClient,
+    store: Store,
+    config: DatasetConfig,
+    limiter: RateLimiter,
+    checkpoint: DatasetCheckpoint,
+}
+
+impl DatasetBuilder {
+    /// Opens the dataset directory, resuming from its checkpoint unless `fresh`
+    pub fn open(client: PolymarketClient, config: DatasetConfig, fresh: bool) -> Result<Self> {
+        let store = Store::open(&config.dir)?;
+        let checkpoint = if fresh {
+            None
+        } else {
+            store.load_json(CHECKPOINT_FILE)?
+        };
+        let checkpoint = checkpoint.unwrap_or_else(|| DatasetCheckpoint {
+            started_at: Utc::now().timestamp(),
+            ..DatasetCheckpoint::default()
+        });
+
+        Ok(Self {
+            client,
+            store,
+            limiter: RateLimiter::new(config.requests_per_minute),
+            config,
+            checkpoint,
+        })
+    }
+
+    /// Progress so far
+    pub fn checkpoint(&self) -> &DatasetCheckpoint {
+        &self.checkpoint
+    }
+
+    /// Runs (or resumes) the build until the dataset is complete
+    pub async fn build(&mut self) -> Result<()> {
+        if !self.checkpoint.markets_complete {
+            self.download_markets().await?;
+        }
+
+        if self.config.wallets > 0 {
+            if self.checkpoint.wallets.is_empty() {
+                self.choose_wallets().await?;
+            }
+            self.download_trades().await?;
+        }
+
+        self.write_manifest()
+    }
+
+    /// Pages through every closed market, appending each page to `markets.jsonl`
+    async fn download_markets(&mut self) -> Result<()> {
+        // Drop any partial page written after the last checkpoint
+        let path = self.store.path(MARKETS_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.set_len(self.checkpoint.markets_bytes)?;
+        drop(file);
+
+        outln!("📚 Downloading closed markets from offset {}...", self.checkpoint.market_offset);
+        let client = self.client.clone();
+        loop {
+            let offset = self.checkpoint.market_offset;
+            let page = self
+                .request("closed markets", || client.fetch_closed_markets_raw(offset, MARKETS_PAGE_SIZE))
+                .await?;
+
+            self.store.append_jsonl(MARKETS_FILE, &page)?;
+            self.checkpoint.markets += page.len();
+            self.checkpoint.market_offset += page.len();
+            self.checkpoint.markets_bytes = std::fs::metadata(&path)?.len();
+            self.checkpoint.markets_complete = page.len() < MARKETS_PAGE_SIZE;
+            self.save_checkpoint()?;
+
+            out!("\r  {} markets ({} requests)", self.checkpoint.markets, self.checkpoint.requests);
+            std::io::stdout().flush().ok();
+
+            if self.checkpoint.markets_complete {
+                outln!("\n✓ Downloaded {} closed markets", self.checkpoint.markets);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Picks the most active wallets from a sample of recent trades
+    async fn choose_wallets(&mut self) -> Result<()> {
+        outln!("🔍 Sampling {} recent trades to pick the {} most active wallets...", self.config.wallet_sample, self.config.wallets);
+        let client = self.client.clone();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut offset = 0;
+        while offset < self.config.wallet_sample {
+            let limit = SAMPLE_PAGE_SIZE.min(self.config.wallet_sample - offset);
+            let page = self
+                .request("recent trades", || client.fetch_recent_trades_page(offset, limit))
+                .await?;
+            for trade in &page.records {
+                *counts.entry(trade.proxy_wallet.to_lowercase()).or_default() += 1;
+            }
+            if page.received < limit {
+                break;
+            }
+            offset += limit;
+        }
+
+        let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        self.checkpoint.wallets = ranked.into_iter().take(self.config.wallets).map(|(wallet, _)| wallet).collect();
+
+        self.store.save_json(WALLETS_FILE, &self.checkpoint.wallets)?;
+        self.save_checkpoint()?;
+        outln!("✓ Selected {} wallets", self.checkpoint.wallets.len());
+        Ok(())
+    }
+
+    /// Downloads each chosen wallet's full history into `trades/<wallet>.jsonl`
+    /// A wallet's file is written only once its history is complete
+    async fn download_trades(&mut self) -> Result<()> {
+        std::fs::create_dir_all(self.store.path(TRADES_DIR))?;
+        let client = self.client.clone();
+        let total = self.checkpoint.wallets.len();
+
+        while self.checkpoint.wallets_done < total {
+            let wallet = self.checkpoint.wallets[self.checkpoint.wallets_done].clone();
+            outln!("📊 [{}/{}] Downloading trades of {}...", self.checkpoint.wallets_done + 1, total, wallet);
+
+            let mut lines = String::new();
+            let mut trades = 0;
+            let mut offset = 0;
+            loop {
+                let page = self
+                    .request("wallet trades", || client.fetch_wallet_trades_raw(&wallet, offset))
+                    .await?;
+                for trade in &page {
+                    lines.push_str(&serde_json::to_string(trade)?);
+                    lines.push('\n');
+                }
+                trades += page.len();
+                if page.len() < WALLET_TRADES_PAGE_SIZE {
+                    break;
+                }
+                offset += page.len();
+            }
+
+            self.store.save_bytes(&format!("{}/{}.jsonl", TRADES_DIR, wallet), lines.as_bytes())?;
+            self.checkpoint.trades += trades;
+            self.checkpoint.wallets_done += 1;
+            self.save_checkpoint()?;
+        }
+
+        outln!("✓ Downloaded {} trades from {} wallets", self.checkpoint.trades, total);
+        Ok(())
+    }
+
+    /// Makes a rate-limited request, retrying with exponential backoff
+    async fn request<T, F, Fut>(&mut self, what: &str, mut fetch: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let attempts = self.config.max_retries.max(1);
+        let mut backoff = Duration::from_secs(INITIAL_BACKOFF_SECS);
+
+        for attempt in 1..=attempts {
+            self.limiter.wait().await;
+            self.checkpoint.requests += 1;
+
+            match fetch().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < attempts => {
+                    eoutln!(
+                        "\nWarning: Request for {} failed (attempt {}/{}): {}; retrying in {}s",
+                        what,
+                        attempt,
+                        attempts,
+                        e,
+                        backoff.as_secs()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(MAX_BACKOFF_SECS));
+                }
+                Err(e) => {
+                    return Err(e.context(format!("Request for {} failed {} times; rerun to resume", what, attempts)))
+                }
+            }
+        }
+
+        unreachable!("at least one attempt is made")
+    }
+
+    fn save_checkpoint(&mut self) -> Result<()> {
+        self.checkpoint.updated_at = Utc::now().timestamp();
+        self.store.save_json(CHECKPOINT_FILE, &self.checkpoint)
+    }
+
+    /// Writes `manifest.json` and `README.md` describing the finished dataset
+    fn write_manifest(&self) -> Result<()> {
+        let files = vec![
+            DatasetFile {
+                path: MARKETS_FILE,
+                format: "jsonl",
+                description: "Every closed market as returned by the Gamma markets API (tags included), one per line, oldest first by market ID",
+            },
+            DatasetFile {
+                path: WALLETS_FILE,
+                format: "json",
+                description: "Addresses whose trade histories were downloaded, most active in the sampled recent trades first",
+            },
+            DatasetFile {
+                path: "trades/<wallet>.jsonl",
+                format: "jsonl",
+                description: "A wallet's full trade history as returned by the Data API trades endpoint, one per line, newest first",
+            },
+            DatasetFile {
+                path: CHECKPOINT_FILE,
+                format: "json",
+                description: "Build progress; lets an interrupted build resume",
+            },
+        ];
+        let files: Vec<DatasetFile> = files
+            .into_iter()
+            .filter(|f| self.config.wallets > 0 || (f.path != WALLETS_FILE && !f.path.starts_with(TRADES_DIR)))
+            .collect();
+
+        let manifest = DatasetManifest {
+            generated_at: Utc::now().timestamp(),
+            started_at: self.checkpoint.started_at,
+            markets: self.checkpoint.markets,
+            wallets: self.checkpoint.wallets_done,
+            trades: self.checkpoint.trades,
+            requests: self.checkpoint.requests,
+            files,
+        };
+        self.store.save_json(MANIFEST_FILE, &manifest)?;
+
+        let mut readme = String::from("# Polymarket research dataset\n\n");
+        readme.push_str(&format!(
+            "Built by `prediction-market-scanner dataset build`, finished {}.\n\n",
+            Utc::now().format("%Y-%m-%d %H:%M UTC")
+        ));
+        readme.push_str(&format!(
+            "- Markets: {}\n- Wallets: {}\n- Trades: {}\n\n",
+            manifest.markets, manifest.wallets, manifest.trades
+        ));
+        readme.push_str("## Files\n\n");
+        for file in &manifest.files {
+            readme.push_str(&format!("- `{}` ({}): {}\n", file.path, file.format, file.description));
+        }
+        readme.push_str(
+            "\nRecords are stored exactly as the APIs returned them. Prices, sizes and volumes are \
+             often JSON strings, and outcome prices are a JSON-encoded array inside a string.\n",
+        );
+        self.store.save_bytes(README_FILE, readme.as_bytes())
+    }
+}
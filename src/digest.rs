@@ -0,0 +1,228 @@
+use crate::knowledge::WalletKnowledgeBase;
+use crate::models::SignalKind;
+use crate::signals::SignalLog;
+use crate::store::Store;
+use crate::watchlist::WatchActivityLog;
+use anyhow::Result;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Append-only log of every digest built
+const DIGESTS_FILE: &str = "digests.jsonl";
+/// Widest arbitrage spreads listed in a digest
+const BEST_SPREADS: usize = 5;
+
+/// Summary of what the scanners recorded over a period, built from the data directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    pub since: i64,
+    pub until: i64,
+    /// Arbitrage opportunities signalled (each counted once, when first seen)
+    pub opportunities: usize,
+    /// Opportunities furthest from a YES + NO total of $1, widest first
+    pub best_spreads: Vec<DigestSpread>,
+    /// Wallets first flagged by an insider scan during the period
+    pub new_insiders: Vec<DigestWallet>,
+    /// Open positions of flagged wallets signalled during the period
+    pub insider_entries: usize,
+    pub watched: WatchedActivity,
+}
+
+/// One arbitrage opportunity in a digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestSpread {
+    pub condition_id: String,
+    pub question: String,
+    pub emitted_at: i64,
+    /// YES + NO total when the opportunity was signalled
+    pub total: f64,
+}
+
+impl DigestSpread {
+    /// Distance of the total from the $1 payout
+    pub fn spread(&self) -> f64 {
+        (1.0 - self.total).abs()
+    }
+}
+
+/// A newly flagged wallet in a digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestWallet {
+    pub address: String,
+    pub name: String,
+    pub flagged_at: i64,
+    pub score: Option<f64>,
+    pub roi: Option<f64>,
+}
+
+/// Trades `watch` reported during a digest's period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchedActivity {
+    pub trades: usize,
+    pub wallets: usize,
+    pub new_positions: usize,
+    /// Dollars traded across every reported trade
+    pub notional: f64,
+}
+
+impl Digest {
+    /// Builds the digest of `[since, until)` from the signal log, the wallet knowledge base
+    /// and the `watch` activity log
+    pub fn build(store: &Store, since: i64, until: i64) -> Result<Self> {
+        let in_period = |at: i64| at >= since && at < until;
+
+        let signals: Vec<_> = SignalLog::new(store.clone())
+            .load()?
+            .into_iter()
+            .filter(|signal| in_period(signal.emitted_at))
+            .collect();
+        let mut best_spreads: Vec<DigestSpread> = signals
+            .iter()
+            .filter(|signal| signal.kind == SignalKind::Arbitrage)
+            .map(|signal| DigestSpread {
+                condition_id: signal.condition_id.clone(),
+                question: signal.question.clone(),
+                emitted_at: signal.emitted_at,
+                total: signal.price,
+            })
+            .collect();
+        let opportunities = best_spreads.len();
+        best_spreads.sort_by(|a, b| b.spread().total_cmp(&a.spread()));
+        best_spreads.truncate(BEST_SPREADS);
+
+        let knowledge = WalletKnowledgeBase::load(store)?;
+        let mut new_insiders: Vec<DigestWallet> = knowledge
+            .wallets
+            .values()
+            .filter_map(|record| {
+                let flagged_at = record.identity.flagged_at.filter(|&at| in_period(at))?;
+                Some(DigestWallet {
+                    address: record.address.clone(),
+                    name: record.display_name().to_string(),
+                    flagged_at,
+                    score: record.scan.as_ref().and_then(|scan| scan.score),
+                    roi: record.scan.as_ref().map(|scan| scan.roi),
+                })
+            })
+            .collect();
+        new_insiders.sort_by_key(|wallet| wallet.flagged_at);
+
+        let reported = WatchActivityLog::new(store.clone()).load_between(since, until)?;
+        let watched = WatchedActivity {
+            trades: reported.len(),
+            wallets: reported.iter().map(|r| r.wallet.as_str()).collect::<HashSet<_>>().len(),
+            new_positions: reported.iter().filter(|r| r.opened_position).count(),
+            notional: reported.iter().map(|r| r.notional).sum(),
+        };
+
+        Ok(Self {
+            since,
+            until,
+            opportunities,
+            best_spreads,
+            new_insiders,
+            insider_entries: signals.iter().filter(|s| s.kind == SignalKind::InsiderEntry).count(),
+            watched,
+        })
+    }
+
+    /// One line per figure, shared by the console report and alerts
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Arbitrage opportunities: {}", self.opportunities),
+            format!("New insider wallets: {} ({} insider entries)", self.new_insiders.len(), self.insider_entries),
+            format!(
+                "Watched wallets: {} trades by {} wallets, {} new positions, ${:.0} traded",
+                self.watched.trades, self.watched.wallets, self.watched.new_positions, self.watched.notional
+            ),
+        ];
+        for spread in &self.best_spreads {
+            lines.push(format!("Spread {:.2}% (total ${:.4}): {}", spread.spread() * 100.0, spread.total, spread.question));
+        }
+        for wallet in &self.new_insiders {
+            let score = wallet.score.map(|s| format!("score {:.0}", s)).unwrap_or_else(|| "unscored".to_string());
+            lines.push(format!("Flagged {} ({}, ROI {:.1}%)", wallet.name, score, wallet.roi.unwrap_or(0.0)));
+        }
+        lines
+    }
+
+    /// Prints the digest as a report
+    pub fn print(&self) {
+        let format = |at: i64| {
+            DateTime::from_timestamp(at, 0)
+                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_default()
+        };
+
+        outln!("\n{}", "=".repeat(80));
+        outln!("DIGEST {} - {}", format(self.since), format(self.until));
+        outln!("{}", "=".repeat(80));
+
+        outln!("\nArbitrage Opportunities: {}", self.opportunities);
+        for (i, spread) in self.best_spreads.iter().enumerate() {
+            outln!(
+                "  {}. {:.2}% (YES + NO ${:.4}) {}",
+                i + 1,
+                spread.spread() * 100.0,
+                spread.total,
+                spread.question
+            );
+            outln!("     {} at {}", spread.condition_id, format(spread.emitted_at));
+        }
+
+        outln!("\nNew Insider Wallets:     {}", self.new_insiders.len());
+        for wallet in &self.new_insiders {
+            let score = wallet.score.map(|s| format!("{:.0}", s)).unwrap_or_else(|| "-".to_string());
+            outln!(
+                "  {} ({}) score {} | ROI {:.1}% | flagged {}",
+                wallet.name,
+                wallet.address,
+                score,
+                wallet.roi.unwrap_or(0.0),
+                format(wallet.flagged_at)
+            );
+        }
+        outln!("Insider Entries:         {}", self.insider_entries);
+
+        outln!("\nWatched Wallet Activity");
+        outln!("  Trades:                {}", self.watched.trades);
+        outln!("  Active Wallets:        {}", self.watched.wallets);
+        outln!("  New Positions:         {}", self.watched.new_positions);
+        outln!("  Traded:                ${:.2}", self.watched.notional);
+    }
+}
+
+/// Persists built digests, so a scheduled digest picks up where the last one ended
+#[derive(Clone)]
+pub struct DigestLog {
+    store: Store,
+}
+
+impl DigestLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Appends a digest
+    pub fn record(&self, digest: &Digest) -> Result<()> {
+        self.store.append_jsonl(DIGESTS_FILE, std::slice::from_ref(digest))
+    }
+
+    /// The most recently built digest
+    pub fn last(&self) -> Result<Option<Digest>> {
+        Ok(self.store.read_jsonl::<Digest>(DIGESTS_FILE)?.into_iter().max_by_key(|d| d.until))
+    }
+}
+
+/// The next time after `now` that the clock reads `hour_utc`:00 UTC
+pub fn next_run(now: i64, hour_utc: u32) -> i64 {
+    let now = DateTime::from_timestamp(now, 0).unwrap_or_default();
+    let today = now
+        .date_naive()
+        .and_hms_opt(hour_utc.min(23), 0, 0)
+        .map(|t| Utc.from_utc_datetime(&t))
+        .unwrap_or(now);
+    let next = if today > now { today } else { today + Duration::days(1) };
+    next.timestamp()
+}
@@ -0,0 +1,390 @@
+use crate::client::{Page, PolymarketClientBuilder};
+use crate::config::ApiConfig;
+use crate::failover;
+use crate::models::{Event, Market, OrderBook, PriceHistory, Trade};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Clock skew beyond this many seconds is reported as a warning
+const MAX_CLOCK_SKEW_WARN_SECS: i64 = 5;
+/// Clock skew beyond this many seconds fails the check (timestamps and windows will be off)
+const MAX_CLOCK_SKEW_FAIL_SECS: i64 = 60;
+/// Records sampled per endpoint by `doctor --fields`
+const FIELD_SAMPLE_SIZE: &str = "100";
+/// Sample values longer than this are cut short in the field report
+const MAX_SAMPLE_CHARS: usize = 60;
+
+/// Outcome of a single health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "✗",
+        }
+    }
+}
+
+/// Result of one health check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub latency: Option<Duration>,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            latency: None,
+        }
+    }
+}
+
+/// Fields a sample of live records carries that the model doesn't know, and the records
+/// that no longer parse, to spot API changes before they silently drop data
+#[derive(Debug, Clone)]
+pub struct FieldReport {
+    pub name: String,
+    pub received: usize,
+    /// Records carrying each unknown field, and one value it held
+    pub unknown: BTreeMap<String, (usize, Value)>,
+    /// Why each skipped record didn't parse
+    pub rejected: Vec<String>,
+}
+
+impl FieldReport {
+    fn from_page<T>(name: &str, page: Page<T>, extra: impl Fn(&T) -> &HashMap<String, Value>) -> Self {
+        let mut unknown: BTreeMap<String, (usize, Value)> = BTreeMap::new();
+        for record in &page.records {
+            for (field, value) in extra(record) {
+                let entry = unknown.entry(field.clone()).or_insert_with(|| (0, value.clone()));
+                entry.0 += 1;
+                if entry.1.is_null() {
+                    entry.1 = value.clone();
+                }
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            received: page.received,
+            unknown,
+            rejected: page.rejected,
+        }
+    }
+}
+
+/// Raw response of a test request
+struct Probe {
+    status: reqwest::StatusCode,
+    headers: reqwest::header::HeaderMap,
+    body: Option<Value>,
+    latency: Duration,
+}
+
+/// Checks connectivity to every API endpoint the crate uses and that responses still match the models
+pub struct Doctor {
+    client: reqwest::Client,
+    config: ApiConfig,
+}
+
+impl Doctor {
+    /// Uses the same transport settings (proxy, timeouts) as the scanners
+    pub fn new(config: &ApiConfig) -> Result<Self> {
+        Ok(Self {
+            client: PolymarketClientBuilder::from_config(config).http_client()?,
+            config: config.clone(),
+        })
+    }
+
+    /// Runs every check, in order; endpoints that depend on an earlier response are skipped if it failed
+    pub async fn run(&self) -> Vec<CheckResult> {
+        let mut results = Vec::new();
+        let mut probes = Vec::new();
+
+        // Gamma markets also supply a token ID for the CLOB checks
+        let markets = self
+            .get(&self.config.gamma_url, &[("active", "true"), ("closed", "false"), ("limit", "1")])
+            .await;
+        let token_id = markets
+            .as_ref()
+            .ok()
+            .and_then(|probe| probe.body.as_ref())
+            .and_then(|body| serde_json::from_value::<Vec<Market>>(body.clone()).ok())
+            .and_then(|markets| markets.into_iter().next())
+            .and_then(|market| market.parsed_clob_token_ids())
+            .and_then(|ids| ids.into_iter().next());
+        results.push(check_schema::<Vec<Market>>(
+            "Gamma markets",
+            &markets,
+            &["conditionId", "question", "outcomes", "outcomePrices", "clobTokenIds"],
+        ));
+        probes.extend(markets.ok());
+
+        let events = self
+            .get(&self.config.events_url, &[("active", "true"), ("closed", "false"), ("limit", "1")])
+            .await;
+        results.push(check_schema::<Vec<Event>>("Gamma events", &events, &["id", "title", "markets"]));
+        probes.extend(events.ok());
+
+        let trades = self.get(&self.config.trades_url, &[("limit", "1")]).await;
+        results.push(check_schema::<Vec<Trade>>(
+            "Data API trades",
+            &trades,
+            &["proxyWallet", "side", "conditionId", "size", "price", "timestamp", "outcomeIndex", "transactionHash"],
+        ));
+        probes.extend(trades.ok());
+
+        // Mirrors must serve the same data as the primary hosts
+        for host in &self.config.gamma_fallback_hosts {
+            let probe = self.get_mirror(&self.config.gamma_url, host).await;
+            results.push(check_schema::<Vec<Market>>(&format!("Gamma mirror {}", host), &probe, &["conditionId"]));
+        }
+        for host in &self.config.data_fallback_hosts {
+            let probe = self.get_mirror(&self.config.trades_url, host).await;
+            results.push(check_schema::<Vec<Trade>>(&format!("Data API mirror {}", host), &probe, &["proxyWallet"]));
+        }
+
+        let clob_url = self.config.clob_url.trim_end_matches('/');
+        match token_id {
+            Some(token_id) => {
+                let book = self.get(&format!("{}/book", clob_url), &[("token_id", &token_id)]).await;
+                results.push(check_schema::<OrderBook>("CLOB order book", &book, &["asset_id", "bids", "asks"]));
+                probes.extend(book.ok());
+
+                let history = self
+                    .get(
+                        &format!("{}/prices-history", clob_url),
+                        &[("market", &token_id), ("interval", "1d"), ("fidelity", "60")],
+                    )
+                    .await;
+                results.push(check_schema::<PriceHistory>("CLOB price history", &history, &["history"]));
+                probes.extend(history.ok());
+            }
+            None => {
+                for name in ["CLOB order book", "CLOB price history"] {
+                    results.push(CheckResult::new(name, CheckStatus::Fail, "Skipped: no token ID from Gamma markets"));
+                }
+            }
+        }
+
+        results.push(check_clock_skew(&probes));
+        results.push(check_rate_limits(&probes));
+        results
+    }
+
+    /// Samples recent markets and trades, reporting fields the models don't know
+    pub async fn field_reports(&self) -> Result<Vec<FieldReport>> {
+        let markets = self
+            .get_text(
+                &self.config.gamma_url,
+                &[("active", "true"), ("closed", "false"), ("include_tag", "true"), ("limit", FIELD_SAMPLE_SIZE)],
+            )
+            .await?;
+        let trades = self.get_text(&self.config.trades_url, &[("limit", FIELD_SAMPLE_SIZE)]).await?;
+
+        Ok(vec![
+            FieldReport::from_page("Gamma markets", Page::<Market>::parse(&markets)?, |market| &market.extra),
+            FieldReport::from_page("Data API trades", Page::<Trade>::parse(&trades)?, |trade| &trade.extra),
+        ])
+    }
+
+    /// Sends a one-record test request for `url` to a mirror host instead
+    async fn get_mirror(&self, url: &str, host: &str) -> Result<Probe> {
+        self.get(&failover::rehost(url, host)?, &[("limit", "1")]).await
+    }
+
+    /// Sends a request, keeping only the body
+    async fn get_text(&self, url: &str, query: &[(&str, &str)]) -> Result<String> {
+        Ok(self.client.get(url).query(query).send().await?.error_for_status()?.text().await?)
+    }
+
+    /// Sends a test request, keeping the raw status, headers and JSON body
+    async fn get(&self, url: &str, query: &[(&str, &str)]) -> Result<Probe> {
+        let start = Instant::now();
+        let response = self.client.get(url).query(query).send().await?;
+        let latency = start.elapsed();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.json().await.ok();
+
+        Ok(Probe {
+            status,
+            headers,
+            body,
+            latency,
+        })
+    }
+}
+
+/// Checks a response parses into the model and has the fields the scanner relies on
+/// Missing optional fields would otherwise be silently defaulted
+fn check_schema<T: DeserializeOwned>(name: &str, probe: &Result<Probe>, required_keys: &[&str]) -> CheckResult {
+    let probe = match probe {
+        Ok(probe) => probe,
+        Err(e) => return CheckResult::new(name, CheckStatus::Fail, format!("Request failed: {}", e)),
+    };
+
+    let result = |status, detail: String| CheckResult {
+        latency: Some(probe.latency),
+        ..CheckResult::new(name, status, detail)
+    };
+
+    if !probe.status.is_success() {
+        return result(CheckStatus::Fail, format!("HTTP {}", probe.status));
+    }
+    let Some(body) = &probe.body else {
+        return result(CheckStatus::Fail, "Response is not JSON".to_string());
+    };
+    if let Err(e) = serde_json::from_value::<T>(body.clone()) {
+        return result(CheckStatus::Fail, format!("Schema mismatch: {}", e));
+    }
+
+    // Lists are checked on their first record
+    let record = match body {
+        Value::Array(items) => match items.first() {
+            Some(item) => item,
+            None => return result(CheckStatus::Warn, "Empty response, fields not checked".to_string()),
+        },
+        other => other,
+    };
+    let missing: Vec<&str> = required_keys
+        .iter()
+        .filter(|key| record.get(**key).is_none_or(Value::is_null))
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        result(CheckStatus::Pass, "Schema OK".to_string())
+    } else {
+        result(CheckStatus::Warn, format!("Missing fields: {}", missing.join(", ")))
+    }
+}
+
+/// Compares the servers' `Date` headers with the local clock
+fn check_clock_skew(probes: &[Probe]) -> CheckResult {
+    let name = "Clock skew";
+    let skews: Vec<i64> = probes
+        .iter()
+        .filter_map(|probe| probe.headers.get(reqwest::header::DATE)?.to_str().ok())
+        .filter_map(|date| DateTime::parse_from_rfc2822(date).ok())
+        .map(|server_time| Utc::now().timestamp() - server_time.timestamp())
+        .collect();
+
+    // Date headers have one-second resolution, so use the smallest skew seen
+    let Some(skew) = skews.iter().copied().min_by_key(|skew| skew.abs()) else {
+        return CheckResult::new(name, CheckStatus::Warn, "No Date header in any response");
+    };
+
+    let detail = format!("Local clock is {:+}s from server time", skew);
+    let status = match skew.abs() {
+        s if s > MAX_CLOCK_SKEW_FAIL_SECS => CheckStatus::Fail,
+        s if s > MAX_CLOCK_SKEW_WARN_SECS => CheckStatus::Warn,
+        _ => CheckStatus::Pass,
+    };
+    CheckResult::new(name, status, detail)
+}
+
+/// Reports rate-limit headers and whether any endpoint is throttling
+fn check_rate_limits(probes: &[Probe]) -> CheckResult {
+    let name = "Rate limits";
+    if probes.is_empty() {
+        return CheckResult::new(name, CheckStatus::Warn, "No responses to check");
+    }
+    if probes.iter().any(|probe| probe.status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+        return CheckResult::new(name, CheckStatus::Fail, "Rate limited (HTTP 429)");
+    }
+
+    let headers: Vec<String> = probes
+        .iter()
+        .flat_map(|probe| probe.headers.iter())
+        .filter(|(key, _)| key.as_str().contains("ratelimit") || key.as_str() == "retry-after")
+        .filter_map(|(key, value)| Some(format!("{}={}", key, value.to_str().ok()?)))
+        .collect();
+
+    let exhausted = probes.iter().any(|probe| {
+        probe
+            .headers
+            .iter()
+            .filter(|(key, _)| key.as_str().contains("ratelimit-remaining"))
+            .any(|(_, value)| value.to_str().ok().and_then(|v| v.parse::<u64>().ok()) == Some(0))
+    });
+
+    if exhausted {
+        CheckResult::new(name, CheckStatus::Warn, format!("Quota exhausted: {}", headers.join(", ")))
+    } else if headers.is_empty() {
+        CheckResult::new(name, CheckStatus::Pass, "Not throttled (no rate-limit headers reported)")
+    } else {
+        CheckResult::new(name, CheckStatus::Pass, format!("Not throttled: {}", headers.join(", ")))
+    }
+}
+
+/// Prints the pass/fail report, returning the number of failed checks
+pub fn print_report(results: &[CheckResult]) -> usize {
+    outln!("\n{}", "=".repeat(80));
+    outln!("HEALTH CHECK");
+    outln!("{}", "=".repeat(80));
+
+    for result in results {
+        let latency = result
+            .latency
+            .map(|l| format!(" ({}ms)", l.as_millis()))
+            .unwrap_or_default();
+        outln!("{} {:<22}{}{}", result.status.symbol(), result.name, result.detail, latency);
+    }
+
+    let failed = results.iter().filter(|r| r.status == CheckStatus::Fail).count();
+    let warned = results.iter().filter(|r| r.status == CheckStatus::Warn).count();
+    outln!("{}", "-".repeat(80));
+    outln!("{} passed, {} warnings, {} failed", results.len() - failed - warned, warned, failed);
+    outln!("{}", "=".repeat(80));
+
+    failed
+}
+
+/// Prints each endpoint's unknown fields and skipped records
+pub fn print_field_reports(reports: &[FieldReport]) {
+    for report in reports {
+        outln!("\n{}", "=".repeat(80));
+        outln!(
+            "{}: {} records sampled, {} unknown fields, {} skipped",
+            report.name.to_uppercase(),
+            report.received,
+            report.unknown.len(),
+            report.rejected.len()
+        );
+        outln!("{}", "=".repeat(80));
+
+        let parsed = report.received - report.rejected.len();
+        for (field, (count, sample)) in &report.unknown {
+            let mut sample = sample.to_string();
+            if sample.chars().count() > MAX_SAMPLE_CHARS {
+                sample = format!("{}...", sample.chars().take(MAX_SAMPLE_CHARS).collect::<String>());
+            }
+            outln!("  {:<32} {:>4}/{:<4} {}", field, count, parsed, sample);
+        }
+
+        if !report.rejected.is_empty() {
+            outln!("\nSkipped records:");
+            for reason in &report.rejected {
+                outln!("  {}", reason);
+            }
+        }
+    }
+}
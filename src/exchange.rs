@@ -0,0 +1,104 @@
+use crate::client::PolymarketClient;
+use crate::manifold::ManifoldClient;
+use crate::models::{Event, Market, Trade};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+
+/// A prediction market venue the arbitrage scanner and wallet analyzer can run against
+/// Each venue's markets and trades are converted into the Polymarket-shaped models the
+/// pipelines already understand
+pub trait ExchangeClient: Send + Sync {
+    /// Venue name shown in output
+    fn venue(&self) -> &'static str;
+
+    /// Every market currently open for trading
+    fn fetch_active_markets(&self) -> impl Future<Output = Result<Vec<Market>>> + Send;
+
+    /// Open events grouping several markets, for multi-market arbitrage (none on venues without them)
+    fn fetch_active_events(&self) -> impl Future<Output = Result<Vec<Event>>> + Send;
+
+    /// Resolved markets, to settle wallets' positions (as many as the client is configured to load)
+    fn fetch_resolved_markets(&self) -> impl Future<Output = Result<Vec<Market>>> + Send;
+
+    /// Markets by ID, open or resolved
+    fn fetch_markets_by_ids(&self, ids: &[String]) -> impl Future<Output = Result<Vec<Market>>> + Send;
+
+    /// Up to `limit` of the venue's most recent trades across all markets, newest first
+    fn fetch_trades(&self, limit: usize) -> impl Future<Output = Result<Vec<Trade>>> + Send;
+
+    /// Every trade of a wallet (the user ID on venues without wallets), as named by `Trade::proxy_wallet`
+    fn fetch_wallet_trades(&self, wallet: &str) -> impl Future<Output = Result<Vec<Trade>>> + Send;
+
+    /// When each of these markets last traded (None if never); markets left out are unknown
+    fn fetch_last_trade_times(&self, ids: &[String]) -> impl Future<Output = Result<HashMap<String, Option<i64>>>> + Send;
+}
+
+impl ExchangeClient for PolymarketClient {
+    fn venue(&self) -> &'static str {
+        "Polymarket"
+    }
+
+    async fn fetch_active_markets(&self) -> Result<Vec<Market>> {
+        self.fetch_all_active_markets().await
+    }
+
+    async fn fetch_active_events(&self) -> Result<Vec<Event>> {
+        PolymarketClient::fetch_active_events(self).await
+    }
+
+    async fn fetch_resolved_markets(&self) -> Result<Vec<Market>> {
+        PolymarketClient::fetch_resolved_markets(self).await
+    }
+
+    async fn fetch_markets_by_ids(&self, ids: &[String]) -> Result<Vec<Market>> {
+        self.fetch_markets_by_condition_ids(ids).await
+    }
+
+    async fn fetch_trades(&self, limit: usize) -> Result<Vec<Trade>> {
+        self.fetch_recent_trades(limit).await
+    }
+
+    async fn fetch_wallet_trades(&self, wallet: &str) -> Result<Vec<Trade>> {
+        PolymarketClient::fetch_wallet_trades(self, wallet).await
+    }
+
+    async fn fetch_last_trade_times(&self, ids: &[String]) -> Result<HashMap<String, Option<i64>>> {
+        PolymarketClient::fetch_last_trade_times(self, ids).await
+    }
+}
+
+impl ExchangeClient for ManifoldClient {
+    fn venue(&self) -> &'static str {
+        "Manifold"
+    }
+
+    async fn fetch_active_markets(&self) -> Result<Vec<Market>> {
+        self.fetch_open_markets().await
+    }
+
+    async fn fetch_active_events(&self) -> Result<Vec<Event>> {
+        Ok(Vec::new())
+    }
+
+    async fn fetch_resolved_markets(&self) -> Result<Vec<Market>> {
+        ManifoldClient::fetch_resolved_markets(self).await
+    }
+
+    async fn fetch_markets_by_ids(&self, ids: &[String]) -> Result<Vec<Market>> {
+        self.fetch_markets(ids).await
+    }
+
+    async fn fetch_trades(&self, limit: usize) -> Result<Vec<Trade>> {
+        self.fetch_recent_trades(limit).await
+    }
+
+    async fn fetch_wallet_trades(&self, wallet: &str) -> Result<Vec<Trade>> {
+        self.fetch_user_trades(wallet).await
+    }
+
+    /// Manifold prices move with every bet on an automated market maker, so they're never stale
+    async fn fetch_last_trade_times(&self, _ids: &[String]) -> Result<HashMap<String, Option<i64>>> {
+        Ok(HashMap::new())
+    }
+}
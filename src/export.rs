@@ -0,0 +1,302 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Column data types declared in an export's schema
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    String,
+    Integer,
+    Decimal,
+    Boolean,
+}
+
+/// Describes one CSV column
+#[derive(Debug, Clone, Serialize)]
+pub struct Column {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub column_type: ColumnType,
+    /// Unit of numeric columns (e.g. "usd", "shares", "percent")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl Column {
+    pub fn new(name: &'static str, column_type: ColumnType, description: &'static str) -> Self {
+        Self {
+            name,
+            column_type,
+            unit: None,
+            description,
+        }
+    }
+
+    /// Sets the unit of a numeric column
+    pub fn unit(mut self, unit: &'static str) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+}
+
+/// A single cell value; rendered according to the export's numeric policy
+#[derive(Debug, Clone)]
+pub enum Value {
+    Text(String),
+    Integer(i64),
+    Decimal(f64),
+    Boolean(bool),
+    /// Empty cell
+    Null,
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Decimal(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Integer(value as i64)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+/// Schema sidecar written next to each CSV file (`<file>.schema.json`)
+#[derive(Debug, Serialize)]
+struct Schema<'a> {
+    format: &'static str,
+    delimiter: &'static str,
+    decimal_separator: &'static str,
+    thousands_separator: Option<&'static str>,
+    decimal_places: usize,
+    null: &'static str,
+    booleans: [&'static str; 2],
+    columns: &'a [Column],
+}
+
+/// Schema sidecar written next to each Parquet file; types are in the file itself,
+/// so this only adds units and descriptions
+#[cfg(feature = "parquet")]
+#[derive(Debug, Serialize)]
+struct ParquetSchema<'a> {
+    format: &'static str,
+    columns: &'a [Column],
+}
+
+/// CSV export with a fixed, locale-independent numeric format:
+/// `.` decimal separator, no thousands separators, a fixed number of decimal places,
+/// and empty cells for missing or non-finite values
+pub struct CsvExport {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Value>>,
+    decimal_places: usize,
+}
+
+impl CsvExport {
+    pub fn new(columns: Vec<Column>, decimal_places: usize) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            decimal_places,
+        }
+    }
+
+    /// Adds a row; must have one value per column
+    pub fn push_row(&mut self, row: Vec<Value>) {
+        debug_assert_eq!(row.len(), self.columns.len(), "row width must match columns");
+        self.rows.push(row);
+    }
+
+    /// Writes the CSV file and its `.schema.json` sidecar, returning the sidecar path
+    pub fn write(&self, path: &Path) -> Result<PathBuf> {
+        let mut text = String::new();
+        let header: Vec<String> = self.columns.iter().map(|c| escape(c.name)).collect();
+        text.push_str(&header.join(","));
+        text.push('\n');
+
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|value| self.render(value)).collect();
+            text.push_str(&cells.join(","));
+            text.push('\n');
+        }
+
+        fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+
+        let schema = Schema {
+            format: "csv",
+            delimiter: ",",
+            decimal_separator: ".",
+            thousands_separator: None,
+            decimal_places: self.decimal_places,
+            null: "",
+            booleans: ["true", "false"],
+            columns: &self.columns,
+        };
+        let schema_path = schema_path(path);
+        fs::write(&schema_path, serde_json::to_string_pretty(&schema)?)
+            .with_context(|| format!("Failed to write {}", schema_path.display()))?;
+
+        Ok(schema_path)
+    }
+
+    /// Writes the rows as a Parquet file (one row group, every column nullable) and its
+    /// `.schema.json` sidecar, returning the sidecar path
+    /// Decimals are written at full precision rather than rounded to `decimal_places`
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet(&self, path: &Path) -> Result<PathBuf> {
+        use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let fields: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let physical = match column.column_type {
+                    ColumnType::String => "BYTE_ARRAY",
+                    ColumnType::Integer => "INT64",
+                    ColumnType::Decimal => "DOUBLE",
+                    ColumnType::Boolean => "BOOLEAN",
+                };
+                let logical = match column.column_type {
+                    ColumnType::String => " (UTF8)",
+                    _ => "",
+                };
+                format!("OPTIONAL {} {}{};", physical, column.name, logical)
+            })
+            .collect();
+        let schema = Arc::new(parse_message_type(&format!("message export {{ {} }}", fields.join(" ")))?);
+
+        let file = fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = SerializedFileWriter::new(file, schema, Arc::new(WriterProperties::builder().build()))?;
+        let mut row_group = writer.next_row_group()?;
+
+        for (index, column) in self.columns.iter().enumerate() {
+            let cells: Vec<&Value> = self.rows.iter().map(|row| &row[index]).collect();
+            let mut column_writer = row_group
+                .next_column()?
+                .context("Parquet schema has fewer columns than the export")?;
+
+            // Definition level 1 = value present, 0 = null; only present values are passed
+            let mut levels = Vec::with_capacity(cells.len());
+            macro_rules! write_values {
+                ($type:ty, $convert:expr) => {{
+                    let values: Vec<_> = cells
+                        .iter()
+                        .map(|cell| $convert(*cell))
+                        .inspect(|value| levels.push(value.is_some() as i16))
+                        .flatten()
+                        .collect();
+                    column_writer.typed::<$type>().write_batch(&values, Some(&levels), None)?;
+                }};
+            }
+
+            match column.column_type {
+                ColumnType::String => write_values!(ByteArrayType, |cell: &Value| match cell {
+                    Value::Text(text) => Some(ByteArray::from(text.as_str())),
+                    _ => None,
+                }),
+                ColumnType::Integer => write_values!(Int64Type, |cell: &Value| match cell {
+                    Value::Integer(n) => Some(*n),
+                    _ => None,
+                }),
+                ColumnType::Decimal => write_values!(DoubleType, |cell: &Value| match cell {
+                    Value::Decimal(x) if x.is_finite() => Some(*x),
+                    Value::Integer(n) => Some(*n as f64),
+                    _ => None,
+                }),
+                ColumnType::Boolean => write_values!(BoolType, |cell: &Value| match cell {
+                    Value::Boolean(b) => Some(*b),
+                    _ => None,
+                }),
+            }
+
+            column_writer.close()?;
+        }
+
+        row_group.close()?;
+        writer.close()?;
+
+        let schema = ParquetSchema {
+            format: "parquet",
+            columns: &self.columns,
+        };
+        let schema_path = schema_path(path);
+        fs::write(&schema_path, serde_json::to_string_pretty(&schema)?)
+            .with_context(|| format!("Failed to write {}", schema_path.display()))?;
+
+        Ok(schema_path)
+    }
+
+    /// Renders a cell; Rust's formatting never uses locale separators
+    fn render(&self, value: &Value) -> String {
+        match value {
+            Value::Text(text) => escape(text),
+            Value::Integer(n) => n.to_string(),
+            Value::Decimal(x) if x.is_finite() => {
+                let rendered = format!("{:.*}", self.decimal_places, x);
+                // Avoid "-0.000" for values that round to zero
+                if rendered.trim_start_matches('-').chars().all(|c| c == '0' || c == '.') {
+                    rendered.trim_start_matches('-').to_string()
+                } else {
+                    rendered
+                }
+            }
+            Value::Decimal(_) => String::new(),
+            Value::Boolean(b) => b.to_string(),
+            Value::Null => String::new(),
+        }
+    }
+}
+
+/// Path of the schema sidecar for a CSV file
+fn schema_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".schema.json");
+    path.with_file_name(name)
+}
+
+/// Quotes a field if it contains a delimiter, quote or newline
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,234 @@
+use crate::rate_budget::RateBudget;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::sync::atomic::{AtomicI64, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Consecutive failures after which a host is taken out of rotation
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+/// Seconds a failing host stays out of rotation before it's tried again
+pub const DEFAULT_COOLDOWN_SECS: i64 = 60;
+
+/// Request counters and cooldown of one host
+#[derive(Debug)]
+struct HostState {
+    origin: String,
+    requests: AtomicU64,
+    failures: AtomicU64,
+    consecutive_failures: AtomicU32,
+    /// Unix seconds until which the host is skipped (0 = in rotation)
+    down_until: AtomicI64,
+}
+
+/// Snapshot of a host's health
+#[derive(Debug, Clone)]
+pub struct HostStatus {
+    pub origin: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    /// Unix seconds until which the host is out of rotation
+    pub down_until: Option<i64>,
+}
+
+/// A primary API host and its mirrors, with health shared by every clone
+/// Requests go to the first host in rotation; one that keeps failing is skipped for a
+/// cooldown so the next mirror takes over, and rejoins once the cooldown expires
+#[derive(Debug, Clone)]
+pub struct HostPool {
+    hosts: Arc<Vec<HostState>>,
+    failure_threshold: u32,
+    cooldown_secs: i64,
+}
+
+impl HostPool {
+    /// Creates a pool from the origin of `primary_url` followed by the fallback hosts
+    pub fn new(primary_url: &str, fallbacks: &[String]) -> Result<Self> {
+        let primary = origin(primary_url)?;
+        let mut origins = vec![primary];
+        for fallback in fallbacks {
+            let fallback = origin(fallback)?;
+            if !origins.contains(&fallback) {
+                origins.push(fallback);
+            }
+        }
+
+        Ok(Self {
+            hosts: Arc::new(
+                origins
+                    .into_iter()
+                    .map(|origin| HostState {
+                        origin,
+                        requests: AtomicU64::new(0),
+                        failures: AtomicU64::new(0),
+                        consecutive_failures: AtomicU32::new(0),
+                        down_until: AtomicI64::new(0),
+                    })
+                    .collect(),
+            ),
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            cooldown_secs: DEFAULT_COOLDOWN_SECS,
+        })
+    }
+
+    /// Takes a host out of rotation after this many consecutive failures, for `cooldown_secs`
+    pub fn with_failover(mut self, failure_threshold: u32, cooldown_secs: i64) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self.cooldown_secs = cooldown_secs.max(0);
+        self
+    }
+
+    /// Whether both pools track the same hosts (one is a clone of the other)
+    pub fn same_hosts(&self, other: &HostPool) -> bool {
+        Arc::ptr_eq(&self.hosts, &other.hosts)
+    }
+
+    /// Whether any mirrors are configured
+    pub fn has_fallbacks(&self) -> bool {
+        self.hosts.len() > 1
+    }
+
+    /// Host indices in the order to try them: hosts in rotation as configured,
+    /// then those cooling down, soonest back first
+    fn order(&self) -> Vec<usize> {
+        let now = Utc::now().timestamp();
+        let (mut up, mut down): (Vec<usize>, Vec<usize>) =
+            (0..self.hosts.len()).partition(|&i| self.hosts[i].down_until.load(Ordering::Relaxed) <= now);
+        down.sort_by_key(|&i| self.hosts[i].down_until.load(Ordering::Relaxed));
+        up.append(&mut down);
+        up
+    }
+
+    fn record_success(&self, index: usize) {
+        let host = &self.hosts[index];
+        host.requests.fetch_add(1, Ordering::Relaxed);
+        host.consecutive_failures.store(0, Ordering::Relaxed);
+        host.down_until.store(0, Ordering::Relaxed);
+    }
+
+    fn record_failure(&self, index: usize, error: &str) {
+        let host = &self.hosts[index];
+        host.requests.fetch_add(1, Ordering::Relaxed);
+        host.failures.fetch_add(1, Ordering::Relaxed);
+        let consecutive = host.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        // Only worth benching a host when there's somewhere else to go
+        if self.has_fallbacks() && consecutive >= self.failure_threshold {
+            let now = Utc::now().timestamp();
+            let was_down = host.down_until.swap(now + self.cooldown_secs, Ordering::Relaxed) > now;
+            if !was_down {
+                eoutln!(
+                    "Warning: {} failed {} times in a row ({}), failing over for {}s",
+                    host.origin, consecutive, error, self.cooldown_secs
+                );
+            }
+        }
+    }
+
+    /// Current health of every host, in configured order
+    pub fn status(&self) -> Vec<HostStatus> {
+        let now = Utc::now().timestamp();
+        self.hosts
+            .iter()
+            .map(|host| HostStatus {
+                origin: host.origin.clone(),
+                requests: host.requests.load(Ordering::Relaxed),
+                failures: host.failures.load(Ordering::Relaxed),
+                consecutive_failures: host.consecutive_failures.load(Ordering::Relaxed),
+                down_until: Some(host.down_until.load(Ordering::Relaxed)).filter(|until| *until > now),
+            })
+            .collect()
+    }
+}
+
+/// One API path served by every host of a pool
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pool: HostPool,
+    /// Path and any fixed query string of the configured URL
+    path: String,
+    /// Rate limit shared with the client's other endpoints; every attempt counts against it
+    budget: RateBudget,
+}
+
+impl Endpoint {
+    /// An endpoint at the path of `url`, served by the hosts of `pool`
+    pub fn new(pool: HostPool, url: &str) -> Result<Self> {
+        let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid API URL: {}", url))?;
+        let mut path = parsed.path().trim_end_matches('/').to_string();
+        if let Some(query) = parsed.query() {
+            path = format!("{}?{}", path, query);
+        }
+        Ok(Self {
+            pool,
+            path,
+            budget: RateBudget::unlimited(),
+        })
+    }
+
+    /// Spends this budget on every request, including each failover attempt
+    pub fn with_budget(mut self, budget: RateBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn pool(&self) -> &HostPool {
+        &self.pool
+    }
+
+    fn url_on(&self, index: usize) -> String {
+        format!("{}{}", self.pool.hosts[index].origin, self.path)
+    }
+
+    /// Sends a GET (with `build` adding the query) to each host in turn until one
+    /// responds without a connection error, a 5xx or a 429
+    /// The last host's response is returned whatever its status, for the caller to handle
+    pub async fn get(
+        &self,
+        client: &reqwest::Client,
+        build: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        let order = self.pool.order();
+        let last = order.len() - 1;
+        let mut last_error = None;
+
+        for (attempt, index) in order.into_iter().enumerate() {
+            self.budget.acquire().await;
+            match build(client.get(self.url_on(index))).send().await {
+                Ok(response) if response.status().is_server_error() || response.status().as_u16() == 429 => {
+                    self.pool.record_failure(index, &response.status().to_string());
+                    if attempt == last {
+                        return Ok(response);
+                    }
+                }
+                Ok(response) => {
+                    self.pool.record_success(index);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.pool.record_failure(index, &e.to_string());
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.context("No hosts to send the request to")?.into())
+    }
+}
+
+/// `url` with its scheme, host and port replaced by those of `host` (e.g. a mirror)
+pub fn rehost(url: &str, host: &str) -> Result<String> {
+    let path = Endpoint::new(HostPool::new(url, &[])?, url)?.path;
+    Ok(format!("{}{}", origin(host)?, path))
+}
+
+/// Whether two URLs are served from the same scheme, host and port
+pub fn same_host(a: &str, b: &str) -> bool {
+    matches!((origin(a), origin(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Scheme, host and port of a URL, e.g. "https://gamma-api.polymarket.com"
+fn origin(url: &str) -> Result<String> {
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid API host: {}", url))?;
+    Ok(parsed.origin().ascii_serialization())
+}
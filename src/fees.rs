@@ -0,0 +1,127 @@
+use crate::config::ArbitrageConfig;
+use crate::models::Market;
+
+/// Tick size assumed when the API doesn't report one
+const DEFAULT_TICK_SIZE: f64 = 0.01;
+
+/// Trade sizes (shares per leg) reported by the `fees` command
+pub const BREAK_EVEN_SIZES: [f64; 4] = [10.0, 100.0, 1000.0, 10000.0];
+
+/// A market's fee rates and order constraints
+#[derive(Debug, Clone, Copy)]
+pub struct FeeSchedule {
+    pub taker_fee_bps: f64,
+    pub maker_fee_bps: f64,
+    pub tick_size: f64,
+    pub min_order_size: f64,
+}
+
+impl FeeSchedule {
+    /// Reads the fee rates and tick size Gamma reports for a market
+    pub fn from_market(market: &Market) -> Self {
+        Self {
+            taker_fee_bps: market.taker_base_fee.unwrap_or(0.0),
+            maker_fee_bps: market.maker_base_fee.unwrap_or(0.0),
+            tick_size: market.order_price_min_tick_size.filter(|t| *t > 0.0).unwrap_or(DEFAULT_TICK_SIZE),
+            min_order_size: market.order_min_size.unwrap_or(0.0),
+        }
+    }
+
+    /// Taker fee per share bought at `price`
+    /// Polymarket charges the base rate on min(price, 1 - price), so fees are highest at 50c
+    pub fn taker_fee_per_share(&self, price: f64) -> f64 {
+        self.taker_fee_bps / 10_000.0 * price.min(1.0 - price).max(0.0)
+    }
+}
+
+/// Expected slippage when buying at a quoted price: a flat rate plus market impact
+/// that grows with the order's share of the market's liquidity
+#[derive(Debug, Clone, Copy)]
+pub struct SlippageModel {
+    /// Slippage on every fill, in basis points of the price
+    pub base_bps: f64,
+    /// Fraction of the price lost per unit of order notional / market liquidity
+    /// (1.0: an order worth 10% of liquidity fills 10% above the quote)
+    pub liquidity_impact: f64,
+}
+
+impl SlippageModel {
+    pub fn from_config(config: &ArbitrageConfig) -> Self {
+        Self {
+            base_bps: config.slippage_bps,
+            liquidity_impact: config.slippage_impact,
+        }
+    }
+
+    /// Expected slippage per share when buying `shares` at `price` in a market with
+    /// `liquidity` dollars on the book; never more than the rest of the way to $1
+    pub fn per_share(&self, price: f64, shares: f64, liquidity: f64) -> f64 {
+        let book_share = if liquidity > 0.0 { (shares * price / liquidity).min(1.0) } else { 1.0 };
+        let slippage = price * (self.base_bps / 10_000.0 + self.liquidity_impact * book_share);
+        slippage.min(1.0 - price).max(0.0)
+    }
+}
+
+/// Costs an arbitrage has to clear beyond the prices themselves
+#[derive(Debug, Clone, Copy)]
+pub struct TradingCosts {
+    /// Fixed cost per arbitrage regardless of size (e.g. gas to merge or redeem), in dollars
+    pub fixed_cost_per_trade: f64,
+    /// Extra margin per $1 of payout required on top of break-even
+    pub min_edge: f64,
+    /// Shares per leg the scanner's threshold and net profit are computed for
+    pub trade_size: f64,
+    /// Slippage expected when filling at quoted (Gamma) prices
+    pub slippage: SlippageModel,
+    /// Most dollars committed to one opportunity when estimating its dollar profit
+    pub max_position: f64,
+}
+
+impl TradingCosts {
+    pub fn from_config(config: &ArbitrageConfig) -> Self {
+        Self {
+            fixed_cost_per_trade: config.fixed_cost_per_trade,
+            min_edge: config.min_edge,
+            trade_size: config.trade_size,
+            slippage: SlippageModel::from_config(config),
+            max_position: config.max_position,
+        }
+    }
+
+    /// Taker fees plus the fixed cost spread over `shares`, per share of each leg bought
+    pub fn fees_per_share(&self, legs: &[(FeeSchedule, f64)], shares: f64) -> f64 {
+        let fees: f64 = legs.iter().map(|(schedule, price)| schedule.taker_fee_per_share(*price)).sum();
+        fees + self.fixed_cost_per_trade / shares
+    }
+
+    /// Basket cost per $1 of payout at which buying `shares` of every leg exactly breaks even
+    /// Each leg is its fee schedule and the price paid
+    pub fn break_even_ratio(&self, legs: &[(FeeSchedule, f64)], payout: f64, shares: f64) -> f64 {
+        1.0 - self.fees_per_share(legs, shares) / payout
+    }
+
+    /// Smallest spread below payout (per $1) worth trading at `shares` per leg
+    /// Never less than one tick, since a smaller spread can't be quoted
+    pub fn min_spread(&self, legs: &[(FeeSchedule, f64)], payout: f64, shares: f64) -> f64 {
+        let tick = legs.iter().map(|(schedule, _)| schedule.tick_size).fold(0.0, f64::max);
+        let required = 1.0 - self.break_even_ratio(legs, payout, shares) + self.min_edge;
+        required.max(tick / payout)
+    }
+
+    /// Cost per $1 of payout below which the scanner reports a basket, at the configured trade size
+    pub fn threshold(&self, legs: &[(FeeSchedule, f64)], payout: f64) -> f64 {
+        1.0 - self.min_spread(legs, payout, self.trade_size)
+    }
+
+    /// Price per $1 of payout above which selling every leg of a freshly split set is
+    /// worth it, at the configured trade size (fees are charged on sells the same way)
+    pub fn overpriced_threshold(&self, legs: &[(FeeSchedule, f64)], payout: f64) -> f64 {
+        1.0 + self.min_spread(legs, payout, self.trade_size)
+    }
+}
+
+impl Default for TradingCosts {
+    fn default() -> Self {
+        Self::from_config(&ArbitrageConfig::default())
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::ExecutorConfig;
+use crate::exchange::ExchangeClient;
+use crate::models::{MarketResolution, ResolutionStatus};
+use crate::store::Store;
+use anyhow::{bail, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// File in the store holding the executor's guardrail state
+const EXECUTOR_STATE_FILE: &str = "executor_state.json";
+/// Lock file serializing updates to the state across processes
+const EXECUTOR_STATE_LOCK: &str = "executor_state.lock";
+
+/// Persisted guardrail state, shared by every executor process using the same data dir
+/// Updates hold an exclusive lock from load to save, so concurrent processes don't lose each other's fills
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutorState {
+    /// Set by `executor halt` or the slippage kill switch; blocks all new orders
+    pub halted: bool,
+    pub halt_reason: Option<String>,
+    pub halted_at: Option<i64>,
+    /// UTC date (YYYY-MM-DD) the daily notional applies to
+    pub day: String,
+    pub daily_notional: f64,
+    /// Cost of positions opened and not yet settled
+    pub open_exposure: f64,
+    /// Cost of each market's unsettled position, by condition ID
+    #[serde(default)]
+    pub positions: BTreeMap<String, f64>,
+}
+
+/// Global limits every automated order must pass before it's placed
+pub struct Guardrails {
+    config: ExecutorConfig,
+    store: Store,
+}
+
+impl Guardrails {
+    pub fn new(config: ExecutorConfig, store: Store) -> Self {
+        Self { config, store }
+    }
+
+    /// Loads the current state, rolling the daily notional over at UTC midnight
+    pub fn state(&self) -> Result<ExecutorState> {
+        let mut state: ExecutorState = self.store.load_json(EXECUTOR_STATE_FILE)?.unwrap_or_default();
+        let today = today();
+        if state.day != today {
+            state.day = today;
+            state.daily_notional = 0.0;
+        }
+        Ok(state)
+    }
+
+    /// Applies a change to the state under the cross-process lock and saves it, unless the
+    /// change fails
+    fn update<T>(&self, change: impl FnOnce(&mut ExecutorState) -> Result<T>) -> Result<T> {
+        let _lock = self.store.lock(EXECUTOR_STATE_LOCK)?;
+        let mut state = self.state()?;
+        let result = change(&mut state)?;
+        self.store.save_json(EXECUTOR_STATE_FILE, &state)?;
+        Ok(result)
+    }
+
+    /// Checks an order of the given notional against the kill switch and limits and, if it
+    /// passes, reserves the notional against both limits in the same locked update, so
+    /// executors sharing the data dir can't all pass the check before any of them fills
+    /// The reservation is settled by `record_fill` or handed back by `release`
+    pub fn reserve(&self, notional: f64) -> Result<()> {
+        self.update(|state| {
+            if state.halted {
+                bail!(
+                    "Executor is halted ({}); run `executor resume` to re-enable trading",
+                    state.halt_reason.as_deref().unwrap_or("no reason given")
+                );
+            }
+            if state.daily_notional + notional > self.config.max_daily_notional {
+                bail!(
+                    "Order of ${:.2} would exceed the daily notional limit (${:.2} of ${:.2} used)",
+                    notional, state.daily_notional, self.config.max_daily_notional
+                );
+            }
+            if state.open_exposure + notional > self.config.max_open_exposure {
+                bail!(
+                    "Order of ${:.2} would exceed the open exposure limit (${:.2} of ${:.2} open)",
+                    notional, state.open_exposure, self.config.max_open_exposure
+                );
+            }
+
+            state.daily_notional += notional;
+            state.open_exposure += notional;
+            Ok(())
+        })
+    }
+
+    /// Hands back a reservation for an order that was rejected or didn't fill
+    pub fn release(&self, reserved: f64) -> Result<()> {
+        self.update(|state| {
+            state.daily_notional = (state.daily_notional - reserved).max(0.0);
+            state.open_exposure = (state.open_exposure - reserved).max(0.0);
+            Ok(())
+        })
+    }
+
+    /// Records a fill in a market in place of the notional reserved for it, tripping the kill
+    /// switch if slippage exceeded the bound
+    /// Returns whether the executor was halted by this fill
+    pub fn record_fill(
+        &self,
+        condition_id: &str,
+        reserved: f64,
+        notional: f64,
+        expected_price: f64,
+        fill_price: f64,
+    ) -> Result<bool> {
+        let slippage = if expected_price > 0.0 {
+            (fill_price - expected_price) / expected_price
+        } else {
+            0.0
+        };
+        let tripped = slippage > self.config.max_slippage;
+
+        self.update(|state| {
+            state.daily_notional = (state.daily_notional + notional - reserved).max(0.0);
+            state.open_exposure = (state.open_exposure + notional - reserved).max(0.0);
+            *state.positions.entry(condition_id.to_string()).or_default() += notional;
+
+            if tripped {
+                state.halted = true;
+                state.halt_reason = Some(format!(
+                    "slippage {:.2}% exceeded the {:.2}% bound",
+                    slippage * 100.0,
+                    self.config.max_slippage * 100.0
+                ));
+                state.halted_at = Some(Utc::now().timestamp());
+            }
+            Ok(())
+        })?;
+        Ok(tripped)
+    }
+
+    /// Releases the exposure of positions in markets that have settled
+    /// Returns the cost released; markets without a position are ignored
+    pub fn record_settlement(&self, condition_ids: &[String]) -> Result<f64> {
+        if condition_ids.is_empty() {
+            return Ok(0.0);
+        }
+        self.update(|state| {
+            let released: f64 = condition_ids.iter().filter_map(|id| state.positions.remove(id)).sum();
+            state.open_exposure = (state.open_exposure - released).max(0.0);
+            Ok(released)
+        })
+    }
+
+    /// Checks the markets the executor holds positions in and settles those that have resolved,
+    /// since their shares can then be redeemed for the payout. Returns the cost released
+    pub async fn settle_resolved(&self, client: &impl ExchangeClient) -> Result<f64> {
+        let held: Vec<String> = self.state()?.positions.into_keys().collect();
+        if held.is_empty() {
+            return Ok(0.0);
+        }
+
+        let resolved: Vec<String> = client
+            .fetch_markets_by_ids(&held)
+            .await?
+            .iter()
+            .filter_map(|market| {
+                let id = market.condition_id.as_deref()?;
+                (MarketResolution::from_market(id, market).status == ResolutionStatus::Resolved).then(|| id.to_string())
+            })
+            .collect();
+        self.record_settlement(&resolved)
+    }
+
+    /// Engages the kill switch
+    pub fn halt(&self, reason: &str) -> Result<()> {
+        self.update(|state| {
+            state.halted = true;
+            state.halt_reason = Some(reason.to_string());
+            state.halted_at = Some(Utc::now().timestamp());
+            Ok(())
+        })
+    }
+
+    /// Releases the kill switch
+    pub fn resume(&self) -> Result<()> {
+        self.update(|state| {
+            state.halted = false;
+            state.halt_reason = None;
+            state.halted_at = None;
+            Ok(())
+        })
+    }
+
+    /// Prints the kill switch state and usage against each limit
+    pub fn print_status(&self, state: &ExecutorState) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("EXECUTOR GUARDRAILS");
+        outln!("{}", "=".repeat(80));
+
+        if state.halted {
+            let since = state
+                .halted_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_default();
+            outln!("\n🛑 HALTED since {}", since);
+            outln!("   Reason: {}", state.halt_reason.as_deref().unwrap_or("no reason given"));
+        } else {
+            outln!("\n✓ Trading enabled");
+        }
+
+        outln!("\nDaily Notional:       ${:.2} / ${:.2}", state.daily_notional, self.config.max_daily_notional);
+        outln!(
+            "Open Exposure:        ${:.2} / ${:.2} across {} markets",
+            state.open_exposure,
+            self.config.max_open_exposure,
+            state.positions.len()
+        );
+        outln!("Max Slippage:         {:.2}%", self.config.max_slippage * 100.0);
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Current UTC date as YYYY-MM-DD
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guardrails(name: &str) -> Guardrails {
+        let dir = std::env::temp_dir().join(format!("guardrails-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Guardrails::new(ExecutorConfig::default(), Store::open(dir).unwrap())
+    }
+
+    #[test]
+    fn settlement_releases_the_market_exposure() {
+        let guardrails = guardrails("settlement");
+        guardrails.record_fill("0xaaa", 0.0, 4.8, 0.48, 0.48).unwrap();
+        guardrails.record_fill("0xaaa", 0.0, 5.0, 0.50, 0.50).unwrap();
+        guardrails.record_fill("0xbbb", 0.0, 3.0, 0.30, 0.30).unwrap();
+
+        let released = guardrails.record_settlement(&["0xaaa".to_string()]).unwrap();
+        assert!((released - 9.8).abs() < 1e-9);
+
+        let state = guardrails.state().unwrap();
+        assert!((state.open_exposure - 3.0).abs() < 1e-9);
+        assert!((state.daily_notional - 12.8).abs() < 1e-9);
+        assert_eq!(state.positions.keys().collect::<Vec<_>>(), ["0xbbb"]);
+    }
+
+    #[test]
+    fn settling_a_market_twice_or_without_a_position_releases_nothing() {
+        let guardrails = guardrails("unheld");
+        guardrails.record_fill("0xaaa", 0.0, 4.8, 0.48, 0.48).unwrap();
+
+        assert_eq!(guardrails.record_settlement(&["0xccc".to_string()]).unwrap(), 0.0);
+        guardrails.record_settlement(&["0xaaa".to_string()]).unwrap();
+        assert_eq!(guardrails.record_settlement(&["0xaaa".to_string()]).unwrap(), 0.0);
+        assert_eq!(guardrails.state().unwrap().open_exposure, 0.0);
+    }
+
+    #[test]
+    fn fills_from_concurrent_writers_are_all_kept() {
+        let guardrails = guardrails("concurrent");
+        std::thread::scope(|scope| {
+            for writer in 0..4 {
+                let guardrails = &guardrails;
+                scope.spawn(move || {
+                    for _ in 0..25 {
+                        guardrails.record_fill(&format!("0x{}", writer), 0.0, 1.0, 0.5, 0.5).unwrap();
+                    }
+                });
+            }
+        });
+
+        let state = guardrails.state().unwrap();
+        assert_eq!(state.daily_notional, 100.0);
+        assert_eq!(state.positions.values().sum::<f64>(), 100.0);
+    }
+
+    #[test]
+    fn reservations_that_together_exceed_a_limit_are_refused() {
+        let guardrails = guardrails("reserve");
+        guardrails.reserve(300.0).unwrap();
+        let refused = guardrails.reserve(300.0).unwrap_err();
+        assert!(refused.to_string().contains("daily notional limit"));
+
+        // A reservation handed back frees its room; a fill replaces it with what was spent
+        guardrails.release(300.0).unwrap();
+        guardrails.reserve(300.0).unwrap();
+        guardrails.record_fill("0xaaa", 300.0, 290.0, 0.48, 0.48).unwrap();
+
+        let state = guardrails.state().unwrap();
+        assert_eq!(state.daily_notional, 290.0);
+        assert_eq!(state.open_exposure, 290.0);
+        assert_eq!(state.positions["0xaaa"], 290.0);
+    }
+
+    #[test]
+    fn concurrent_reservations_never_exceed_the_limit() {
+        let guardrails = guardrails("reserve-concurrent");
+        let granted = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let (guardrails, granted) = (&guardrails, &granted);
+                scope.spawn(move || {
+                    for _ in 0..10 {
+                        if guardrails.reserve(20.0).is_ok() {
+                            granted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        }
+                    }
+                });
+            }
+        });
+
+        // $500 a day allows 25 of the 40 $20 orders
+        assert_eq!(granted.into_inner(), 25);
+        assert_eq!(guardrails.state().unwrap().daily_notional, 500.0);
+    }
+}
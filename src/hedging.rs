@@ -0,0 +1,229 @@
+use crate::client::PolymarketClient;
+use crate::fees::{FeeSchedule, TradingCosts};
+use crate::models::{Market, OrderBook, Position};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Order books fetched at once while planning hedges
+const MAX_CONCURRENT_BOOKS: usize = 10;
+
+/// What buying the opposite side achieves for a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HedgeKind {
+    /// Both outcomes end in profit: the position is locked in
+    LockProfit,
+    /// The position is underwater, and the hedge bounds how much more it can lose
+    CapLoss,
+}
+
+impl HedgeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HedgeKind::LockProfit => "LOCK PROFIT",
+            HedgeKind::CapLoss => "CAP LOSS",
+        }
+    }
+}
+
+/// Buying the opposite outcome of an open position at the current asks
+#[derive(Debug, Clone, Serialize)]
+pub struct HedgeSuggestion {
+    pub kind: HedgeKind,
+    pub condition_id: String,
+    pub market_title: String,
+    /// Outcome held
+    pub outcome: String,
+    pub held_shares: f64,
+    pub avg_price: f64,
+    /// Cost of the shares still held
+    pub cost_basis: f64,
+    /// Gamma price of the outcome held
+    pub current_price: Option<f64>,
+    /// Outcome to buy
+    pub hedge_outcome: String,
+    /// Shares to buy: the shares held, or as many as the book can fill
+    pub hedge_shares: f64,
+    /// Average ask paid for the hedge
+    pub hedge_price: f64,
+    /// Hedge cost including taker fees and the fixed cost per trade
+    pub hedge_cost: f64,
+    pub pnl_if_held_wins: f64,
+    pub pnl_if_hedge_wins: f64,
+}
+
+impl HedgeSuggestion {
+    /// Worst-case P&L once hedged
+    pub fn locked_pnl(&self) -> f64 {
+        self.pnl_if_held_wins.min(self.pnl_if_hedge_wins)
+    }
+
+    /// How much less the position can lose than unhedged (where the worst case is the cost basis)
+    pub fn loss_reduction(&self) -> f64 {
+        self.locked_pnl() + self.cost_basis
+    }
+}
+
+/// Finds open positions where buying the other side at the current asks locks in
+/// profit or caps the loss - arbitrage with the inventory already held
+pub struct HedgePlanner {
+    client: PolymarketClient,
+    costs: TradingCosts,
+}
+
+impl HedgePlanner {
+    pub fn new(client: PolymarketClient, costs: TradingCosts) -> Self {
+        Self { client, costs }
+    }
+
+    /// Checks every open position in a binary market still trading
+    /// `current_markets` should cover the condition IDs of `positions`; most profitable first
+    pub async fn plan(&self, positions: &[Position], current_markets: &[Market]) -> Vec<HedgeSuggestion> {
+        let markets: HashMap<&str, &Market> = current_markets
+            .iter()
+            .filter(|m| m.closed != Some(true))
+            .filter_map(|m| Some((m.condition_id.as_deref()?, m)))
+            .collect();
+
+        let candidates: Vec<(&Position, &Market, String)> = positions
+            .iter()
+            .filter(|p| p.net_shares > 0.0 && p.outcome_index < 2)
+            .filter_map(|p| {
+                let market = *markets.get(p.condition_id.as_str())?;
+                let token_ids = market.parsed_clob_token_ids().filter(|ids| ids.len() == 2)?;
+                Some((p, market, token_ids[1 - p.outcome_index].clone()))
+            })
+            .collect();
+
+        let mut suggestions: Vec<HedgeSuggestion> = stream::iter(candidates)
+            .map(|(position, market, token_id)| async move {
+                match self.client.fetch_order_book(&token_id).await {
+                    Ok(book) => self.suggest(position, market, &book),
+                    Err(e) => {
+                        eoutln!("Warning: Failed to fetch order book for {}: {}", market.question, e);
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_BOOKS)
+            .filter_map(|suggestion| async move { suggestion })
+            .collect()
+            .await;
+
+        suggestions.sort_by(|a, b| match (a.kind, b.kind) {
+            (HedgeKind::LockProfit, HedgeKind::CapLoss) => std::cmp::Ordering::Less,
+            (HedgeKind::CapLoss, HedgeKind::LockProfit) => std::cmp::Ordering::Greater,
+            (HedgeKind::LockProfit, HedgeKind::LockProfit) => b.locked_pnl().total_cmp(&a.locked_pnl()),
+            (HedgeKind::CapLoss, HedgeKind::CapLoss) => b.loss_reduction().total_cmp(&a.loss_reduction()),
+        });
+        suggestions
+    }
+
+    /// Prices buying the opposite outcome against its order book
+    /// Returns None when that neither locks in profit nor helps an underwater position
+    pub fn suggest(&self, position: &Position, market: &Market, opposite_book: &OrderBook) -> Option<HedgeSuggestion> {
+        let depth: f64 = opposite_book.asks.iter().map(|level| level.size).sum();
+        let hedge_shares = position.net_shares.min(depth);
+        if hedge_shares <= 0.0 {
+            return None;
+        }
+
+        let fees = FeeSchedule::from_market(market);
+        let hedge_price = opposite_book.average_ask_price(hedge_shares)?;
+        let hedge_cost = hedge_shares * (hedge_price + fees.taker_fee_per_share(hedge_price))
+            + self.costs.fixed_cost_per_trade;
+
+        // Whichever outcome wins pays $1 per share
+        let cost_basis = position.total_invested;
+        let current_price = market
+            .parsed_outcome_prices()
+            .and_then(|prices| prices.get(position.outcome_index).copied());
+        let outcomes = market.parsed_outcomes().unwrap_or_default();
+
+        let mut suggestion = HedgeSuggestion {
+            kind: HedgeKind::LockProfit,
+            condition_id: position.condition_id.clone(),
+            market_title: market.question.clone(),
+            outcome: position.outcome.clone(),
+            held_shares: position.net_shares,
+            avg_price: position.avg_price,
+            cost_basis,
+            current_price,
+            hedge_outcome: outcomes.get(1 - position.outcome_index).cloned().unwrap_or_default(),
+            hedge_shares,
+            hedge_price,
+            hedge_cost,
+            pnl_if_held_wins: position.net_shares - cost_basis - hedge_cost,
+            pnl_if_hedge_wins: hedge_shares - cost_basis - hedge_cost,
+        };
+
+        if suggestion.locked_pnl() >= 0.0 {
+            return Some(suggestion);
+        }
+
+        // Only worth capping a loss the market is already pricing in
+        let underwater = current_price.is_some_and(|price| price < position.avg_price);
+        if underwater && suggestion.loss_reduction() > 0.0 {
+            suggestion.kind = HedgeKind::CapLoss;
+            return Some(suggestion);
+        }
+
+        None
+    }
+}
+
+/// Prints each suggestion with the P&L under either outcome once hedged
+pub fn print_suggestions(wallet: &str, positions: usize, suggestions: &[HedgeSuggestion]) {
+    outln!("\n{}", "=".repeat(80));
+    outln!("HEDGING SUGGESTIONS");
+    outln!("{}", "=".repeat(80));
+    outln!("\nWallet:               {}", wallet);
+    outln!("Open Positions:       {}", positions);
+
+    let locks: Vec<&HedgeSuggestion> = suggestions.iter().filter(|s| s.kind == HedgeKind::LockProfit).collect();
+    let caps = suggestions.len() - locks.len();
+    outln!("Lock Profit:          {} (${:.2} locked in total)", locks.len(), locks.iter().map(|s| s.locked_pnl()).sum::<f64>());
+    outln!("Cap Loss:             {}", caps);
+
+    if suggestions.is_empty() {
+        outln!("\nNo open position can be hedged at a profit or usefully capped at current prices.");
+    }
+
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        outln!("\n{}. [{}] {}", i + 1, suggestion.kind.label(), suggestion.market_title);
+        outln!(
+            "   Holding {:.1} {} at ${:.3} (cost ${:.2}){}",
+            suggestion.held_shares,
+            suggestion.outcome,
+            suggestion.avg_price,
+            suggestion.cost_basis,
+            suggestion
+                .current_price
+                .map(|p| format!(", now ${:.3}", p))
+                .unwrap_or_default()
+        );
+        outln!(
+            "   Buy {:.1} {} at ${:.3} avg (${:.2} with fees){}",
+            suggestion.hedge_shares,
+            suggestion.hedge_outcome,
+            suggestion.hedge_price,
+            suggestion.hedge_cost,
+            if suggestion.hedge_shares < suggestion.held_shares { "  (all the book can fill)" } else { "" }
+        );
+        outln!(
+            "   P&L if {} wins: ${:.2} | if {} wins: ${:.2}",
+            suggestion.outcome, suggestion.pnl_if_held_wins, suggestion.hedge_outcome, suggestion.pnl_if_hedge_wins
+        );
+        match suggestion.kind {
+            HedgeKind::LockProfit => outln!("   Locks in at least ${:.2}", suggestion.locked_pnl()),
+            HedgeKind::CapLoss => outln!(
+                "   Caps the loss at ${:.2} (vs ${:.2} unhedged)",
+                -suggestion.locked_pnl(),
+                suggestion.cost_basis
+            ),
+        }
+    }
+
+    outln!("\n{}", "=".repeat(80));
+}
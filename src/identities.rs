@@ -0,0 +1,192 @@
+use crate::knowledge::WalletKnowledgeBase;
+use crate::models::Trade;
+use crate::store::Store;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+
+/// A username/pseudonym pair a wallet was seen trading under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityRecord {
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+impl IdentityRecord {
+    fn same_identity(&self, name: &Option<String>, pseudonym: &Option<String>) -> bool {
+        &self.name == name && &self.pseudonym == pseudonym
+    }
+
+    /// "name (pseudonym)", or whichever of the two is set
+    pub fn label(&self) -> String {
+        match (&self.name, &self.pseudonym) {
+            (Some(name), Some(pseudonym)) if name != pseudonym => format!("{} ({})", name, pseudonym),
+            (Some(name), _) => name.clone(),
+            (None, Some(pseudonym)) => pseudonym.clone(),
+            (None, None) => "(no name)".to_string(),
+        }
+    }
+}
+
+/// Every identity a wallet has been seen with, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletIdentity {
+    pub history: Vec<IdentityRecord>,
+    /// When a scan first flagged the wallet as suspicious
+    pub flagged_at: Option<i64>,
+}
+
+impl WalletIdentity {
+    pub fn current(&self) -> Option<&IdentityRecord> {
+        self.history.last()
+    }
+
+    /// Times the wallet switched identity
+    pub fn changes(&self) -> usize {
+        self.history.len().saturating_sub(1)
+    }
+
+    /// Identity switches observed after the wallet was first flagged
+    pub fn changes_since_flagged(&self) -> usize {
+        let Some(flagged_at) = self.flagged_at else {
+            return 0;
+        };
+        self.history.iter().skip(1).filter(|r| r.first_seen > flagged_at).count()
+    }
+}
+
+/// A wallet seen under a different identity than last time
+#[derive(Debug, Clone)]
+pub struct IdentityChange {
+    pub wallet: String,
+    pub previous: IdentityRecord,
+    pub current: IdentityRecord,
+}
+
+/// Display names observed for each scanned wallet, persisted across scans (in the
+/// wallet knowledge base) so renames, especially after being flagged, can be followed over time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdentityHistory {
+    pub wallets: BTreeMap<String, WalletIdentity>,
+}
+
+impl IdentityHistory {
+    /// Loads the saved history, or starts an empty one
+    pub fn load(store: &Store) -> Result<Self> {
+        Ok(WalletKnowledgeBase::load(store)?.identities())
+    }
+
+    /// Saves every wallet's history to the knowledge base, leaving other wallet knowledge alone
+    pub fn save(&self, store: &Store) -> Result<()> {
+        WalletKnowledgeBase::update(store, |knowledge| knowledge.set_identities(self))
+    }
+
+    pub fn get(&self, wallet: &str) -> Option<&WalletIdentity> {
+        self.wallets.get(&wallet.to_lowercase())
+    }
+
+    /// Records the identity on a wallet's most recent trade, marking the wallet if it
+    /// was flagged. Returns the change if the identity differs from the last one seen
+    pub fn observe(&mut self, wallet: &str, trades: &[Trade], flagged: bool) -> Option<IdentityChange> {
+        let now = Utc::now().timestamp();
+        let entry = self.wallets.entry(wallet.to_lowercase()).or_default();
+        if flagged && entry.flagged_at.is_none() {
+            entry.flagged_at = Some(now);
+        }
+
+        let latest = trades
+            .iter()
+            .filter(|t| non_empty(&t.name).is_some() || non_empty(&t.pseudonym).is_some())
+            .max_by_key(|t| t.timestamp)?;
+        let (name, pseudonym) = (non_empty(&latest.name), non_empty(&latest.pseudonym));
+
+        match entry.history.last_mut() {
+            Some(last) if last.same_identity(&name, &pseudonym) => {
+                last.last_seen = now;
+                None
+            }
+            last => {
+                let previous = last.cloned();
+                let current = IdentityRecord { name, pseudonym, first_seen: now, last_seen: now };
+                entry.history.push(current.clone());
+                previous.map(|previous| IdentityChange { wallet: wallet.to_string(), previous, current })
+            }
+        }
+    }
+
+    /// Prints wallets that changed identity, most changes first, or one wallet's full history
+    pub fn print_report(&self, wallet: Option<&str>) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WALLET IDENTITY HISTORY");
+        outln!("{}", "=".repeat(80));
+
+        if let Some(wallet) = wallet {
+            match self.get(wallet) {
+                Some(identity) => print_history(wallet, identity),
+                None => outln!("\n{} hasn't been seen by a scan yet.", wallet),
+            }
+            outln!("\n{}", "=".repeat(80));
+            return;
+        }
+
+        let mut changed: Vec<(&String, &WalletIdentity)> =
+            self.wallets.iter().filter(|(_, identity)| identity.changes() > 0).collect();
+        changed.sort_by(|a, b| {
+            b.1.changes_since_flagged()
+                .cmp(&a.1.changes_since_flagged())
+                .then(b.1.changes().cmp(&a.1.changes()))
+        });
+
+        outln!("\nWallets Tracked:      {}", self.wallets.len());
+        outln!("Changed Identity:     {}", changed.len());
+        outln!(
+            "Renamed After Flag:   {}",
+            changed.iter().filter(|(_, identity)| identity.changes_since_flagged() > 0).count()
+        );
+
+        for (wallet, identity) in changed {
+            outln!(
+                "\n{} - {} change(s), {} since flagged",
+                wallet,
+                identity.changes(),
+                identity.changes_since_flagged()
+            );
+            let names: Vec<String> = identity.history.iter().map(|r| r.label()).collect();
+            outln!("   {}", names.join(" → "));
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Prints every identity a wallet has used, with when each was seen
+fn print_history(wallet: &str, identity: &WalletIdentity) {
+    outln!("\nWallet:               {}", wallet);
+    if let Some(flagged_at) = identity.flagged_at {
+        outln!("First Flagged:        {}", format_time(flagged_at));
+    }
+    outln!("Changes:              {} ({} since flagged)", identity.changes(), identity.changes_since_flagged());
+
+    for record in &identity.history {
+        outln!(
+            "   {} to {}  {}",
+            format_time(record.first_seen),
+            format_time(record.last_seen),
+            record.label()
+        );
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
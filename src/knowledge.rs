@@ -0,0 +1,614 @@
+use crate::identities::{IdentityHistory, WalletIdentity};
+use crate::models::{SuspicionReport, WalletPerformance};
+use crate::signals::SignalLog;
+use crate::store::Store;
+use crate::watchlist::{Watchlist, WatchlistEntry};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// File in the store holding the wallet knowledge base
+const KNOWLEDGE_FILE: &str = "wallet_kb.json";
+/// Files the knowledge base replaced, imported the first time it's loaded
+const LEGACY_WATCHLIST_FILE: &str = "watchlist.json";
+const LEGACY_IDENTITIES_FILE: &str = "wallet_identities.json";
+
+/// A dated free-text note about a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletNote {
+    pub at: i64,
+    pub text: String,
+}
+
+/// Watchlist membership: alert on trades in these categories (empty = all)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchSettings {
+    pub added_at: i64,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+/// Why and when a wallet was blacklisted; blacklisted wallets are skipped by insider
+/// scans and by `watch`/`copy` unless named on the command line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlacklistEntry {
+    pub at: i64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Headline figures from a wallet's most recent analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSummary {
+    pub scanned_at: i64,
+    pub username: Option<String>,
+    pub total_trades: usize,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate: f64,
+    pub roi: f64,
+    pub total_invested: f64,
+    pub net_profit: f64,
+    pub volume: f64,
+    /// Insider score, None when the wallet didn't meet the scan criteria and wasn't scored
+    pub score: Option<f64>,
+    pub flagged: bool,
+    pub likely_bot: bool,
+    #[serde(default)]
+    pub likely_market_maker: bool,
+}
+
+impl ScanSummary {
+    pub fn new(username: Option<&str>, performance: &WalletPerformance, report: Option<&SuspicionReport>) -> Self {
+        Self {
+            scanned_at: Utc::now().timestamp(),
+            username: username.map(str::to_string),
+            total_trades: performance.total_trades,
+            resolved_positions: performance.resolved_positions,
+            wins: performance.wins,
+            losses: performance.losses,
+            win_rate: performance.win_rate,
+            roi: performance.roi,
+            total_invested: performance.total_invested,
+            net_profit: performance.net_profit,
+            volume: performance.volume,
+            score: report.map(|r| r.score),
+            flagged: report.is_some_and(|r| r.flagged),
+            likely_bot: performance.cadence.as_ref().is_some_and(|c| c.likely_bot),
+            likely_market_maker: performance.market_making.as_ref().is_some_and(|m| m.likely_market_maker),
+        }
+    }
+}
+
+/// Everything known about one wallet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletRecord {
+    pub address: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Vec<WalletNote>,
+    #[serde(default)]
+    pub watch: Option<WatchSettings>,
+    #[serde(default)]
+    pub blacklist: Option<BlacklistEntry>,
+    /// Display names seen by scans, and when the wallet was first flagged
+    #[serde(default)]
+    pub identity: WalletIdentity,
+    #[serde(default)]
+    pub scan: Option<ScanSummary>,
+}
+
+impl WalletRecord {
+    /// Label, else the latest scanned username, else the address
+    pub fn display_name(&self) -> &str {
+        self.label
+            .as_deref()
+            .or_else(|| self.scan.as_ref().and_then(|s| s.username.as_deref()))
+            .unwrap_or(&self.address)
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.identity.flagged_at.is_some() || self.scan.as_ref().is_some_and(|s| s.flagged)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.label.is_none()
+            && self.tags.is_empty()
+            && self.notes.is_empty()
+            && self.watch.is_none()
+            && self.blacklist.is_none()
+            && self.identity.history.is_empty()
+            && self.identity.flagged_at.is_none()
+            && self.scan.is_none()
+    }
+}
+
+/// One local store of everything known about wallets: watchlist membership,
+/// blacklisting, labels, tags, notes, display-name history and latest scan results
+/// The watchlist and identity history read and write through it, and each writer
+/// only replaces its own fields (see [`WalletKnowledgeBase::update`])
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletKnowledgeBase {
+    /// Records keyed by lowercase address
+    pub wallets: BTreeMap<String, WalletRecord>,
+}
+
+impl WalletKnowledgeBase {
+    /// Loads the knowledge base, importing the legacy watchlist, identity history
+    /// and flagged wallets the first time
+    pub fn load(store: &Store) -> Result<Self> {
+        if let Some(knowledge) = store.load_json(KNOWLEDGE_FILE)? {
+            return Ok(knowledge);
+        }
+
+        let knowledge = Self::import_legacy(store)?;
+        if !knowledge.wallets.is_empty() {
+            knowledge.save(store)?;
+        }
+        Ok(knowledge)
+    }
+
+    pub fn save(&self, store: &Store) -> Result<()> {
+        store.save_json(KNOWLEDGE_FILE, self)
+    }
+
+    /// Reloads the knowledge base, applies `change` and saves it straight away, so
+    /// processes sharing the data directory don't overwrite each other's fields
+    pub fn update<T>(store: &Store, change: impl FnOnce(&mut Self) -> T) -> Result<T> {
+        let mut knowledge = Self::load(store)?;
+        let result = change(&mut knowledge);
+        knowledge.wallets.retain(|_, record| !record.is_empty());
+        knowledge.save(store)?;
+        Ok(result)
+    }
+
+    fn import_legacy(store: &Store) -> Result<Self> {
+        let mut knowledge = Self::default();
+
+        if let Some(watchlist) = store.load_json::<Watchlist>(LEGACY_WATCHLIST_FILE)? {
+            knowledge.set_watchlist(&watchlist);
+        }
+        if let Some(history) = store.load_json::<IdentityHistory>(LEGACY_IDENTITIES_FILE)? {
+            knowledge.set_identities(&history);
+        }
+
+        // Flagged wallets used to be read back from insider-entry signals
+        let now = Utc::now().timestamp();
+        for wallet in SignalLog::new(store.clone()).flagged_wallets()? {
+            let record = knowledge.record_mut(&wallet);
+            record.identity.flagged_at.get_or_insert(now);
+        }
+
+        Ok(knowledge)
+    }
+
+    pub fn get(&self, wallet: &str) -> Option<&WalletRecord> {
+        self.wallets.get(&wallet.to_lowercase())
+    }
+
+    /// The wallet's record, created if it doesn't exist yet
+    pub fn record_mut(&mut self, wallet: &str) -> &mut WalletRecord {
+        self.wallets
+            .entry(wallet.to_lowercase())
+            .or_insert_with(|| WalletRecord { address: wallet.to_string(), ..Default::default() })
+    }
+
+    /// Watched wallets as a watchlist, in the order they were added
+    pub fn watchlist(&self) -> Watchlist {
+        let mut watched: Vec<(&WalletRecord, &WatchSettings)> = self
+            .wallets
+            .values()
+            .filter_map(|record| record.watch.as_ref().map(|watch| (record, watch)))
+            .collect();
+        watched.sort_by_key(|(_, watch)| watch.added_at);
+
+        Watchlist {
+            entries: watched
+                .into_iter()
+                .map(|(record, watch)| WatchlistEntry {
+                    address: record.address.clone(),
+                    label: record.label.clone(),
+                    categories: watch.categories.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Makes the watchlist's wallets the watched ones, keeping each one's place in line;
+    /// labels set on entries are kept, and unwatched wallets keep their other knowledge
+    pub fn set_watchlist(&mut self, watchlist: &Watchlist) {
+        let now = Utc::now().timestamp();
+        let watched: HashSet<String> = watchlist.entries.iter().map(|e| e.address.to_lowercase()).collect();
+        for record in self.wallets.values_mut() {
+            if !watched.contains(&record.address.to_lowercase()) {
+                record.watch = None;
+            }
+        }
+
+        for (position, entry) in watchlist.entries.iter().enumerate() {
+            let record = self.record_mut(&entry.address);
+            if entry.label.is_some() {
+                record.label = entry.label.clone();
+            }
+            let added_at = record.watch.as_ref().map(|w| w.added_at).unwrap_or(now + position as i64);
+            record.watch = Some(WatchSettings { added_at, categories: entry.categories.clone() });
+        }
+    }
+
+    /// Display-name histories of every wallet seen by a scan
+    pub fn identities(&self) -> IdentityHistory {
+        IdentityHistory {
+            wallets: self
+                .wallets
+                .iter()
+                .filter(|(_, record)| !record.identity.history.is_empty() || record.identity.flagged_at.is_some())
+                .map(|(wallet, record)| (wallet.clone(), record.identity.clone()))
+                .collect(),
+        }
+    }
+
+    /// Replaces the identity of every wallet in the history
+    pub fn set_identities(&mut self, history: &IdentityHistory) {
+        for (wallet, identity) in &history.wallets {
+            self.record_mut(wallet).identity = identity.clone();
+        }
+    }
+
+    /// Stores the latest analysis of a wallet, marking it flagged if its report was
+    pub fn record_scan(&mut self, wallet: &str, summary: ScanSummary) {
+        let record = self.record_mut(wallet);
+        if summary.flagged {
+            record.identity.flagged_at.get_or_insert(summary.scanned_at);
+        }
+        record.scan = Some(summary);
+    }
+
+    pub fn is_blacklisted(&self, wallet: &str) -> bool {
+        self.get(wallet).is_some_and(|record| record.blacklist.is_some())
+    }
+
+    /// Lowercase addresses of blacklisted wallets
+    pub fn blacklisted(&self) -> HashSet<String> {
+        self.wallets
+            .iter()
+            .filter(|(_, record)| record.blacklist.is_some())
+            .map(|(wallet, _)| wallet.clone())
+            .collect()
+    }
+
+    /// Wallets flagged by insider scans, in the order they were first flagged
+    pub fn flagged_wallets(&self) -> Vec<String> {
+        let mut flagged: Vec<(&WalletRecord, i64)> = self
+            .wallets
+            .values()
+            .filter(|record| record.is_flagged())
+            .map(|record| {
+                let at = record.identity.flagged_at.or(record.scan.as_ref().map(|s| s.scanned_at)).unwrap_or(0);
+                (record, at)
+            })
+            .collect();
+        flagged.sort_by_key(|(_, at)| *at);
+        flagged.into_iter().map(|(record, _)| record.address.clone()).collect()
+    }
+
+    /// Records matching every term, highest insider score first
+    pub fn search(&self, query: &WalletQuery) -> Vec<&WalletRecord> {
+        let mut matches: Vec<&WalletRecord> = self.wallets.values().filter(|r| query.matches(r)).collect();
+        let score = |record: &WalletRecord| record.scan.as_ref().and_then(|s| s.score).unwrap_or(-1.0);
+        matches.sort_by(|a, b| score(b).total_cmp(&score(a)));
+        matches
+    }
+
+    /// Prints one table row per record
+    pub fn print_search(&self, query: &WalletQuery, records: &[&WalletRecord]) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WALLET KNOWLEDGE BASE: {}", if query.terms.is_empty() { "all wallets".to_string() } else { query.describe() });
+        outln!("{}", "=".repeat(80));
+
+        if records.is_empty() {
+            outln!("\nNo wallets match.");
+            return;
+        }
+
+        outln!(
+            "\n{:<42}  {:>5}  {:>8}  {:>6}  {:>5}  {:<12}  Name",
+            "Wallet", "Score", "ROI", "Win%", "Pos", "Status"
+        );
+        for record in records {
+            let number = |value: Option<f64>, suffix: &str| {
+                value.map(|v| format!("{:.0}{}", v, suffix)).unwrap_or_else(|| "-".to_string())
+            };
+            let scan = record.scan.as_ref();
+            outln!(
+                "{:<42}  {:>5}  {:>8}  {:>6}  {:>5}  {:<12}  {}",
+                record.address,
+                number(scan.and_then(|s| s.score), ""),
+                number(scan.map(|s| s.roi), "%"),
+                number(scan.map(|s| s.win_rate), "%"),
+                scan.map(|s| s.resolved_positions.to_string()).unwrap_or_else(|| "-".to_string()),
+                status_tags(record),
+                if record.display_name() == record.address { "" } else { record.display_name() }
+            );
+        }
+        outln!("\n{} wallet(s)", records.len());
+    }
+
+    /// Prints everything known about one wallet
+    pub fn print_record(&self, wallet: &str) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WALLET KNOWLEDGE BASE");
+        outln!("{}", "=".repeat(80));
+
+        let Some(record) = self.get(wallet) else {
+            outln!("\nNothing known about {} yet.", wallet);
+            return;
+        };
+
+        outln!("\nWallet:               {}", record.address);
+        if let Some(label) = &record.label {
+            outln!("Label:                {}", label);
+        }
+        if !record.tags.is_empty() {
+            outln!("Tags:                 {}", record.tags.join(", "));
+        }
+        match &record.watch {
+            Some(watch) if watch.categories.is_empty() => outln!("Watched:              yes (all categories)"),
+            Some(watch) => outln!("Watched:              yes ({})", watch.categories.join(", ")),
+            None => outln!("Watched:              no"),
+        }
+        if let Some(blacklist) = &record.blacklist {
+            outln!(
+                "Blacklisted:          {}{}",
+                format_time(blacklist.at),
+                blacklist.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default()
+            );
+        }
+        if let Some(flagged_at) = record.identity.flagged_at {
+            outln!("First Flagged:        {}", format_time(flagged_at));
+        }
+        if !record.identity.history.is_empty() {
+            let names: Vec<String> = record.identity.history.iter().map(|r| r.label()).collect();
+            outln!("Names Seen:           {}", names.join(" → "));
+        }
+
+        if let Some(scan) = &record.scan {
+            outln!("\n--- Latest Scan ({}) ---", format_time(scan.scanned_at));
+            if let Some(username) = &scan.username {
+                outln!("Username:             {}", username);
+            }
+            outln!("Total Trades:         {}", scan.total_trades);
+            outln!("Resolved Positions:   {} ({} wins / {} losses)", scan.resolved_positions, scan.wins, scan.losses);
+            outln!("Win Rate:             {:.1}%", scan.win_rate);
+            outln!("ROI:                  {:.1}%", scan.roi);
+            outln!("Total Invested:       ${:.2}", scan.total_invested);
+            outln!("Net Profit:           ${:.2}", scan.net_profit);
+            outln!("Trading Volume:       ${:.2}", scan.volume);
+            match scan.score {
+                Some(score) => outln!("Insider Score:        {:.0}/100{}", score, if scan.flagged { " (flagged)" } else { "" }),
+                None => outln!("Insider Score:        not scored (below scan criteria)"),
+            }
+            if scan.likely_bot {
+                outln!("Trade Cadence:        likely bot");
+            }
+            if scan.likely_market_maker {
+                outln!("Market Making:        likely market maker");
+            }
+        }
+
+        if !record.notes.is_empty() {
+            outln!("\n--- Notes ---");
+            for note in &record.notes {
+                outln!("{}  {}", format_time(note.at), note.text);
+            }
+        }
+    }
+}
+
+/// Comparison in a numeric search term
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds(self, value: f64, target: f64) -> bool {
+        match self {
+            Comparison::Greater => value > target,
+            Comparison::GreaterOrEqual => value >= target,
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+            Comparison::Equal => (value - target).abs() < 1e-9,
+            Comparison::NotEqual => (value - target).abs() >= 1e-9,
+        }
+    }
+}
+
+/// One condition of a search
+#[derive(Debug, Clone)]
+enum SearchTerm {
+    /// A latest-scan figure compared with a number, e.g. `roi>50`
+    Number { field: String, comparison: Comparison, value: f64 },
+    /// Case-insensitive substring of a text field, e.g. `label=whale`
+    Text { field: String, needle: String },
+    /// A status keyword (flagged, watched, blacklisted, bot, scanned), optionally negated with `!`
+    Status { status: String, negated: bool },
+}
+
+/// Search over the knowledge base: every term must match
+/// Terms are `<field><op><number>` with fields score, roi, win_rate, profit, invested,
+/// volume, positions, wins, losses, trades and ops `>`, `>=`, `<`, `<=`, `=`, `!=`;
+/// `label=`, `tag=`, `note=` or `name=` followed by text; or a status keyword
+#[derive(Debug, Clone, Default)]
+pub struct WalletQuery {
+    terms: Vec<(String, SearchTerm)>,
+}
+
+const NUMBER_FIELDS: &[&str] =
+    &["score", "roi", "win_rate", "profit", "invested", "volume", "positions", "wins", "losses", "trades"];
+const TEXT_FIELDS: &[&str] = &["label", "tag", "note", "name"];
+const STATUSES: &[&str] = &["flagged", "watched", "blacklisted", "bot", "mm", "scanned"];
+
+impl WalletQuery {
+    /// Parses whitespace-separated terms (several arguments are joined)
+    pub fn parse(terms: &[String]) -> Result<Self> {
+        let mut query = Self::default();
+        for term in terms.iter().flat_map(|t| t.split_whitespace()) {
+            query.terms.push((term.to_string(), parse_term(term)?));
+        }
+        Ok(query)
+    }
+
+    fn describe(&self) -> String {
+        self.terms.iter().map(|(text, _)| text.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn matches(&self, record: &WalletRecord) -> bool {
+        self.terms.iter().all(|(_, term)| term_matches(term, record))
+    }
+}
+
+fn parse_term(term: &str) -> Result<SearchTerm> {
+    let lower = term.to_lowercase();
+    let (negated, keyword) = match lower.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, lower.as_str()),
+    };
+    if STATUSES.contains(&keyword) {
+        return Ok(SearchTerm::Status { status: keyword.to_string(), negated });
+    }
+
+    // Two-character operators first, so ">=" isn't read as ">"
+    let operators = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        ("!=", Comparison::NotEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ];
+    let Some((index, operator, comparison)) = operators
+        .iter()
+        .filter_map(|(operator, comparison)| lower.find(operator).map(|index| (index, *operator, *comparison)))
+        .min_by_key(|(index, operator, _)| (*index, std::cmp::Reverse(operator.len())))
+    else {
+        bail!(
+            "Unrecognized search term '{}': expected <field><op><value> or one of {}",
+            term,
+            STATUSES.join(", ")
+        );
+    };
+
+    let field = lower[..index].replace('-', "_");
+    let value = &term[index + operator.len()..];
+    let field = if field == "winrate" { "win_rate".to_string() } else { field };
+
+    if TEXT_FIELDS.contains(&field.as_str()) {
+        if comparison != Comparison::Equal {
+            bail!("'{}' only supports '=' (substring match)", field);
+        }
+        return Ok(SearchTerm::Text { field, needle: value.to_lowercase() });
+    }
+    if !NUMBER_FIELDS.contains(&field.as_str()) {
+        bail!(
+            "Unknown field '{}' in '{}': expected one of {}, {}",
+            field,
+            term,
+            NUMBER_FIELDS.join(", "),
+            TEXT_FIELDS.join(", ")
+        );
+    }
+    let value: f64 = value
+        .trim_end_matches('%')
+        .trim_start_matches('$')
+        .parse()
+        .map_err(|_| anyhow::anyhow!("'{}' in '{}' is not a number", value, term))?;
+
+    Ok(SearchTerm::Number { field, comparison, value })
+}
+
+fn term_matches(term: &SearchTerm, record: &WalletRecord) -> bool {
+    match term {
+        SearchTerm::Status { status, negated } => {
+            let holds = match status.as_str() {
+                "flagged" => record.is_flagged(),
+                "watched" => record.watch.is_some(),
+                "blacklisted" => record.blacklist.is_some(),
+                "bot" => record.scan.as_ref().is_some_and(|s| s.likely_bot),
+                "mm" => record.scan.as_ref().is_some_and(|s| s.likely_market_maker),
+                _ => record.scan.is_some(),
+            };
+            holds != *negated
+        }
+        SearchTerm::Text { field, needle } => {
+            let contains = |text: &str| text.to_lowercase().contains(needle.as_str());
+            match field.as_str() {
+                "label" => record.label.as_deref().is_some_and(contains),
+                "tag" => record.tags.iter().any(|tag| contains(tag)),
+                "note" => record.notes.iter().any(|note| contains(&note.text)),
+                _ => {
+                    record.label.as_deref().is_some_and(contains)
+                        || record.scan.as_ref().and_then(|s| s.username.as_deref()).is_some_and(contains)
+                        || record.identity.history.iter().any(|r| contains(&r.label()))
+                }
+            }
+        }
+        SearchTerm::Number { field, comparison, value } => {
+            let Some(scan) = &record.scan else {
+                return false;
+            };
+            let actual = match field.as_str() {
+                "score" => match scan.score {
+                    Some(score) => score,
+                    None => return false,
+                },
+                "roi" => scan.roi,
+                "win_rate" => scan.win_rate,
+                "profit" => scan.net_profit,
+                "invested" => scan.total_invested,
+                "volume" => scan.volume,
+                "positions" => scan.resolved_positions as f64,
+                "wins" => scan.wins as f64,
+                "losses" => scan.losses as f64,
+                _ => scan.total_trades as f64,
+            };
+            comparison.holds(actual, *value)
+        }
+    }
+}
+
+/// Short status markers for the search table
+fn status_tags(record: &WalletRecord) -> String {
+    let mut tags = Vec::new();
+    if record.is_flagged() {
+        tags.push("FLAG");
+    }
+    if record.watch.is_some() {
+        tags.push("WATCH");
+    }
+    if record.blacklist.is_some() {
+        tags.push("BLOCK");
+    }
+    if record.scan.as_ref().is_some_and(|s| s.likely_bot) {
+        tags.push("BOT");
+    }
+    if record.scan.as_ref().is_some_and(|s| s.likely_market_maker) {
+        tags.push("MM");
+    }
+    tags.join(",")
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,236 @@
+use crate::models::{
+    ActivitySummary, ConcentrationFlag, FreshWalletWin, MarketMaking, OpenPosition, PeriodPerformance, ResolvedPosition, StakeSizing, SuspicionReport, TradeCadence, WalletPerformance,
+};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// A wallet's analytics in the shape of Polymarket's public data API: the top-level fields
+/// match a `/v1/leaderboard` entry, `positions` and `closedPositions` match the
+/// `/positions` and `/closed-positions` endpoints, and everything the API doesn't
+/// provide lives under `analytics`
+/// Amounts are in dollars and timestamps in unix seconds, as in the API
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    /// Position in the export, by insider score (None for a single-wallet export)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<usize>,
+    pub proxy_wallet: String,
+    pub user_name: Option<String>,
+    /// Dollar value of every buy and sell
+    pub vol: f64,
+    /// Resolved profit plus unrealized P&L of open positions
+    pub pnl: f64,
+    pub positions: Vec<PositionEntry>,
+    pub closed_positions: Vec<ClosedPositionEntry>,
+    pub analytics: WalletAnalytics,
+}
+
+/// An open position, with the fields of the `/positions` endpoint
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionEntry {
+    pub proxy_wallet: String,
+    pub condition_id: String,
+    pub title: String,
+    pub outcome: String,
+    pub outcome_index: usize,
+    pub size: f64,
+    pub avg_price: f64,
+    pub initial_value: f64,
+    pub current_value: Option<f64>,
+    pub cash_pnl: Option<f64>,
+    pub percent_pnl: Option<f64>,
+    pub cur_price: Option<f64>,
+}
+
+/// A position with a known outcome, with the fields of the `/closed-positions` endpoint
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosedPositionEntry {
+    pub proxy_wallet: String,
+    pub condition_id: String,
+    pub title: String,
+    pub outcome: String,
+    pub outcome_index: usize,
+    pub avg_price: f64,
+    /// Shares bought
+    pub total_bought: f64,
+    /// Profit over the life of the position, including the resolution payout
+    pub realized_pnl: f64,
+    /// Settled price of the outcome held (None when exited early or settled as a split)
+    pub cur_price: Option<f64>,
+    pub timestamp: Option<i64>,
+}
+
+/// Statistics this crate computes beyond what the API reports
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAnalytics {
+    pub total_trades: usize,
+    pub total_markets: usize,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate: f64,
+    pub expected_win_rate: Option<f64>,
+    pub win_rate_p_value: Option<f64>,
+    pub roi: f64,
+    pub total_invested: f64,
+    pub resolved_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub late_winning_buys: usize,
+    pub avg_hours_before_resolution: Option<f64>,
+    pub taker_ratio: Option<f64>,
+    pub trade_cadence: Option<TradeCadence>,
+    pub stake_sizing: Option<StakeSizing>,
+    pub first_trade_at: Option<i64>,
+    pub fresh_wallet_wins: Vec<FreshWalletWin>,
+    pub likely_bot: bool,
+    pub market_making: Option<MarketMaking>,
+    pub activity: Option<ActivitySummary>,
+    pub insider_score: f64,
+    pub flagged: bool,
+    pub concentration_flags: Vec<ConcentrationFlag>,
+    pub reasons: Vec<String>,
+    pub periods: Vec<PeriodEntry>,
+}
+
+/// One period of the wallet's performance breakdown
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodEntry {
+    pub period: String,
+    pub start: i64,
+    pub positions: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub invested: f64,
+    pub pnl: f64,
+    pub roi: f64,
+}
+
+impl LeaderboardEntry {
+    pub fn new(username: Option<&str>, performance: &WalletPerformance, report: &SuspicionReport) -> Self {
+        let wallet = &performance.wallet_address;
+        Self {
+            rank: None,
+            proxy_wallet: wallet.clone(),
+            user_name: username.map(str::to_string),
+            vol: performance.volume,
+            pnl: performance.net_profit + performance.unrealized_pnl,
+            positions: performance.open_positions.iter().map(|p| PositionEntry::new(wallet, p)).collect(),
+            closed_positions: performance.positions.iter().map(|p| ClosedPositionEntry::new(wallet, p)).collect(),
+            analytics: WalletAnalytics {
+                total_trades: performance.total_trades,
+                total_markets: performance.total_markets,
+                resolved_positions: performance.resolved_positions,
+                wins: performance.wins,
+                losses: performance.losses,
+                win_rate: performance.win_rate,
+                expected_win_rate: performance.expected_win_rate,
+                win_rate_p_value: report.win_rate_p_value,
+                roi: performance.roi,
+                total_invested: performance.total_invested,
+                resolved_pnl: performance.net_profit,
+                unrealized_pnl: performance.unrealized_pnl,
+                late_winning_buys: performance.late_winning_buys,
+                avg_hours_before_resolution: performance.avg_hours_before_resolution,
+                taker_ratio: performance.taker_ratio,
+                trade_cadence: performance.cadence.clone(),
+                stake_sizing: performance.stake_sizing.clone(),
+                first_trade_at: performance.first_trade_at,
+                fresh_wallet_wins: performance.fresh_wallet_wins.clone(),
+                likely_bot: report.likely_bot,
+                market_making: performance.market_making.clone(),
+                activity: performance.activity.clone(),
+                insider_score: report.score,
+                flagged: report.flagged,
+                concentration_flags: report.concentration_flags.clone(),
+                reasons: report.reasons.clone(),
+                periods: performance.periods.iter().map(PeriodEntry::from).collect(),
+            },
+        }
+    }
+
+    /// Entries for scan results (wallet, username, performance, score), ranked by insider score
+    pub fn ranked(wallets: &[(String, Option<String>, WalletPerformance, SuspicionReport)]) -> Vec<Self> {
+        let mut entries: Vec<Self> = wallets
+            .iter()
+            .map(|(wallet, username, performance, report)| {
+                let mut entry = Self::new(username.as_deref(), performance, report);
+                entry.proxy_wallet = wallet.clone();
+                entry
+            })
+            .collect();
+        entries.sort_by(|a, b| b.analytics.insider_score.total_cmp(&a.analytics.insider_score));
+        for (index, entry) in entries.iter_mut().enumerate() {
+            entry.rank = Some(index + 1);
+        }
+        entries
+    }
+}
+
+impl PositionEntry {
+    fn new(wallet: &str, position: &OpenPosition) -> Self {
+        Self {
+            proxy_wallet: wallet.to_string(),
+            condition_id: position.condition_id.clone(),
+            title: position.market_title.clone(),
+            outcome: position.outcome.clone(),
+            outcome_index: position.outcome_index,
+            size: position.net_shares,
+            avg_price: position.avg_price,
+            initial_value: position.cost_basis,
+            current_value: position.market_value,
+            cash_pnl: position.unrealized_pnl,
+            percent_pnl: position
+                .unrealized_pnl
+                .filter(|_| position.cost_basis > 0.0)
+                .map(|pnl| pnl / position.cost_basis * 100.0),
+            cur_price: position.current_price,
+        }
+    }
+}
+
+impl ClosedPositionEntry {
+    fn new(wallet: &str, position: &ResolvedPosition) -> Self {
+        let cur_price = position
+            .winning_outcome_index
+            .map(|winner| if winner == position.bet_outcome_index { 1.0 } else { 0.0 });
+        Self {
+            proxy_wallet: wallet.to_string(),
+            condition_id: position.condition_id.clone(),
+            title: position.market_title.clone(),
+            outcome: position.bet_outcome.clone(),
+            outcome_index: position.bet_outcome_index,
+            avg_price: position.avg_price,
+            total_bought: position.shares_bought,
+            realized_pnl: position.profit,
+            cur_price,
+            timestamp: position.resolved_at,
+        }
+    }
+}
+
+impl From<&PeriodPerformance> for PeriodEntry {
+    fn from(period: &PeriodPerformance) -> Self {
+        Self {
+            period: period.label.clone(),
+            start: period.start,
+            positions: period.positions,
+            wins: period.wins,
+            losses: period.losses,
+            invested: period.invested,
+            pnl: period.profit,
+            roi: period.roi,
+        }
+    }
+}
+
+/// Writes entries as pretty-printed JSON
+pub fn write_json<T: Serialize>(value: &T, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
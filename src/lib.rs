@@ -0,0 +1,87 @@
+//! Polymarket arbitrage and insider-trading analysis.
+//!
+//! The `prediction-market-scanner` binary is a thin CLI over this library; the
+//! same scanners can be embedded in other programs:
+//!
+//! ```no_run
+//! use prediction_market_scanner::{ArbitrageScanner, PolymarketClient};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let client = PolymarketClient::new();
+//! let markets = client.fetch_all_active_markets().await?;
+//! for opp in ArbitrageScanner::default().scan(&markets) {
+//!     println!("{}: {:.2}%", opp.question, opp.profit_percent);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+// Declared first so its console macros (outln!, eoutln!, ...) are in scope in every module below
+#[macro_use]
+pub mod output;
+
+// Declare modules (each module corresponds to a file in src/)
+pub mod address;
+pub mod alerts;
+pub mod backtest;
+pub mod calibration;
+pub mod client;
+pub mod clusters;
+pub mod cohort;
+pub mod commands;
+pub mod config;
+pub mod copy_trading;
+pub mod daemon;
+pub mod dashboard;
+pub mod dataset;
+pub mod digest;
+pub mod doctor;
+pub mod exchange;
+pub mod executor;
+pub mod export;
+pub mod failover;
+pub mod fees;
+pub mod guardrails;
+pub mod hedging;
+pub mod identities;
+pub mod knowledge;
+pub mod leaderboard;
+pub mod manifold;
+pub mod market_cache;
+pub mod matching;
+pub mod metrics;
+pub mod models;
+pub mod monitor;
+pub mod paper;
+pub mod polling;
+pub mod positions;
+pub mod priority;
+pub mod rate_budget;
+pub mod report;
+pub mod resolutions;
+pub mod rewards;
+pub mod scanner;
+pub mod schedule;
+pub mod shutdown;
+pub mod signals;
+pub mod snapshots;
+pub mod store;
+pub mod trade_history;
+pub mod wallet_analyzer;
+pub mod wallet_pipeline;
+pub mod wallet_queue;
+pub mod wallet_scanner;
+pub mod watchlist;
+
+// Re-export the main entry points
+pub use client::{PolymarketClient, PolymarketClientBuilder};
+pub use config::{Config, InsiderCriteria};
+pub use models::{
+    ArbitrageKind, ArbitrageOpportunity, Event, EventArbitrageOpportunity, EventRef,
+    LeaderboardRanking, LinkArbitrageOpportunity, LeaderboardWindow, Market, OracleStatus, PeriodPerformance, Position,
+    PriceInterval, PricePoint, PriceSeries, ResolvedPosition, StakeSizing, SuspicionReport, Trade,
+    TradeCadence, WalletActivity, WalletPerformance, WalletTimeline,
+};
+pub use scanner::ArbitrageScanner;
+pub use wallet_analyzer::{PositionLedger, WalletAnalyzer};
+pub use wallet_scanner::WalletScanner;
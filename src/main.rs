@@ -1,238 +1,378 @@
 use anyhow::Result;
-use chrono::Utc;
-use std::time::Duration;
-use tokio::time::Instant;
-
-// Declare modules (each module corresponds to a file in src/)
-mod client;
-mod models;
-mod scanner;
-mod wallet_analyzer;
-mod wallet_scanner;
-
-// Import items from our modules
-use client::PolymarketClient;
-use scanner::ArbitrageScanner;
-use wallet_analyzer::WalletAnalyzer;
-use wallet_scanner::WalletScanner;
-
-/// Run a single scan iteration
-async fn run_single_scan(
-    client: &PolymarketClient,
-    scanner: &ArbitrageScanner,
-) -> Result<usize> {
-    let total_start = Instant::now();
-
-    // Fetch all active markets with timing
-    let fetch_start = Instant::now();
-    let markets = client.fetch_all_active_markets().await?;
-    let fetch_duration = fetch_start.elapsed();
-
-    println!("✓ Fetched {} markets in {:.2}s (concurrent pagination)\n",
-        markets.len(),
-        fetch_duration.as_secs_f64()
-    );
-
-    // Scan for opportunities with timing
-    let scan_start = Instant::now();
-    let opportunities = scanner.scan(&markets);
-    let scan_duration = scan_start.elapsed();
-
-    println!("✓ Scanned markets in {:.3}s (parallel processing)\n",
-        scan_duration.as_secs_f64()
-    );
-
-    // Display results
-    if opportunities.is_empty() {
-        println!("No arbitrage opportunities found (threshold: total < $0.995)");
-        println!("\nThis is normal - efficient markets eliminate arbitrage quickly.");
-        println!("Run this periodically to catch fleeting opportunities.");
-    } else {
-        println!("Found {} arbitrage opportunities:\n", opportunities.len());
-        println!("{}", "=".repeat(80));
-
-        for (i, opp) in opportunities.iter().enumerate() {
-            opp.print(i + 1);
-        }
-    }
 
-    let total_elapsed = total_start.elapsed();
-    println!("\n[{}] Scan completed - Total: {:.2}s | Fetch: {:.2}s | Scan: {:.3}s",
-        Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
-        total_elapsed.as_secs_f64(),
-        fetch_duration.as_secs_f64(),
-        scan_duration.as_secs_f64()
-    );
+use prediction_market_scanner::address::validate_address;
+use prediction_market_scanner::commands::args::{flag_value, resolve_usernames, take_flag};
+use prediction_market_scanner::commands::{arbitrage, markets, research, tracking, wallets};
+use prediction_market_scanner::output::{self, Console};
+use prediction_market_scanner::{outln, Config};
 
-    Ok(opportunities.len())
-}
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Check for command-line arguments
+    let mut args: Vec<String> = std::env::args().collect();
 
-/// Analyzes a wallet's trading performance
-async fn analyze_wallet(wallet_address: &str) -> Result<()> {
-    println!("Polymarket Wallet Analyzer");
-    println!("==========================\n");
-    println!("Analyzing wallet: {}\n", wallet_address);
-
-    let client = PolymarketClient::new();
-    let analyzer = WalletAnalyzer::new();
-
-    // Fetch wallet trades
-    println!("📊 Fetching trade history...");
-    let fetch_start = Instant::now();
-    let trades = client.fetch_wallet_trades(wallet_address).await?;
-    let fetch_duration = fetch_start.elapsed();
-    println!("✓ Fetched {} trades in {:.2}s\n", trades.len(), fetch_duration.as_secs_f64());
-
-    if trades.is_empty() {
-        println!("No trades found for this wallet.");
-        return Ok(());
-    }
-
-    // Fetch resolved markets
-    println!("🔍 Fetching resolved markets...");
-    let markets_start = Instant::now();
-    let resolved_markets = client.fetch_resolved_markets().await?;
-    let markets_duration = markets_start.elapsed();
-    println!(
-        "✓ Fetched {} resolved markets in {:.2}s\n",
-        resolved_markets.len(),
-        markets_duration.as_secs_f64()
-    );
-
-    // Analyze performance
-    println!("📈 Analyzing performance...");
-    let analysis_start = Instant::now();
-    let performance = analyzer.analyze(&trades, &resolved_markets);
-    let analysis_duration = analysis_start.elapsed();
-    println!("✓ Analysis completed in {:.3}s", analysis_duration.as_secs_f64());
-
-    // Print results
-    analyzer.print_performance(&performance);
-
-    Ok(())
-}
+    // --plain may appear anywhere, so remove it before positional arguments are read
+    let plain = args.iter().any(|arg| arg == "--plain");
+    args.retain(|arg| arg != "--plain");
+    Console::global().set_plain(plain || output::plain_from_env());
 
-/// Auto-scan mode: Find and analyze active wallets for insider patterns
-async fn auto_scan_for_insiders(sample_size: usize, max_wallets: usize, continuous: bool) -> Result<()> {
-    println!("Polymarket Insider Scanner");
-    println!("==========================\n");
+    let mut config = Config::load()?;
 
-    if continuous {
-        println!("Running in CONTINUOUS mode - Press Ctrl+C to stop");
-        println!("Will keep scanning for profitable wallets and accumulate results...\n");
-    } else {
-        println!("Automatically finding and analyzing wallets for insider patterns...\n");
+    // Insider criteria may follow any command, so they're removed before positional arguments are read
+    if let Some(dollars) = take_flag(&mut args, "--min-invested", "a dollar amount")? {
+        config.insider.min_position_invested = dollars;
+    }
+    if let Some(positions) = take_flag(&mut args, "--min-positions", "a number of resolved positions")? {
+        config.insider.min_positions = positions;
+    }
+    if let Some(percent) = take_flag(&mut args, "--min-roi", "an ROI percentage")? {
+        config.insider.min_roi = percent;
+    }
+    if let Some(dollars) = take_flag(&mut args, "--min-profit", "a dollar amount")? {
+        config.insider.min_profit = dollars;
+    }
+    if let Some(percent) = take_flag(&mut args, "--min-winrate", "a win rate percentage")? {
+        config.insider.min_win_rate = percent;
+    }
+    if let Some(days) = take_flag(&mut args, "--resolved-within", "a number of days")? {
+        config.insider.resolved_within_days = days;
     }
 
-    let scanner = WalletScanner::new();
-
-    if continuous {
-        scanner.continuous_scan(sample_size, max_wallets).await?;
-    } else {
-        // Step 1: Find active wallets
-        let wallets = scanner.find_active_wallets(sample_size, max_wallets).await?;
-
-        if wallets.is_empty() {
-            println!("No active wallets found.");
-            return Ok(());
-        }
+    // Manifold usernames aren't Polymarket ones, so they're dispatched before @usernames are resolved
+    if args.len() > 1 && args[1] == "manifold" {
+        return arbitrage::manifold(&config, &args).await;
+    }
 
-        // Step 2: Analyze them for insider patterns
-        scanner.scan_for_insiders(&wallets).await?;
+    // Search terms aren't usernames either
+    if args.len() > 1 && args[1] == "search" {
+        return markets::search_markets(&config, &args).await;
     }
 
-    Ok(())
-}
+    // Wallets may be given as @username anywhere a wallet address is accepted
+    resolve_usernames(&config, &mut args).await?;
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Check for command-line arguments
-    let args: Vec<String> = std::env::args().collect();
+    // Check for whois subcommand
+    if args.len() > 1 && args[1] == "whois" {
+        let Some(wallet) = args.get(2) else {
+            outln!("Usage: cargo run -- whois <wallet_address|@username>");
+            return Ok(());
+        };
+        return wallets::whois(&config, wallet).await;
+    }
 
     // Check for --scan flag
     if args.len() > 1 && args[1] == "--scan" {
+        if let Some(discovery) = take_flag(&mut args, "--discovery", "a discovery strategy")? {
+            config.scan.discovery = discovery;
+        }
         let sample_size = if args.len() > 2 {
-            args[2].parse().unwrap_or(5000)
+            args[2].parse().unwrap_or(config.scan.sample_size)
         } else {
-            5000
+            config.scan.sample_size
         };
         let max_wallets = if args.len() > 3 {
-            args[3].parse().unwrap_or(30)
+            args[3].parse().unwrap_or(config.scan.max_wallets)
         } else {
-            30
+            config.scan.max_wallets
         };
         let continuous = args.len() > 4 && args[4] == "--continuous";
-        return auto_scan_for_insiders(sample_size, max_wallets, continuous).await;
+        let fresh = args.iter().any(|arg| arg == "--fresh");
+        let json_path = flag_value(&args, "--json");
+        let html_path = flag_value(&args, "--html");
+        return wallets::auto_scan_for_insiders(&config, sample_size, max_wallets, continuous, fresh, json_path, html_path)
+            .await;
     }
 
-    // If wallet address provided, run wallet analysis mode
-    if args.len() > 1 && args[1].starts_with("0x") {
-        let wallet_address = &args[1];
-        return analyze_wallet(wallet_address).await;
+    // Check for resolutions subcommand
+    if args.len() > 1 && args[1] == "resolutions" {
+        return match flag_value(&args, "--ids") {
+            Some(ids_path) => markets::check_resolutions(&config, ids_path, flag_value(&args, "--csv")).await,
+            None => {
+                outln!("Usage: cargo run -- resolutions --ids <file> [--csv <file>]");
+                Ok(())
+            }
+        };
     }
 
-    // Otherwise, run arbitrage scanner
-    println!("Polymarket Analysis Tools");
-    println!("=========================\n");
-    println!("Usage:");
-    println!("  cargo run -- --scan [sample_size] [max_wallets] [--continuous]");
-    println!("                                     - Auto-scan for profitable wallets");
-    println!("                                       (defaults: 5000 trades, 30 wallets)");
-    println!("                                       Add --continuous to run indefinitely");
-    println!("  cargo run -- <wallet_address>      - Analyze a specific wallet");
-    println!("  cargo run                          - Run arbitrage scanner\n");
-    println!("Running arbitrage scanner...\n");
-
-    // Create API client and scanner (reused across iterations)
-    let client = PolymarketClient::new();
-    let scanner = ArbitrageScanner::default();
-
-    // Setup shutdown signal handler
-    let (shutdown_tx, mut shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
-
-    // Spawn signal handler task
-    tokio::spawn(async move {
-        tokio::signal::ctrl_c().await.ok();
-        shutdown_tx.send(()).ok();
-    });
-
-    // Create 10-second polling interval
-    let mut interval = tokio::time::interval(Duration::from_secs(10));
-    let mut scan_count = 0u32;
-
-    loop {
-        tokio::select! {
-            _ = interval.tick() => {
-                scan_count += 1;
-                println!("[{}] Scan #{} starting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), scan_count);
-
-                // Run scan with error handling
-                match run_single_scan(&client, &scanner).await {
-                    Ok(opportunities_found) => {
-                        if opportunities_found > 0 {
-                            println!("\n[{}] Arbitrage opportunity found! Stopping scanner.",
-                                Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
-                            break;
-                        }
-                        // Otherwise continue to next iteration
-                    }
-                    Err(e) => {
-                        println!("[{}] ERROR (Scan #{}): {}",
-                            Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
-                            scan_count,
-                            e
-                        );
-                        println!("Retrying in 10 seconds...\n");
-                    }
-                }
-            }
-            _ = shutdown_rx.recv() => {
-                println!("\n[{}] Shutdown signal received, exiting...", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
-                println!("Goodbye!");
-                break;
-            }
+    // Check for signals subcommand
+    if args.len() > 1 && args[1] == "signals" {
+        let grade = args.get(2).map(|a| a.as_str()) != Some("report");
+        return tracking::signal_report(&config, grade).await;
+    }
+
+    // Check for watchlist subcommand
+    if args.len() > 1 && args[1] == "watchlist" {
+        return tracking::manage_watchlist(&config, &args).await;
+    }
+
+    // Check for durations subcommand
+    if args.len() > 1 && args[1] == "durations" {
+        return arbitrage::durations_report(&config, &args);
+    }
+
+    // Check for efficiency subcommand
+    if args.len() > 1 && args[1] == "efficiency" {
+        return arbitrage::efficiency_report(&config, &args);
+    }
+
+    // Check for snapshots subcommand
+    if args.len() > 1 && args[1] == "snapshots" {
+        return arbitrage::snapshot_report(&config, &args);
+    }
+
+    // Check for calendar subcommand
+    if args.len() > 1 && args[1] == "calendar" {
+        return markets::calendar_report(&config, &args).await;
+    }
+
+    // Check for identities subcommand
+    if args.len() > 1 && args[1] == "identities" {
+        return wallets::identities_report(&config, &args);
+    }
+
+    // Check for paper subcommand
+    if args.len() > 1 && args[1] == "paper" {
+        return arbitrage::paper_report(&config, &args);
+    }
+
+    // Check for history subcommand
+    if args.len() > 1 && args[1] == "history" {
+        let Some(condition_id) = args.get(2) else {
+            outln!("Usage: cargo run -- history <condition_id> [--interval 1h|6h|1d|1w|1m|max]");
+            return Ok(());
+        };
+        let interval = flag_value(&args, "--interval").unwrap_or("1w").parse()?;
+        return markets::price_history(&config, condition_id, interval).await;
+    }
+
+    // Check for rewards subcommand
+    if args.len() > 1 && args[1] == "rewards" {
+        return markets::scan_rewards(&config, &args).await;
+    }
+
+    // Check for backtest subcommand
+    if args.len() > 1 && args[1] == "backtest" {
+        return arbitrage::run_backtest(&config, &args).await;
+    }
+
+    // Check for fees subcommand
+    if args.len() > 1 && args[1] == "fees" {
+        let Some(condition_id) = args.get(2) else {
+            outln!("Usage: cargo run -- fees <condition_id>");
+            return Ok(());
+        };
+        return markets::fee_report(&config, condition_id).await;
+    }
+
+    // Check for executor subcommand
+    if args.len() > 1 && args[1] == "executor" {
+        return arbitrage::manage_executor(&config, &args).await;
+    }
+
+    // Check for dataset subcommand
+    if args.len() > 1 && args[1] == "dataset" {
+        return research::dataset_command(&config, &args).await;
+    }
+
+    // Check for calibration subcommand
+    if args.len() > 1 && args[1] == "calibration" {
+        return research::calibration_report(&config, &args).await;
+    }
+
+    // Check for doctor subcommand
+    if args.len() > 1 && args[1] == "doctor" {
+        return research::run_doctor(&config, &args).await;
+    }
+
+    // Check for cluster subcommand
+    if args.len() > 1 && args[1] == "cluster" {
+        return wallets::cluster_report(&config, &args).await;
+    }
+
+    // Check for compare subcommand
+    if args.len() > 1 && args[1] == "compare" {
+        return wallets::compare_wallets(&config, &args).await;
+    }
+
+    // Check for hedge subcommand
+    if args.len() > 1 && args[1] == "hedge" {
+        let Some(wallet) = args.get(2) else {
+            outln!("Usage: cargo run -- hedge <wallet_address>");
+            return Ok(());
+        };
+        return wallets::hedge_positions(&config, &validate_address(wallet)?).await;
+    }
+
+    // Check for positions subcommand
+    if args.len() > 1 && args[1] == "positions" {
+        let Some(wallet) = args.get(2) else {
+            outln!("Usage: cargo run -- positions <wallet_address> [--check]");
+            return Ok(());
+        };
+        return wallets::wallet_positions(&config, &validate_address(wallet)?, args.iter().any(|arg| arg == "--check")).await;
+    }
+
+    // Check for export subcommand
+    if args.len() > 1 && args[1] == "export" {
+        return wallets::export_trades(&config, &args).await;
+    }
+
+    // Check for copy subcommand
+    if args.len() > 1 && args[1] == "copy" {
+        return tracking::copy_signals(&config, &args).await;
+    }
+
+    // Check for leaderboard subcommand
+    if args.len() > 1 && args[1] == "leaderboard" {
+        if args.get(2).map(String::as_str) == Some("export") {
+            return wallets::export_leaderboard(&config, &args);
         }
+        return wallets::scan_leaderboard(&config, &args).await;
+    }
+
+    // Check for kb subcommand
+    if args.len() > 1 && args[1] == "kb" {
+        return tracking::manage_knowledge(&config, &args);
     }
 
-    Ok(())
+    // Check for dashboard subcommand
+    if args.len() > 1 && args[1] == "dashboard" {
+        return arbitrage::run_dashboard(&config, &args).await;
+    }
+
+    // Check for daemon subcommand
+    if args.len() > 1 && args[1] == "daemon" {
+        return arbitrage::run_daemon(&config, &args).await;
+    }
+
+    // Check for digest subcommand
+    if args.len() > 1 && args[1] == "digest" {
+        return tracking::digest_command(&config, &args).await;
+    }
+
+    // Check for monitor subcommand
+    if args.len() > 1 && args[1] == "monitor" {
+        return markets::monitor_market(&config, &args).await;
+    }
+
+    // Check for watch subcommand
+    if args.len() > 1 && args[1] == "watch" {
+        return tracking::watch_wallets(&config, &args).await;
+    }
+
+    // If wallet address provided, run wallet analysis mode
+    if args.len() > 1 && args[1].starts_with("0x") {
+        let wallet_address = &validate_address(&args[1])?;
+        return wallets::analyze_wallet(&config, wallet_address, &args).await;
+    }
+
+    // Otherwise, run arbitrage scanner
+    outln!("Polymarket Analysis Tools");
+    outln!("=========================\n");
+    outln!("Usage:");
+    outln!("  cargo run -- --scan [sample_size] [max_wallets] [--continuous] [--fresh] [--discovery <strategy>] [--json <file>] [--html <file>]");
+    outln!("                                     - Auto-scan for profitable wallets");
+    outln!("                                       (defaults: 5000 trades, 30 wallets, see scanner.toml)");
+    outln!("                                       Add --discovery to pick wallets by count, notional,");
+    outln!("                                       category:<name> or resolving:<days> (default count)");
+    outln!("                                       Add --continuous to run indefinitely (resumes after restarts;");
+    outln!("                                       --fresh discards the saved progress)");
+    outln!("                                       Add --json to export results as leaderboard JSON");
+    outln!("                                       Add --html for a shareable HTML report");
+    outln!("  cargo run -- <wallet_address> [--detailed] [--attribution] [--csv <file>] [--json <file>] [--html <file>] [--timeline <file>]");
+    outln!("                                     - Analyze a specific wallet");
+    outln!("                                       Add --detailed for a per-market breakdown");
+    outln!("                                       Add --attribution to split profit into drift vs skill and score entries against the closing line");
+    outln!("                                       Add --csv to export resolved positions");
+    outln!("                                       Add --json for Polymarket leaderboard-style JSON");
+    outln!("                                       Add --html for a shareable HTML report");
+    outln!("                                       Add --timeline to export trades against market prices as JSON");
+    outln!("  cargo run -- leaderboard [--window day|week|month|all] [--limit <n>] [--category <name>] [--analyze] [--json <file>] [--html <file>]");
+    outln!("                                     - Rank wallets by profit from Polymarket's leaderboard");
+    outln!("                                       Add --analyze to scan them for insider patterns (--json/--html to export)");
+    outln!("  cargo run -- leaderboard export [--out <file>]");
+    outln!("                                     - Export continuous-scan results as leaderboard JSON");
+    outln!("  cargo run -- resolutions --ids <file> [--csv <file>]");
+    outln!("                                     - Check resolution status of condition IDs");
+    outln!("  cargo run -- signals [grade|report]");
+    outln!("                                     - Grade recorded signals and show accuracy");
+    outln!("  cargo run -- identities [wallet_address]");
+    outln!("                                     - Show wallets seen under more than one display name");
+    outln!("  cargo run -- watchlist [list|add|remove|activity]");
+    outln!("                                     - Manage followed wallets and category filters");
+    outln!("  cargo run -- kb [show|search|label|tag|untag|note|blacklist|unblacklist]");
+    outln!("                                     - Query and annotate the wallet knowledge base");
+    outln!("  cargo run -- efficiency [--hours <n>]");
+    outln!("                                     - Show the market efficiency index over time");
+    outln!("  cargo run -- durations [--hours <n>]");
+    outln!("                                     - Show how long arbitrage opportunities stayed open, by category and spread");
+    outln!("  cargo run -- snapshots [--hours <n>] [--market <condition_id>] [--limit <n>]");
+    outln!("                                     - Audit captured order books against the prices opportunities were flagged at");
+    outln!("  cargo run -- calendar [--hours <n>]");
+    outln!("                                     - Show markets ending per hour and upcoming resolution bursts");
+    outln!("  cargo run -- digest [--hours <n>] [--send] [--daily]");
+    outln!("                                     - Summarize recorded opportunities, new insiders and watched-wallet activity");
+    outln!("                                       Add --send to push it to [alerts]; --daily sends one every day");
+    outln!("  cargo run -- paper [--days <n>]    - Show simulated execution P&L of arbitrage opportunities");
+    outln!("  cargo run -- history <condition_id> [--interval <1h|6h|1d|1w|1m|max>]");
+    outln!("                                     - Show a market's price history per outcome");
+    outln!("  cargo run -- calibration [--sample <n>] [--horizon <6h|2d>] [--csv <file>]");
+    outln!("                                     - Show how often markets priced at each level resolved YES");
+    outln!("  cargo run -- dataset <build|status> [--out <dir>] [--wallets <n>] [--rate <per minute>] [--fresh]");
+    outln!("                                     - Download a resumable offline research dataset");
+    outln!("  cargo run -- fees <condition_id>   - Show a market's fees and break-even spread by size");
+    outln!("  cargo run -- rewards [--size <dollars>] [--min-yield <percent>] [--limit <n>] [--include <categories>] [--exclude <categories>]");
+    outln!("                                     - Find markets whose liquidity rewards outweigh adverse selection");
+    outln!("  cargo run -- backtest [--markets <n>] [--days <n>] [--fidelity <minutes>] [--interval <secs>] [--threshold <total>] [--cached] [--json <file>]");
+    outln!("                                     - Replay price history through the arbitrage scanner");
+    outln!("                                       Add --cached to replay the last fetched history with other settings");
+    outln!("                                       (also takes --include/--exclude like the scanner)");
+    outln!("  cargo run -- executor [status|halt|resume] [--reason <text>]");
+    outln!("                                     - Show or toggle the executor kill switch");
+    outln!("  cargo run -- doctor                - Check API connectivity, schemas and clock skew");
+    outln!("  cargo run -- doctor --fields       - List API fields the models don't know, and records that no longer parse");
+    outln!("  cargo run -- cluster <wallet_address>... [--name <id>] [--csv <file>]");
+    outln!("                                     - Report a group of wallets' combined performance");
+    outln!("  cargo run -- compare <wallet_address>... [--csv <file>]");
+    outln!("                                     - Compare wallets side by side and rank them by consistency");
+    outln!("  cargo run -- hedge <wallet_address>");
+    outln!("                                     - Find open positions to lock in or cap by buying the other side");
+    outln!("  cargo run -- positions <wallet_address> [--check]");
+    outln!("                                     - List a wallet's open positions with mark prices and unrealized P&L");
+    outln!("                                       Add --check to compare them with the positions rebuilt from trades");
+    outln!("  cargo run -- export <wallet_address> [--out <file>] [--format csv|parquet]");
+    outln!("                                     - Export a wallet's full trade history with running P&L");
+    outln!("  cargo run -- watch [wallet_address...] [--flagged] [--replay-since <time>]");
+    outln!("                                     - Tail watched wallets and alert on new positions");
+    outln!("  cargo run -- copy [wallet_address...] [--flagged] [--jsonl]");
+    outln!("                                     - Turn tracked wallets' new trades into copy-trading signals");
+    outln!("  cargo run -- whois <wallet_address|@username>");
+    outln!("                                     - Show a wallet's Polymarket username, or a username's wallet");
+    outln!("  Wallets can be given as @username anywhere a wallet address is accepted");
+    outln!("  cargo run -- dashboard [--interval <secs>] [--include <categories>] [--exclude <categories>] [--resolving-within <48h>]");
+    outln!("                                     - Run the arbitrage scanner in a full-screen terminal dashboard");
+    outln!("  cargo run -- daemon [--bind <addr:port>] [--interval <secs>] [--adaptive] [--include <categories>] [--exclude <categories>]");
+    outln!("                                     - Scan in the background and serve results, wallet analysis and the");
+    outln!("                                       watchlist over an HTTP API (GET /opportunities, GET /health,");
+    outln!("                                       GET /wallets/<address>/performance, POST /watchlist)");
+    outln!("  cargo run -- search <query|slug> [--open] [--limit <n>]");
+    outln!("                                     - Find markets by question or slug, with their condition IDs");
+    outln!("  cargo run -- monitor <condition_id|slug> [--interval <secs>]");
+    outln!("                                     - Poll one market's order books, alerting when it crosses the threshold");
+    outln!("  cargo run -- manifold [scan|<username> [--detailed]]");
+    outln!("                                     - Scan Manifold Markets for arbitrage, or analyze a Manifold user");
+    outln!("  Add --plain to any command for ASCII-only output (no emojis or box-drawing)");
+    outln!("  Add --min-invested <dollars> to wallet analysis commands to ignore dust positions");
+    outln!("  Add --min-positions <n>, --min-roi <%>, --min-profit <dollars> or --min-winrate <%> to scans");
+    outln!("  to change which wallets are reported as profitable (defaults under [insider] in scanner.toml)");
+    outln!("  cargo run [-- --include <categories>] [--exclude <categories>] [--paper] [--execute]");
+    outln!("                                     - Run arbitrage scanner (comma-separated category/tag filters)");
+    outln!("                                       Add --resolving-within <48h|2d> to only scan markets ending soon");
+    outln!("                                       Add --interval <secs> to change how often it scans, or --adaptive to");
+    outln!("                                       scan faster after opportunities and slower in quiet hours");
+    outln!("                                       Add --paper to simulate fills against the order book and keep scanning");
+    outln!("                                       Add --execute to trade opportunities live (needs [executor] keys)");
+    outln!("                                       Stops at the first opportunity; --stop-after-n-hits <n>,");
+    outln!("                                       --stop-after-duration <30m|6h|2d> or --run-forever change that\n");
+    arbitrage::run_scanner(&config, &args).await
 }
@@ -0,0 +1,314 @@
+use crate::client::PolymarketClientBuilder;
+use crate::config::{ApiConfig, ManifoldConfig};
+use crate::models::{Market, Trade};
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Most markets Manifold's search returns per request
+const SEARCH_PAGE_SIZE: usize = 1000;
+/// Most bets Manifold returns per request
+const BETS_PAGE_SIZE: usize = 1000;
+
+/// A market from Manifold's API (binary markets are the only kind converted)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifoldMarket {
+    pub id: String,
+    pub question: String,
+    #[serde(default)]
+    pub outcome_type: Option<String>,
+    /// Current YES probability (binary markets only)
+    #[serde(default)]
+    pub probability: Option<f64>,
+    #[serde(default)]
+    pub total_liquidity: Option<f64>,
+    #[serde(default)]
+    pub volume: Option<f64>,
+    #[serde(default)]
+    pub volume_24_hours: Option<f64>,
+    /// Milliseconds
+    #[serde(default)]
+    pub created_time: Option<i64>,
+    /// Milliseconds
+    #[serde(default)]
+    pub close_time: Option<i64>,
+    #[serde(default)]
+    pub is_resolved: bool,
+    /// "YES", "NO", "MKT" (resolved to a probability) or "CANCEL"
+    #[serde(default)]
+    pub resolution: Option<String>,
+    /// Milliseconds
+    #[serde(default)]
+    pub resolution_time: Option<i64>,
+    /// Payout of a YES share when resolved "MKT"
+    #[serde(default)]
+    pub resolution_probability: Option<f64>,
+}
+
+impl ManifoldMarket {
+    /// Converts a binary market into the scanner's market model, priced YES at the
+    /// probability and NO at its complement (resolved markets at their payouts)
+    /// Returns None for other market types and cancelled markets
+    pub fn to_market(&self) -> Option<Market> {
+        if self.outcome_type.as_deref() != Some("BINARY") {
+            return None;
+        }
+        let yes = match (self.is_resolved, self.resolution.as_deref()) {
+            (false, _) => self.probability?,
+            (true, Some("YES")) => 1.0,
+            (true, Some("NO")) => 0.0,
+            (true, Some("MKT")) => self.resolution_probability.or(self.probability)?,
+            (true, _) => return None,
+        };
+
+        Some(Market {
+            question: self.question.clone(),
+            slug: None,
+            outcome_prices: Some(format!("[\"{}\",\"{}\"]", yes, 1.0 - yes)),
+            volume: Some(self.volume.unwrap_or(0.0).to_string()),
+            volume_24hr: self.volume_24_hours,
+            liquidity: Some(self.total_liquidity.unwrap_or(0.0).to_string()),
+            condition_id: Some(self.id.clone()),
+            closed: Some(self.is_resolved),
+            outcomes: Some(r#"["Yes","No"]"#.to_string()),
+            start_date: self.created_time.and_then(iso_timestamp),
+            end_date: self.close_time.and_then(iso_timestamp),
+            closed_time: self.resolution_time.and_then(iso_timestamp),
+            category: None,
+            clob_token_ids: None,
+            tags: Vec::new(),
+            order_price_min_tick_size: None,
+            order_min_size: None,
+            taker_base_fee: None,
+            maker_base_fee: None,
+            neg_risk: None,
+            resolution_source: None,
+            uma_resolution_status: None,
+            uma_resolution_statuses: None,
+            events: Vec::new(),
+            best_bid: None,
+            best_ask: None,
+            one_day_price_change: None,
+            rewards_min_size: None,
+            rewards_max_spread: None,
+            clob_rewards: None,
+            extra: HashMap::new(),
+        })
+    }
+}
+
+/// A bet from Manifold's API: buying shares spends mana (positive amount), selling returns it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifoldBet {
+    pub id: String,
+    pub user_id: String,
+    pub contract_id: String,
+    /// Milliseconds
+    pub created_time: i64,
+    pub amount: f64,
+    pub shares: f64,
+    pub outcome: String,
+    #[serde(default)]
+    pub user_username: Option<String>,
+    /// Set on bets in multiple-choice markets, which aren't converted
+    #[serde(default)]
+    pub answer_id: Option<String>,
+    #[serde(default)]
+    pub is_cancelled: bool,
+}
+
+impl ManifoldBet {
+    /// Converts a binary-market bet into the scanner's trade model, priced at the mana
+    /// spent or returned per share; None for unfilled limit orders and multiple-choice bets
+    pub fn to_trade(&self) -> Option<Trade> {
+        if self.answer_id.is_some() || self.is_cancelled || self.shares == 0.0 || self.amount == 0.0 {
+            return None;
+        }
+        let outcome_index = match self.outcome.as_str() {
+            "YES" => 0,
+            "NO" => 1,
+            _ => return None,
+        };
+
+        Some(Trade {
+            proxy_wallet: self.user_id.clone(),
+            side: if self.amount > 0.0 { "BUY" } else { "SELL" }.to_string(),
+            condition_id: self.contract_id.clone(),
+            size: self.shares.abs(),
+            price: (self.amount / self.shares).abs(),
+            timestamp: self.created_time / 1000,
+            outcome: if outcome_index == 0 { "Yes" } else { "No" }.to_string(),
+            outcome_index,
+            title: None,
+            name: self.user_username.clone(),
+            pseudonym: None,
+            transaction_hash: Some(self.id.clone()),
+            extra: HashMap::new(),
+        })
+    }
+}
+
+/// A user from Manifold's API
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifoldUser {
+    pub id: String,
+}
+
+/// Client for Manifold Markets' public API
+/// Manifold trades in mana (play money), so amounts read as dollars elsewhere are mana here
+#[derive(Clone)]
+pub struct ManifoldClient {
+    client: reqwest::Client,
+    api_url: String,
+    max_markets: usize,
+    max_concurrent_requests: usize,
+}
+
+impl ManifoldClient {
+    /// Creates a client sharing the HTTP transport settings (timeouts, proxy) of `[api]`
+    pub fn with_config(api: &ApiConfig, config: &ManifoldConfig) -> Result<Self> {
+        Ok(Self {
+            client: PolymarketClientBuilder::from_config(api).http_client()?,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            max_markets: config.max_markets,
+            max_concurrent_requests: api.max_concurrent_requests.max(1),
+        })
+    }
+
+    /// Fetches open binary markets, most liquid first, up to the configured maximum
+    pub async fn fetch_open_markets(&self) -> Result<Vec<Market>> {
+        self.search_markets("open", "liquidity").await
+    }
+
+    /// Fetches resolved binary markets, most recently resolved first, up to the configured maximum
+    pub async fn fetch_resolved_markets(&self) -> Result<Vec<Market>> {
+        self.search_markets("resolved", "resolve-date").await
+    }
+
+    /// Pages through binary markets matching a search filter, up to the configured maximum
+    async fn search_markets(&self, filter: &str, sort: &str) -> Result<Vec<Market>> {
+        let mut markets = Vec::new();
+        let mut offset = 0;
+
+        while offset < self.max_markets {
+            let limit = SEARCH_PAGE_SIZE.min(self.max_markets - offset);
+            let page: Vec<ManifoldMarket> = self
+                .get(
+                    "search-markets",
+                    &[
+                        ("term", String::new()),
+                        ("filter", filter.to_string()),
+                        ("contractType", "BINARY".to_string()),
+                        ("sort", sort.to_string()),
+                        ("limit", limit.to_string()),
+                        ("offset", offset.to_string()),
+                    ],
+                )
+                .await
+                .with_context(|| format!("Failed to fetch {} Manifold markets", filter))?;
+
+            let page_len = page.len();
+            markets.extend(page.iter().filter_map(ManifoldMarket::to_market));
+            if page_len < limit {
+                break;
+            }
+            offset += page_len;
+        }
+
+        Ok(markets)
+    }
+
+    /// Fetches markets by ID, skipping any that fail or aren't binary
+    pub async fn fetch_markets(&self, ids: &[String]) -> Result<Vec<Market>> {
+        let markets = futures::stream::iter(ids.to_vec())
+            .map(|id| async move {
+                let market = self.get::<ManifoldMarket>(&format!("market/{}", id), &[]).await;
+                (id, market)
+            })
+            .buffer_unordered(self.max_concurrent_requests)
+            .filter_map(|(id, market)| async move {
+                match market {
+                    Ok(market) => market.to_market(),
+                    Err(e) => {
+                        eoutln!("Warning: Failed to fetch Manifold market {}: {}", id, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await;
+
+        Ok(markets)
+    }
+
+    /// Looks up a user's ID (what trades carry as their wallet) from their username
+    pub async fn resolve_user(&self, username: &str) -> Result<String> {
+        let username = username.trim_start_matches('@');
+        let user: ManifoldUser = self
+            .get(&format!("user/{}", username), &[])
+            .await
+            .with_context(|| format!("No Manifold user named {}", username))?;
+        Ok(user.id)
+    }
+
+    /// Fetches every bet a user placed in binary markets, newest first
+    pub async fn fetch_user_trades(&self, user_id: &str) -> Result<Vec<Trade>> {
+        self.fetch_bets(Some(user_id), usize::MAX)
+            .await
+            .with_context(|| format!("Failed to fetch Manifold bets for {}", user_id))
+    }
+
+    /// Fetches the most recent bets across all binary markets, newest first
+    pub async fn fetch_recent_trades(&self, limit: usize) -> Result<Vec<Trade>> {
+        self.fetch_bets(None, limit).await.context("Failed to fetch recent Manifold bets")
+    }
+
+    /// Pages back through bets, optionally of one user, until `limit` bets were read
+    async fn fetch_bets(&self, user_id: Option<&str>, limit: usize) -> Result<Vec<Trade>> {
+        let mut trades = Vec::new();
+        let mut read = 0;
+        let mut before: Option<String> = None;
+
+        while read < limit {
+            let page_size = BETS_PAGE_SIZE.min(limit - read);
+            let mut query = vec![("limit", page_size.to_string())];
+            if let Some(user_id) = user_id {
+                query.push(("userId", user_id.to_string()));
+            }
+            if let Some(id) = &before {
+                query.push(("before", id.clone()));
+            }
+            let page: Vec<ManifoldBet> = self.get("bets", &query).await?;
+
+            read += page.len();
+            trades.extend(page.iter().filter_map(ManifoldBet::to_trade));
+            match page.last() {
+                Some(last) if page.len() == page_size => before = Some(last.id.clone()),
+                _ => break,
+            }
+        }
+
+        Ok(trades)
+    }
+
+    /// Sends a GET request to an API path and parses the JSON response
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        Ok(self.client
+            .get(format!("{}/{}", self.api_url, path))
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+/// Formats a millisecond timestamp the way Gamma returns dates
+fn iso_timestamp(millis: i64) -> Option<String> {
+    DateTime::from_timestamp_millis(millis).map(|date| date.to_rfc3339())
+}
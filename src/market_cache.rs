@@ -0,0 +1,239 @@
+use crate::exchange::ExchangeClient;
+use crate::models::{EventRef, Market};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+/// The records' generation, then the bincode-encoded resolved markets one after another
+const RECORDS_FILE: &str = "resolved_markets.bin";
+/// Bincode-encoded index of record offsets by condition ID
+const INDEX_FILE: &str = "resolved_markets.idx";
+/// Bumped whenever `CachedMarket` or the file layout changes, invalidating older caches
+const CACHE_VERSION: u32 = 5;
+/// Bytes of the generation header at the start of the records file
+const GENERATION_LEN: usize = 8;
+
+/// Market record as stored in the binary cache
+/// Kept separate from `Market` so API-facing serde attributes can't break the encoding
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedMarket {
+    question: String,
+    outcome_prices: Option<String>,
+    volume: Option<String>,
+    liquidity: Option<String>,
+    condition_id: Option<String>,
+    closed: Option<bool>,
+    outcomes: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    closed_time: Option<String>,
+    category: Option<String>,
+    clob_token_ids: Option<String>,
+    uma_resolution_status: Option<String>,
+    uma_resolution_statuses: Option<String>,
+    events: Vec<EventRef>,
+}
+
+impl From<&Market> for CachedMarket {
+    fn from(market: &Market) -> Self {
+        Self {
+            question: market.question.clone(),
+            outcome_prices: market.outcome_prices.clone(),
+            volume: market.volume.clone(),
+            liquidity: market.liquidity.clone(),
+            condition_id: market.condition_id.clone(),
+            closed: market.closed,
+            outcomes: market.outcomes.clone(),
+            start_date: market.start_date.clone(),
+            end_date: market.end_date.clone(),
+            closed_time: market.closed_time.clone(),
+            category: market.category.clone(),
+            clob_token_ids: market.clob_token_ids.clone(),
+            uma_resolution_status: market.uma_resolution_status.clone(),
+            uma_resolution_statuses: market.uma_resolution_statuses.clone(),
+            events: market.events.clone(),
+        }
+    }
+}
+
+impl From<CachedMarket> for Market {
+    fn from(cached: CachedMarket) -> Self {
+        Self {
+            question: cached.question,
+            outcome_prices: cached.outcome_prices,
+            volume: cached.volume,
+            liquidity: cached.liquidity,
+            condition_id: cached.condition_id,
+            closed: cached.closed,
+            outcomes: cached.outcomes,
+            start_date: cached.start_date,
+            end_date: cached.end_date,
+            closed_time: cached.closed_time,
+            category: cached.category,
+            clob_token_ids: cached.clob_token_ids,
+            uma_resolution_status: cached.uma_resolution_status,
+            uma_resolution_statuses: cached.uma_resolution_statuses,
+            events: cached.events,
+            // Slugs, tags, fee, tick and reward settings and unknown fields only matter for live markets, so they aren't cached
+            slug: None,
+            tags: Vec::new(),
+            order_price_min_tick_size: None,
+            order_min_size: None,
+            taker_base_fee: None,
+            maker_base_fee: None,
+            neg_risk: None,
+            resolution_source: None,
+            volume_24hr: None,
+            best_bid: None,
+            best_ask: None,
+            one_day_price_change: None,
+            rewards_min_size: None,
+            rewards_max_spread: None,
+            clob_rewards: None,
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Location of each record in the records file
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheIndex {
+    version: u32,
+    fetched_at: i64,
+    /// Generation of the records file the offsets point into
+    generation: u64,
+    /// Condition ID -> (byte offset, length)
+    entries: HashMap<String, (u64, u32)>,
+}
+
+/// Binary cache of resolved markets with a condition-ID index, so wallet analysis
+/// can load just the markets a wallet traded instead of parsing every market
+pub struct ResolvedMarketCache {
+    store: Store,
+    max_age_secs: i64,
+}
+
+impl ResolvedMarketCache {
+    pub fn new(store: Store, max_age_secs: i64) -> Self {
+        Self { store, max_age_secs }
+    }
+
+    /// Loads the index if the cache exists, matches this version and isn't too old
+    fn fresh_index(&self) -> Option<CacheIndex> {
+        let bytes = std::fs::read(self.store.path(INDEX_FILE)).ok()?;
+        let index: CacheIndex = bincode::deserialize(&bytes).ok()?;
+
+        let age = Utc::now().timestamp() - index.fetched_at;
+        (index.version == CACHE_VERSION && age <= self.max_age_secs).then_some(index)
+    }
+
+    /// Whether a usable cache exists
+    pub fn is_fresh(&self) -> bool {
+        self.fresh_index().is_some()
+    }
+
+    /// Replaces the cache with the given markets
+    pub fn save(&self, markets: &[Market]) -> Result<()> {
+        let now = Utc::now();
+        let generation = now.timestamp_nanos_opt().unwrap_or_else(|| now.timestamp()) as u64;
+        let mut records = generation.to_le_bytes().to_vec();
+        let mut entries = HashMap::with_capacity(markets.len());
+
+        for market in markets {
+            let Some(condition_id) = &market.condition_id else { continue };
+            let encoded = bincode::serialize(&CachedMarket::from(market))?;
+            entries.insert(condition_id.clone(), (records.len() as u64, encoded.len() as u32));
+            records.extend_from_slice(&encoded);
+        }
+
+        let index = CacheIndex {
+            version: CACHE_VERSION,
+            fetched_at: now.timestamp(),
+            generation,
+            entries,
+        };
+
+        // Both files are replaced atomically, the index last. A crash in between leaves the old
+        // index with the new records, which `load` rejects because their generations differ
+        self.store.save_bytes(RECORDS_FILE, &records)?;
+        self.store.save_bytes(INDEX_FILE, &bincode::serialize(&index)?)
+    }
+
+    /// Loads only the cached markets with the given condition IDs (None if the cache is stale)
+    pub fn load(&self, condition_ids: &[String]) -> Result<Option<Vec<Market>>> {
+        let Some(index) = self.fresh_index() else { return Ok(None) };
+
+        let path = self.store.path(RECORDS_FILE);
+        let mut file = File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut header = [0u8; GENERATION_LEN];
+        if file.read_exact(&mut header).is_err() || u64::from_le_bytes(header) != index.generation {
+            return Ok(None);
+        }
+
+        let mut markets = Vec::new();
+        let mut buffer = Vec::new();
+        for condition_id in condition_ids {
+            let Some(&(offset, len)) = index.entries.get(condition_id) else { continue };
+
+            buffer.resize(len as usize, 0);
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buffer)?;
+            let cached: CachedMarket = bincode::deserialize(&buffer)
+                .with_context(|| format!("Corrupt cache record for {}", condition_id))?;
+            // An index that doesn't match its records means the cache can't be trusted at all
+            if cached.condition_id.as_ref() != Some(condition_id) {
+                return Ok(None);
+            }
+            markets.push(cached.into());
+        }
+
+        Ok(Some(markets))
+    }
+
+    /// Loads every cached market (None if the cache is stale)
+    pub fn load_all(&self) -> Result<Option<Vec<Market>>> {
+        let Some(index) = self.fresh_index() else { return Ok(None) };
+
+        let path = self.store.path(RECORDS_FILE);
+        let bytes = std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let generation = bytes.get(..GENERATION_LEN).and_then(|header| header.try_into().ok()).map(u64::from_le_bytes);
+        if generation != Some(index.generation) {
+            return Ok(None);
+        }
+
+        let mut offsets: Vec<(u64, u32)> = index.entries.into_values().collect();
+        offsets.sort_unstable();
+
+        offsets
+            .into_iter()
+            .map(|(offset, len)| {
+                let record = bytes
+                    .get(offset as usize..offset as usize + len as usize)
+                    .context("Cache index points past the end of the records file")?;
+                let cached: CachedMarket = bincode::deserialize(record)?;
+                Ok(cached.into())
+            })
+            .collect::<Result<Vec<Market>>>()
+            .map(Some)
+    }
+
+    /// Returns every resolved market, from the cache when fresh, otherwise fetched
+    /// from the API and written back to the cache
+    pub async fn load_or_fetch_all(&self, client: &impl ExchangeClient) -> Result<Vec<Market>> {
+        match self.load_all() {
+            Ok(Some(markets)) => return Ok(markets),
+            Ok(None) => {}
+            Err(e) => eoutln!("Warning: Ignoring unreadable resolved-market cache: {}", e),
+        }
+
+        let markets = client.fetch_resolved_markets().await?;
+        if let Err(e) = self.save(&markets) {
+            eoutln!("Warning: Failed to write resolved-market cache: {}", e);
+        }
+        Ok(markets)
+    }
+}
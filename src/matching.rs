@@ -0,0 +1,571 @@
+use crate::models::{parse_api_timestamp, Market};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Words that carry no meaning for matching ("Will the Fed cut rates?" ~ "Fed cuts rates")
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "at", "be", "by", "does", "for", "in", "is", "of", "on", "or",
+    "the", "this", "to", "will", "with",
+];
+
+/// Spelling variants mapped to one canonical token
+const SYNONYMS: &[(&str, &str)] = &[
+    ("v", "vs"),
+    ("versus", "vs"),
+    ("usa", "us"),
+    ("btc", "bitcoin"),
+    ("eth", "ethereum"),
+    ("sol", "solana"),
+    ("jan", "january"),
+    ("feb", "february"),
+    ("mar", "march"),
+    ("apr", "april"),
+    ("jun", "june"),
+    ("jul", "july"),
+    ("aug", "august"),
+    ("sep", "september"),
+    ("sept", "september"),
+    ("oct", "october"),
+    ("nov", "november"),
+    ("dec", "december"),
+    ("pct", "percent"),
+];
+
+/// Numbers in a title (thresholds, years, scores) count this much more than words
+const NUMBER_WEIGHT: f64 = 2.0;
+/// Similarity multiplier when both titles have numbers but they differ
+/// ("BTC above $100k" is a different market from "BTC above $120k")
+const NUMBER_MISMATCH_PENALTY: f64 = 0.5;
+/// Confidence multiplier when either market has no date to compare
+const MISSING_DATE_FACTOR: f64 = 0.9;
+/// Share of the confidence kept however far apart the dates are; the rest scales with date proximity
+const TITLE_WEIGHT: f64 = 0.8;
+
+/// Anything that can be matched: a market title plus, optionally, the date it ends
+pub trait Matchable {
+    fn match_title(&self) -> &str;
+
+    /// When the market ends or resolves (unix seconds)
+    fn match_date(&self) -> Option<i64> {
+        None
+    }
+}
+
+impl Matchable for Market {
+    fn match_title(&self) -> &str {
+        &self.question
+    }
+
+    fn match_date(&self) -> Option<i64> {
+        self.end_date.as_deref().and_then(parse_api_timestamp)
+    }
+}
+
+impl Matchable for str {
+    fn match_title(&self) -> &str {
+        self
+    }
+}
+
+impl Matchable for String {
+    fn match_title(&self) -> &str {
+        self
+    }
+}
+
+/// A title reduced to comparable tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedTitle {
+    /// Tokens joined by spaces, in title order
+    pub text: String,
+    pub tokens: Vec<String>,
+}
+
+impl NormalizedTitle {
+    /// Lowercases, drops punctuation and stopwords, canonicalizes numbers ("$100,000" and
+    /// "100k" both become "100000"), ordinals, month abbreviations and common synonyms,
+    /// and strips plural "s"
+    pub fn new(title: &str) -> Self {
+        let lowered = title.to_lowercase().replace('&', " and ").replace('%', " percent ").replace("u.s.", "us");
+
+        // Split on anything that isn't part of a word or number; keep "." and "," only
+        // between digits ("1.5", "100,000") and drop possessives ("trump's" -> "trump")
+        let chars: Vec<char> = lowered.chars().collect();
+        let mut cleaned = String::with_capacity(lowered.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let between_digits = i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit());
+            let possessive = matches!(c, '\'' | '’')
+                && chars.get(i + 1) == Some(&'s')
+                && !chars.get(i + 2).is_some_and(|next| next.is_alphanumeric());
+            match c {
+                c if c.is_alphanumeric() => cleaned.push(c),
+                '.' if between_digits => cleaned.push('.'),
+                ',' if between_digits => {}
+                _ if possessive => i += 1,
+                _ => cleaned.push(' '),
+            }
+            i += 1;
+        }
+
+        let tokens: Vec<String> = cleaned
+            .split_whitespace()
+            .filter(|word| !(word.len() == 1 && word.chars().all(char::is_alphabetic) && *word != "v"))
+            .map(canonical_token)
+            .filter(|token| !STOPWORDS.contains(&token.as_str()))
+            .collect();
+
+        Self {
+            text: tokens.join(" "),
+            tokens,
+        }
+    }
+
+    /// Tokens that are numbers
+    fn numbers(&self) -> HashSet<&str> {
+        self.tokens.iter().filter(|t| is_number(t)).map(String::as_str).collect()
+    }
+}
+
+/// How closely two markets match
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MatchScore {
+    /// Overall confidence the two are the same market, 0 to 1
+    pub confidence: f64,
+    /// Weighted token overlap of the titles, 0 to 1
+    pub title_similarity: f64,
+    /// Seconds between the markets' dates, None if either has no date
+    pub date_gap_secs: Option<i64>,
+}
+
+/// A pair of matched markets, by index into the lists that were matched
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MarketMatch {
+    pub left: usize,
+    pub right: usize,
+    pub score: MatchScore,
+}
+
+/// Pairs up markets that describe the same event (across venues, or within one venue for
+/// complement pairs), scoring title similarity and date proximity
+#[derive(Debug, Clone)]
+pub struct MarketMatcher {
+    min_confidence: f64,
+    date_tolerance_secs: i64,
+    max_date_gap_secs: i64,
+}
+
+impl Default for MarketMatcher {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.6,
+            date_tolerance_secs: 24 * 3600,
+            max_date_gap_secs: 7 * 24 * 3600,
+        }
+    }
+}
+
+impl MarketMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum confidence for two markets to count as a match
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Dates within `tolerance_secs` count as the same; beyond `max_gap_secs` markets never match
+    pub fn with_date_window(mut self, tolerance_secs: i64, max_gap_secs: i64) -> Self {
+        self.date_tolerance_secs = tolerance_secs.max(0);
+        self.max_date_gap_secs = max_gap_secs.max(self.date_tolerance_secs);
+        self
+    }
+
+    /// Scores two markets (confidence 0 when their dates are too far apart)
+    pub fn score<A, B>(&self, a: &A, b: &B) -> MatchScore
+    where
+        A: Matchable + ?Sized,
+        B: Matchable + ?Sized,
+    {
+        let title_a = NormalizedTitle::new(a.match_title());
+        let title_b = NormalizedTitle::new(b.match_title());
+        self.score_normalized(&title_a, a.match_date(), &title_b, b.match_date())
+    }
+
+    /// The best match for one market among candidates, if any reaches the minimum confidence
+    pub fn best_match<A, B>(&self, item: &A, candidates: &[B]) -> Option<MarketMatch>
+    where
+        A: Matchable + ?Sized,
+        B: Matchable,
+    {
+        let title = NormalizedTitle::new(item.match_title());
+        candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| MarketMatch {
+                left: 0,
+                right: index,
+                score: self.score_normalized(
+                    &title,
+                    item.match_date(),
+                    &NormalizedTitle::new(candidate.match_title()),
+                    candidate.match_date(),
+                ),
+            })
+            .filter(|m| m.score.confidence >= self.min_confidence)
+            .max_by(|a, b| a.score.confidence.total_cmp(&b.score.confidence))
+    }
+
+    /// One-to-one matches between two lists (e.g. two venues), best first
+    /// Each market is paired at most once, greedily by confidence
+    pub fn match_all<A, B>(&self, left: &[A], right: &[B]) -> Vec<MarketMatch>
+    where
+        A: Matchable,
+        B: Matchable,
+    {
+        let right_titles: Vec<NormalizedTitle> = right.iter().map(|m| NormalizedTitle::new(m.match_title())).collect();
+        let index = token_index(&right_titles);
+
+        let mut candidates = Vec::new();
+        for (i, market) in left.iter().enumerate() {
+            let title = NormalizedTitle::new(market.match_title());
+            for j in shared_token_candidates(&title, &index) {
+                let score = self.score_normalized(&title, market.match_date(), &right_titles[j], right[j].match_date());
+                if score.confidence >= self.min_confidence {
+                    candidates.push(MarketMatch { left: i, right: j, score });
+                }
+            }
+        }
+
+        pair_greedily(candidates, false)
+    }
+
+    /// One-to-one matches among the markets of a single list, never pairing a market with
+    /// itself (e.g. complement pairs on one venue); `left` is always the lower index
+    pub fn match_within<A: Matchable>(&self, markets: &[A]) -> Vec<MarketMatch> {
+        let titles: Vec<NormalizedTitle> = markets.iter().map(|m| NormalizedTitle::new(m.match_title())).collect();
+        let index = token_index(&titles);
+
+        let mut candidates = Vec::new();
+        for (i, title) in titles.iter().enumerate() {
+            for j in shared_token_candidates(title, &index).into_iter().filter(|j| *j > i) {
+                let score = self.score_normalized(title, markets[i].match_date(), &titles[j], markets[j].match_date());
+                if score.confidence >= self.min_confidence {
+                    candidates.push(MarketMatch { left: i, right: j, score });
+                }
+            }
+        }
+
+        pair_greedily(candidates, true)
+    }
+
+    fn score_normalized(&self, a: &NormalizedTitle, date_a: Option<i64>, b: &NormalizedTitle, date_b: Option<i64>) -> MatchScore {
+        let title_similarity = title_similarity(a, b);
+        let date_gap_secs = date_a.zip(date_b).map(|(a, b)| (a - b).abs());
+
+        let confidence = match date_gap_secs {
+            Some(gap) if gap > self.max_date_gap_secs => 0.0,
+            Some(gap) => {
+                let date_score = if gap <= self.date_tolerance_secs {
+                    1.0
+                } else {
+                    1.0 - (gap - self.date_tolerance_secs) as f64
+                        / (self.max_date_gap_secs - self.date_tolerance_secs).max(1) as f64
+                };
+                title_similarity * (TITLE_WEIGHT + (1.0 - TITLE_WEIGHT) * date_score)
+            }
+            None => title_similarity * MISSING_DATE_FACTOR,
+        };
+
+        MatchScore {
+            confidence,
+            title_similarity,
+            date_gap_secs,
+        }
+    }
+}
+
+/// Weighted Dice overlap of two titles' tokens, with numbers weighing more and a penalty
+/// when both titles contain numbers that differ
+pub fn title_similarity(a: &NormalizedTitle, b: &NormalizedTitle) -> f64 {
+    let weight = |token: &str| if is_number(token) { NUMBER_WEIGHT } else { 1.0 };
+    let set_a: HashSet<&str> = a.tokens.iter().map(String::as_str).collect();
+    let set_b: HashSet<&str> = b.tokens.iter().map(String::as_str).collect();
+
+    let total: f64 = set_a.iter().chain(set_b.iter()).map(|t| weight(t)).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let shared: f64 = set_a.intersection(&set_b).map(|t| weight(t)).sum();
+    let similarity = 2.0 * shared / total;
+
+    let (numbers_a, numbers_b) = (a.numbers(), b.numbers());
+    if !numbers_a.is_empty() && !numbers_b.is_empty() && numbers_a != numbers_b {
+        similarity * NUMBER_MISMATCH_PENALTY
+    } else {
+        similarity
+    }
+}
+
+/// Canonical form of a single lowercase word
+fn canonical_token(word: &str) -> String {
+    if let Some(number) = canonical_number(word) {
+        return number;
+    }
+    if let Some((_, canonical)) = SYNONYMS.iter().find(|(variant, _)| *variant == word) {
+        return canonical.to_string();
+    }
+    // Plural or third-person "s", but not "ss", "us" or "is" ("process", "plus", "crisis")
+    // or short words ("gas")
+    match word.strip_suffix('s') {
+        Some(stem) if word.len() > 3 && !stem.ends_with(['s', 'u', 'i']) => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+/// "100k" -> "100000", "1.5m" -> "1500000", "3rd" -> "3", "2.50" -> "2.5"
+fn canonical_number(word: &str) -> Option<String> {
+    let (digits, multiplier) = match word.char_indices().find(|(_, c)| !c.is_ascii_digit() && *c != '.') {
+        None => (word, 1.0),
+        Some((i, _)) => match &word[i..] {
+            "k" => (&word[..i], 1e3),
+            "m" | "mm" => (&word[..i], 1e6),
+            "b" | "bn" => (&word[..i], 1e9),
+            "st" | "nd" | "rd" | "th" => (&word[..i], 1.0),
+            _ => return None,
+        },
+    };
+
+    let value: f64 = digits.parse().ok()?;
+    let value = value * multiplier;
+    Some(if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    })
+}
+
+fn is_number(token: &str) -> bool {
+    token.parse::<f64>().is_ok()
+}
+
+/// Token -> indexes of the titles containing it
+fn token_index(titles: &[NormalizedTitle]) -> HashMap<&str, Vec<usize>> {
+    let mut index: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, title) in titles.iter().enumerate() {
+        let unique: HashSet<&str> = title.tokens.iter().map(String::as_str).collect();
+        for token in unique {
+            index.entry(token).or_default().push(i);
+        }
+    }
+    index
+}
+
+/// Titles sharing at least one word (not just a number) with `title`
+fn shared_token_candidates(title: &NormalizedTitle, index: &HashMap<&str, Vec<usize>>) -> Vec<usize> {
+    let mut candidates: Vec<usize> = title
+        .tokens
+        .iter()
+        .filter(|token| !is_number(token))
+        .filter_map(|token| index.get(token.as_str()))
+        .flatten()
+        .copied()
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+}
+
+/// Keeps the highest-confidence matches such that each market appears at most once
+/// (`same_list`: left and right index the same markets)
+fn pair_greedily(mut candidates: Vec<MarketMatch>, same_list: bool) -> Vec<MarketMatch> {
+    candidates.sort_by(|a, b| b.score.confidence.total_cmp(&a.score.confidence));
+
+    let mut used_left = HashSet::new();
+    let mut used_right = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|m| {
+            let right_used = if same_list { &used_left } else { &used_right };
+            let free = !used_left.contains(&m.left) && !right_used.contains(&m.right);
+            if free {
+                used_left.insert(m.left);
+                if same_list {
+                    used_left.insert(m.right);
+                } else {
+                    used_right.insert(m.right);
+                }
+            }
+            free
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    const DAY: i64 = 86400;
+
+    /// A market title with its end date, as listed by a venue
+    struct Listing {
+        title: String,
+        end: Option<i64>,
+    }
+
+    impl Listing {
+        fn new(title: &str, end: &str) -> Self {
+            Self {
+                title: title.to_string(),
+                end: parse_api_timestamp(end),
+            }
+        }
+    }
+
+    impl Matchable for Listing {
+        fn match_title(&self) -> &str {
+            &self.title
+        }
+
+        fn match_date(&self) -> Option<i64> {
+            self.end
+        }
+    }
+
+    /// A pair of real market titles, from different venues or different markets of one venue
+    #[derive(Deserialize)]
+    struct FixturePair {
+        left: String,
+        left_end: String,
+        right: String,
+        right_end: String,
+        matches: bool,
+    }
+
+    fn fixture_pairs() -> Vec<FixturePair> {
+        serde_json::from_str(include_str!("../tests/fixtures/matching_titles.json")).unwrap()
+    }
+
+    fn normalized(title: &str) -> String {
+        NormalizedTitle::new(title).text
+    }
+
+    #[test]
+    fn numbers_are_canonicalized() {
+        assert_eq!(normalized("$100,000"), "100000");
+        assert_eq!(normalized("100k"), "100000");
+        assert_eq!(normalized("1.5M"), "1500000");
+        assert_eq!(normalized("$2.50"), "2.5");
+        assert_eq!(normalized("3rd"), "3");
+        assert_eq!(normalized("45%"), "45 percent");
+    }
+
+    #[test]
+    fn words_are_canonicalized() {
+        assert_eq!(normalized("Will the Fed cut rates?"), normalized("Fed cuts rates"));
+        assert_eq!(normalized("Trump's approval"), "trump approval");
+        assert_eq!(normalized("U.S. recession"), "us recession");
+        assert_eq!(normalized("Chiefs vs. Eagles"), normalized("Chiefs v Eagles"));
+        assert_eq!(normalized("BTC above $100k in Sept."), "bitcoin above 100000 september");
+        assert_eq!(normalized("Dune & Wicked"), "dune wicked");
+    }
+
+    #[test]
+    fn short_words_and_double_s_keep_their_s() {
+        assert_eq!(normalized("gas prices process plus crisis"), "gas price process plus crisis");
+    }
+
+    #[test]
+    fn fixture_pairs_match_as_expected() {
+        let matcher = MarketMatcher::new();
+        for pair in fixture_pairs() {
+            let left = Listing::new(&pair.left, &pair.left_end);
+            let right = Listing::new(&pair.right, &pair.right_end);
+            let score = matcher.score(&left, &right);
+            assert_eq!(
+                score.confidence >= 0.6,
+                pair.matches,
+                "{:?} vs {:?} scored {:.3}",
+                pair.left,
+                pair.right,
+                score.confidence
+            );
+        }
+    }
+
+    #[test]
+    fn fixture_titles_pair_one_to_one_across_venues() {
+        let pairs: Vec<FixturePair> = fixture_pairs().into_iter().filter(|p| p.matches).collect();
+        let left: Vec<Listing> = pairs.iter().map(|p| Listing::new(&p.left, &p.left_end)).collect();
+        // Listed in reverse, so only matching (not list order) can pair them back up
+        let right: Vec<Listing> = pairs.iter().rev().map(|p| Listing::new(&p.right, &p.right_end)).collect();
+
+        let mut matched: Vec<(usize, usize)> =
+            MarketMatcher::new().match_all(&left, &right).iter().map(|m| (m.left, m.right)).collect();
+        matched.sort();
+        let expected: Vec<(usize, usize)> = (0..pairs.len()).map(|i| (i, pairs.len() - 1 - i)).collect();
+        assert_eq!(matched, expected);
+    }
+
+    #[test]
+    fn dates_within_tolerance_keep_the_title_similarity() {
+        let matcher = MarketMatcher::new();
+        let score = matcher.score(
+            &Listing::new("Will Bitcoin reach $100,000 by December 31?", "2024-12-31"),
+            &Listing::new("Will Bitcoin reach $100,000 by December 31?", "2025-01-01"),
+        );
+        assert_eq!(score.date_gap_secs, Some(DAY));
+        assert!((score.confidence - score.title_similarity).abs() < 1e-9);
+    }
+
+    #[test]
+    fn confidence_falls_with_the_date_gap_and_vanishes_past_the_max() {
+        let matcher = MarketMatcher::new();
+        let title = "Will the Fed cut interest rates in September?";
+        let base = Listing::new(title, "2024-09-18");
+        let confidence = |end: &str| matcher.score(&base, &Listing::new(title, end)).confidence;
+
+        let (near, far, past) = (confidence("2024-09-21"), confidence("2024-09-24"), confidence("2024-09-26"));
+        assert!(near < 1.0 && far < near && far >= TITLE_WEIGHT);
+        assert_eq!(past, 0.0);
+    }
+
+    #[test]
+    fn a_missing_date_discounts_the_confidence() {
+        let title = "Will Kamala Harris win the 2024 US Presidential Election?";
+        let score = MarketMatcher::new().score(&Listing::new(title, "2024-11-05"), title);
+        assert_eq!(score.date_gap_secs, None);
+        assert!((score.confidence - MISSING_DATE_FACTOR).abs() < 1e-9);
+    }
+
+    #[test]
+    fn recurring_markets_pair_by_date() {
+        let polymarket = [
+            Listing::new("Will Bitcoin reach $100,000 by December 31?", "2024-12-31"),
+            Listing::new("Will Bitcoin reach $100,000 by December 31?", "2025-12-31"),
+        ];
+        let other = [Listing::new("Will BTC hit $100k by Dec 31?", "2025-12-31")];
+
+        let matches = MarketMatcher::new().match_all(&polymarket, &other);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].left, matches[0].right), (1, 0));
+    }
+
+    #[test]
+    fn markets_are_never_paired_with_themselves() {
+        let markets = [
+            Listing::new("Will Donald Trump win the 2024 US Presidential Election?", "2024-11-05"),
+            Listing::new("Chiefs vs. Eagles", "2025-02-09"),
+            Listing::new("Donald Trump wins the 2024 U.S. presidential election?", "2024-11-05"),
+        ];
+        let matches = MarketMatcher::new().match_within(&markets);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].left, matches[0].right), (0, 2));
+    }
+}
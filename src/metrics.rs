@@ -0,0 +1,232 @@
+use crate::models::{MarketEfficiency, OpportunityWindow};
+use crate::store::Store;
+use anyhow::Result;
+use chrono::{DateTime, Timelike, Utc};
+use std::collections::BTreeMap;
+
+/// Append-only log of per-scan market efficiency readings
+const EFFICIENCY_FILE: &str = "market_efficiency.jsonl";
+/// Append-only log of closed arbitrage windows
+const WINDOWS_FILE: &str = "opportunity_windows.jsonl";
+/// Net spread buckets for the duration report: (lower bound %, label)
+const SPREAD_BUCKETS: [(f64, &str); 4] = [(5.0, "5%+"), (2.0, "2-5%"), (1.0, "1-2%"), (f64::NEG_INFINITY, "under 1%")];
+
+/// Persists the market efficiency index of each arbitrage scan
+#[derive(Clone)]
+pub struct EfficiencyLog {
+    store: Store,
+}
+
+impl EfficiencyLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Appends one scan's reading
+    pub fn record(&self, efficiency: &MarketEfficiency) -> Result<()> {
+        self.store.append_jsonl(EFFICIENCY_FILE, std::slice::from_ref(efficiency))
+    }
+
+    /// Loads readings taken within the last `hours`
+    pub fn load_since(&self, hours: i64) -> Result<Vec<MarketEfficiency>> {
+        let since = Utc::now().timestamp() - hours * 3600;
+        Ok(self
+            .store
+            .read_jsonl::<MarketEfficiency>(EFFICIENCY_FILE)?
+            .into_iter()
+            .filter(|e| e.timestamp >= since)
+            .collect())
+    }
+
+    /// Prints the index over time, with averages by hour of day to show when
+    /// the venue tends to be least efficient
+    pub fn print_report(&self, readings: &[MarketEfficiency], hours: i64) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("MARKET EFFICIENCY INDEX (last {}h)", hours);
+        outln!("{}", "=".repeat(80));
+
+        if readings.is_empty() {
+            outln!("\nNo readings recorded yet. Run the arbitrage scanner to collect them.");
+            return;
+        }
+
+        let indexes: Vec<f64> = readings.iter().map(|r| r.index).collect();
+        let mean = indexes.iter().sum::<f64>() / indexes.len() as f64;
+        let min = indexes.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = indexes.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let latest = &readings[readings.len() - 1];
+
+        outln!("\nReadings:             {}", readings.len());
+        outln!("Latest:               {:.4} ({} markets)", latest.index, latest.markets);
+        outln!("Mean:                 {:.4}", mean);
+        outln!("Min / Max:            {:.4} / {:.4}", min, max);
+
+        // Mean index by UTC hour of day
+        let mut by_hour = [(0.0f64, 0usize); 24];
+        for reading in readings {
+            if let Some(time) = DateTime::from_timestamp(reading.timestamp, 0) {
+                let slot = &mut by_hour[time.hour() as usize];
+                slot.0 += reading.index;
+                slot.1 += 1;
+            }
+        }
+
+        outln!("\n--- By Hour of Day (UTC) ---");
+        for (hour, (total, count)) in by_hour.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let hour_mean = total / *count as f64;
+            let bar = "#".repeat(((hour_mean / max.max(f64::EPSILON)) * 40.0).round() as usize);
+            outln!("{:02}:00  {:.4}  {}", hour, hour_mean, bar);
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Persists how long each arbitrage opportunity stayed open, to show how quickly windows
+/// close and so how fast scanning and execution need to be
+#[derive(Clone)]
+pub struct OpportunityWindowLog {
+    store: Store,
+}
+
+impl OpportunityWindowLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Appends windows that just closed
+    pub fn record(&self, windows: &[OpportunityWindow]) -> Result<()> {
+        if windows.is_empty() {
+            return Ok(());
+        }
+        self.store.append_jsonl(WINDOWS_FILE, windows)
+    }
+
+    /// Loads windows that opened within the last `hours`
+    pub fn load_since(&self, hours: i64) -> Result<Vec<OpportunityWindow>> {
+        let since = Utc::now().timestamp() - hours * 3600;
+        Ok(self
+            .store
+            .read_jsonl::<OpportunityWindow>(WINDOWS_FILE)?
+            .into_iter()
+            .filter(|w| w.opened_at >= since)
+            .collect())
+    }
+
+    /// Prints how long windows stayed open overall, by category and by the net spread they
+    /// opened at. Durations are upper bounds: a window closed somewhere between the last
+    /// scan that saw it and the next one
+    pub fn print_report(&self, windows: &[OpportunityWindow], hours: i64) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("OPPORTUNITY DURATIONS (last {}h)", hours);
+        outln!("{}", "=".repeat(80));
+
+        if windows.is_empty() {
+            outln!("\nNo closed opportunities recorded yet. Run the arbitrage scanner to collect them.");
+            return;
+        }
+
+        let stats = DurationStats::of(windows.iter());
+        outln!("\nWindows:              {}", windows.len());
+        outln!("Median Duration:      {}", format_secs(stats.median_secs));
+        outln!("90th Percentile:      {}", format_secs(stats.p90_secs));
+        outln!("Longest:              {}", format_secs(stats.max_secs));
+        outln!("Gone by Next Scan:    {:.0}% (seen on one scan only)", stats.single_scan_share * 100.0);
+        if stats.single_scan_share >= 0.5 {
+            outln!("Most windows closed before the next scan, so they may have lasted far less than the");
+            outln!("poll interval: scanning faster (or --adaptive) would measure and catch them better.");
+        }
+
+        let mut by_category: BTreeMap<&str, Vec<&OpportunityWindow>> = BTreeMap::new();
+        for window in windows {
+            by_category.entry(window.category.as_deref().unwrap_or("uncategorized")).or_default().push(window);
+        }
+        let mut categories: Vec<(&str, DurationStats)> = by_category
+            .into_iter()
+            .map(|(category, windows)| (category, DurationStats::of(windows.into_iter())))
+            .collect();
+        categories.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+
+        outln!("\n--- By Category ---");
+        print_header("Category");
+        for (category, stats) in &categories {
+            print_row(category, stats);
+        }
+
+        outln!("\n--- By Net Spread When Found ---");
+        print_header("Spread");
+        for (i, (floor, label)) in SPREAD_BUCKETS.iter().enumerate() {
+            let ceiling = if i == 0 { f64::INFINITY } else { SPREAD_BUCKETS[i - 1].0 };
+            let bucket = windows
+                .iter()
+                .filter(|w| w.opening_profit_percent >= *floor && w.opening_profit_percent < ceiling);
+            let stats = DurationStats::of(bucket);
+            if stats.count > 0 {
+                print_row(label, &stats);
+            }
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Duration distribution of a group of windows
+struct DurationStats {
+    count: usize,
+    median_secs: i64,
+    p90_secs: i64,
+    max_secs: i64,
+    /// Share of windows only one scan saw
+    single_scan_share: f64,
+}
+
+impl DurationStats {
+    fn of<'a>(windows: impl Iterator<Item = &'a OpportunityWindow>) -> Self {
+        let mut durations = Vec::new();
+        let mut single_scans = 0;
+        for window in windows {
+            durations.push(window.duration_secs());
+            if window.scans <= 1 {
+                single_scans += 1;
+            }
+        }
+        durations.sort_unstable();
+
+        let count = durations.len();
+        let at = |quantile: f64| durations.get(((count as f64 * quantile) as usize).min(count.saturating_sub(1))).copied();
+        Self {
+            count,
+            median_secs: at(0.5).unwrap_or(0),
+            p90_secs: at(0.9).unwrap_or(0),
+            max_secs: durations.last().copied().unwrap_or(0),
+            single_scan_share: if count > 0 { single_scans as f64 / count as f64 } else { 0.0 },
+        }
+    }
+}
+
+fn print_header(label: &str) {
+    outln!("{:<24} {:>8} {:>10} {:>10} {:>10}", label, "Windows", "Median", "p90", "One scan");
+}
+
+fn print_row(label: &str, stats: &DurationStats) {
+    outln!(
+        "{:<24} {:>8} {:>10} {:>10} {:>9.0}%",
+        label.chars().take(24).collect::<String>(),
+        stats.count,
+        format_secs(stats.median_secs),
+        format_secs(stats.p90_secs),
+        stats.single_scan_share * 100.0
+    );
+}
+
+/// Formats a duration as e.g. 45s, 3m20s or 2h05m
+fn format_secs(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{}h{:02}m", s / 3600, s % 3600 / 60),
+    }
+}
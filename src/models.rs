@@ -1,91 +1,1404 @@
-use serde::Deserialize;
+use crate::fees::{FeeSchedule, TradingCosts};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents a market from the Polymarket API
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Market {
     pub question: String,
+    /// URL slug, the last part of the market's polymarket.com address
+    #[serde(default)]
+    pub slug: Option<String>,
     #[serde(default)]
     pub outcome_prices: Option<String>,
     #[serde(default)]
     pub volume: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub volume_24hr: Option<f64>,
+    #[serde(default)]
+    pub liquidity: Option<String>,
+    #[serde(default)]
+    pub condition_id: Option<String>,
+    #[serde(default)]
+    pub closed: Option<bool>,
+    #[serde(default)]
+    pub outcomes: Option<String>,
+    /// When trading opened
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub closed_time: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub clob_token_ids: Option<String>,
+    /// Only returned when requested with `include_tag=true`
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub order_price_min_tick_size: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub order_min_size: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub taker_base_fee: Option<f64>,  // Basis points
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub maker_base_fee: Option<f64>,  // Basis points
+    /// Part of a negative-risk event; its orders settle on the neg-risk exchange
+    #[serde(default)]
+    pub neg_risk: Option<bool>,
+    /// Where the result is read from when the market resolves (e.g. an official site)
+    #[serde(default)]
+    pub resolution_source: Option<String>,
+    /// Current state of the market's UMA oracle request ("proposed", "disputed", "resolved")
+    #[serde(default)]
+    pub uma_resolution_status: Option<String>,
+    /// Every state the oracle request went through, as a JSON array string
+    #[serde(default)]
+    pub uma_resolution_statuses: Option<String>,
+    /// The event grouping this market with related ones (same election, same game)
+    #[serde(default)]
+    pub events: Vec<EventRef>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub best_bid: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub best_ask: Option<f64>,
+    /// Change of the YES price over the last 24 hours
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub one_day_price_change: Option<f64>,
+    /// Smallest order, in shares, that earns liquidity rewards
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub rewards_min_size: Option<f64>,
+    /// Widest distance from the midpoint, in cents, at which orders earn liquidity rewards
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub rewards_max_spread: Option<f64>,
+    /// Liquidity reward programs paying makers who quote near the midpoint
+    #[serde(default)]
+    pub clob_rewards: Option<Vec<ClobReward>>,
+    /// Fields the model doesn't know, kept so API changes show up in `doctor --fields`
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A liquidity reward program on a market
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClobReward {
+    /// Dollars paid per day, shared among qualifying makers by how much they quote and how tightly
+    #[serde(default, deserialize_with = "deserialize_optional_number_string")]
+    pub rewards_daily_rate: Option<f64>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+impl ClobReward {
+    /// Whether the program is paying at a moment (unparseable dates count as open-ended)
+    pub fn is_active(&self, now: i64) -> bool {
+        let started = self.start_date.as_deref().and_then(parse_api_timestamp).is_none_or(|start| start <= now);
+        let ended = self.end_date.as_deref().and_then(parse_api_timestamp).is_some_and(|end| end < now);
+        started && !ended
+    }
+}
+
+impl Market {
+    /// The event this market belongs to, if the API reported one
+    pub fn event(&self) -> Option<&EventRef> {
+        self.events.first()
+    }
+
+    /// Liquidity rewards paid per day across the market's active reward programs
+    pub fn daily_reward_rate(&self, now: i64) -> f64 {
+        self.clob_rewards
+            .iter()
+            .flatten()
+            .filter(|reward| reward.is_active(now))
+            .filter_map(|reward| reward.rewards_daily_rate)
+            .sum()
+    }
+
+    /// Midpoint of the best bid and ask, falling back to the YES price
+    pub fn midpoint(&self) -> Option<f64> {
+        match (self.best_bid, self.best_ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > bid => Some((bid + ask) / 2.0),
+            _ => self.parsed_outcome_prices()?.first().copied(),
+        }
+    }
+
+    /// Parses outcome prices - they come as a JSON array string like "[\"0.5\",\"0.5\"]"
+    pub fn parsed_outcome_prices(&self) -> Option<Vec<f64>> {
+        let prices_str = self.outcome_prices.as_ref()?;
+
+        let prices = serde_json::from_str::<Vec<String>>(prices_str)
+            .ok()?
+            .iter()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        Some(prices)
+    }
+
+    /// Total traded volume in dollars (0 if missing)
+    pub fn parsed_volume(&self) -> f64 {
+        self.volume.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0)
+    }
+
+    /// Current order book liquidity in dollars (0 if missing)
+    pub fn parsed_liquidity(&self) -> f64 {
+        self.liquidity.as_ref().and_then(|l| l.parse().ok()).unwrap_or(0.0)
+    }
+
+    /// Parses outcome labels - they come as a JSON array string like "[\"Yes\",\"No\"]"
+    pub fn parsed_outcomes(&self) -> Option<Vec<String>> {
+        let outcomes_str = self.outcomes.as_ref()?;
+        serde_json::from_str(outcomes_str).ok()
+    }
+
+    /// Name of an outcome from the market's labels (e.g. "Chiefs" rather than "Yes"),
+    /// falling back to Yes/No for unlabelled binary markets
+    pub fn outcome_name(&self, index: usize) -> String {
+        self.parsed_outcomes()
+            .and_then(|outcomes| outcomes.get(index).cloned())
+            .unwrap_or_else(|| Self::default_outcome_name(index))
+    }
+
+    /// Name of an outcome in a market without labels
+    pub fn default_outcome_name(index: usize) -> String {
+        match index {
+            0 => "Yes".to_string(),
+            1 => "No".to_string(),
+            _ => format!("Outcome {}", index),
+        }
+    }
+
+    /// Parses CLOB token IDs (one per outcome) from their JSON array string
+    pub fn parsed_clob_token_ids(&self) -> Option<Vec<String>> {
+        let ids_str = self.clob_token_ids.as_ref()?;
+        serde_json::from_str(ids_str).ok()
+    }
+
+    /// Whether every word of a search query appears in the question or slug (case-insensitive)
+    pub fn matches_query(&self, query: &str) -> bool {
+        let haystack = format!("{} {}", self.question, self.slug.as_deref().unwrap_or_default()).to_lowercase();
+        query
+            .split(|c: char| c.is_whitespace() || c == '-')
+            .filter(|word| !word.is_empty())
+            .all(|word| haystack.contains(&word.to_lowercase()))
+    }
+
+    /// Returns the market's category and tag labels and slugs, lowercased for matching
+    pub fn categories(&self) -> Vec<String> {
+        self.category
+            .iter()
+            .chain(self.tags.iter().flat_map(|tag| tag.names()))
+            .map(|category| category.to_lowercase())
+            .collect()
+    }
+
+    /// The category the market is grouped under: its category, else its first tag
+    pub fn primary_category(&self) -> Option<String> {
+        self.categories().into_iter().next()
+    }
+
+    /// When trading opened (unix seconds)
+    pub fn start_timestamp(&self) -> Option<i64> {
+        self.start_date.as_deref().and_then(parse_api_timestamp)
+    }
+
+    /// When the market is scheduled to end (unix seconds)
+    pub fn end_timestamp(&self) -> Option<i64> {
+        self.end_date.as_deref().and_then(parse_api_timestamp)
+    }
+
+    /// Best estimate of when the market resolved (unix seconds)
+    /// Uses the actual close time when available, otherwise the scheduled end date
+    pub fn resolution_timestamp(&self) -> Option<i64> {
+        self.closed_time
+            .as_deref()
+            .and_then(parse_api_timestamp)
+            .or_else(|| self.end_timestamp())
+    }
+
+    /// State of the market's UMA oracle request, None for markets without oracle data
+    pub fn oracle_status(&self) -> Option<OracleStatus> {
+        self.uma_resolution_status.as_deref().and_then(OracleStatus::parse)
+    }
+
+    /// Whether the oracle's proposed result was ever disputed
+    pub fn was_disputed(&self) -> bool {
+        self.oracle_status() == Some(OracleStatus::Disputed)
+            || self
+                .uma_resolution_statuses
+                .as_deref()
+                .and_then(|statuses| serde_json::from_str::<Vec<String>>(statuses).ok())
+                .is_some_and(|statuses| statuses.iter().any(|s| OracleStatus::parse(s) == Some(OracleStatus::Disputed)))
+    }
+
+    /// Payout per share settled by the oracle: once UMA reports the market resolved, its
+    /// outcome prices are the final payouts, including intermediate ones (e.g. 50/50)
+    fn oracle_payouts(&self) -> Option<Vec<f64>> {
+        if self.oracle_status()? != OracleStatus::Resolved {
+            return None;
+        }
+
+        let prices = self.parsed_outcome_prices()?;
+        let total: f64 = prices.iter().sum();
+        let settled = prices.len() >= 2
+            && prices.iter().all(|price| (0.0..=1.0).contains(price))
+            && (total - 1.0).abs() < 0.01;
+        settled.then_some(prices)
+    }
+
+    /// Index of the outcome that paid out in full, None if the market hasn't resolved
+    /// or settled as a split
+    /// Uses the oracle result when the market has one, inferring from prices otherwise
+    pub fn winning_outcome_index(&self) -> Option<usize> {
+        if self.oracle_status().is_some() {
+            return self.oracle_payouts()?.iter().position(|payout| *payout > 0.999);
+        }
+        self.inferred_winning_outcome_index()
+    }
+
+    /// Determines the winning outcome from the market's outcome prices
+    /// Works for any number of outcomes: exactly one outcome must be priced near 1.0
+    /// Returns None if market is not closed or outcome is ambiguous; an open market's
+    /// favourite trading above $0.90 hasn't won anything yet
+    fn inferred_winning_outcome_index(&self) -> Option<usize> {
+        if self.closed != Some(true) {
+            return None;
+        }
+        let prices = self.parsed_outcome_prices()?;
+
+        if prices.len() < 2 {
+            return None;
+        }
+
+        // The winning outcome has a price close to 1.0
+        // The losing outcomes have a price close to 0.0
+        let mut winners = prices.iter().enumerate().filter(|(_, price)| **price > 0.9);
+        let winner = winners.next().map(|(index, _)| index);
+
+        // Market not yet resolved or ambiguous (several outcomes near 1.0)
+        if winners.next().is_some() {
+            return None;
+        }
+
+        winner
+    }
+
+    /// Payout per share for each outcome once the market has resolved
+    /// Markets with oracle data use the settled result, and count as unresolved while a
+    /// proposal is pending or disputed; older markets fall back to inferring a single
+    /// winner or an even split (e.g. 50/50) from the closing prices
+    pub fn resolution_payouts(&self) -> Option<Vec<f64>> {
+        if self.oracle_status().is_some() {
+            return self.oracle_payouts();
+        }
+
+        let prices = self.parsed_outcome_prices()?;
+        let outcome_count = prices.len();
+
+        if let Some(winner) = self.inferred_winning_outcome_index() {
+            let payouts = (0..outcome_count)
+                .map(|index| if index == winner { 1.0 } else { 0.0 })
+                .collect();
+            return Some(payouts);
+        }
+
+        if self.closed == Some(true) && outcome_count >= 2 {
+            let even_share = 1.0 / outcome_count as f64;
+            if prices.iter().all(|price| (price - even_share).abs() < 0.01) {
+                return Some(vec![even_share; outcome_count]);
+            }
+        }
+
+        None
+    }
+}
+
+/// State of a market's request to the UMA optimistic oracle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OracleStatus {
+    /// A result was proposed and is in its challenge window
+    Proposed,
+    /// The proposed result was challenged and awaits a vote or a new proposal
+    Disputed,
+    /// The oracle settled the market
+    Resolved,
+}
+
+impl OracleStatus {
+    /// Parses the gamma API's `umaResolutionStatus` (unknown states give None)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "proposed" => Some(OracleStatus::Proposed),
+            "disputed" | "challenged" => Some(OracleStatus::Disputed),
+            "resolved" | "settled" => Some(OracleStatus::Resolved),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OracleStatus::Proposed => "proposed",
+            OracleStatus::Disputed => "disputed",
+            OracleStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// Parses the timestamp formats used by the gamma API into unix seconds
+/// e.g. "2024-11-05T12:00:00Z" (end dates) or "2024-11-06 03:10:11+00" (close times)
+pub fn parse_api_timestamp(value: &str) -> Option<i64> {
+    if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(parsed.timestamp());
+    }
+
+    let normalized = match value.rfind(['+', '-']) {
+        // Expand a bare "+00" offset into "+0000"
+        Some(pos) if pos > 10 && value.len() - pos == 3 => format!("{}00", value),
+        _ => value.to_string(),
+    };
+
+    chrono::DateTime::parse_from_str(&normalized, "%Y-%m-%d %H:%M:%S%.f%z")
+        .map(|parsed| parsed.timestamp())
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|datetime| datetime.and_utc().timestamp())
+        })
+}
+
+/// Deserializes a number that the API may send either as a JSON number or a string
+pub fn deserialize_number_string<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(f64),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Like `deserialize_number_string`, for fields that may also be null
+pub fn deserialize_optional_number_string<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "deserialize_number_string")] f64);
+
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(n)| n))
+}
+
+/// A price level in a CLOB order book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderLevel {
+    #[serde(deserialize_with = "deserialize_number_string")]
+    pub price: f64,
+    #[serde(deserialize_with = "deserialize_number_string")]
+    pub size: f64,
+}
+
+/// Order book for a single outcome token from the CLOB API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBook {
+    #[serde(default)]
+    pub market: Option<String>,
+    pub asset_id: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub bids: Vec<OrderLevel>,
+    #[serde(default)]
+    pub asks: Vec<OrderLevel>,
+}
+
+impl OrderBook {
+    /// Highest bid price, if any
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.iter().map(|l| l.price).fold(None, |best, p| Some(best.map_or(p, |b: f64| b.max(p))))
+    }
+
+    /// Lowest ask price, if any
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.iter().map(|l| l.price).fold(None, |best, p| Some(best.map_or(p, |b: f64| b.min(p))))
+    }
+
+    /// Highest ask price needed to buy `shares` by walking the asks from the best price
+    /// Returns None if the book doesn't have that much depth
+    pub fn marginal_ask_price(&self, shares: f64) -> Option<f64> {
+        let mut asks: Vec<&OrderLevel> = self.asks.iter().collect();
+        asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+
+        let mut remaining = shares;
+        for level in asks {
+            remaining -= level.size;
+            if remaining <= 0.0 {
+                return Some(level.price);
+            }
+        }
+
+        None
+    }
+
+    /// Average price paid to buy `shares` by walking the asks from the best price
+    /// Returns None if the book doesn't have that much depth
+    pub fn average_ask_price(&self, shares: f64) -> Option<f64> {
+        let mut asks: Vec<&OrderLevel> = self.asks.iter().collect();
+        asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+        average_fill_price(asks, shares)
+    }
+
+    /// Average price received for selling `shares` by walking the bids from the best price
+    /// Returns None if the book doesn't have that much depth
+    pub fn average_bid_price(&self, shares: f64) -> Option<f64> {
+        let mut bids: Vec<&OrderLevel> = self.bids.iter().collect();
+        bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
+        average_fill_price(bids, shares)
+    }
+}
+
+/// Average price of filling `shares` against levels in the order given
+fn average_fill_price(levels: Vec<&OrderLevel>, shares: f64) -> Option<f64> {
+    let mut remaining = shares;
+    let mut cost = 0.0;
+    for level in levels {
+        let filled = remaining.min(level.size);
+        cost += filled * level.price;
+        remaining -= filled;
+        if remaining <= 0.0 {
+            return Some(cost / shares);
+        }
+    }
+
+    None
+}
+
+/// Order books captured for a flagged market, kept for later audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookSnapshot {
+    pub captured_at: i64,
+    pub reason: String,
+    pub condition_id: String,
+    pub question: String,
+    pub outcomes: Vec<String>,
+    pub outcome_prices: Vec<f64>,  // Gamma prices at capture time, for comparison
+    pub books: Vec<OrderBook>,
+    /// Market settings at capture time (missing from snapshots taken before they were recorded)
+    #[serde(default)]
+    pub market: Option<SnapshotMarket>,
+    /// The arbitrage that triggered the capture, as the scanner priced it
+    #[serde(default)]
+    pub opportunity: Option<SnapshotOpportunity>,
+}
+
+/// Gamma's view of a market when its order books were captured
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMarket {
+    pub slug: Option<String>,
+    pub clob_token_ids: Vec<String>,
+    pub liquidity: f64,
+    pub volume: f64,
+    pub volume_24hr: Option<f64>,
+    /// Gamma's best bid and ask for the first outcome, which can lag the book
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub end_date: Option<String>,
+    pub closed: Option<bool>,
+    pub order_price_min_tick_size: Option<f64>,
+    pub order_min_size: Option<f64>,
+    pub taker_base_fee: Option<f64>,
+    pub neg_risk: Option<bool>,
+}
+
+impl SnapshotMarket {
+    pub fn from_market(market: &Market) -> Self {
+        Self {
+            slug: market.slug.clone(),
+            clob_token_ids: market.parsed_clob_token_ids().unwrap_or_default(),
+            liquidity: market.parsed_liquidity(),
+            volume: market.parsed_volume(),
+            volume_24hr: market.volume_24hr,
+            best_bid: market.best_bid,
+            best_ask: market.best_ask,
+            end_date: market.end_date.clone(),
+            closed: market.closed,
+            order_price_min_tick_size: market.order_price_min_tick_size,
+            order_min_size: market.order_min_size,
+            taker_base_fee: market.taker_base_fee,
+            neg_risk: market.neg_risk,
+        }
+    }
+}
+
+/// An arbitrage opportunity as the scanner flagged it, kept with its snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotOpportunity {
+    pub kind: ArbitrageKind,
+    /// Prices of both outcomes the opportunity was detected at
+    pub prices: [f64; 2],
+    pub total_cost: f64,
+    pub profit_percent: f64,
+}
+
+impl SnapshotOpportunity {
+    pub fn from_opportunity(opportunity: &ArbitrageOpportunity) -> Self {
+        Self {
+            kind: opportunity.kind,
+            prices: [opportunity.yes_price, opportunity.no_price],
+            total_cost: opportunity.total_cost,
+            profit_percent: opportunity.profit_percent,
+        }
+    }
+}
+
+/// Whether a flagged arbitrage was on the order book when it was captured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotVerdict {
+    /// The best prices on the book still crossed $1 the way the scanner saw
+    Executable,
+    /// The book didn't offer the edge: the flagged prices were stale or indicative
+    Stale,
+    /// A side of the book was empty, so the edge can't be judged
+    NoBook,
+}
+
+impl SnapshotVerdict {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapshotVerdict::Executable => "EXECUTABLE",
+            SnapshotVerdict::Stale => "STALE",
+            SnapshotVerdict::NoBook => "NO BOOK",
+        }
+    }
+}
+
+impl OrderBookSnapshot {
+    /// YES + NO at the top of the captured books on the side the opportunity trades:
+    /// the best asks to buy both, the best bids to sell both
+    pub fn book_total(&self) -> Option<f64> {
+        let opportunity = self.opportunity.as_ref()?;
+        let (first, second) = (self.books.first()?, self.books.get(1)?);
+        match opportunity.kind {
+            ArbitrageKind::BuyBoth => Some(first.best_ask()? + second.best_ask()?),
+            ArbitrageKind::SellBoth => Some(first.best_bid()? + second.best_bid()?),
+        }
+    }
+
+    /// Whether the captured books bore out the flagged opportunity (None if it wasn't an arbitrage)
+    pub fn verdict(&self) -> Option<SnapshotVerdict> {
+        let opportunity = self.opportunity.as_ref()?;
+        let Some(total) = self.book_total() else {
+            return Some(SnapshotVerdict::NoBook);
+        };
+        let executable = match opportunity.kind {
+            ArbitrageKind::BuyBoth => total < 1.0,
+            ArbitrageKind::SellBoth => total > 1.0,
+        };
+        Some(if executable { SnapshotVerdict::Executable } else { SnapshotVerdict::Stale })
+    }
+
+    /// Prints a one-line-per-outcome summary of the captured books
+    pub fn print_summary(&self) {
+        outln!("📸 Order book snapshot ({}): {}", self.reason, self.question);
+        for (i, book) in self.books.iter().enumerate() {
+            let outcome = self.outcomes.get(i).map(|o| o.as_str()).unwrap_or("?");
+            let format_price = |price: Option<f64>| {
+                price.map(|p| format!("${:.3}", p)).unwrap_or_else(|| "-".to_string())
+            };
+            outln!(
+                "   {}: bid {} / ask {} ({} bids, {} asks)",
+                outcome,
+                format_price(book.best_bid()),
+                format_price(book.best_ask()),
+                book.bids.len(),
+                book.asks.len()
+            );
+        }
+        if let Some(opportunity) = &self.opportunity {
+            let book_total = self.book_total().map(|t| format!("${:.4}", t)).unwrap_or_else(|| "-".to_string());
+            outln!(
+                "   Flagged at ${:.4} + ${:.4} = ${:.4}, book {}: {}",
+                opportunity.prices[0],
+                opportunity.prices[1],
+                opportunity.total_cost,
+                book_total,
+                self.verdict().map_or("-", |v| v.label())
+            );
+        }
+    }
+}
+
+/// Resolution state of a market as seen by the resolution checker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResolutionStatus {
+    /// Market is closed and has a clear winning outcome
+    Resolved,
+    /// Market is closed but the winner can't be determined (yet)
+    Pending,
+    /// Market is still open for trading
+    Open,
+    /// No market with this condition ID was returned by the API
+    NotFound,
+}
+
+impl ResolutionStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResolutionStatus::Resolved => "RESOLVED",
+            ResolutionStatus::Pending => "PENDING",
+            ResolutionStatus::Open => "OPEN",
+            ResolutionStatus::NotFound => "NOT FOUND",
+        }
+    }
+}
+
+/// Resolution result for a single condition ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketResolution {
+    pub condition_id: String,
+    pub question: Option<String>,
+    pub status: ResolutionStatus,
+    pub winning_outcome_index: Option<usize>,
+    pub winning_outcome: Option<String>,
+    /// Payout per share of each outcome once resolved
+    #[serde(default)]
+    pub payouts: Option<Vec<f64>>,
+    #[serde(default)]
+    pub resolved_at: Option<i64>,
+    #[serde(default)]
+    pub resolution_source: Option<String>,
+    /// UMA oracle state, None for markets without oracle data
+    #[serde(default)]
+    pub oracle_status: Option<OracleStatus>,
+    #[serde(default)]
+    pub disputed: bool,
+}
+
+impl MarketResolution {
+    /// Builds a resolution result from a market returned by the API
+    pub fn from_market(condition_id: &str, market: &Market) -> Self {
+        let closed = market.closed.unwrap_or(false);
+        let winning_outcome_index = if closed { market.winning_outcome_index() } else { None };
+
+        // Split resolutions (e.g. 50/50) are final even though there's no single winner;
+        // closed markets whose oracle result is proposed or disputed are still pending
+        let payouts = if closed { market.resolution_payouts() } else { None };
+        let status = match (closed, payouts.is_some()) {
+            (true, true) => ResolutionStatus::Resolved,
+            (true, false) => ResolutionStatus::Pending,
+            (false, _) => ResolutionStatus::Open,
+        };
+
+        let winning_outcome = winning_outcome_index.and_then(|index| {
+            market
+                .parsed_outcomes()
+                .and_then(|outcomes| outcomes.get(index).cloned())
+        });
+
+        Self {
+            condition_id: condition_id.to_string(),
+            question: Some(market.question.clone()),
+            status,
+            winning_outcome_index,
+            winning_outcome,
+            resolved_at: payouts.as_ref().and_then(|_| market.resolution_timestamp()),
+            payouts,
+            resolution_source: market.resolution_source.clone().filter(|source| !source.is_empty()),
+            oracle_status: market.oracle_status(),
+            disputed: market.was_disputed(),
+        }
+    }
+
+    /// Builds a result for a condition ID the API knows nothing about
+    pub fn not_found(condition_id: &str) -> Self {
+        Self {
+            condition_id: condition_id.to_string(),
+            question: None,
+            status: ResolutionStatus::NotFound,
+            winning_outcome_index: None,
+            winning_outcome: None,
+            payouts: None,
+            resolved_at: None,
+            resolution_source: None,
+            oracle_status: None,
+            disputed: false,
+        }
+    }
+}
+
+/// A tag attached to a market or event (e.g. "Politics", "Crypto")
+#[derive(Debug, Deserialize, Clone)]
+pub struct Tag {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
+impl Tag {
+    /// The tag's label and slug, whichever are set
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.label.iter().chain(self.slug.iter())
+    }
+}
+
+/// Represents a Polymarket event grouping one or more markets
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Candidate markets are mutually exclusive: exactly one resolves YES
+    #[serde(default)]
+    pub neg_risk: Option<bool>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub markets: Vec<Market>,
+}
+
+impl Event {
+    /// Whether the event's markets are mutually exclusive candidates
+    pub fn is_neg_risk(&self) -> bool {
+        self.neg_risk.unwrap_or(false)
+    }
+
+    /// The event's tags plus its markets' categories, lowercased for matching
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .tags
+            .iter()
+            .flat_map(|tag| tag.names())
+            .map(|name| name.to_lowercase())
+            .chain(self.markets.iter().flat_map(|market| market.categories()))
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    /// When the event is scheduled to end (unix seconds): its own end date, or else the
+    /// latest of its open markets', since a basket pays out only once every leg has resolved
+    pub fn end_timestamp(&self) -> Option<i64> {
+        self.end_date
+            .as_deref()
+            .and_then(parse_api_timestamp)
+            .or_else(|| self.open_markets().iter().filter_map(|market| market.end_timestamp()).max())
+    }
+
+    /// Candidate markets still trading
+    pub fn open_markets(&self) -> Vec<&Market> {
+        self.markets.iter().filter(|m| m.closed != Some(true)).collect()
+    }
+
+    /// Groups markets by the event each belongs to, for markets fetched on their own rather
+    /// than through the events endpoint; markets without an event are left out
+    pub fn group_markets(markets: &[Market]) -> Vec<Event> {
+        let mut events: Vec<Event> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for market in markets {
+            let Some(event_ref) = market.event() else { continue };
+            let position = *index.entry(event_ref.id.as_str()).or_insert_with(|| {
+                events.push(Event {
+                    id: event_ref.id.clone(),
+                    title: event_ref.title.clone(),
+                    slug: event_ref.slug.clone(),
+                    neg_risk: market.neg_risk,
+                    tags: Vec::new(),
+                    end_date: None,
+                    markets: Vec::new(),
+                });
+                events.len() - 1
+            });
+            events[position].markets.push(market.clone());
+        }
+        events
+    }
+
+    /// Condition IDs of the event's markets
+    pub fn condition_ids(&self) -> Vec<&str> {
+        self.markets.iter().filter_map(|m| m.condition_id.as_deref()).collect()
+    }
+}
+
+/// The event a market belongs to, as embedded in Gamma market responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventRef {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
+impl EventRef {
+    /// The event's title, or its ID when it has none
+    pub fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.id)
+    }
+}
+
+/// Which side of every candidate market an event arbitrage buys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventArbitrageKind {
+    /// Buy YES on every candidate: exactly one pays $1
+    AllYes,
+    /// Buy NO on every candidate: all but one pay $1
+    AllNo,
+}
+
+impl EventArbitrageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventArbitrageKind::AllYes => "Buy all YES",
+            EventArbitrageKind::AllNo => "Buy all NO",
+        }
+    }
+}
+
+/// Arbitrage across the mutually exclusive markets of a negative-risk event
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventArbitrageOpportunity {
+    pub event_id: String,
+    pub title: String,
+    pub kind: EventArbitrageKind,
+    pub legs: usize,
+    pub total_cost: f64,  // Cost of one share on every leg
+    pub payout: f64,      // Guaranteed payout of the basket
+    pub profit_per_dollar: f64,
+    pub profit_percent: f64,
+    pub min_liquidity: f64,  // Thinnest leg's liquidity
+    pub expected_profit: f64,  // Dollars made putting the thinnest leg's liquidity, up to the max position, to work
+}
+
+impl EventArbitrageOpportunity {
+    /// Prints this opportunity in a formatted way
+    pub fn print(&self, index: usize) {
+        outln!("\n{}. [EVENT] {}", index, self.title);
+        outln!(
+            "   {} across {} markets | Cost: ${:.4} | Payout: ${:.2}",
+            self.kind.label(), self.legs, self.total_cost, self.payout
+        );
+        outln!(
+            "   Profit: ${:.4} per $1 ({:.2}%)",
+            self.profit_per_dollar, self.profit_percent
+        );
+        outln!("   Thinnest Leg Liquidity: ${:.2} | Expected Profit: ${:.2}", self.min_liquidity, self.expected_profit);
+        outln!("{}", "-".repeat(80));
+    }
+}
+
+/// A linked market pair priced against its implication: the implying market's YES above the
+/// implied market's, so buying implied YES and implying NO costs less than the $1 it must pay
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkArbitrageOpportunity {
+    pub label: Option<String>,
+    pub implying_condition_id: String,
+    pub implying_question: String,
+    pub implied_condition_id: String,
+    pub implied_question: String,
+    pub implying_yes: f64,
+    pub implied_yes: f64,
+    pub total_cost: f64,  // Implied YES plus implying NO
+    pub profit_per_dollar: f64,
+    pub profit_percent: f64,
+    pub min_liquidity: f64,  // Thinner market's liquidity
+    pub expected_profit: f64,  // Dollars made putting the thinner market's liquidity, up to the max position, to work
+}
+
+impl LinkArbitrageOpportunity {
+    /// Prints this opportunity in a formatted way
+    pub fn print(&self, index: usize) {
+        match &self.label {
+            Some(label) => outln!("\n{}. [LINKED] {}", index, label),
+            None => outln!("\n{}. [LINKED] {} => {}", index, self.implying_question, self.implied_question),
+        }
+        outln!("   Implying YES: ${:.4} | {}", self.implying_yes, self.implying_question);
+        outln!("   Implied YES:  ${:.4} | {}", self.implied_yes, self.implied_question);
+        outln!(
+            "   Buy implied YES + implying NO | Cost: ${:.4} | Profit: ${:.4} per $1 ({:.2}%)",
+            self.total_cost, self.profit_per_dollar, self.profit_percent
+        );
+        outln!("   Thinner Leg Liquidity: ${:.2} | Expected Profit: ${:.2}", self.min_liquidity, self.expected_profit);
+        outln!("{}", "-".repeat(80));
+    }
+}
+
+/// A point in an outcome token's price history
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PricePoint {
+    #[serde(rename = "t")]
+    pub timestamp: i64,
+    #[serde(rename = "p")]
+    pub price: f64,
+}
+
+/// Response of the CLOB price history endpoint
+#[derive(Debug, Deserialize)]
+pub struct PriceHistory {
+    #[serde(default)]
+    pub history: Vec<PricePoint>,
+}
+
+/// One outcome token's entry in a CLOB prices response, keyed by side
+#[derive(Debug, Default, Deserialize)]
+pub struct TokenPrices {
+    #[serde(rename = "BUY", default, deserialize_with = "deserialize_optional_number_string")]
+    pub buy: Option<f64>,
+    #[serde(rename = "SELL", default, deserialize_with = "deserialize_optional_number_string")]
+    pub sell: Option<f64>,
+}
+
+/// Current best bid and ask of one outcome token on the CLOB
+/// Fresher and finer than Gamma's `outcomePrices`, which lag the book and are rounded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenQuote {
+    pub token_id: String,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+}
+
+impl TokenQuote {
+    /// Builds a quote from a prices response entry; the response doesn't say which side
+    /// is the bid, so the lower price is taken as the bid and the higher as the ask
+    pub fn from_prices(token_id: &str, prices: &TokenPrices) -> Self {
+        let (bid, ask) = match (prices.buy, prices.sell) {
+            (Some(buy), Some(sell)) => (Some(buy.min(sell)), Some(buy.max(sell))),
+            (buy, sell) => (buy, sell),
+        };
+        Self { token_id: token_id.to_string(), bid, ask }
+    }
+
+    /// Midpoint of the bid and ask, or whichever side is quoted
+    pub fn midpoint(&self) -> Option<f64> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    /// Ask minus bid, when both sides are quoted
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.ask? - self.bid?)
+    }
+}
+
+/// Price history of one outcome of a market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSeries {
+    pub outcome: String,
+    pub token_id: String,
+    pub points: Vec<PricePoint>,
+}
+
+impl PriceSeries {
+    /// Largest single-step price change as (timestamp, change), to spot sharp moves
+    pub fn largest_move(&self) -> Option<(i64, f64)> {
+        self.points
+            .windows(2)
+            .map(|pair| (pair[1].timestamp, pair[1].price - pair[0].price))
+            .max_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap())
+    }
+}
+
+/// Trailing window for price history queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceInterval {
+    Hour,
+    SixHours,
+    Day,
+    Week,
+    Month,
+    Max,
+}
+
+impl PriceInterval {
+    /// Value of the endpoint's `interval` parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceInterval::Hour => "1h",
+            PriceInterval::SixHours => "6h",
+            PriceInterval::Day => "1d",
+            PriceInterval::Week => "1w",
+            PriceInterval::Month => "1m",
+            PriceInterval::Max => "max",
+        }
+    }
+}
+
+impl std::str::FromStr for PriceInterval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1h" => Ok(PriceInterval::Hour),
+            "6h" => Ok(PriceInterval::SixHours),
+            "1d" => Ok(PriceInterval::Day),
+            "1w" => Ok(PriceInterval::Week),
+            "1m" => Ok(PriceInterval::Month),
+            "max" => Ok(PriceInterval::Max),
+            _ => anyhow::bail!("Unknown interval {} (expected 1h, 6h, 1d, 1w, 1m or max)", s),
+        }
+    }
+}
+
+/// Trailing window Polymarket's leaderboard ranks wallets over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardWindow {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl LeaderboardWindow {
+    /// Value of the endpoint's `timePeriod` parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardWindow::Day => "DAY",
+            LeaderboardWindow::Week => "WEEK",
+            LeaderboardWindow::Month => "MONTH",
+            LeaderboardWindow::All => "ALL",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeaderboardWindow::Day => "past day",
+            LeaderboardWindow::Week => "past week",
+            LeaderboardWindow::Month => "past month",
+            LeaderboardWindow::All => "all time",
+        }
+    }
+}
+
+impl std::str::FromStr for LeaderboardWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "day" | "1d" => Ok(LeaderboardWindow::Day),
+            "week" | "1w" => Ok(LeaderboardWindow::Week),
+            "month" | "1m" => Ok(LeaderboardWindow::Month),
+            "all" | "max" => Ok(LeaderboardWindow::All),
+            _ => anyhow::bail!("Unknown window {} (expected day, week, month or all)", s),
+        }
+    }
+}
+
+/// A Polymarket user's public profile
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    /// Username chosen by the user, if any
     #[serde(default)]
-    pub liquidity: Option<String>,
+    pub name: Option<String>,
+    /// Name generated by Polymarket for users without one
     #[serde(default)]
-    pub condition_id: Option<String>,
+    pub pseudonym: Option<String>,
     #[serde(default)]
-    #[allow(dead_code)]
-    pub closed: Option<bool>,
+    pub proxy_wallet: Option<String>,
+}
+
+impl Profile {
+    /// The username, or the pseudonym when there's none
+    pub fn display_name(&self) -> Option<&str> {
+        self.name.as_deref().filter(|name| !name.is_empty()).or(self.pseudonym.as_deref())
+    }
+
+    /// Whether this profile goes by the given name (case-insensitive)
+    pub fn is_named(&self, name: &str) -> bool {
+        [&self.name, &self.pseudonym]
+            .into_iter()
+            .flatten()
+            .any(|candidate| candidate.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A wallet ranked by profit on Polymarket's leaderboard
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardRanking {
+    pub proxy_wallet: String,
     #[serde(default)]
-    #[allow(dead_code)]
-    pub outcomes: Option<String>,
+    pub user_name: Option<String>,
+    /// Dollar value of every buy and sell in the window
+    #[serde(default, deserialize_with = "deserialize_number_string")]
+    pub vol: f64,
+    /// Profit over the window
+    #[serde(default, deserialize_with = "deserialize_number_string")]
+    pub pnl: f64,
+}
+
+/// Aggregate pricing efficiency of binary markets at a single scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketEfficiency {
+    pub timestamp: i64,
+    pub index: f64,  // Volume-weighted mean |YES + NO - 1|; 0 = perfectly priced
+    pub unweighted_index: f64,  // Plain mean |YES + NO - 1|
+    pub markets: usize,  // Binary markets included
+    pub total_volume: f64,
+}
+
+/// How long one market's arbitrage stayed open, as seen by consecutive scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpportunityWindow {
+    pub condition_id: String,
+    pub question: String,
+    pub category: Option<String>,
+    /// Scan that first found it
+    pub opened_at: i64,
+    /// Last scan that still found it
+    pub last_seen_at: i64,
+    /// First scan that no longer did
+    pub closed_at: i64,
+    /// Scans that found it
+    pub scans: usize,
+    /// Net profit percentage when first found
+    pub opening_profit_percent: f64,
+    /// Widest net profit percentage while open
+    pub peak_profit_percent: f64,
+}
+
+impl OpportunityWindow {
+    /// Upper bound on how long it lasted: it closed at some point between the last scan
+    /// that saw it and the one that didn't
+    pub fn duration_secs(&self) -> i64 {
+        self.closed_at - self.opened_at
+    }
+}
+
+/// Which way a binary market is mispriced, and so which side of the book to trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArbitrageKind {
+    /// YES + NO below $1: buy both sides, one of which pays $1
+    BuyBoth,
+    /// YES + NO above $1: split $1 into a YES/NO pair and sell both sides
+    SellBoth,
+}
+
+impl ArbitrageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArbitrageKind::BuyBoth => "Buy both",
+            ArbitrageKind::SellBoth => "Sell both",
+        }
+    }
 }
 
 /// Represents a detected arbitrage opportunity
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ArbitrageOpportunity {
     pub question: String,
+    pub condition_id: Option<String>,
+    pub kind: ArbitrageKind,
+    /// Names of the first and second outcome, priced at `yes_price` and `no_price`
+    /// (Yes and No, or labels like Chiefs and Eagles)
+    pub outcomes: [String; 2],
     pub yes_price: f64,
     pub no_price: f64,
     pub total_cost: f64,
+    /// Distance of YES + NO from $1, before any costs
+    pub gross_profit_per_dollar: f64,
+    /// Taker fees and the fixed cost per trade, per share of each leg
+    pub fees_per_dollar: f64,
+    /// Expected slippage on both legs, per share
+    pub slippage_per_dollar: f64,
+    /// Net expected profit per $1 pair, after fees and slippage
     pub profit_per_dollar: f64,
+    /// Net expected profit as a percentage of the capital the basket ties up
     pub profit_percent: f64,
+    /// Shares per leg the costs were computed for
+    pub trade_size: f64,
+    /// Net profit in dollars of putting the market's liquidity, up to the max position, to work
+    pub expected_profit: f64,
     pub volume: f64,
+    /// Volume traded in the last 24 hours
+    pub volume_24h: f64,
     pub liquidity: f64,
+    /// Title of the event the market belongs to
+    pub event: Option<String>,
+    /// The market's category, else its first tag
+    pub category: Option<String>,
+    /// When the market last traded, if checked (None if unchecked or it never has)
+    pub last_trade_at: Option<i64>,
+    /// Hasn't traded recently, so its prices are likely stale quotes rather than executable
+    pub stale: bool,
 }
 
 impl ArbitrageOpportunity {
-    /// Creates a new arbitrage opportunity from a market
-    pub fn from_market(market: &Market, yes_price: f64, no_price: f64) -> Self {
+    /// Creates a new arbitrage opportunity from a market, net of the costs of
+    /// trading `costs.trade_size` shares of each leg; priced above $1 it sells both sides
+    pub fn from_market(market: &Market, yes_price: f64, no_price: f64, costs: &TradingCosts) -> Self {
         let total_cost = yes_price + no_price;
-        let profit_per_dollar = 1.0 - total_cost;
-        let profit_percent = (profit_per_dollar / total_cost) * 100.0;
+        let kind = if total_cost > 1.0 { ArbitrageKind::SellBoth } else { ArbitrageKind::BuyBoth };
+        let gross_profit_per_dollar = (1.0 - total_cost).abs();
 
-        let volume: f64 = market
-            .volume
-            .as_ref()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(0.0);
+        let fees = FeeSchedule::from_market(market);
+        let trade_size = costs.trade_size.max(1.0);
+        let liquidity = market.parsed_liquidity();
+        let fees_per_dollar = costs.fees_per_share(&[(fees, yes_price), (fees, no_price)], trade_size);
+        let slippage_per_dollar = costs.slippage.per_share(yes_price, trade_size, liquidity)
+            + costs.slippage.per_share(no_price, trade_size, liquidity);
 
-        let liquidity: f64 = market
-            .liquidity
-            .as_ref()
-            .and_then(|l| l.parse().ok())
-            .unwrap_or(0.0);
+        let profit_per_dollar = gross_profit_per_dollar - fees_per_dollar - slippage_per_dollar;
+
+        // Buying ties up the basket and its costs; selling ties up the $1 split into the pair
+        let capital = match kind {
+            ArbitrageKind::BuyBoth => total_cost + fees_per_dollar + slippage_per_dollar,
+            ArbitrageKind::SellBoth => 1.0,
+        };
+        let profit_percent = (profit_per_dollar / capital) * 100.0;
 
         Self {
             question: market.question.clone(),
+            condition_id: market.condition_id.clone(),
+            kind,
+            outcomes: [market.outcome_name(0), market.outcome_name(1)],
             yes_price,
             no_price,
             total_cost,
+            gross_profit_per_dollar,
+            fees_per_dollar,
+            slippage_per_dollar,
             profit_per_dollar,
             profit_percent,
-            volume,
+            trade_size,
+            expected_profit: profit_per_dollar * liquidity.min(costs.max_position),
+            volume: market.parsed_volume(),
+            volume_24h: market.volume_24hr.unwrap_or(0.0),
             liquidity,
+            event: market.event().map(|event| event.label().to_string()),
+            category: market.primary_category(),
+            last_trade_at: None,
+            stale: false,
         }
     }
 
     /// Prints this opportunity in a formatted way
     pub fn print(&self, index: usize) {
-        println!("\n{}. {}", index, self.question);
-        println!(
-            "   YES: ${:.4} | NO: ${:.4} | Total: ${:.4}",
-            self.yes_price, self.no_price, self.total_cost
+        match self.kind {
+            ArbitrageKind::BuyBoth => outln!("\n{}. {}", index, self.question),
+            ArbitrageKind::SellBoth => outln!("\n{}. [SELL BOTH] {}", index, self.question),
+        }
+        if let Some(event) = self.event.as_deref().filter(|event| *event != self.question) {
+            outln!("   Event: {}", event);
+        }
+        outln!(
+            "   {}: ${:.4} | {}: ${:.4} | Total: ${:.4}",
+            self.outcomes[0], self.yes_price, self.outcomes[1], self.no_price, self.total_cost
         );
-        println!(
-            "   Profit: ${:.4} per $1 ({:.2}%)",
-            self.profit_per_dollar, self.profit_percent
+        outln!(
+            "   Profit: ${:.4} per $1 ({:.2}%) net at {:.0} shares",
+            self.profit_per_dollar, self.profit_percent, self.trade_size
+        );
+        outln!(
+            "   Gross: ${:.4} | Fees: ${:.4} | Slippage: ${:.4}",
+            self.gross_profit_per_dollar, self.fees_per_dollar, self.slippage_per_dollar
         );
-        println!(
+        outln!("   Expected Profit: ${:.2}", self.expected_profit);
+        outln!(
             "   Volume: ${:.2} | Liquidity: ${:.2}",
             self.volume, self.liquidity
         );
-        println!("{}", "-".repeat(80));
+        if self.stale {
+            match self.last_trade_at {
+                Some(at) => outln!(
+                    "   ⚠️  STALE: last traded {:.0}h ago, the prices may not be executable",
+                    (chrono::Utc::now().timestamp() - at) as f64 / 3600.0
+                ),
+                None => outln!("   ⚠️  STALE: never traded, the prices may not be executable"),
+            }
+        }
+        outln!("{}", "-".repeat(80));
+    }
+}
+
+/// A market whose liquidity rewards are expected to pay more than quoting it loses to
+/// better-informed traders
+#[derive(Debug, Clone)]
+pub struct RewardOpportunity {
+    pub question: String,
+    pub condition_id: Option<String>,
+    /// Title of the event the market belongs to
+    pub event: Option<String>,
+    pub midpoint: f64,
+    /// Reward pool paid per day across the market's programs
+    pub daily_rate: f64,
+    /// Widest distance from the midpoint that earns rewards, in cents
+    pub max_spread: f64,
+    /// Smallest qualifying order, in shares
+    pub min_size: f64,
+    /// Dollars quoted across both sides
+    pub capital: f64,
+    /// Expected fraction of the pool: our quotes over our quotes plus the book's liquidity
+    pub reward_share: f64,
+    pub expected_reward: f64,
+    /// Expected daily loss from fills ahead of price moves
+    pub adverse_selection: f64,
+    pub net_per_day: f64,
+    /// Net reward per day over the capital quoted
+    pub daily_yield: f64,
+    pub volume_24h: f64,
+    pub liquidity: f64,
+}
+
+impl RewardOpportunity {
+    /// Prints this opportunity in a formatted way
+    pub fn print(&self, index: usize) {
+        outln!("\n{}. {}", index, self.question);
+        if let Some(event) = self.event.as_deref().filter(|event| *event != self.question) {
+            outln!("   Event: {}", event);
+        }
+        outln!(
+            "   Midpoint: ${:.3} | Rewards: ${:.2}/day within {:.1}c, min {:.0} shares",
+            self.midpoint, self.daily_rate, self.max_spread, self.min_size
+        );
+        outln!(
+            "   Net: ${:.2}/day ({:.2}% of ${:.0} quoted) | Share: {:.1}%",
+            self.net_per_day, self.daily_yield * 100.0, self.capital, self.reward_share * 100.0
+        );
+        outln!(
+            "   Reward: ${:.2}/day | Adverse selection: ${:.2}/day",
+            self.expected_reward, self.adverse_selection
+        );
+        outln!(
+            "   Volume 24h: ${:.2} | Liquidity: ${:.2}",
+            self.volume_24h, self.liquidity
+        );
+        outln!("{}", "-".repeat(80));
     }
 }
 
 /// Represents a trade from the Polymarket trades API
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
     pub proxy_wallet: String,
@@ -93,9 +1406,7 @@ pub struct Trade {
     pub condition_id: String,
     pub size: f64,
     pub price: f64,
-    #[allow(dead_code)]
     pub timestamp: i64,
-    #[allow(dead_code)]
     pub outcome: String,
     pub outcome_index: usize,
     #[serde(default)]
@@ -104,6 +1415,164 @@ pub struct Trade {
     pub name: Option<String>,
     #[serde(default)]
     pub pseudonym: Option<String>,
+    #[serde(default)]
+    pub transaction_hash: Option<String>,
+    /// Fields the model doesn't know, kept so API changes show up in `doctor --fields`
+    /// Not written to trade histories
+    #[serde(flatten, skip_serializing)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Kind of a record in the data API's activity feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ActivityKind {
+    Trade,
+    /// $1 of USDC turned into one share of every outcome
+    Split,
+    /// One share of every outcome turned back into $1
+    Merge,
+    /// Shares of a resolved market exchanged for their payout
+    Redeem,
+    /// Negative-risk NO shares converted into YES shares of the event's other markets
+    Conversion,
+    /// Liquidity or holding rewards paid out
+    Reward,
+    #[serde(other)]
+    Other,
+}
+
+/// A record from a wallet's activity feed: the trades plus what happens outside the order book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletActivity {
+    pub proxy_wallet: String,
+    #[serde(rename = "type")]
+    pub kind: ActivityKind,
+    /// Empty for rewards not tied to a market
+    #[serde(default)]
+    pub condition_id: String,
+    pub timestamp: i64,
+    /// Shares, or complete sets for splits and merges
+    #[serde(default)]
+    pub size: f64,
+    /// Dollars paid or received
+    #[serde(default)]
+    pub usdc_size: f64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub transaction_hash: Option<String>,
+}
+
+/// A wallet's current holding of one outcome, as the Data API positions endpoint reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletPosition {
+    pub proxy_wallet: String,
+    /// Token ID of the outcome held
+    pub asset: String,
+    pub condition_id: String,
+    pub size: f64,
+    pub avg_price: f64,
+    /// Cost of the shares held
+    #[serde(default)]
+    pub initial_value: f64,
+    /// Shares held at `cur_price`
+    #[serde(default)]
+    pub current_value: f64,
+    /// Unrealized profit: `current_value` less `initial_value`
+    #[serde(default)]
+    pub cash_pnl: f64,
+    #[serde(default)]
+    pub percent_pnl: f64,
+    /// Profit already taken from sells
+    #[serde(default)]
+    pub realized_pnl: f64,
+    #[serde(default)]
+    pub cur_price: f64,
+    /// The market resolved and the shares can be redeemed
+    #[serde(default)]
+    pub redeemable: bool,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub outcome: String,
+    #[serde(default)]
+    pub outcome_index: usize,
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+impl Trade {
+    /// Identifies a trade across fetches (several fills can share a transaction)
+    pub fn key(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.transaction_hash.as_deref().unwrap_or(""),
+            self.timestamp,
+            self.condition_id,
+            self.outcome_index,
+            self.side,
+            self.size,
+            self.price
+        )
+    }
+}
+
+/// Newest trade already seen in a trade history, so later fetches can stop once they reach it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeCursor {
+    pub timestamp: i64,
+    /// Trades at exactly `timestamp` already seen (several can share a second)
+    #[serde(default)]
+    pub trade_keys: Vec<String>,
+}
+
+impl TradeCursor {
+    /// A cursor that treats every trade from `timestamp` onwards as new
+    pub fn since(timestamp: i64) -> Self {
+        Self {
+            timestamp: timestamp - 1,
+            trade_keys: Vec::new(),
+        }
+    }
+
+    /// A cursor just past the newest of these trades
+    pub fn after(trades: &[Trade]) -> Self {
+        let mut cursor = Self::default();
+        for trade in trades {
+            cursor.advance(trade);
+        }
+        cursor
+    }
+
+    /// Whether a trade was at or before the cursor
+    pub fn covers(&self, trade: &Trade) -> bool {
+        trade.timestamp < self.timestamp
+            || (trade.timestamp == self.timestamp && self.trade_keys.contains(&trade.key()))
+    }
+
+    /// Moves the cursor forward to a trade
+    pub fn advance(&mut self, trade: &Trade) {
+        if trade.timestamp > self.timestamp {
+            self.timestamp = trade.timestamp;
+            self.trade_keys.clear();
+        }
+        if trade.timestamp == self.timestamp {
+            let key = trade.key();
+            if !self.trade_keys.contains(&key) {
+                self.trade_keys.push(key);
+            }
+        }
+    }
+}
+
+/// Whether a fill took liquidity from the book or rested on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityRole {
+    Taker,
+    Maker,
 }
 
 /// Represents a wallet's position in a market
@@ -111,41 +1580,161 @@ pub struct Trade {
 pub struct Position {
     pub condition_id: String,
     pub outcome_index: usize,
+    pub outcome: String,
     pub net_shares: f64,
+    pub shares_bought: f64,
     pub avg_price: f64,
     pub total_invested: f64,
     pub realized_profit: f64,  // Profit/loss from sells before resolution
-    #[allow(dead_code)]
+    pub cost_basis_sold: f64,  // Cost basis of shares exited via sells
     pub market_title: String,
+    pub buys: Vec<Fill>,
 }
 
-/// Represents a resolved position outcome
+/// A single buy fill within a position
 #[derive(Debug, Clone)]
+pub struct Fill {
+    pub timestamp: i64,
+    pub price: f64,
+    pub size: f64,
+}
+
+/// Represents a position with a known outcome: either its market resolved,
+/// or it was fully exited via sells before resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResolvedPosition {
-    #[allow(dead_code)]
     pub condition_id: String,
-    #[allow(dead_code)]
     pub market_title: String,
-    #[allow(dead_code)]
     pub bet_outcome_index: usize,
-    #[allow(dead_code)]
-    pub winning_outcome_index: usize,
-    #[allow(dead_code)]
+    pub bet_outcome: String,
+    pub winning_outcome_index: Option<usize>,  // None if exited before resolution
+    /// Name of the winning outcome, from the market's labels
+    #[serde(default)]
+    pub winning_outcome: Option<String>,
     pub net_shares: f64,
-    #[allow(dead_code)]
-    pub avg_price: f64,
-    pub total_invested: f64,
-    pub payout: f64,
+    pub shares_bought: f64,
+    pub avg_price: f64,  // Average entry price across all buys
+    pub total_invested: f64,  // Full cost basis, including shares later sold
+    pub payout: f64,          // Resolution payout plus sell proceeds
     pub profit: f64,
+    pub realized_profit: f64,  // Portion of profit realized via sells
+    pub exited_early: bool,    // Fully sold before resolution
     pub won: bool,
+    pub hours_before_resolution: Option<f64>,  // Cost-weighted time from buys to resolution
+    pub late_low_price_buy: bool,  // Bought cheaply shortly before resolution
+    pub resolved_at: Option<i64>,
+    #[serde(default)]
+    pub disputed: bool,  // The oracle's proposed result was challenged
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Title of the event the market belongs to
+    #[serde(default)]
+    pub event: Option<String>,
+    #[serde(default)]
+    pub entered_at: Option<i64>,  // Timestamp of the first buy
+    pub drift_pnl: Option<f64>,  // Profit from the market repricing between entry and resolution
+    pub skill_pnl: Option<f64>,  // Profit from the resolution the market hadn't priced in
+    #[serde(default)]
+    pub closing_price: Option<f64>,  // Outcome's price shortly before resolution: the closing line
+}
+
+impl ResolvedPosition {
+    /// Closing line value: how far the outcome's price moved from the entry to the close,
+    /// in price points. Positive when the wallet bought cheaper than the market's final estimate
+    pub fn closing_line_value(&self) -> Option<f64> {
+        self.closing_price.map(|price| price - self.avg_price)
+    }
+}
+
+/// A position in a market that hasn't resolved, valued at the current price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenPosition {
+    pub condition_id: String,
+    pub market_title: String,
+    pub outcome_index: usize,
+    pub outcome: String,
+    pub net_shares: f64,
+    pub avg_price: f64,
+    pub cost_basis: f64,  // Cost of the shares still held
+    pub current_price: Option<f64>,  // None if the market's price couldn't be fetched
+    pub market_value: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+}
+
+/// Weighted insider score for a wallet, with the sub-scores it's built from
+/// Scores run from 0 (nothing unusual) to 100 (every signal maxed out)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspicionReport {
+    pub score: f64,
+    pub flagged: bool,
+    /// 0-1 multiplier applied for small samples
+    pub confidence: f64,
+    pub win_rate: f64,
+    pub roi: f64,
+    pub timing: f64,  // Late cheap winning buys, or winning while paying the spread
+    pub concentration: f64,  // Winnings concentrated in a few positions
+    pub size_asymmetry: f64,  // Larger stakes on winners than on losers
+    /// Win rate (%) the record was tested against: 50, or the one the entry prices implied
+    #[serde(default)]
+    pub win_rate_baseline: Option<f64>,
+    /// Chance of winning at least as often as the wallet did at the baseline rate, over
+    /// positions held to resolution (None without any)
+    #[serde(default)]
+    pub win_rate_p_value: Option<f64>,
+    /// Categories or related markets that most of the winnings came from
+    #[serde(default)]
+    pub concentration_flags: Vec<ConcentrationFlag>,
+    /// Machine-like trade cadence; the win rate is left out of the score
+    #[serde(default)]
+    pub likely_bot: bool,
+    /// Trades like a market maker; its profit is spread capture rather than picks
+    #[serde(default)]
+    pub likely_market_maker: bool,
+    /// Human-readable explanation of each notable sub-score
+    pub reasons: Vec<String>,
+}
+
+/// What a wallet's winnings are concentrated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConcentrationKind {
+    /// A market category (e.g. "sports")
+    Category,
+    /// Markets sharing a title keyword (e.g. one team's games)
+    Topic,
+    /// Markets of one event (e.g. every candidate of an election)
+    Event,
+}
+
+impl ConcentrationKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConcentrationKind::Category => "category",
+            ConcentrationKind::Topic => "topic",
+            ConcentrationKind::Event => "event",
+        }
+    }
+}
+
+/// A category or group of related markets that produced most of a wallet's winnings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcentrationFlag {
+    pub kind: ConcentrationKind,
+    pub label: String,
+    /// Share of the wallet's winnings (percent)
+    pub share: f64,
+    pub profit: f64,
+    /// Winning positions in it
+    pub positions: usize,
 }
 
 /// Represents performance metrics for a wallet
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletPerformance {
     pub wallet_address: String,
     pub total_trades: usize,
     pub total_markets: usize,
+    #[serde(default)]
+    pub volume: f64,  // Dollar value of every buy and sell
     pub resolved_positions: usize,
     pub wins: usize,
     pub losses: usize,
@@ -153,7 +1742,305 @@ pub struct WalletPerformance {
     pub total_invested: f64,
     pub total_payout: f64,
     pub net_profit: f64,
+    pub realized_profit: f64,
+    pub closed_positions: usize,
+    #[serde(default)]
+    pub dust_positions: usize,  // Positions under the minimum invested, left out of the stats
     pub roi: f64,
     pub avg_profit_per_win: f64,
     pub avg_loss_per_loss: f64,
+    pub late_winning_buys: usize,  // Winning positions bought cheaply shortly before resolution
+    pub avg_hours_before_resolution: Option<f64>,  // Across winning positions
+    pub taker_fills: usize,  // Fills that took liquidity (market orders / crossing limits)
+    pub maker_fills: usize,  // Fills that rested on the book
+    pub taker_ratio: Option<f64>,  // Share of fills taken, None until roles are inferred
+    pub open_positions: Vec<OpenPosition>,  // Unresolved holdings, marked to market
+    pub unrealized_pnl: f64,  // Across open positions with a current price
+    pub attributed_positions: usize,  // Positions with a drift/skill split
+    pub drift_pnl: f64,
+    pub skill_pnl: f64,
+    pub positions: Vec<ResolvedPosition>,  // Per-market breakdown, sorted by profit
+    #[serde(default)]
+    pub periods: Vec<PeriodPerformance>,  // Resolved positions grouped by entry time, oldest first
+    #[serde(default)]
+    pub cadence: Option<TradeCadence>,  // Spacing of trades in time, None with fewer than two
+    #[serde(default)]
+    pub stake_sizing: Option<StakeSizing>,  // Sizes of the wallet's bets, None without any buys
+    #[serde(default)]
+    pub first_trade_at: Option<i64>,  // Earliest trade fetched: the wallet's age, as far as its history shows
+    #[serde(default)]
+    pub fresh_wallet_wins: Vec<FreshWalletWin>,  // Large winning bets placed soon after the first trade
+    #[serde(default)]
+    pub activity: Option<ActivitySummary>,  // Splits, merges, redemptions and rewards, None unless fetched
+    #[serde(default)]
+    pub expected_win_rate: Option<f64>,  // Win rate (%) the entry prices implied, over positions held to resolution
+    #[serde(default)]
+    pub market_making: Option<MarketMaking>,  // Two-sided, flat trading, None without trades
+    #[serde(default)]
+    pub closing_line: Option<ClosingLineValue>,  // Entry prices against the closing line, None without attribution
+}
+
+/// How a wallet's entry prices compared with the closing line: each outcome's price shortly
+/// before resolution, the market's last estimate of its probability. Beating the close
+/// consistently is hard to do by luck, whatever the results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosingLineValue {
+    pub positions: usize,
+    /// Positions entered below their closing price
+    pub beat_close: usize,
+    /// Closing price minus entry price, averaged per share bought (price points)
+    pub mean_edge: f64,
+    /// Return from marking every share bought to the closing line, over the capital
+    /// invested (%): the ROI the wallet's entries earned before resolution luck
+    pub roi: f64,
+}
+
+impl ClosingLineValue {
+    /// Share of positions entered below their closing price (%)
+    pub fn beat_rate(&self) -> f64 {
+        if self.positions == 0 { 0.0 } else { self.beat_close as f64 / self.positions as f64 * 100.0 }
+    }
+}
+
+/// A wallet's activity outside the order book, from its activity feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivitySummary {
+    /// Complete sets split, their cost shared evenly between the outcomes
+    pub splits: usize,
+    /// Complete sets merged back into USDC, as sells of every outcome at an even share of $1
+    pub merges: usize,
+    /// Redemptions of resolved markets; already counted by the resolution payout
+    pub redemptions: usize,
+    /// Negative-risk conversions, which aren't replayed into positions
+    pub conversions: usize,
+    /// Dollars of rewards received, kept out of position P&L
+    pub rewards: f64,
+}
+
+/// A large bet held to a win, entered within days of the wallet's first trade: new wallets
+/// funded to place one big bet are a common insider pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreshWalletWin {
+    pub condition_id: String,
+    pub market_title: String,
+    pub outcome: String,
+    pub entered_at: i64,
+    /// Days between the wallet's first trade and this position's first buy
+    pub wallet_age_days: f64,
+    pub stake: f64,
+    pub profit: f64,
+}
+
+/// How regularly a wallet's trades are spaced: bots tend to trade on a fixed timer
+/// (e.g. every 60s), people at irregular moments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeCadence {
+    /// Gaps between consecutive trades (fills in the same second count as one trade)
+    pub intervals: usize,
+    /// Most common gap in seconds: the median of the densest tolerance-wide bucket
+    pub typical_interval_secs: f64,
+    /// Share of gaps within the tolerance of the typical gap (0-1)
+    pub regularity: f64,
+    /// Standard deviation of the gaps over their mean
+    pub variation: f64,
+    /// Regular enough, over enough trades, to look automated
+    pub likely_bot: bool,
+}
+
+/// How much a wallet trades like a market maker: many small fills on both sides of its
+/// markets that leave it close to flat, earning the spread rather than betting on outcomes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketMaking {
+    pub trades: usize,
+    pub avg_trade_size: f64,
+    /// Share of dollars traded on the sell side (0-1); market makers sit near half
+    pub sell_share: f64,
+    /// Share of markets traded on both sides: both outcomes bought, or one bought and sold (0-1)
+    pub two_sided_share: f64,
+    /// Net dollars committed to one outcome in each market, over dollars traded (0-1)
+    pub net_exposure: f64,
+    pub likely_market_maker: bool,
+}
+
+/// How much a wallet stakes per bet, where a bet is one outcome bought in one second
+/// (the fills of one order count together)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeSizing {
+    pub bets: usize,
+    pub median_stake: f64,
+    pub p90_stake: f64,
+    /// Largest single bet, and the market it was placed on
+    pub max_stake: f64,
+    pub max_stake_market: String,
+    /// Largest bet over the wallet's estimated balance before it, among bets with enough
+    /// history before them to estimate one
+    pub max_balance_multiple: Option<f64>,
+    /// Outsized bets on thinly traded markets, oldest first
+    pub all_in_bets: Vec<AllInBet>,
+}
+
+/// A bet far larger than the wallet's estimated balance, on a market few others traded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllInBet {
+    pub condition_id: String,
+    pub market_title: String,
+    pub outcome: String,
+    pub timestamp: i64,
+    pub stake: f64,
+    /// Most the wallet had in open positions at once before the bet: a lower bound on its balance
+    pub prior_balance: f64,
+    /// Stake over the prior balance
+    pub balance_multiple: f64,
+    /// Stake over the wallet's median stake
+    pub median_multiple: f64,
+    /// Lifetime volume of the market, including the bet
+    pub market_volume: f64,
+    /// Whether the outcome bet on won; None for split resolutions
+    pub won: Option<bool>,
+}
+
+/// What a trade did to the wallet's position in an outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineAction {
+    Open,
+    Add,
+    Reduce,
+    Close,
+}
+
+impl TimelineAction {
+    /// Whether the trade bought into the outcome
+    pub fn is_entry(self) -> bool {
+        matches!(self, Self::Open | Self::Add)
+    }
+}
+
+/// One trade on a wallet's timeline, with the market's price around it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub timestamp: i64,
+    pub condition_id: String,
+    pub market_title: String,
+    pub outcome: String,
+    pub outcome_index: usize,
+    pub action: TimelineAction,
+    pub shares: f64,
+    /// Price the wallet filled at
+    pub fill_price: f64,
+    /// Shares held in the outcome after the trade
+    pub position_shares: f64,
+    /// Outcome's last traded price at the time of the trade, from price history
+    pub market_price: Option<f64>,
+    /// Outcome's price the lookahead window later
+    pub price_after: Option<f64>,
+    /// `price_after` minus `market_price`: positive when the outcome rallied after the trade
+    pub price_change: Option<f64>,
+}
+
+/// Price history of one outcome a wallet traded, to plot its trades against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineSeries {
+    pub condition_id: String,
+    pub outcome_index: usize,
+    pub outcome: String,
+    pub token_id: String,
+    pub points: Vec<PricePoint>,
+}
+
+/// Chronological record of a wallet's position entries and exits alongside market prices,
+/// serialized for plotting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletTimeline {
+    pub wallet_address: String,
+    pub generated_at: i64,
+    /// Hours after each trade its `price_after` is taken
+    pub lookahead_hours: i64,
+    /// Outcome price rise after a buy that counts as a jump
+    pub jump_threshold: f64,
+    pub events: Vec<TimelineEvent>,
+    pub series: Vec<TimelineSeries>,
+    /// Buys with a known price before and after
+    pub priced_entries: usize,
+    /// Priced buys followed by a rise of at least the jump threshold
+    pub entries_before_jumps: usize,
+    /// Mean price change over the lookahead window after priced buys
+    pub mean_change_after_entry: Option<f64>,
+}
+
+/// Record of the resolved positions entered within one calendar month or fixed-length window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodPerformance {
+    pub label: String,  // "2026-09" for a month, the first day for a fixed-length window
+    pub start: i64,
+    pub positions: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub invested: f64,
+    pub profit: f64,
+    pub roi: f64,
+}
+
+/// Type of alert emitted by the scanners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignalKind {
+    /// YES + NO priced below the arbitrage threshold
+    Arbitrage,
+    /// Open position held by a wallet flagged as a potential insider
+    InsiderEntry,
+    /// Buy by a wallet tracked for copy trading
+    SmartMoney,
+}
+
+impl SignalKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignalKind::Arbitrage => "Arbitrage",
+            SignalKind::InsiderEntry => "Insider entry",
+            SignalKind::SmartMoney => "Smart-money entry",
+        }
+    }
+}
+
+/// Outcome of grading a signal after the fact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignalGrade {
+    /// Arbitrage was still available on the next scan
+    Persisted,
+    /// Arbitrage was gone by the next scan
+    Vanished,
+    /// The signalled side won at resolution
+    Won,
+    /// The signalled side lost at resolution
+    Lost,
+}
+
+impl SignalGrade {
+    /// Whether this grade counts as the signal being right
+    pub fn is_success(&self) -> bool {
+        matches!(self, SignalGrade::Persisted | SignalGrade::Won)
+    }
+}
+
+/// A persisted alert, graded once its outcome is known
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signal {
+    pub id: String,
+    pub kind: SignalKind,
+    pub emitted_at: i64,
+    pub condition_id: String,
+    pub question: String,
+    pub outcome_index: Option<usize>,  // Side for directional signals
+    pub price: f64,                    // Entry price (or YES+NO total for arbitrage)
+    pub wallet: Option<String>,
+    #[serde(default)]
+    pub grade: Option<SignalGrade>,
+}
+
+/// Grade recorded for a signal, stored separately from the append-only signal log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalGradeRecord {
+    pub signal_id: String,
+    pub grade: SignalGrade,
+    pub graded_at: i64,
 }
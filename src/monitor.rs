@@ -0,0 +1,154 @@
+use crate::client::PolymarketClient;
+use crate::config::ArbitrageConfig;
+use crate::fees::{FeeSchedule, TradingCosts};
+use crate::models::{ArbitrageKind, Market, OrderBook, OrderLevel};
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+
+/// Shares resting within this distance of the best price count toward a side's depth
+pub const DEPTH_WINDOW: f64 = 0.05;
+
+/// One poll of a market's YES and NO order books
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorReading {
+    pub timestamp: i64,
+    pub yes: BookTop,
+    pub no: BookTop,
+}
+
+/// Best prices of one outcome's book and the shares resting near them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookTop {
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// Shares bid within [`DEPTH_WINDOW`] of the best bid
+    pub bid_depth: f64,
+    /// Shares offered within [`DEPTH_WINDOW`] of the best ask
+    pub ask_depth: f64,
+}
+
+impl BookTop {
+    pub fn from_book(book: &OrderBook) -> Self {
+        let bid = book.best_bid();
+        let ask = book.best_ask();
+        let depth = |levels: &[OrderLevel], best: Option<f64>| {
+            best.map_or(0.0, |best| {
+                levels.iter().filter(|l| (l.price - best).abs() <= DEPTH_WINDOW + 1e-9).map(|l| l.size).sum()
+            })
+        };
+        Self {
+            bid,
+            ask,
+            bid_depth: depth(&book.bids, bid),
+            ask_depth: depth(&book.asks, ask),
+        }
+    }
+
+    /// Ask minus bid, when both sides are quoted
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.ask? - self.bid?)
+    }
+}
+
+impl MonitorReading {
+    /// Cost of buying one YES and one NO share at the best asks
+    pub fn ask_total(&self) -> Option<f64> {
+        Some(self.yes.ask? + self.no.ask?)
+    }
+
+    /// Proceeds of selling one YES and one NO share at the best bids
+    pub fn bid_total(&self) -> Option<f64> {
+        Some(self.yes.bid? + self.no.bid?)
+    }
+
+    /// Whether anything but the time differs from another reading
+    pub fn changed_from(&self, other: &MonitorReading) -> bool {
+        self.yes != other.yes || self.no != other.no
+    }
+}
+
+/// Totals past which a monitored market is an arbitrage
+#[derive(Debug, Clone, Copy)]
+pub struct MonitorThresholds {
+    /// Buy both when the ask total is below this
+    pub buy_below: f64,
+    /// Sell both when the bid total is above this
+    pub sell_above: f64,
+}
+
+/// Polls one market's order books to stake it out, e.g. around a news event
+pub struct MarketMonitor {
+    client: PolymarketClient,
+    costs: TradingCosts,
+    /// Fixed totals from the config, used instead of the fee-derived ones when set
+    threshold: Option<f64>,
+    overpriced_threshold: Option<f64>,
+}
+
+impl MarketMonitor {
+    pub fn new(client: PolymarketClient, config: &ArbitrageConfig) -> Self {
+        Self {
+            client,
+            costs: TradingCosts::from_config(config),
+            threshold: config.threshold,
+            overpriced_threshold: config.overpriced_threshold,
+        }
+    }
+
+    /// Looks a binary market up by condition ID or, failing that, by its URL slug
+    pub async fn resolve(&self, id_or_slug: &str) -> Result<Market> {
+        let market = if id_or_slug.starts_with("0x") {
+            self.client.fetch_markets_by_condition_ids(&[id_or_slug.to_string()]).await?.into_iter().next()
+        } else {
+            self.client.fetch_market_by_slug(id_or_slug).await?
+        };
+        let market = market.with_context(|| format!("No market found for {}", id_or_slug))?;
+
+        match market.parsed_clob_token_ids() {
+            Some(tokens) if tokens.len() == 2 => Ok(market),
+            _ => bail!("{} isn't a binary market with CLOB token IDs", market.question),
+        }
+    }
+
+    /// Fetches both outcome books of a market resolved by [`MarketMonitor::resolve`]
+    pub async fn read(&self, market: &Market) -> Result<MonitorReading> {
+        let tokens = market.parsed_clob_token_ids().unwrap_or_default();
+        let [yes_token, no_token] = tokens.as_slice() else {
+            bail!("{} isn't a binary market", market.question);
+        };
+        let (yes, no) = tokio::try_join!(self.client.fetch_order_book(yes_token), self.client.fetch_order_book(no_token))?;
+
+        Ok(MonitorReading {
+            timestamp: Utc::now().timestamp(),
+            yes: BookTop::from_book(&yes),
+            no: BookTop::from_book(&no),
+        })
+    }
+
+    /// Totals past which the reading is worth trading after fees and the configured edge,
+    /// the same break-even the arbitrage scanner uses (or its fixed thresholds)
+    pub fn thresholds(&self, market: &Market, reading: &MonitorReading) -> MonitorThresholds {
+        let schedule = FeeSchedule::from_market(market);
+        let legs = |yes: Option<f64>, no: Option<f64>| [(schedule, yes.unwrap_or(0.5)), (schedule, no.unwrap_or(0.5))];
+
+        MonitorThresholds {
+            buy_below: self
+                .threshold
+                .unwrap_or_else(|| self.costs.threshold(&legs(reading.yes.ask, reading.no.ask), 1.0)),
+            sell_above: self
+                .overpriced_threshold
+                .unwrap_or_else(|| self.costs.overpriced_threshold(&legs(reading.yes.bid, reading.no.bid), 1.0)),
+        }
+    }
+
+    /// The arbitrage a reading shows, if its totals cross the thresholds
+    pub fn crossing(&self, reading: &MonitorReading, thresholds: &MonitorThresholds) -> Option<ArbitrageKind> {
+        if reading.ask_total().is_some_and(|total| total < thresholds.buy_below) {
+            Some(ArbitrageKind::BuyBoth)
+        } else if reading.bid_total().is_some_and(|total| total > thresholds.sell_above) {
+            Some(ArbitrageKind::SellBoth)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Console output, with an optional plain mode for logging systems and terminals
+//! that mangle Unicode.
+//!
+//! Library modules and the CLI print through the crate's `outln!`, `out!`, `eoutln!` and
+//! `eout!` macros (exported at the crate root), which pass every line through
+//! [`Console::render`], so call sites don't need to know about plain mode. In plain mode
+//! emojis and other symbols are replaced with ASCII tags or dropped, box-drawing becomes
+//! `-`, `|` and `+`, and ANSI escape sequences are removed. Letters in any script (e.g.
+//! market titles) are kept.
+//!
+//! While a full-screen view such as the dashboard owns the terminal,
+//! [`Console::start_capture`] diverts printed lines into a buffer it can display, and drops
+//! progress output printed without a newline.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Captured lines kept before the oldest are dropped
+const CAPTURE_LIMIT: usize = 500;
+
+/// The process's console: how output is rendered and whether it's printed or captured
+pub struct Console {
+    plain: AtomicBool,
+    /// Captured lines, oldest first (None when printing normally)
+    capture: Mutex<Option<VecDeque<String>>>,
+}
+
+/// Standard output and standard error are process-wide, so is the console writing to them
+static CONSOLE: Console = Console {
+    plain: AtomicBool::new(false),
+    capture: Mutex::new(None),
+};
+
+impl Console {
+    pub fn global() -> &'static Console {
+        &CONSOLE
+    }
+
+    /// Turns plain output on or off for the rest of the process
+    pub fn set_plain(&self, plain: bool) {
+        self.plain.store(plain, Ordering::Relaxed);
+    }
+
+    /// Whether plain output is on
+    pub fn is_plain(&self) -> bool {
+        self.plain.load(Ordering::Relaxed)
+    }
+
+    /// Renders text for the console: unchanged normally, ASCII-safe in plain mode
+    pub fn render<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.is_plain() || text.is_ascii() && !text.contains('\x1b') {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(to_plain(text))
+    }
+
+    /// Diverts printed lines into the capture buffer until [`Console::stop_capture`]
+    pub fn start_capture(&self) {
+        *self.capture.lock().unwrap_or_else(|e| e.into_inner()) = Some(VecDeque::new());
+    }
+
+    /// Goes back to printing normally, discarding captured lines
+    pub fn stop_capture(&self) {
+        *self.capture.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Whether output is being captured
+    pub fn is_capturing(&self) -> bool {
+        self.capture.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    /// The most recent `count` captured lines, oldest first
+    pub fn captured_lines(&self, count: usize) -> Vec<String> {
+        let capture = self.capture.lock().unwrap_or_else(|e| e.into_inner());
+        capture
+            .as_ref()
+            .map(|lines| lines.iter().skip(lines.len().saturating_sub(count)).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Prints a line to standard output, rendered (or captured)
+    pub fn line(&self, args: fmt::Arguments) {
+        let text = args.to_string();
+        if !self.capture(&text) {
+            std::println!("{}", self.render(&text));
+        }
+    }
+
+    /// Prints text to standard output without a newline, rendered (dropped while capturing)
+    pub fn text(&self, args: fmt::Arguments) {
+        if !self.is_capturing() {
+            std::print!("{}", self.render(&args.to_string()));
+        }
+    }
+
+    /// Prints a line to standard error, rendered (or captured)
+    pub fn error_line(&self, args: fmt::Arguments) {
+        let text = args.to_string();
+        if !self.capture(&text) {
+            std::eprintln!("{}", self.render(&text));
+        }
+    }
+
+    /// Prints text to standard error without a newline, rendered (dropped while capturing)
+    pub fn error_text(&self, args: fmt::Arguments) {
+        if !self.is_capturing() {
+            std::eprint!("{}", self.render(&args.to_string()));
+        }
+    }
+
+    /// Adds text to the capture buffer, one entry per non-blank line
+    /// Returns false when not capturing, so the caller prints it instead
+    fn capture(&self, text: &str) -> bool {
+        let mut capture = self.capture.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(lines) = capture.as_mut() else {
+            return false;
+        };
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            if lines.len() == CAPTURE_LIMIT {
+                lines.pop_front();
+            }
+            lines.push_back(self.render(line).into_owned());
+        }
+        true
+    }
+}
+
+/// Whether the environment asks for plain output: `SCANNER_PLAIN` set to anything but
+/// `0`/`false`, `TERM=dumb`, or a locale that isn't UTF-8
+pub fn plain_from_env() -> bool {
+    if let Ok(value) = std::env::var("SCANNER_PLAIN") {
+        return !matches!(value.trim().to_lowercase().as_str(), "" | "0" | "false");
+    }
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return true;
+    }
+
+    // The first locale variable set wins, as in libc
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        })
+}
+
+/// Strips or replaces everything that isn't plain text
+pub fn to_plain(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // ANSI escape sequence: ESC [ parameters final-byte
+        if c == '\x1b' {
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if c.is_ascii() || c.is_alphanumeric() {
+            plain.push(c);
+            continue;
+        }
+
+        match replacement(c) {
+            Some(ascii) => plain.push_str(ascii),
+            // Dropped symbols are usually a leading icon followed by a space
+            None => {
+                if chars.peek() == Some(&' ') && (plain.is_empty() || plain.ends_with([' ', '\n', '\t'])) {
+                    chars.next();
+                }
+            }
+        }
+    }
+
+    plain
+}
+
+/// ASCII stand-in for a symbol, or None to drop it
+fn replacement(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        '✓' | '✔' | '✅' => "[OK]",
+        '✗' | '✘' | '❌' => "[FAIL]",
+        '⚠' => "[WARN]",
+        '🛑' => "[STOP]",
+        '🆕' => "[NEW]",
+        '•' | '·' => "*",
+        '→' => "->",
+        '←' => "<-",
+        '≥' => ">=",
+        '≤' => "<=",
+        '×' => "x",
+        '±' => "+/-",
+        '¢' => "c",
+        '…' => "...",
+        '–' | '—' | '−' => "-",
+        '‘' | '’' => "'",
+        '“' | '”' => "\"",
+        '\u{a0}' => " ",
+        // Box drawing
+        '─' | '━' | '┄' | '┈' | '╌' => "-",
+        '═' => "=",
+        '│' | '┃' | '║' | '┆' | '┊' => "|",
+        '\u{2500}'..='\u{257f}' => "+",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Like `println!`, through the console
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::output::Console::global().line(::std::format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::Console::global().line(::std::format_args!($($arg)*))
+    };
+}
+
+/// Like `print!`, through the console
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::Console::global().text(::std::format_args!($($arg)*))
+    };
+}
+
+/// Like `eprintln!`, through the console
+#[macro_export]
+macro_rules! eoutln {
+    () => {
+        $crate::output::Console::global().error_line(::std::format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::Console::global().error_line(::std::format_args!($($arg)*))
+    };
+}
+
+/// Like `eprint!`, through the console
+#[macro_export]
+macro_rules! eout {
+    ($($arg:tt)*) => {
+        $crate::output::Console::global().error_text(::std::format_args!($($arg)*))
+    };
+}
@@ -0,0 +1,288 @@
+use crate::client::PolymarketClient;
+use crate::config::PaperConfig;
+use crate::fees::{FeeSchedule, TradingCosts};
+use crate::models::{ArbitrageKind, ArbitrageOpportunity, Market, OrderBook};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Append-only log of simulated executions
+const PAPER_TRADES_FILE: &str = "paper_trades.jsonl";
+
+/// What happened when an opportunity was executed against the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaperStatus {
+    /// Both legs filled at a profit
+    Filled,
+    /// The book was unprofitable to walk at every size down to the minimum order
+    Unprofitable,
+    /// One side had no asks at all
+    NoDepth,
+}
+
+impl PaperStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaperStatus::Filled => "filled",
+            PaperStatus::Unprofitable => "unprofitable",
+            PaperStatus::NoDepth => "no depth",
+        }
+    }
+}
+
+/// One simulated execution of a YES + NO arbitrage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperTrade {
+    pub timestamp: i64,
+    pub condition_id: String,
+    pub question: String,
+    pub status: PaperStatus,
+    /// Shares per leg the simulation aimed for
+    pub target_shares: f64,
+    /// Shares per leg bought (0 unless filled)
+    pub shares: f64,
+    /// YES + NO when the scanner flagged the market
+    pub quoted_total: f64,
+    /// Average ask paid per leg, at the size tried last
+    pub yes_fill_price: Option<f64>,
+    pub no_fill_price: Option<f64>,
+    /// Taker fees plus the fixed cost per trade, in dollars
+    pub costs: f64,
+    /// Profit had the target size filled at the quoted prices, before costs
+    pub theoretical_profit: f64,
+    /// Profit locked in by the simulated fill after costs (0 unless filled)
+    pub simulated_profit: f64,
+}
+
+/// Simulates executing both legs of each new arbitrage against the live order
+/// book, to measure how much of the quoted edge survives depth and fees
+#[derive(Clone)]
+pub struct PaperTrader {
+    client: PolymarketClient,
+    store: Store,
+    costs: TradingCosts,
+    shares: f64,
+}
+
+impl PaperTrader {
+    pub fn new(client: PolymarketClient, store: Store, costs: TradingCosts, config: &PaperConfig) -> Self {
+        Self {
+            client,
+            store,
+            costs,
+            shares: config.shares.max(1.0),
+        }
+    }
+
+    /// Fetches both books for an opportunity's market and simulates buying each leg
+    pub async fn execute(&self, market: &Market, opportunity: &ArbitrageOpportunity) -> Result<PaperTrade> {
+        let token_ids = market
+            .parsed_clob_token_ids()
+            .filter(|ids| ids.len() == 2)
+            .with_context(|| format!("Market {} is not a binary market with CLOB tokens", market.question))?;
+
+        let (yes_book, no_book) = tokio::join!(
+            self.client.fetch_order_book(&token_ids[0]),
+            self.client.fetch_order_book(&token_ids[1])
+        );
+
+        Ok(self.simulate(market, opportunity, &yes_book?, &no_book?))
+    }
+
+    /// Walks both books for the target size, halving it until the basket is
+    /// profitable after fees or falls below the market's minimum order
+    pub fn simulate(
+        &self,
+        market: &Market,
+        opportunity: &ArbitrageOpportunity,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+    ) -> PaperTrade {
+        let fees = FeeSchedule::from_market(market);
+        let mut trade = PaperTrade {
+            timestamp: Utc::now().timestamp(),
+            condition_id: opportunity.condition_id.clone().unwrap_or_default(),
+            question: opportunity.question.clone(),
+            status: PaperStatus::NoDepth,
+            target_shares: self.shares,
+            shares: 0.0,
+            quoted_total: opportunity.total_cost,
+            yes_fill_price: None,
+            no_fill_price: None,
+            costs: 0.0,
+            theoretical_profit: (1.0 - opportunity.total_cost) * self.shares,
+            simulated_profit: 0.0,
+        };
+
+        if yes_book.asks.is_empty() || no_book.asks.is_empty() {
+            return trade;
+        }
+
+        // Never ask for more than either side can fill
+        let depth = |book: &OrderBook| book.asks.iter().map(|level| level.size).sum::<f64>();
+        let mut shares = self.shares.min(depth(yes_book)).min(depth(no_book));
+        let min_shares = fees.min_order_size.max(1.0);
+        trade.status = PaperStatus::Unprofitable;
+
+        while shares >= min_shares {
+            if let Some((yes_price, no_price)) = yes_book.average_ask_price(shares).zip(no_book.average_ask_price(shares)) {
+                let costs = shares * (fees.taker_fee_per_share(yes_price) + fees.taker_fee_per_share(no_price))
+                    + self.costs.fixed_cost_per_trade;
+                let profit = shares * (1.0 - yes_price - no_price) - costs;
+
+                trade.yes_fill_price = Some(yes_price);
+                trade.no_fill_price = Some(no_price);
+                trade.costs = costs;
+                if profit > 0.0 {
+                    trade.status = PaperStatus::Filled;
+                    trade.shares = shares;
+                    trade.simulated_profit = profit;
+                    break;
+                }
+            }
+            shares = (shares / 2.0).floor();
+        }
+
+        trade
+    }
+
+    /// Simulates each opportunity and records the results, logging (not propagating) failures
+    /// Only buy-both opportunities are taken; selling both sides needs a split first
+    pub async fn execute_flagged(&self, opportunities: &[&ArbitrageOpportunity]) {
+        let opportunities: Vec<&ArbitrageOpportunity> =
+            opportunities.iter().copied().filter(|o| o.kind == ArbitrageKind::BuyBoth).collect();
+        let condition_ids: Vec<String> = opportunities.iter().filter_map(|o| o.condition_id.clone()).collect();
+        if condition_ids.is_empty() {
+            return;
+        }
+
+        let markets = match self.client.fetch_markets_by_condition_ids(&condition_ids).await {
+            Ok(markets) => markets,
+            Err(e) => {
+                eoutln!("Warning: Failed to fetch markets for paper trading: {}", e);
+                return;
+            }
+        };
+
+        let mut trades = Vec::new();
+        for opportunity in opportunities {
+            let Some(market) = markets.iter().find(|m| m.condition_id == opportunity.condition_id) else {
+                continue;
+            };
+            match self.execute(market, opportunity).await {
+                Ok(trade) => {
+                    print_trade(&trade);
+                    trades.push(trade);
+                }
+                Err(e) => eoutln!("Warning: Failed to paper trade {}: {}", opportunity.question, e),
+            }
+        }
+
+        if let Err(e) = self.store.append_jsonl(PAPER_TRADES_FILE, &trades) {
+            eoutln!("Warning: Failed to record paper trades: {}", e);
+        }
+    }
+}
+
+/// Reads back the simulated executions
+pub struct PaperLedger {
+    store: Store,
+}
+
+impl PaperLedger {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Loads executions simulated within the last `days`
+    pub fn load_since(&self, days: i64) -> Result<Vec<PaperTrade>> {
+        let since = Utc::now().timestamp() - days * 86_400;
+        Ok(self
+            .store
+            .read_jsonl::<PaperTrade>(PAPER_TRADES_FILE)?
+            .into_iter()
+            .filter(|t| t.timestamp >= since)
+            .collect())
+    }
+
+    /// Prints how much of the quoted edge survived execution, overall and by day
+    pub fn print_report(&self, trades: &[PaperTrade], days: i64) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("PAPER TRADING (last {} days)", days);
+        outln!("{}", "=".repeat(80));
+
+        if trades.is_empty() {
+            outln!("\nNo simulated executions yet. Run the arbitrage scanner with --paper to record them.");
+            return;
+        }
+
+        let count = |status: PaperStatus| trades.iter().filter(|t| t.status == status).count();
+        let theoretical: f64 = trades.iter().map(|t| t.theoretical_profit).sum();
+        let simulated: f64 = trades.iter().map(|t| t.simulated_profit).sum();
+        let invested: f64 = trades
+            .iter()
+            .filter(|t| t.status == PaperStatus::Filled)
+            .map(|t| t.shares - t.simulated_profit)
+            .sum();
+
+        outln!("\nOpportunities:        {}", trades.len());
+        outln!("Filled:               {}", count(PaperStatus::Filled));
+        outln!("Unprofitable:         {}", count(PaperStatus::Unprofitable));
+        outln!("No Depth:             {}", count(PaperStatus::NoDepth));
+        outln!("Theoretical Profit:   ${:.2}", theoretical);
+        outln!("Simulated Profit:     ${:.2}", simulated);
+        if theoretical > 0.0 {
+            outln!("Edge Captured:        {:.1}%", simulated / theoretical * 100.0);
+        }
+        if invested > 0.0 {
+            outln!("Return on Capital:    {:.2}%", simulated / invested * 100.0);
+        }
+
+        // (opportunities, filled, theoretical, simulated) per UTC day
+        let mut by_day: BTreeMap<String, (usize, usize, f64, f64)> = BTreeMap::new();
+        for trade in trades {
+            let day = DateTime::from_timestamp(trade.timestamp, 0)
+                .map(|t| t.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            let entry = by_day.entry(day).or_default();
+            entry.0 += 1;
+            entry.1 += (trade.status == PaperStatus::Filled) as usize;
+            entry.2 += trade.theoretical_profit;
+            entry.3 += trade.simulated_profit;
+        }
+
+        outln!("\n--- By Day (UTC) ---");
+        outln!("{:<10} {:>6} {:>6} {:>12} {:>12} {:>12}", "Day", "Opps", "Filled", "Theoretical", "Simulated", "Cumulative");
+        let mut cumulative = 0.0;
+        for (day, (opportunities, filled, theoretical, simulated)) in &by_day {
+            cumulative += simulated;
+            outln!(
+                "{:<10} {:>6} {:>6} {:>12.2} {:>12.2} {:>12.2}",
+                day, opportunities, filled, theoretical, simulated, cumulative
+            );
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Prints one line summarizing a simulated execution
+fn print_trade(trade: &PaperTrade) {
+    let fill = match trade.yes_fill_price.zip(trade.no_fill_price) {
+        Some((yes, no)) => format!("YES {:.4} + NO {:.4} = {:.4}", yes, no, yes + no),
+        None => "no fill".to_string(),
+    };
+    outln!(
+        "🧾 Paper trade ({}): {} | quoted {:.4}, book {} | {:.0} shares, profit ${:.2} of ${:.2} quoted",
+        trade.status.label(),
+        trade.question,
+        trade.quoted_total,
+        fill,
+        trade.shares,
+        trade.simulated_profit,
+        trade.theoretical_profit
+    );
+}
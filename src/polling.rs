@@ -0,0 +1,102 @@
+use crate::config::ArbitrageConfig;
+use crate::models::{Signal, SignalKind};
+use chrono::{DateTime, Timelike};
+use std::time::Duration;
+
+/// Days of past arbitrage signals used to learn which hours of the day are busy
+const HISTORY_DAYS: i64 = 14;
+/// Hours seeing less than this share of the average hour's opportunities are quiet
+const QUIET_ACTIVITY: f64 = 0.5;
+/// Hours seeing more than this multiple of the average hour's opportunities are busy
+const BUSY_ACTIVITY: f64 = 2.0;
+/// Growth of the interval after each scan that finds nothing
+const BACKOFF: f64 = 1.5;
+
+/// Chooses the wait before each arbitrage scan from what recent scans found and how busy the
+/// hour of day has been: scans that find opportunities drop to the fastest interval, since
+/// mispricings cluster around news, and empty scans back off toward the hour's resting
+/// interval, which is the slowest one in hours that historically see few opportunities
+#[derive(Debug, Clone)]
+pub struct AdaptivePolling {
+    base_secs: f64,
+    min_secs: f64,
+    max_secs: f64,
+    /// Opportunities first seen in each UTC hour, relative to the average hour (1.0 = average)
+    hourly_activity: [f64; 24],
+    current_secs: f64,
+}
+
+impl AdaptivePolling {
+    /// Polls between `min_poll_interval_secs` and `max_poll_interval_secs`, resting at
+    /// `poll_interval_secs` in ordinary hours
+    pub fn from_config(config: &ArbitrageConfig) -> Self {
+        let min_secs = config.min_poll_interval_secs.max(1) as f64;
+        let max_secs = (config.max_poll_interval_secs as f64).max(min_secs);
+        let base_secs = (config.poll_interval_secs as f64).clamp(min_secs, max_secs);
+        Self {
+            base_secs,
+            min_secs,
+            max_secs,
+            hourly_activity: [1.0; 24],
+            current_secs: base_secs,
+        }
+    }
+
+    /// Learns each hour's activity from the arbitrage signals of the last two weeks
+    /// Without any, every hour counts as average
+    pub fn with_history(mut self, signals: &[Signal], now: i64) -> Self {
+        let since = now - HISTORY_DAYS * 86400;
+        let mut counts = [0usize; 24];
+        for signal in signals.iter().filter(|s| s.kind == SignalKind::Arbitrage && s.emitted_at >= since) {
+            if let Some(time) = DateTime::from_timestamp(signal.emitted_at, 0) {
+                counts[time.hour() as usize] += 1;
+            }
+        }
+
+        let total: usize = counts.iter().sum();
+        if total > 0 {
+            let average = total as f64 / 24.0;
+            for (activity, count) in self.hourly_activity.iter_mut().zip(counts) {
+                *activity = count as f64 / average;
+            }
+        }
+        self
+    }
+
+    /// Activity of the hour of day `now` falls in, relative to the average hour
+    pub fn activity_at(&self, now: i64) -> f64 {
+        DateTime::from_timestamp(now, 0).map_or(1.0, |time| self.hourly_activity[time.hour() as usize])
+    }
+
+    /// The interval empty scans back off to at `now`: the slowest in quiet hours, halfway to
+    /// the fastest in busy ones, and the configured interval otherwise
+    pub fn resting_interval(&self, now: i64) -> Duration {
+        let activity = self.activity_at(now);
+        let secs = if activity < QUIET_ACTIVITY {
+            self.max_secs
+        } else if activity > BUSY_ACTIVITY {
+            (self.base_secs + self.min_secs) / 2.0
+        } else {
+            self.base_secs
+        };
+        Duration::from_secs_f64(secs)
+    }
+
+    /// Records how many opportunities a scan found and returns the wait before the next
+    pub fn observe(&mut self, opportunities: usize, now: i64) -> Duration {
+        let resting = self.resting_interval(now).as_secs_f64();
+        self.current_secs = if opportunities > 0 {
+            self.min_secs
+        } else if self.current_secs < resting {
+            (self.current_secs * BACKOFF).min(resting)
+        } else {
+            resting
+        };
+        self.current()
+    }
+
+    /// The wait chosen after the latest scan
+    pub fn current(&self) -> Duration {
+        Duration::from_secs_f64(self.current_secs)
+    }
+}
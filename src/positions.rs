@@ -0,0 +1,179 @@
+use crate::models::{Position, WalletPosition};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Holdings smaller than this many shares are rounding residue, not positions
+const MIN_SHARES: f64 = 0.01;
+/// Share of the larger size the API and the ledger may differ by and still agree
+const SIZE_TOLERANCE: f64 = 0.01;
+/// Dollars per share the average prices may differ by and still agree
+const PRICE_TOLERANCE: f64 = 0.005;
+
+/// A position the positions API and the ledger rebuilt from trade history disagree on
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionMismatch {
+    pub condition_id: String,
+    pub outcome_index: usize,
+    pub market_title: String,
+    pub outcome: String,
+    /// None if the API doesn't report the position
+    pub api_size: Option<f64>,
+    pub api_avg_price: Option<f64>,
+    /// None if the ledger doesn't hold the position
+    pub ledger_size: Option<f64>,
+    pub ledger_avg_price: Option<f64>,
+}
+
+impl PositionMismatch {
+    /// What differs, e.g. "size 120.0 vs 100.0" or "missing from trade history"
+    pub fn describe(&self) -> String {
+        match (self.api_size, self.ledger_size) {
+            (Some(_), None) => "missing from trade history".to_string(),
+            (None, Some(_)) => "not reported by the positions API".to_string(),
+            (Some(api_size), Some(ledger_size)) => {
+                let mut differences = Vec::new();
+                if !sizes_agree(api_size, ledger_size) {
+                    differences.push(format!("size {:.2} vs {:.2}", api_size, ledger_size));
+                }
+                if let (Some(api_price), Some(ledger_price)) = (self.api_avg_price, self.ledger_avg_price) {
+                    if (api_price - ledger_price).abs() > PRICE_TOLERANCE {
+                        differences.push(format!("avg price ${:.3} vs ${:.3}", api_price, ledger_price));
+                    }
+                }
+                differences.join(", ")
+            }
+            (None, None) => String::new(),
+        }
+    }
+}
+
+fn sizes_agree(a: f64, b: f64) -> bool {
+    (a - b).abs() <= SIZE_TOLERANCE * a.max(b).max(1.0)
+}
+
+/// Compares the API's unresolved positions against the analyzer's open positions (the
+/// ledger's, in markets still trading), returning every one they disagree on by size or
+/// average price, or that only one side holds
+pub fn reconcile(api_positions: &[WalletPosition], ledger_positions: &[Position]) -> Vec<PositionMismatch> {
+    let api: HashMap<(&str, usize), &WalletPosition> = api_positions
+        .iter()
+        .filter(|p| !p.redeemable && p.size >= MIN_SHARES)
+        .map(|p| ((p.condition_id.as_str(), p.outcome_index), p))
+        .collect();
+    let ledger: HashMap<(&str, usize), &Position> = ledger_positions
+        .iter()
+        .filter(|p| p.net_shares >= MIN_SHARES)
+        .map(|p| ((p.condition_id.as_str(), p.outcome_index), p))
+        .collect();
+    let keys: BTreeSet<(&str, usize)> = api.keys().chain(ledger.keys()).copied().collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let api_position = api.get(&key);
+            let ledger_position = ledger.get(&key);
+            if let (Some(a), Some(l)) = (api_position, ledger_position) {
+                if sizes_agree(a.size, l.net_shares) && (a.avg_price - l.avg_price).abs() <= PRICE_TOLERANCE {
+                    return None;
+                }
+            }
+            Some(PositionMismatch {
+                condition_id: key.0.to_string(),
+                outcome_index: key.1,
+                market_title: api_position
+                    .map(|p| p.title.clone())
+                    .or_else(|| ledger_position.map(|p| p.market_title.clone()))
+                    .unwrap_or_default(),
+                outcome: api_position
+                    .map(|p| p.outcome.clone())
+                    .or_else(|| ledger_position.map(|p| p.outcome.clone()))
+                    .unwrap_or_default(),
+                api_size: api_position.map(|p| p.size),
+                api_avg_price: api_position.map(|p| p.avg_price),
+                ledger_size: ledger_position.map(|p| p.net_shares),
+                ledger_avg_price: ledger_position.map(|p| p.avg_price),
+            })
+        })
+        .collect()
+}
+
+/// Prints a wallet's positions, largest market value first, with totals for those still open
+pub fn print_positions(wallet: &str, positions: &[WalletPosition]) {
+    let mut positions: Vec<&WalletPosition> = positions.iter().filter(|p| p.size >= MIN_SHARES).collect();
+    positions.sort_by(|a, b| b.current_value.total_cmp(&a.current_value));
+    let (redeemable, open): (Vec<&WalletPosition>, Vec<&WalletPosition>) = positions.iter().partition(|p| p.redeemable);
+
+    let cost: f64 = open.iter().map(|p| p.initial_value).sum();
+    let value: f64 = open.iter().map(|p| p.current_value).sum();
+    let unrealized = value - cost;
+
+    outln!("\n{}", "=".repeat(80));
+    outln!("WALLET POSITIONS");
+    outln!("{}", "=".repeat(80));
+    outln!("\nWallet:               {}", wallet);
+    outln!("Open Positions:       {}", open.len());
+    outln!("Cost Basis:           ${:.2}", cost);
+    outln!("Market Value:         ${:.2}", value);
+    outln!(
+        "Unrealized P&L:       ${:.2} ({:+.1}%)",
+        unrealized,
+        if cost > 0.0 { unrealized / cost * 100.0 } else { 0.0 }
+    );
+    outln!("Realized P&L:         ${:.2}", positions.iter().map(|p| p.realized_pnl).sum::<f64>());
+    if !redeemable.is_empty() {
+        outln!(
+            "Redeemable:           {} (${:.2})",
+            redeemable.len(),
+            redeemable.iter().map(|p| p.current_value).sum::<f64>()
+        );
+    }
+
+    if positions.is_empty() {
+        outln!("\nThe wallet holds no positions.");
+    }
+
+    for (i, position) in positions.iter().enumerate() {
+        outln!(
+            "\n{}. {}{}",
+            i + 1,
+            position.title,
+            if position.redeemable { "  (resolved, redeemable)" } else { "" }
+        );
+        outln!(
+            "   {:.1} {} at ${:.3} avg, now ${:.3} | value ${:.2} | P&L ${:.2} ({:+.1}%)",
+            position.size,
+            position.outcome,
+            position.avg_price,
+            position.cur_price,
+            position.current_value,
+            position.cash_pnl,
+            position.percent_pnl
+        );
+    }
+
+    outln!("\n{}", "=".repeat(80));
+}
+
+/// Prints how the API's open positions compare with the ones rebuilt from trade history
+pub fn print_reconciliation(
+    api_positions: &[WalletPosition],
+    ledger_positions: &[Position],
+    mismatches: &[PositionMismatch],
+) {
+    outln!("\n--- Cross-check against trade history ---");
+    outln!(
+        "API Positions:        {}",
+        api_positions.iter().filter(|p| !p.redeemable && p.size >= MIN_SHARES).count()
+    );
+    outln!(
+        "Ledger Positions:     {}",
+        ledger_positions.iter().filter(|p| p.net_shares >= MIN_SHARES).count()
+    );
+    outln!("Mismatches:           {}", mismatches.len());
+    for mismatch in mismatches {
+        outln!("  • {} [{}]: {}", mismatch.market_title, mismatch.outcome, mismatch.describe());
+    }
+    if mismatches.is_empty() {
+        outln!("The analyzer's open positions match the API's.");
+    }
+    outln!("\n{}", "=".repeat(80));
+}
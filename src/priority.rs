@@ -0,0 +1,132 @@
+use crate::alerts::{Alert, AlertDispatcher};
+use crate::client::PolymarketClient;
+use crate::config::{AlertsConfig, PriorityAlertConfig};
+use crate::fees::{FeeSchedule, TradingCosts};
+use crate::models::{ArbitrageKind, ArbitrageOpportunity, Market, OrderBook};
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Prices an opportunity's full size fills at on both books, and the profit left after fees
+#[derive(Debug, Clone, Serialize)]
+pub struct BookCheck {
+    pub shares: f64,
+    /// Average fill price of each leg: asks when buying both, bids when selling both
+    pub yes_price: f64,
+    pub no_price: f64,
+    /// Profit on the whole basket after taker fees and the fixed cost per trade
+    pub net_profit: f64,
+}
+
+/// Separates actionable arbitrage from research noise: an opportunity only raises a
+/// high-confidence alert when its market is busy and deep enough, and its edge still
+/// holds when the trade size is walked through both order books
+pub struct PriorityAlerts {
+    client: PolymarketClient,
+    config: PriorityAlertConfig,
+    costs: TradingCosts,
+    dispatcher: AlertDispatcher,
+}
+
+impl PriorityAlerts {
+    /// Priority alerting as configured, or None when it's disabled or has nowhere to send
+    pub fn from_config(client: PolymarketClient, alerts: &AlertsConfig, costs: TradingCosts) -> Result<Option<Self>> {
+        if !alerts.priority.enabled {
+            return Ok(None);
+        }
+        let dispatcher = AlertDispatcher::from_config(alerts).single_channel(alerts.priority.channel.as_deref())?;
+        if !dispatcher.is_enabled() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            client,
+            config: alerts.priority.clone(),
+            costs,
+            dispatcher,
+        }))
+    }
+
+    /// Whether the opportunity's market clears the 24h volume and liquidity floors
+    pub fn qualifies(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        opportunity.volume_24h >= self.config.min_volume_24h && opportunity.liquidity >= self.config.min_liquidity
+    }
+
+    /// Fetches both books and checks the opportunity's trade size fills at a profit
+    /// Returns None when either book is too thin or the fills eat the edge
+    pub async fn verify(&self, market: &Market, opportunity: &ArbitrageOpportunity) -> Result<Option<BookCheck>> {
+        let token_ids = market
+            .parsed_clob_token_ids()
+            .filter(|ids| ids.len() == 2)
+            .with_context(|| format!("Market {} is not a binary market with CLOB tokens", market.question))?;
+
+        let (yes_book, no_book) = tokio::join!(
+            self.client.fetch_order_book(&token_ids[0]),
+            self.client.fetch_order_book(&token_ids[1])
+        );
+
+        Ok(self.check_books(market, opportunity, &yes_book?, &no_book?))
+    }
+
+    fn check_books(
+        &self,
+        market: &Market,
+        opportunity: &ArbitrageOpportunity,
+        yes_book: &OrderBook,
+        no_book: &OrderBook,
+    ) -> Option<BookCheck> {
+        let shares = opportunity.trade_size.max(1.0);
+        let (yes_price, no_price) = match opportunity.kind {
+            ArbitrageKind::BuyBoth => (yes_book.average_ask_price(shares)?, no_book.average_ask_price(shares)?),
+            ArbitrageKind::SellBoth => (yes_book.average_bid_price(shares)?, no_book.average_bid_price(shares)?),
+        };
+
+        let fees = FeeSchedule::from_market(market);
+        let costs = shares * (fees.taker_fee_per_share(yes_price) + fees.taker_fee_per_share(no_price))
+            + self.costs.fixed_cost_per_trade;
+        let gross = match opportunity.kind {
+            ArbitrageKind::BuyBoth => 1.0 - yes_price - no_price,
+            ArbitrageKind::SellBoth => yes_price + no_price - 1.0,
+        };
+        let net_profit = shares * gross - costs;
+
+        (net_profit > 0.0).then_some(BookCheck {
+            shares,
+            yes_price,
+            no_price,
+            net_profit,
+        })
+    }
+
+    /// Verifies each qualifying opportunity and sends a high-confidence alert for those
+    /// that pass, logging (not propagating) failures
+    pub async fn process(&self, opportunities: &[&ArbitrageOpportunity]) {
+        let candidates: Vec<&ArbitrageOpportunity> =
+            opportunities.iter().copied().filter(|o| self.qualifies(o)).collect();
+        let condition_ids: Vec<String> = candidates.iter().filter_map(|o| o.condition_id.clone()).collect();
+        if condition_ids.is_empty() {
+            return;
+        }
+
+        let markets = match self.client.fetch_markets_by_condition_ids(&condition_ids).await {
+            Ok(markets) => markets,
+            Err(e) => {
+                eoutln!("Warning: Failed to fetch markets for priority alerts: {}", e);
+                return;
+            }
+        };
+
+        for opportunity in candidates {
+            let Some(market) = markets.iter().find(|m| m.condition_id == opportunity.condition_id) else {
+                continue;
+            };
+            match self.verify(market, opportunity).await {
+                Ok(Some(check)) => {
+                    outln!("🚨 High-confidence arbitrage: {} ({:.2}%)", opportunity.question, opportunity.profit_percent);
+                    self.dispatcher.send(&Alert::priority_arbitrage(opportunity, &check)).await;
+                }
+                Ok(None) => {}
+                Err(e) => eoutln!("Warning: Failed to verify {} against the order book: {}", opportunity.question, e),
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A requests-per-second limit shared by every clone, so concurrent wallet fetches, market
+/// pagination and price lookups together stay under it however many are in flight
+/// Requests are spaced evenly rather than let through in bursts
+#[derive(Debug, Clone)]
+pub struct RateBudget {
+    /// Time between requests; None when unlimited
+    spacing: Option<Duration>,
+    /// Earliest time the next request may be sent
+    next: Arc<Mutex<Instant>>,
+}
+
+impl RateBudget {
+    /// A budget of `requests_per_second` (0 = unlimited)
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            spacing: (requests_per_second > 0.0).then(|| Duration::from_secs_f64(1.0 / requests_per_second)),
+            next: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0.0)
+    }
+
+    /// Waits for this request's turn
+    /// Each caller reserves the next free slot before sleeping, so waiters are served in order
+    pub async fn acquire(&self) {
+        let Some(spacing) = self.spacing else { return };
+        let slot = {
+            let mut next = self.next.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let slot = (*next).max(Instant::now());
+            *next = slot + spacing;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+impl Default for RateBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
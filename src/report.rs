@@ -0,0 +1,341 @@
+use crate::models::{OpenPosition, ResolvedPosition, SuspicionReport, WalletPerformance};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+/// Size of the P&L sparklines in the scan summary table, in pixels
+const SMALL_SPARKLINE: (f64, f64) = (160.0, 28.0);
+
+/// Size of the P&L sparkline at the top of each wallet's section
+const LARGE_SPARKLINE: (f64, f64) = (480.0, 80.0);
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em auto; max-width: 1200px; padding: 0 1em; color: #222; }
+h1 { margin-bottom: 0.2em; }
+h2 { margin-top: 2em; border-bottom: 1px solid #ddd; padding-bottom: 0.3em; }
+.muted { color: #777; font-size: 0.9em; }
+.stats { display: grid; grid-template-columns: repeat(auto-fill, minmax(150px, 1fr)); gap: 0.6em; margin: 1em 0; }
+.stat { background: #f6f8fa; border-radius: 6px; padding: 0.5em 0.8em; }
+.stat .label { color: #666; font-size: 0.8em; }
+.stat .value { font-size: 1.2em; font-weight: 600; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; font-size: 0.9em; }
+th, td { padding: 0.35em 0.6em; border-bottom: 1px solid #eee; text-align: left; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; white-space: nowrap; }
+th[data-order=asc]::after { content: ' \\25B2'; }
+th[data-order=desc]::after { content: ' \\25BC'; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; white-space: nowrap; }
+.pos { color: #1a7f37; }
+.neg { color: #cf222e; }
+.flag { background: #cf222e; color: #fff; border-radius: 4px; padding: 0 0.4em; font-size: 0.8em; }
+svg.sparkline { vertical-align: middle; }
+";
+
+/// Sorts a table by the clicked column: numeric cells carry their value in data-value
+const SCRIPT: &str = "
+function sortKey(cell) {
+  var value = cell.dataset.value;
+  return value !== undefined ? parseFloat(value) : cell.textContent.trim().toLowerCase();
+}
+document.querySelectorAll('table.sortable th').forEach(function (th) {
+  th.addEventListener('click', function () {
+    var table = th.closest('table'), body = table.tBodies[0], column = th.cellIndex;
+    var ascending = th.dataset.order !== 'asc';
+    table.querySelectorAll('th').forEach(function (other) { delete other.dataset.order; });
+    th.dataset.order = ascending ? 'asc' : 'desc';
+    var rows = Array.from(body.rows);
+    rows.sort(function (a, b) {
+      var x = sortKey(a.cells[column]), y = sortKey(b.cells[column]);
+      return (x < y ? -1 : x > y ? 1 : 0) * (ascending ? 1 : -1);
+    });
+    rows.forEach(function (row) { body.appendChild(row); });
+  });
+});
+";
+
+/// Renders wallet analyses (wallet, username, performance, score) as a standalone HTML page:
+/// a sortable summary table when there's more than one wallet, then a section per wallet with
+/// its statistics, a sparkline of cumulative P&L and its positions. Everything is inline, so
+/// the file can be mailed or opened offline
+pub fn render(title: &str, wallets: &[(String, Option<String>, WalletPerformance, SuspicionReport)]) -> String {
+    let mut ranked: Vec<_> = wallets.iter().collect();
+    ranked.sort_by(|a, b| b.3.score.total_cmp(&a.3.score));
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n", escape(title), STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    html.push_str(&format!(
+        "<p class=\"muted\">Generated {} &middot; {} wallet{} &middot; click a column header to sort</p>\n",
+        Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        ranked.len(),
+        if ranked.len() == 1 { "" } else { "s" }
+    ));
+
+    if ranked.is_empty() {
+        html.push_str("<p>No wallets met the criteria.</p>\n");
+    } else if ranked.len() > 1 {
+        summary_table(&mut html, &ranked);
+    }
+    for (wallet, username, performance, report) in &ranked {
+        wallet_section(&mut html, wallet, username.as_deref(), performance, report);
+    }
+
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+/// Renders the report and writes it to a file
+pub fn write_html(
+    title: &str,
+    wallets: &[(String, Option<String>, WalletPerformance, SuspicionReport)],
+    path: &Path,
+) -> Result<()> {
+    std::fs::write(path, render(title, wallets)).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// One row per wallet, highest insider score first
+fn summary_table(html: &mut String, ranked: &[&(String, Option<String>, WalletPerformance, SuspicionReport)]) {
+    html.push_str("<h2>Summary</h2>\n<table class=\"sortable\">\n<thead><tr>");
+    for header in ["Rank", "Wallet", "Name", "Score", "Win rate", "ROI", "Profit", "Invested", "Positions", "P&amp;L"] {
+        html.push_str(&format!("<th>{}</th>", header));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+
+    for (rank, (wallet, username, performance, report)) in ranked.iter().enumerate() {
+        let flag = if report.flagged { " <span class=\"flag\">flagged</span>" } else { "" };
+        html.push_str("<tr>");
+        html.push_str(&number_cell(Some((rank + 1) as f64), (rank + 1).to_string()));
+        html.push_str(&format!("<td><a href=\"#{}\">{}</a>{}</td>", anchor(wallet), escape(wallet), flag));
+        html.push_str(&format!("<td>{}</td>", escape(username.as_deref().unwrap_or("-"))));
+        html.push_str(&number_cell(Some(report.score), format!("{:.1}", report.score)));
+        html.push_str(&number_cell(Some(performance.win_rate), format!("{:.1}%", performance.win_rate)));
+        html.push_str(&signed_cell(performance.roi, format!("{:.1}%", performance.roi)));
+        html.push_str(&signed_cell(performance.net_profit, dollars(performance.net_profit)));
+        html.push_str(&number_cell(Some(performance.total_invested), dollars(performance.total_invested)));
+        html.push_str(&number_cell(Some(performance.resolved_positions as f64), performance.resolved_positions.to_string()));
+        html.push_str(&format!(
+            "<td data-value=\"{}\">{}</td>",
+            performance.net_profit,
+            sparkline(&performance.positions, SMALL_SPARKLINE)
+        ));
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// A wallet's statistics, insider score reasons, P&L sparkline and positions
+fn wallet_section(
+    html: &mut String,
+    wallet: &str,
+    username: Option<&str>,
+    performance: &WalletPerformance,
+    report: &SuspicionReport,
+) {
+    let heading = match username {
+        Some(name) => format!("{} ({})", escape(name), escape(wallet)),
+        None => escape(wallet),
+    };
+    html.push_str(&format!("<h2 id=\"{}\">{}</h2>\n", anchor(wallet), heading));
+    html.push_str(&format!(
+        "<p class=\"muted\"><a href=\"https://polymarket.com/profile/{}\">Polymarket profile</a></p>\n",
+        escape(wallet)
+    ));
+
+    let mut stats = vec![
+        ("Insider score", format!("{:.1}{}", report.score, if report.flagged { " (flagged)" } else { "" })),
+        ("Trades", performance.total_trades.to_string()),
+        ("Markets", performance.total_markets.to_string()),
+        ("Resolved positions", format!("{} ({}W / {}L)", performance.resolved_positions, performance.wins, performance.losses)),
+        ("Win rate", format!("{:.1}%", performance.win_rate)),
+        ("ROI", format!("{:.1}%", performance.roi)),
+        ("Invested", dollars(performance.total_invested)),
+        ("Net profit", dollars(performance.net_profit)),
+        ("Unrealized P&amp;L", dollars(performance.unrealized_pnl)),
+        ("Volume", dollars(performance.volume)),
+    ];
+    if let Some(sizing) = &performance.stake_sizing {
+        stats.push(("Median / max bet", format!("{} / {}", dollars(sizing.median_stake), dollars(sizing.max_stake))));
+    }
+    html.push_str("<div class=\"stats\">\n");
+    for (label, value) in stats {
+        html.push_str(&format!(
+            "<div class=\"stat\"><div class=\"label\">{}</div><div class=\"value\">{}</div></div>\n",
+            label, value
+        ));
+    }
+    html.push_str("</div>\n");
+
+    html.push_str(&format!("<p>Cumulative P&amp;L by resolution date:<br>{}</p>\n", sparkline(&performance.positions, LARGE_SPARKLINE)));
+
+    if !report.reasons.is_empty() {
+        html.push_str("<ul>\n");
+        for reason in &report.reasons {
+            html.push_str(&format!("<li>{}</li>\n", escape(reason)));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !performance.positions.is_empty() {
+        html.push_str(&format!("<h3>Resolved positions ({})</h3>\n", performance.positions.len()));
+        positions_table(html, &performance.positions);
+    }
+    if !performance.open_positions.is_empty() {
+        html.push_str(&format!("<h3>Open positions ({})</h3>\n", performance.open_positions.len()));
+        open_positions_table(html, &performance.open_positions);
+    }
+}
+
+fn positions_table(html: &mut String, positions: &[ResolvedPosition]) {
+    html.push_str("<table class=\"sortable\">\n<thead><tr>");
+    for header in ["Market", "Event", "Outcome", "Avg price", "Invested", "Payout", "Profit", "Result", "Resolved"] {
+        html.push_str(&format!("<th>{}</th>", header));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+
+    for position in positions {
+        let result = if position.exited_early {
+            "exited"
+        } else if position.won {
+            "won"
+        } else {
+            "lost"
+        };
+        html.push_str("<tr>");
+        html.push_str(&format!("<td>{}</td>", escape(&position.market_title)));
+        html.push_str(&format!("<td>{}</td>", escape(position.event.as_deref().unwrap_or("-"))));
+        html.push_str(&format!("<td>{}</td>", escape(&position.bet_outcome)));
+        html.push_str(&number_cell(Some(position.avg_price), format!("{:.3}", position.avg_price)));
+        html.push_str(&number_cell(Some(position.total_invested), dollars(position.total_invested)));
+        html.push_str(&number_cell(Some(position.payout), dollars(position.payout)));
+        html.push_str(&signed_cell(position.profit, dollars(position.profit)));
+        html.push_str(&format!("<td>{}</td>", result));
+        html.push_str(&number_cell(position.resolved_at.map(|t| t as f64), position.resolved_at.map(date_of).unwrap_or_else(|| "-".to_string())));
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+fn open_positions_table(html: &mut String, positions: &[OpenPosition]) {
+    html.push_str("<table class=\"sortable\">\n<thead><tr>");
+    for header in ["Market", "Outcome", "Shares", "Avg price", "Cost", "Price", "Value", "Unrealized"] {
+        html.push_str(&format!("<th>{}</th>", header));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+
+    for position in positions {
+        html.push_str("<tr>");
+        html.push_str(&format!("<td>{}</td>", escape(&position.market_title)));
+        html.push_str(&format!("<td>{}</td>", escape(&position.outcome)));
+        html.push_str(&number_cell(Some(position.net_shares), format!("{:.2}", position.net_shares)));
+        html.push_str(&number_cell(Some(position.avg_price), format!("{:.3}", position.avg_price)));
+        html.push_str(&number_cell(Some(position.cost_basis), dollars(position.cost_basis)));
+        html.push_str(&number_cell(position.current_price, position.current_price.map(|p| format!("{:.3}", p)).unwrap_or_else(|| "-".to_string())));
+        html.push_str(&number_cell(position.market_value, position.market_value.map(dollars).unwrap_or_else(|| "-".to_string())));
+        match position.unrealized_pnl {
+            Some(pnl) => html.push_str(&signed_cell(pnl, dollars(pnl))),
+            None => html.push_str(&number_cell(None, "-".to_string())),
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// Inline SVG of cumulative profit over the positions' resolution (or entry) times, with
+/// a dashed zero line; green if the wallet ends up, red if it ends down
+fn sparkline(positions: &[ResolvedPosition], (width, height): (f64, f64)) -> String {
+    let mut dated: Vec<(i64, f64)> = positions
+        .iter()
+        .filter_map(|p| p.resolved_at.or(p.entered_at).map(|t| (t, p.profit)))
+        .collect();
+    if dated.len() < 2 {
+        return "<span class=\"muted\">not enough dated positions</span>".to_string();
+    }
+    dated.sort_by_key(|(time, _)| *time);
+
+    let mut total = 0.0;
+    let points: Vec<(i64, f64)> = std::iter::once((dated[0].0, 0.0))
+        .chain(dated.iter().map(|(time, profit)| {
+            total += profit;
+            (*time, total)
+        }))
+        .collect();
+
+    let first = points[0].0;
+    let span = (points[points.len() - 1].0 - first) as f64;
+    let low = points.iter().map(|(_, pnl)| *pnl).fold(0.0_f64, f64::min);
+    let high = points.iter().map(|(_, pnl)| *pnl).fold(0.0_f64, f64::max);
+    let range = if high > low { high - low } else { 1.0 };
+    let padding = 2.0;
+    let x = |index: usize, time: i64| {
+        let share = if span > 0.0 {
+            (time - first) as f64 / span
+        } else {
+            index as f64 / (points.len() - 1) as f64
+        };
+        padding + share * (width - 2.0 * padding)
+    };
+    let y = |pnl: f64| padding + (high - pnl) / range * (height - 2.0 * padding);
+
+    let path: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(index, (time, pnl))| format!("{:.1},{:.1}", x(index, *time), y(*pnl)))
+        .collect();
+    let colour = if total >= 0.0 { "#1a7f37" } else { "#cf222e" };
+    let title = format!("{} to {}: {}", date_of(points[0].0), date_of(points[points.len() - 1].0), dollars(total));
+    format!(
+        "<svg class=\"sparkline\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\"><title>{title}</title>\
+         <line x1=\"0\" x2=\"{w}\" y1=\"{zero:.1}\" y2=\"{zero:.1}\" stroke=\"#bbb\" stroke-dasharray=\"2,2\"/>\
+         <polyline fill=\"none\" stroke=\"{colour}\" stroke-width=\"1.5\" points=\"{points}\"/></svg>",
+        w = width,
+        h = height,
+        title = title,
+        zero = y(0.0),
+        colour = colour,
+        points = path.join(" "),
+    )
+}
+
+/// A right-aligned cell sorted by its value (missing values sort first)
+fn number_cell(value: Option<f64>, text: String) -> String {
+    let value = value.filter(|v| v.is_finite()).map(|v| v.to_string()).unwrap_or_else(|| "-Infinity".to_string());
+    format!("<td class=\"num\" data-value=\"{}\">{}</td>", value, escape(&text))
+}
+
+/// A number cell coloured by sign
+fn signed_cell(value: f64, text: String) -> String {
+    let class = if value > 0.0 { "num pos" } else if value < 0.0 { "num neg" } else { "num" };
+    let sort_value = if value.is_finite() { value.to_string() } else { "-Infinity".to_string() };
+    format!("<td class=\"{}\" data-value=\"{}\">{}</td>", class, sort_value, escape(&text))
+}
+
+fn dollars(amount: f64) -> String {
+    format!("${:.2}", amount)
+}
+
+fn date_of(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Id of a wallet's section, for links from the summary table
+fn anchor(wallet: &str) -> String {
+    format!("wallet-{}", escape(&wallet.to_lowercase()))
+}
+
+/// Escapes text for HTML element content and quoted attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,196 @@
+use crate::client::PolymarketClient;
+use crate::exchange::ExchangeClient;
+use crate::export::{Column, ColumnType, CsvExport};
+use crate::models::{MarketResolution, ResolutionStatus};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// File in the store holding cached final resolutions
+const RESOLUTION_CACHE_FILE: &str = "resolutions.json";
+
+/// Checks resolution status for batches of condition IDs
+pub struct ResolutionChecker<C = PolymarketClient> {
+    client: C,
+    store: Store,
+}
+
+impl ResolutionChecker {
+    /// Reads condition IDs from a file (one per line, `#` comments and blank lines ignored)
+    pub fn read_ids_file(path: &Path) -> Result<Vec<String>> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read ID file {}", path.display()))?;
+
+        let mut ids: Vec<String> = Vec::new();
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            for id in line.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                if !ids.iter().any(|existing| existing == id) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Builds a CSV export of resolution results
+    pub fn to_csv(resolutions: &[MarketResolution], decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("condition_id", ColumnType::String, "Market condition ID"),
+                Column::new("question", ColumnType::String, "Market question"),
+                Column::new("status", ColumnType::String, "RESOLVED, PENDING, OPEN or NOT FOUND"),
+                Column::new("winning_outcome_index", ColumnType::Integer, "Index of the winning outcome"),
+                Column::new("winning_outcome", ColumnType::String, "Name of the winning outcome"),
+                Column::new("payouts", ColumnType::String, "Payout per share of each outcome, separated by '/'"),
+                Column::new("resolved_at", ColumnType::Integer, "Resolution time (unix seconds)"),
+                Column::new("oracle_status", ColumnType::String, "UMA oracle state: proposed, disputed or resolved"),
+                Column::new("disputed", ColumnType::Boolean, "Whether the oracle's proposed result was ever disputed"),
+                Column::new("resolution_source", ColumnType::String, "Where the result is read from"),
+            ],
+            decimal_places,
+        );
+
+        for resolution in resolutions {
+            export.push_row(vec![
+                resolution.condition_id.as_str().into(),
+                resolution.question.clone().into(),
+                resolution.status.label().into(),
+                resolution.winning_outcome_index.into(),
+                resolution.winning_outcome.clone().into(),
+                resolution
+                    .payouts
+                    .as_ref()
+                    .map(|payouts| payouts.iter().map(|payout| payout.to_string()).collect::<Vec<_>>().join("/"))
+                    .into(),
+                resolution.resolved_at.into(),
+                resolution.oracle_status.map(|status| status.label()).into(),
+                resolution.disputed.into(),
+                resolution.resolution_source.clone().into(),
+            ]);
+        }
+
+        export
+    }
+}
+
+impl<C: ExchangeClient> ResolutionChecker<C> {
+    pub fn new(client: C, store: Store) -> Self {
+        Self { client, store }
+    }
+
+    /// Returns the resolution of every requested condition ID, in input order
+    /// Resolved markets are final, so they are served from the cache when possible
+    pub async fn check(&self, condition_ids: &[String]) -> Result<Vec<MarketResolution>> {
+        let mut cache: HashMap<String, MarketResolution> = self
+            .store
+            .load_json(RESOLUTION_CACHE_FILE)?
+            .unwrap_or_default();
+
+        let missing: Vec<String> = condition_ids
+            .iter()
+            .filter(|id| !cache.contains_key(*id))
+            .cloned()
+            .collect();
+
+        eoutln!(
+            "  {} cached, {} to fetch",
+            condition_ids.len() - missing.len(),
+            missing.len()
+        );
+
+        let mut fetched: HashMap<String, MarketResolution> = HashMap::new();
+        if !missing.is_empty() {
+            let markets = self.client.fetch_markets_by_ids(&missing).await?;
+            for market in &markets {
+                if let Some(id) = &market.condition_id {
+                    fetched.insert(id.clone(), MarketResolution::from_market(id, market));
+                }
+            }
+        }
+
+        // Only cache final results; open/pending markets must be re-checked later
+        let mut cache_dirty = false;
+        for resolution in fetched.values() {
+            if resolution.status == ResolutionStatus::Resolved {
+                cache.insert(resolution.condition_id.clone(), resolution.clone());
+                cache_dirty = true;
+            }
+        }
+        if cache_dirty {
+            self.store.save_json(RESOLUTION_CACHE_FILE, &cache)?;
+        }
+
+        let results = condition_ids
+            .iter()
+            .map(|id| {
+                cache
+                    .get(id)
+                    .or_else(|| fetched.get(id))
+                    .cloned()
+                    .unwrap_or_else(|| MarketResolution::not_found(id))
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    /// Prints a resolution report
+    pub fn print_report(&self, resolutions: &[MarketResolution]) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("MARKET RESOLUTIONS");
+        outln!("{}", "=".repeat(80));
+
+        for resolution in resolutions {
+            outln!("\n{}", resolution.condition_id);
+            if let Some(question) = &resolution.question {
+                outln!("   {}", question);
+            }
+
+            match (resolution.winning_outcome_index, &resolution.winning_outcome) {
+                (Some(index), Some(name)) => outln!(
+                    "   Status: {} | Winner: {} (outcome {})",
+                    resolution.status.label(), name, index
+                ),
+                (Some(index), None) => outln!(
+                    "   Status: {} | Winner: outcome {}",
+                    resolution.status.label(), index
+                ),
+                _ => outln!("   Status: {}", resolution.status.label()),
+            }
+
+            if let Some(payouts) = resolution.payouts.as_ref().filter(|_| resolution.winning_outcome_index.is_none()) {
+                let payouts: Vec<String> = payouts.iter().map(|payout| format!("{:.2}", payout)).collect();
+                outln!("   Payouts: {}", payouts.join(" / "));
+            }
+            if let Some(oracle) = resolution.oracle_status {
+                let disputed = if resolution.disputed { " (disputed)" } else { "" };
+                outln!("   Oracle: {}{}", oracle.label(), disputed);
+            }
+            if let Some(resolved_at) = resolution.resolved_at.and_then(|at| chrono::DateTime::from_timestamp(at, 0)) {
+                outln!("   Resolved: {}", resolved_at.format("%Y-%m-%d %H:%M UTC"));
+            }
+            if let Some(source) = &resolution.resolution_source {
+                outln!("   Source: {}", source);
+            }
+        }
+
+        let count = |status: ResolutionStatus| resolutions.iter().filter(|r| r.status == status).count();
+
+        outln!("\n{}", "-".repeat(80));
+        outln!(
+            "Resolved: {} | Pending: {} | Open: {} | Not found: {}",
+            count(ResolutionStatus::Resolved),
+            count(ResolutionStatus::Pending),
+            count(ResolutionStatus::Open),
+            count(ResolutionStatus::NotFound)
+        );
+        let disputed = resolutions.iter().filter(|r| r.disputed).count();
+        if disputed > 0 {
+            outln!("Disputed oracle results: {}", disputed);
+        }
+        outln!("{}", "-".repeat(80));
+    }
+}
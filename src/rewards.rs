@@ -0,0 +1,116 @@
+use crate::config::RewardsConfig;
+use crate::fees::FeeSchedule;
+use crate::models::{Market, RewardOpportunity};
+use crate::scanner::CategoryFilter;
+use rayon::prelude::*;
+
+/// Finds markets where quoting both sides near the midpoint is expected to earn more in
+/// liquidity rewards than it loses to adverse selection
+///
+/// The estimate is deliberately rough and errs on the side of caution: the reward pool is
+/// shared in proportion to dollars quoted, with the whole book's liquidity competing for it
+/// (only quotes within the max spread actually qualify), and quotes are filled as often as
+/// the book turns over (24h volume over liquidity, at most once a day), losing the day's
+/// price move (at least a tick) on every share filled
+#[derive(Debug, Clone)]
+pub struct RewardScanner {
+    /// Dollars quoted on each side
+    quote_size: f64,
+    /// Net reward per day over the capital quoted, below which markets aren't reported
+    min_daily_yield: f64,
+    categories: CategoryFilter,
+}
+
+impl RewardScanner {
+    pub fn from_config(config: &RewardsConfig) -> Self {
+        Self {
+            quote_size: config.quote_size,
+            min_daily_yield: config.min_daily_yield,
+            categories: CategoryFilter::default(),
+        }
+    }
+
+    /// Quotes this many dollars on each side
+    pub fn with_quote_size(mut self, quote_size: f64) -> Self {
+        self.quote_size = quote_size;
+        self
+    }
+
+    /// Only reports markets earning at least this net fraction of the capital quoted per day
+    pub fn with_min_daily_yield(mut self, min_daily_yield: f64) -> Self {
+        self.min_daily_yield = min_daily_yield;
+        self
+    }
+
+    /// Only scans markets that pass the given category filter
+    pub fn with_categories(mut self, categories: CategoryFilter) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Scans markets with active reward programs, best net reward per day first
+    pub fn scan(&self, markets: &[Market], now: i64) -> Vec<RewardOpportunity> {
+        let mut opportunities: Vec<RewardOpportunity> = markets
+            .par_iter()
+            .filter(|market| self.categories.is_empty() || self.categories.allows(&market.categories()))
+            .filter_map(|market| self.check_market(market, now))
+            .collect();
+
+        opportunities.sort_by(|a, b| b.net_per_day.total_cmp(&a.net_per_day));
+
+        opportunities
+    }
+
+    /// Estimates the net daily reward of quoting one market
+    fn check_market(&self, market: &Market, now: i64) -> Option<RewardOpportunity> {
+        if market.closed == Some(true) {
+            return None;
+        }
+        let daily_rate = market.daily_reward_rate(now);
+        if daily_rate <= 0.0 {
+            return None;
+        }
+        let max_spread = market.rewards_max_spread.filter(|spread| *spread > 0.0)?;
+        let midpoint = market.midpoint().filter(|price| *price > 0.0 && *price < 1.0)?;
+        let min_size = market.rewards_min_size.unwrap_or(0.0);
+
+        // A YES bid below the midpoint and a NO bid below its complement, each at least the minimum size
+        let prices = [midpoint, 1.0 - midpoint];
+        let shares = prices.map(|price| (self.quote_size / price).max(min_size));
+        let capital: f64 = prices.iter().zip(&shares).map(|(price, shares)| price * shares).sum();
+
+        let liquidity = market.parsed_liquidity();
+        let reward_share = capital / (capital + liquidity);
+        let expected_reward = daily_rate * reward_share;
+
+        let volume_24h = market.volume_24hr.unwrap_or(0.0);
+        let turnover = if liquidity > 0.0 { (volume_24h / liquidity).min(1.0) } else { 1.0 };
+        let tick_size = FeeSchedule::from_market(market).tick_size;
+        let price_move = market.one_day_price_change.unwrap_or(0.0).abs().max(tick_size);
+        let adverse_selection = shares.iter().sum::<f64>() * turnover * price_move;
+
+        let net_per_day = expected_reward - adverse_selection;
+        let daily_yield = net_per_day / capital;
+        if daily_yield < self.min_daily_yield {
+            return None;
+        }
+
+        Some(RewardOpportunity {
+            question: market.question.clone(),
+            condition_id: market.condition_id.clone(),
+            event: market.event().map(|event| event.label().to_string()),
+            midpoint,
+            daily_rate,
+            max_spread,
+            min_size,
+            capital,
+            reward_share,
+            expected_reward,
+            adverse_selection,
+            net_per_day,
+            daily_yield,
+            volume_24h,
+            liquidity,
+        })
+    }
+}
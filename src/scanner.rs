@@ -1,44 +1,602 @@
-use crate::models::{ArbitrageOpportunity, Market};
+use crate::config::{ArbitrageConfig, CategoryThresholds, MarketLink};
+use crate::exchange::ExchangeClient;
+use crate::fees::{FeeSchedule, TradingCosts};
+use crate::models::{
+    ArbitrageOpportunity, Event, EventArbitrageKind, EventArbitrageOpportunity, LinkArbitrageOpportunity,
+    Market, MarketEfficiency,
+};
+use anyhow::Result;
+use chrono::Utc;
 use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Restricts scanning to markets in (or out of) given categories or tags
+/// Names are matched case-insensitively against a market's category and its tags' labels and slugs
+#[derive(Debug, Clone, Default)]
+pub struct CategoryFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl CategoryFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Self {
+        let normalize = |names: &[String]| names.iter().map(|n| n.trim().to_lowercase()).collect();
+        Self {
+            include: normalize(include),
+            exclude: normalize(exclude),
+        }
+    }
+
+    /// Whether the filter lets everything through
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether a market or event with these (lowercased) categories passes the filter
+    pub fn allows(&self, categories: &[String]) -> bool {
+        let has = |name: &String| categories.contains(name);
+        (self.include.is_empty() || self.include.iter().any(has)) && !self.exclude.iter().any(has)
+    }
+}
+
+/// Thresholds applied to markets in one category, with the scanner's defaults filled in
+#[derive(Debug, Clone)]
+struct CategoryRule {
+    category: String,
+    threshold: Option<f64>,
+    costs: TradingCosts,
+    min_liquidity: f64,
+    min_volume: f64,
+}
 
 /// Scans markets for arbitrage opportunities
 #[derive(Clone)]
 pub struct ArbitrageScanner {
-    /// The threshold for detecting arbitrage (e.g., 0.99 means YES+NO < $0.99)
-    threshold: f64,
+    /// Fixed threshold for detecting arbitrage (e.g., 0.99 means YES+NO < $0.99)
+    /// When unset, each market's threshold is derived from its fees and tick size
+    threshold: Option<f64>,
+    /// Whether to also report markets priced above $1, and above what fixed total if set
+    detect_overpriced: bool,
+    overpriced_threshold: Option<f64>,
+    costs: TradingCosts,
+    categories: CategoryFilter,
+    /// Markets with less order book liquidity than this (in dollars) are skipped
+    min_liquidity: f64,
+    /// Markets with less traded volume than this (in dollars) are skipped
+    min_volume: f64,
+    /// Markets that traded less than this in the last 24 hours (in dollars) are skipped
+    min_volume_24h: f64,
+    /// Opportunities expected to make less than this (in dollars) are skipped
+    min_expected_profit: f64,
+    /// Per-category overrides of the settings above
+    category_rules: Vec<CategoryRule>,
+    /// Market pairs checked against their implication
+    links: Vec<MarketLink>,
+    /// Only markets scheduled to end within this many hours are scanned
+    resolving_within_hours: Option<u64>,
+    /// Opportunities in markets that haven't traded within this many hours are stale
+    max_trade_age_hours: Option<f64>,
+    /// Drop stale opportunities rather than flag them
+    suppress_stale: bool,
+}
+
+/// Everything one scan of the venue's active markets and events found
+pub struct ScanPass {
+    pub markets: Vec<Market>,
+    pub events: Vec<Event>,
+    pub opportunities: Vec<ArbitrageOpportunity>,
+    pub event_opportunities: Vec<EventArbitrageOpportunity>,
+    pub link_opportunities: Vec<LinkArbitrageOpportunity>,
+    /// Opportunities dropped because their market hasn't traded within the max trade age
+    pub stale_suppressed: usize,
+    pub fetch_duration: Duration,
+    pub scan_duration: Duration,
+}
+
+impl ScanPass {
+    /// Opportunities of every kind the scan found
+    pub fn found(&self) -> usize {
+        self.opportunities.len() + self.event_opportunities.len() + self.link_opportunities.len()
+    }
 }
 
 impl ArbitrageScanner {
-    /// Creates a new scanner with the given threshold
+    /// Creates a new scanner with a fixed threshold for every market
     pub fn new(threshold: f64) -> Self {
-        Self { threshold }
+        Self {
+            threshold: Some(threshold),
+            ..Self::with_costs(TradingCosts::default())
+        }
+    }
+
+    /// Creates a scanner that derives each market's threshold from its fees and tick size
+    pub fn with_costs(costs: TradingCosts) -> Self {
+        Self {
+            threshold: None,
+            detect_overpriced: true,
+            overpriced_threshold: None,
+            costs,
+            categories: CategoryFilter::default(),
+            min_liquidity: 0.0,
+            min_volume: 0.0,
+            min_volume_24h: 0.0,
+            min_expected_profit: 0.0,
+            category_rules: Vec::new(),
+            links: Vec::new(),
+            resolving_within_hours: None,
+            max_trade_age_hours: None,
+            suppress_stale: true,
+        }
+    }
+
+    /// Creates a scanner from config: fixed if `threshold` is set, fee-derived otherwise
+    pub fn from_config(config: &ArbitrageConfig) -> Self {
+        Self {
+            threshold: config.threshold,
+            detect_overpriced: config.detect_overpriced,
+            overpriced_threshold: config.overpriced_threshold,
+            categories: CategoryFilter::new(&config.include_categories, &config.exclude_categories),
+            min_liquidity: config.min_liquidity,
+            min_volume: config.min_volume,
+            min_volume_24h: config.min_volume_24h,
+            min_expected_profit: config.min_expected_profit,
+            links: config.linked_markets.clone(),
+            ..Self::with_costs(TradingCosts::from_config(config))
+        }
+        .with_resolving_within(config.resolving_within_hours)
+        .with_max_trade_age(config.max_trade_age_hours, config.suppress_stale)
+        .with_category_thresholds(&config.category_thresholds)
+    }
+
+    /// Reports markets where YES + NO is above this fixed total as sell-both opportunities
+    pub fn with_overpriced_threshold(mut self, threshold: f64) -> Self {
+        self.detect_overpriced = true;
+        self.overpriced_threshold = Some(threshold);
+        self
+    }
+
+    /// Only reports markets priced below $1
+    pub fn without_overpriced(mut self) -> Self {
+        self.detect_overpriced = false;
+        self
+    }
+
+    /// Skips markets with less order book liquidity than this, in dollars
+    pub fn with_min_liquidity(mut self, min_liquidity: f64) -> Self {
+        self.min_liquidity = min_liquidity;
+        self
+    }
+
+    /// Skips markets with less traded volume than this, in dollars
+    pub fn with_min_volume(mut self, min_volume: f64) -> Self {
+        self.min_volume = min_volume;
+        self
+    }
+
+    /// Skips markets that traded less than this in the last 24 hours, in dollars
+    pub fn with_min_volume_24h(mut self, min_volume_24h: f64) -> Self {
+        self.min_volume_24h = min_volume_24h;
+        self
+    }
+
+    /// Treats opportunities in markets that haven't traded within this many hours (0 = never)
+    /// as stale quotes: dropped by `check_staleness`, or only flagged if `suppress` is false
+    pub fn with_max_trade_age(mut self, hours: f64, suppress: bool) -> Self {
+        self.max_trade_age_hours = (hours > 0.0).then_some(hours);
+        self.suppress_stale = suppress;
+        self
+    }
+
+    /// Hours without a trade after which a market's prices count as stale, if checked
+    pub fn max_trade_age_hours(&self) -> Option<f64> {
+        self.max_trade_age_hours
+    }
+
+    /// Marks opportunities whose market last traded before the max trade age, or never,
+    /// as stale, and drops them unless stale opportunities are only flagged. `last_trades`
+    /// maps condition IDs to their last trade; markets missing from it are left unchecked
+    pub fn check_staleness(
+        &self,
+        opportunities: Vec<ArbitrageOpportunity>,
+        last_trades: &HashMap<String, Option<i64>>,
+        now: i64,
+    ) -> Vec<ArbitrageOpportunity> {
+        let Some(hours) = self.max_trade_age_hours else {
+            return opportunities;
+        };
+        let cutoff = now - (hours * 3600.0) as i64;
+
+        opportunities
+            .into_iter()
+            .filter_map(|mut opp| {
+                let Some(last_trade) = opp.condition_id.as_ref().and_then(|id| last_trades.get(id)) else {
+                    return Some(opp);
+                };
+                opp.last_trade_at = *last_trade;
+                opp.stale = last_trade.is_none_or(|at| at < cutoff);
+                (!opp.stale || !self.suppress_stale).then_some(opp)
+            })
+            .collect()
+    }
+
+    /// Fetches the active markets and events and scans them for every kind of arbitrage,
+    /// checking binary opportunities for stale prices. Only failing to fetch the markets
+    /// fails the scan; events and last trades that can't be fetched are skipped with a warning
+    pub async fn fetch_and_scan(&self, client: &impl ExchangeClient) -> Result<ScanPass> {
+        let fetch_start = Instant::now();
+        let (markets, events) = tokio::join!(client.fetch_active_markets(), client.fetch_active_events());
+        let markets = markets?;
+        let events = events.unwrap_or_else(|e| {
+            eoutln!("Warning: Failed to fetch events, skipping event arbitrage: {}", e);
+            Vec::new()
+        });
+        let fetch_duration = fetch_start.elapsed();
+
+        let scan_start = Instant::now();
+        let opportunities = self.scan(&markets);
+        let event_opportunities = self.scan_events(&events);
+        let link_opportunities = self.scan_links(&markets);
+        let scan_duration = scan_start.elapsed();
+
+        let found = opportunities.len();
+        let opportunities = match self.max_trade_age_hours.filter(|_| !opportunities.is_empty()) {
+            Some(_) => {
+                let condition_ids: Vec<String> = opportunities.iter().filter_map(|o| o.condition_id.clone()).collect();
+                match client.fetch_last_trade_times(&condition_ids).await {
+                    Ok(last_trades) => self.check_staleness(opportunities, &last_trades, Utc::now().timestamp()),
+                    Err(e) => {
+                        eoutln!("Warning: Failed to check when markets last traded, stale prices not filtered: {}", e);
+                        opportunities
+                    }
+                }
+            }
+            None => opportunities,
+        };
+
+        Ok(ScanPass {
+            stale_suppressed: found - opportunities.len(),
+            markets,
+            events,
+            opportunities,
+            event_opportunities,
+            link_opportunities,
+            fetch_duration,
+            scan_duration,
+        })
+    }
+
+    /// Skips opportunities expected to make less than this many dollars
+    pub fn with_min_expected_profit(mut self, dollars: f64) -> Self {
+        self.min_expected_profit = dollars;
+        self
+    }
+
+    /// Applies different thresholds to markets in the given categories or tags
+    /// Call after setting the defaults: unset overrides are filled in from them
+    pub fn with_category_thresholds(mut self, overrides: &BTreeMap<String, CategoryThresholds>) -> Self {
+        self.category_rules = overrides
+            .iter()
+            .map(|(category, thresholds)| CategoryRule {
+                category: category.trim().to_lowercase(),
+                threshold: thresholds.threshold.or(self.threshold),
+                costs: TradingCosts {
+                    min_edge: thresholds.min_edge.unwrap_or(self.costs.min_edge),
+                    ..self.costs
+                },
+                min_liquidity: thresholds.min_liquidity.unwrap_or(self.min_liquidity),
+                min_volume: thresholds.min_volume.unwrap_or(self.min_volume),
+            })
+            .collect();
+        self
+    }
+
+    /// Checks these market pairs against their implication in `scan_links`
+    pub fn with_links(mut self, links: &[MarketLink]) -> Self {
+        self.links = links.to_vec();
+        self
+    }
+
+    /// Only scans markets and events that pass the given category filter
+    pub fn with_categories(mut self, categories: CategoryFilter) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Only scans markets scheduled to end within this many hours (0 = any end date), where
+    /// capital is tied up for the least time. Markets past their end date but not yet resolved
+    /// are kept; markets without an end date are skipped
+    pub fn with_resolving_within(mut self, hours: u64) -> Self {
+        self.resolving_within_hours = (hours > 0).then_some(hours);
+        self
+    }
+
+    /// The horizon in hours markets must end within, if one is set
+    pub fn resolving_within_hours(&self) -> Option<u64> {
+        self.resolving_within_hours
+    }
+
+    /// Whether something ending at `end` falls within the horizon
+    fn within_horizon(&self, end: Option<i64>) -> bool {
+        match self.resolving_within_hours {
+            Some(hours) => end.is_some_and(|end| end <= Utc::now().timestamp() + hours as i64 * 3600),
+            None => true,
+        }
+    }
+
+    /// The category filter applied when scanning
+    pub fn categories(&self) -> &CategoryFilter {
+        &self.categories
+    }
+
+    /// Returns the fixed YES + NO total below which a market is reported, if one is set
+    pub fn threshold(&self) -> Option<f64> {
+        self.threshold
+    }
+
+    /// Returns the fixed YES + NO total above which a market is reported, if one is set
+    /// and overpriced markets are reported at all
+    pub fn overpriced_threshold(&self) -> Option<f64> {
+        self.overpriced_threshold.filter(|_| self.detect_overpriced)
+    }
+
+    /// The override for the first of these (lowercased) categories that has one
+    fn rule_for(&self, categories: &[String]) -> Option<&CategoryRule> {
+        categories
+            .iter()
+            .find_map(|category| self.category_rules.iter().find(|rule| &rule.category == category))
+    }
+
+    /// Cost per $1 of payout below which a basket with these legs is reported
+    fn threshold_for(&self, rule: Option<&CategoryRule>, legs: &[(FeeSchedule, f64)], payout: f64) -> f64 {
+        match rule {
+            Some(rule) => rule.threshold.unwrap_or_else(|| rule.costs.threshold(legs, payout)),
+            None => self.threshold.unwrap_or_else(|| self.costs.threshold(legs, payout)),
+        }
+    }
+
+    /// Minimum liquidity and volume, in dollars, for markets under a rule
+    fn minimums(&self, rule: Option<&CategoryRule>) -> (f64, f64) {
+        match rule {
+            Some(rule) => (rule.min_liquidity, rule.min_volume),
+            None => (self.min_liquidity, self.min_volume),
+        }
     }
 
     /// Scans a list of markets and returns all arbitrage opportunities found
     pub fn scan(&self, markets: &[Market]) -> Vec<ArbitrageOpportunity> {
         // Use parallel iterator for CPU-bound scanning across multiple cores
-        let mut opportunities: Vec<ArbitrageOpportunity> = markets
+        let mut opportunities: Vec<ArbitrageOpportunity> =
+            markets.par_iter().filter_map(|market| self.scan_market(market)).collect();
+
+        // Most dollars to be made first: a wide spread on a thin market can't be traded at size
+        opportunities.sort_by(|a, b| {
+            b.expected_profit.total_cmp(&a.expected_profit).then(b.profit_percent.total_cmp(&a.profit_percent))
+        });
+
+        opportunities
+    }
+
+    /// Checks a single market the way `scan` checks each one, category filter and horizon included
+    pub fn scan_market(&self, market: &Market) -> Option<ArbitrageOpportunity> {
+        if !self.within_horizon(market.end_timestamp()) {
+            return None;
+        }
+        let categories = market.categories();
+        if !self.categories.is_empty() && !self.categories.allows(&categories) {
+            return None;
+        }
+        self.check_market(market, self.rule_for(&categories))
+    }
+
+    /// Scans negative-risk events for arbitrage across their candidate markets
+    pub fn scan_events(&self, events: &[Event]) -> Vec<EventArbitrageOpportunity> {
+        let mut opportunities: Vec<EventArbitrageOpportunity> = events
             .par_iter()
-            .filter_map(|market| self.check_market(market))
+            .filter(|event| event.is_neg_risk() && self.within_horizon(event.end_timestamp()))
+            .flat_map_iter(|event| {
+                let categories = event.categories();
+                if !self.categories.is_empty() && !self.categories.allows(&categories) {
+                    return Vec::new();
+                }
+                self.check_event(event, self.rule_for(&categories))
+            })
             .collect();
 
-        // Sort by profit percentage (highest first)
-        opportunities.sort_by(|a, b| b.profit_percent.partial_cmp(&a.profit_percent).unwrap());
+        opportunities.sort_by(|a, b| {
+            b.expected_profit.total_cmp(&a.expected_profit).then(b.profit_percent.total_cmp(&a.profit_percent))
+        });
 
         opportunities
     }
 
-    /// Checks a single market for arbitrage opportunity
-    fn check_market(&self, market: &Market) -> Option<ArbitrageOpportunity> {
-        let prices_str = market.outcome_prices.as_ref()?;
+    /// Checks one negative-risk event for all-YES and all-NO baskets priced below payout
+    fn check_event(&self, event: &Event, rule: Option<&CategoryRule>) -> Vec<EventArbitrageOpportunity> {
+        let markets = event.open_markets();
+
+        // Every candidate needs a YES/NO price, otherwise the basket isn't complete
+        let legs: Option<Vec<(f64, f64, f64, FeeSchedule)>> = markets
+            .iter()
+            .map(|market| {
+                let prices = market.parsed_outcome_prices()?;
+                if prices.len() != 2 {
+                    return None;
+                }
+                Some((prices[0], prices[1], market.parsed_liquidity(), FeeSchedule::from_market(market)))
+            })
+            .collect();
+
+        let legs = match legs {
+            Some(legs) if legs.len() >= 2 => legs,
+            _ => return Vec::new(),
+        };
+
+        // The basket is only as actionable as its thinnest leg
+        let leg_count = legs.len();
+        let min_liquidity = legs.iter().map(|l| l.2).fold(f64::INFINITY, f64::min);
+        let volume: f64 = markets.iter().map(|market| market.parsed_volume()).sum();
+        let (required_liquidity, required_volume) = self.minimums(rule);
+        if min_liquidity < required_liquidity || volume < required_volume {
+            return Vec::new();
+        }
+        let yes_cost: f64 = legs.iter().map(|l| l.0).sum();
+        let no_cost: f64 = legs.iter().map(|l| l.1).sum();
+
+        let yes_legs: Vec<(FeeSchedule, f64)> = legs.iter().map(|l| (l.3, l.0)).collect();
+        let no_legs: Vec<(FeeSchedule, f64)> = legs.iter().map(|l| (l.3, l.1)).collect();
+
+        [
+            (EventArbitrageKind::AllYes, yes_cost, 1.0, yes_legs),
+            (EventArbitrageKind::AllNo, no_cost, (leg_count - 1) as f64, no_legs),
+        ]
+        .into_iter()
+        .filter(|(_, cost, payout, fee_legs)| *cost > 0.0 && cost / payout < self.threshold_for(rule, fee_legs, *payout))
+        .map(|(kind, total_cost, payout, _)| {
+            let profit_per_dollar = (payout - total_cost) / payout;
+            EventArbitrageOpportunity {
+                event_id: event.id.clone(),
+                title: event.title.clone().unwrap_or_else(|| event.id.clone()),
+                kind,
+                legs: leg_count,
+                total_cost,
+                payout,
+                profit_per_dollar,
+                profit_percent: (payout - total_cost) / total_cost * 100.0,
+                min_liquidity,
+                expected_profit: profit_per_dollar * min_liquidity.min(self.costs.max_position),
+            }
+        })
+        .filter(|opportunity| opportunity.expected_profit >= self.min_expected_profit)
+        .collect()
+    }
+
+    /// Checks each linked pair for the implying market's YES priced above the implied one's,
+    /// by more than the fees on buying implied YES and implying NO
+    /// Links are checked whatever the category filter, though both markets must end within the
+    /// horizon; pairs not among `markets` are skipped
+    pub fn scan_links(&self, markets: &[Market]) -> Vec<LinkArbitrageOpportunity> {
+        let by_key = market_index(markets);
+        let mut opportunities: Vec<LinkArbitrageOpportunity> = self
+            .links
+            .iter()
+            .filter_map(|link| self.check_link(link, by_key.get(link.implying.as_str())?, by_key.get(link.implied.as_str())?))
+            .collect();
+
+        opportunities.sort_by(|a, b| {
+            b.expected_profit.total_cmp(&a.expected_profit).then(b.profit_percent.total_cmp(&a.profit_percent))
+        });
+
+        opportunities
+    }
+
+    /// The market pairs checked by `scan_links`
+    pub fn links(&self) -> &[MarketLink] {
+        &self.links
+    }
+
+    /// Linked pairs with a market that isn't among `markets` (closed, or a mistyped slug)
+    pub fn missing_links(&self, markets: &[Market]) -> Vec<&MarketLink> {
+        let by_key = market_index(markets);
+        self.links
+            .iter()
+            .filter(|link| !by_key.contains_key(link.implying.as_str()) || !by_key.contains_key(link.implied.as_str()))
+            .collect()
+    }
+
+    fn check_link(&self, link: &MarketLink, implying: &Market, implied: &Market) -> Option<LinkArbitrageOpportunity> {
+        // The pair pays out once both markets have resolved
+        if !self.within_horizon(implying.end_timestamp()) || !self.within_horizon(implied.end_timestamp()) {
+            return None;
+        }
+        let implying_prices = implying.parsed_outcome_prices().filter(|prices| prices.len() == 2)?;
+        let implied_prices = implied.parsed_outcome_prices().filter(|prices| prices.len() == 2)?;
+
+        // The pair is only as actionable as its thinner market
+        let min_liquidity = implying.parsed_liquidity().min(implied.parsed_liquidity());
+        let (required_liquidity, required_volume) = self.minimums(None);
+        if min_liquidity < required_liquidity || implying.parsed_volume() + implied.parsed_volume() < required_volume {
+            return None;
+        }
+
+        // Implied YES pays whenever implying YES would, and implying NO covers the rest
+        let (implied_yes, implying_no) = (implied_prices[0], implying_prices[1]);
+        let total_cost = implied_yes + implying_no;
+        let legs = [
+            (FeeSchedule::from_market(implied), implied_yes),
+            (FeeSchedule::from_market(implying), implying_no),
+        ];
+        if total_cost <= 0.0 || total_cost >= self.threshold_for(None, &legs, 1.0) {
+            return None;
+        }
 
-        // Parse outcome prices - they come as a JSON array string like "[\"0.5\",\"0.5\"]"
-        let prices: Vec<f64> = serde_json::from_str::<Vec<String>>(prices_str)
-            .ok()?
+        let profit_per_dollar = 1.0 - total_cost;
+        let opportunity = LinkArbitrageOpportunity {
+            label: link.label.clone(),
+            implying_condition_id: implying.condition_id.clone().unwrap_or_default(),
+            implying_question: implying.question.clone(),
+            implied_condition_id: implied.condition_id.clone().unwrap_or_default(),
+            implied_question: implied.question.clone(),
+            implying_yes: implying_prices[0],
+            implied_yes,
+            total_cost,
+            profit_per_dollar,
+            profit_percent: profit_per_dollar / total_cost * 100.0,
+            min_liquidity,
+            expected_profit: profit_per_dollar * min_liquidity.min(self.costs.max_position),
+        };
+        (opportunity.expected_profit >= self.min_expected_profit).then_some(opportunity)
+    }
+
+    /// Computes the market efficiency index: the volume-weighted average deviation
+    /// of YES + NO from $1.00 across binary markets
+    pub fn efficiency(&self, markets: &[Market]) -> Option<MarketEfficiency> {
+        let deviations: Vec<(f64, f64)> = markets
             .iter()
-            .filter_map(|s| s.parse().ok())
+            .filter_map(|market| {
+                let prices = market.parsed_outcome_prices()?;
+                if prices.len() != 2 {
+                    return None;
+                }
+
+                Some(((prices[0] + prices[1] - 1.0).abs(), market.parsed_volume()))
+            })
             .collect();
 
+        if deviations.is_empty() {
+            return None;
+        }
+
+        let total_volume: f64 = deviations.iter().map(|(_, volume)| volume).sum();
+        let unweighted_index = deviations.iter().map(|(dev, _)| dev).sum::<f64>() / deviations.len() as f64;
+        let index = if total_volume > 0.0 {
+            deviations.iter().map(|(dev, volume)| dev * volume).sum::<f64>() / total_volume
+        } else {
+            unweighted_index
+        };
+
+        Some(MarketEfficiency {
+            timestamp: Utc::now().timestamp(),
+            index,
+            unweighted_index,
+            markets: deviations.len(),
+            total_volume,
+        })
+    }
+
+    /// Checks a single market for arbitrage opportunity
+    fn check_market(&self, market: &Market, rule: Option<&CategoryRule>) -> Option<ArbitrageOpportunity> {
+        // Illiquid or barely traded markets can't be acted on
+        let (min_liquidity, min_volume) = self.minimums(rule);
+        if market.parsed_liquidity() < min_liquidity || market.parsed_volume() < min_volume {
+            return None;
+        }
+        // Markets nobody trades any more show frozen quotes, not prices anyone would fill at
+        if self.min_volume_24h > 0.0 && market.volume_24hr.unwrap_or(0.0) < self.min_volume_24h {
+            return None;
+        }
+
+        let prices = market.parsed_outcome_prices()?;
+
         // Only consider binary markets (Yes/No)
         if prices.len() != 2 {
             return None;
@@ -47,21 +605,50 @@ impl ArbitrageScanner {
         let yes_price = prices[0];
         let no_price = prices[1];
         let total_cost = yes_price + no_price;
+        let fees = FeeSchedule::from_market(market);
+        let legs = [(fees, yes_price), (fees, no_price)];
+        let (threshold, costs) = match rule {
+            Some(rule) => (rule.threshold, &rule.costs),
+            None => (self.threshold, &self.costs),
+        };
 
-        // Check for arbitrage opportunity (total cost < threshold)
-        if total_cost < self.threshold {
-            Some(ArbitrageOpportunity::from_market(
-                market, yes_price, no_price,
-            ))
+        // Check for arbitrage opportunity (total cost < threshold, or above the upper threshold)
+        let fixed = if total_cost < self.threshold_for(rule, &legs, 1.0) {
+            threshold.is_some()
+        } else if self.detect_overpriced
+            && total_cost > self.overpriced_threshold.unwrap_or_else(|| costs.overpriced_threshold(&legs, 1.0))
+        {
+            self.overpriced_threshold.is_some()
         } else {
-            None
+            return None;
+        };
+
+        // Fee-derived thresholds also require the edge to survive expected slippage
+        let opportunity = ArbitrageOpportunity::from_market(market, yes_price, no_price, costs);
+        if !fixed && opportunity.profit_per_dollar < costs.min_edge {
+            return None;
+        }
+        if opportunity.expected_profit < self.min_expected_profit {
+            return None;
+        }
+
+        Some(opportunity)
+    }
+}
+
+/// Markets keyed by both condition ID and slug, the two ways a link can name them
+fn market_index(markets: &[Market]) -> HashMap<&str, &Market> {
+    let mut index = HashMap::new();
+    for market in markets {
+        for key in [market.condition_id.as_deref(), market.slug.as_deref()].into_iter().flatten() {
+            index.insert(key, market);
         }
     }
+    index
 }
 
 impl Default for ArbitrageScanner {
     fn default() -> Self {
-        // Default threshold of 0.995 to account for slippage and execution costs
-        Self::new(0.995)
+        Self::with_costs(TradingCosts::default())
     }
 }
@@ -0,0 +1,211 @@
+use crate::exchange::ExchangeClient;
+use crate::config::ScheduleConfig;
+use crate::models::{parse_api_timestamp, Market};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const HOUR: i64 = 3600;
+
+/// Markets scheduled to resolve in each hour, read from active markets' end dates
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionCalendar {
+    /// Start of the hour (unix seconds) -> markets ending in it
+    hours: BTreeMap<i64, usize>,
+    pub built_at: i64,
+}
+
+impl ResolutionCalendar {
+    pub fn from_markets(markets: &[Market]) -> Self {
+        let mut hours = BTreeMap::new();
+        for market in markets.iter().filter(|m| m.closed != Some(true)) {
+            if let Some(end) = market.end_date.as_deref().and_then(parse_api_timestamp) {
+                *hours.entry(end - end.rem_euclid(HOUR)).or_insert(0) += 1;
+            }
+        }
+        Self {
+            hours,
+            built_at: Utc::now().timestamp(),
+        }
+    }
+
+    /// Fetches every active market and buckets their end dates by hour
+    pub async fn fetch(client: &impl ExchangeClient) -> Result<Self> {
+        Ok(Self::from_markets(&client.fetch_active_markets().await?))
+    }
+
+    /// Hours between `from` and `to` in which at least `min_markets` markets end, earliest first
+    pub fn bursts(&self, from: i64, to: i64, min_markets: usize) -> Vec<(i64, usize)> {
+        self.hours
+            .range(from - from.rem_euclid(HOUR)..to)
+            .filter(|(_, count)| **count >= min_markets)
+            .map(|(hour, count)| (*hour, *count))
+            .collect()
+    }
+
+    /// Prints the markets ending per hour over the next `hours`, marking bursts
+    pub fn print_upcoming(&self, hours: i64, min_markets: usize) {
+        let now = Utc::now().timestamp();
+        outln!("\n{}", "=".repeat(80));
+        outln!("RESOLUTION CALENDAR (next {}h)", hours);
+        outln!("{}", "=".repeat(80));
+
+        let upcoming: Vec<(&i64, &usize)> = self.hours.range(now - now.rem_euclid(HOUR)..now + hours * HOUR).collect();
+        let total: usize = upcoming.iter().map(|(_, count)| **count).sum();
+        let bursts = upcoming.iter().filter(|(_, count)| **count >= min_markets).count();
+        outln!("\nMarkets Ending:       {}", total);
+        outln!("Burst Hours:          {} ({}+ markets)", bursts, min_markets);
+
+        if upcoming.is_empty() {
+            outln!("\nNo active market ends in this window.");
+        } else {
+            outln!("\n{:<17} {:>8}", "Hour (UTC)", "Markets");
+            for (hour, count) in upcoming {
+                let marker = if *count >= min_markets { "  ◀ burst" } else { "" };
+                outln!("{:<17} {:>8}{}", format_hour(*hour), count, marker);
+            }
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// How the next continuous-scan iteration should run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanMode {
+    /// Fresh results are landing: scan back-to-back with more wallets until `until`
+    Intensive { until: i64, markets: usize },
+    /// Between bursts: pace iterations, waking early for the next burst
+    Idle { next_burst: Option<i64> },
+}
+
+/// Decides when a continuous insider scan spends its requests: intensively in the hours
+/// after a burst of markets resolves (when fresh wins and losses appear), slowly otherwise
+pub struct ScanScheduler {
+    config: ScheduleConfig,
+    calendar: Option<ResolutionCalendar>,
+    /// A burst seen in the resolved-market refresh: (window end, markets)
+    observed: Option<(i64, usize)>,
+    /// End of the intensive window the previous iteration ran in
+    current_window: Option<i64>,
+}
+
+impl ScanScheduler {
+    pub fn new(config: ScheduleConfig) -> Self {
+        Self {
+            config,
+            calendar: None,
+            observed: None,
+            current_window: None,
+        }
+    }
+
+    fn window_secs(&self) -> i64 {
+        (self.config.intensive_hours as i64 + 1) * HOUR
+    }
+
+    /// Rebuilds the calendar when it's missing or older than `calendar_refresh_hours`,
+    /// logging (not propagating) failures so the scan carries on with the old one
+    pub async fn refresh_calendar(&mut self, client: &impl ExchangeClient) {
+        let max_age = self.config.calendar_refresh_hours as i64 * HOUR;
+        let stale = self
+            .calendar
+            .as_ref()
+            .is_none_or(|calendar| Utc::now().timestamp() - calendar.built_at >= max_age);
+        if !stale {
+            return;
+        }
+
+        match ResolutionCalendar::fetch(client).await {
+            Ok(calendar) => {
+                let now = Utc::now().timestamp();
+                let bursts = calendar.bursts(now, now + 24 * HOUR, self.config.burst_min_markets);
+                outln!("📅 Resolution calendar: {} burst hour(s) in the next 24h", bursts.len());
+                self.calendar = Some(calendar);
+            }
+            Err(e) => eoutln!("Warning: Failed to build the resolution calendar: {}", e),
+        }
+    }
+
+    /// Starts an intensive window when a resolved-market refresh finds a burst the calendar missed
+    pub fn observe_resolved(&mut self, newly_resolved: usize) {
+        if newly_resolved >= self.config.burst_min_markets {
+            self.observed = Some((Utc::now().timestamp() + self.window_secs(), newly_resolved));
+        }
+    }
+
+    /// The mode for an iteration starting now
+    pub fn mode(&self) -> ScanMode {
+        let now = Utc::now().timestamp();
+        let window = self.window_secs();
+        let min_markets = self.config.burst_min_markets;
+
+        // A burst's window opens at its hour and stays open for intensive_hours after it
+        let scheduled = self
+            .calendar
+            .as_ref()
+            .map(|calendar| calendar.bursts(now - window + HOUR, now + HOUR, min_markets))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(hour, _)| *hour <= now)
+            .map(|(hour, markets)| (hour + window, markets));
+        let active = scheduled
+            .chain(self.observed.filter(|(until, _)| *until > now))
+            .max_by_key(|(until, _)| *until);
+
+        match active {
+            Some((until, markets)) => ScanMode::Intensive { until, markets },
+            None => ScanMode::Idle {
+                next_burst: self
+                    .calendar
+                    .as_ref()
+                    .and_then(|calendar| calendar.bursts(now, i64::MAX, min_markets).first().map(|(hour, _)| *hour)),
+            },
+        }
+    }
+
+    /// Whether `mode` opens an intensive window the previous iteration wasn't in
+    /// (the moment to pick up newly resolved markets)
+    pub fn enter(&mut self, mode: ScanMode) -> bool {
+        match mode {
+            ScanMode::Intensive { until, .. } => {
+                let entered = self.current_window.is_none_or(|current| current < Utc::now().timestamp());
+                self.current_window = Some(until);
+                entered
+            }
+            ScanMode::Idle { .. } => {
+                self.current_window = None;
+                false
+            }
+        }
+    }
+
+    /// Wallets to analyze in an iteration run in `mode`
+    pub fn max_wallets(&self, mode: ScanMode, max_wallets: usize) -> usize {
+        match mode {
+            ScanMode::Intensive { .. } => max_wallets * self.config.intensive_wallet_multiplier.max(1),
+            ScanMode::Idle { .. } => max_wallets,
+        }
+    }
+
+    /// How long to wait after an iteration run in `mode`: nothing during a burst, otherwise
+    /// the idle interval, cut short when the next burst starts sooner
+    pub fn pause(&self, mode: ScanMode) -> Duration {
+        match mode {
+            ScanMode::Intensive { .. } => Duration::ZERO,
+            ScanMode::Idle { next_burst } => {
+                let idle = self.config.idle_interval_secs as i64;
+                let until_burst = next_burst.map_or(idle, |hour| hour - Utc::now().timestamp());
+                Duration::from_secs(idle.min(until_burst).max(0) as u64)
+            }
+        }
+    }
+}
+
+/// "YYYY-MM-DD HH:00" for an hour's start
+pub fn format_hour(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:00").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+pub use tokio_util::sync::CancellationToken;
+
+/// Error returned by requests and scans stopped because their cancellation token was cancelled
+#[derive(Debug, Clone, Copy)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Whether an error, or anything in its chain of causes, is a cancellation
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<Cancelled>())
+}
+
+/// A token cancelled on the first Ctrl+C, so in-flight scans stop and the caller can shut down
+/// cleanly; a second Ctrl+C exits at once
+pub fn ctrl_c_token() -> CancellationToken {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        cancel.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+    token
+}
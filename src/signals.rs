@@ -0,0 +1,536 @@
+use crate::metrics::OpportunityWindowLog;
+use crate::models::{
+    ArbitrageOpportunity, LinkArbitrageOpportunity, OpportunityWindow, Position, ResolutionStatus, Signal, SignalGrade,
+    SignalGradeRecord, SignalKind,
+};
+use crate::copy_trading::CopySignal;
+use crate::exchange::ExchangeClient;
+use crate::resolutions::ResolutionChecker;
+use crate::scanner::ArbitrageScanner;
+use crate::store::Store;
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+
+/// Append-only log of emitted signals
+const SIGNALS_FILE: &str = "signals.jsonl";
+/// Append-only log of grades for previously emitted signals
+const SIGNAL_GRADES_FILE: &str = "signal_grades.jsonl";
+
+/// Persists emitted signals and grades them once their outcome is known
+#[derive(Clone)]
+pub struct SignalLog {
+    store: Store,
+}
+
+impl SignalLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Records new signals, skipping any whose ID was already logged
+    pub fn record(&self, signals: &[Signal]) -> Result<usize> {
+        let known: HashSet<String> = self
+            .store
+            .read_jsonl::<Signal>(SIGNALS_FILE)?
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+
+        let new_signals: Vec<&Signal> = signals.iter().filter(|s| !known.contains(&s.id)).collect();
+        self.store.append_jsonl(SIGNALS_FILE, &new_signals)?;
+
+        Ok(new_signals.len())
+    }
+
+    /// Records a grade for a previously emitted signal
+    pub fn record_grades(&self, grades: &[(String, SignalGrade)]) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let records: Vec<SignalGradeRecord> = grades
+            .iter()
+            .map(|(signal_id, grade)| SignalGradeRecord {
+                signal_id: signal_id.clone(),
+                grade: *grade,
+                graded_at: now,
+            })
+            .collect();
+
+        self.store.append_jsonl(SIGNAL_GRADES_FILE, &records)
+    }
+
+    /// Loads every signal with its latest grade applied
+    pub fn load(&self) -> Result<Vec<Signal>> {
+        let grades: HashMap<String, SignalGrade> = self
+            .store
+            .read_jsonl::<SignalGradeRecord>(SIGNAL_GRADES_FILE)?
+            .into_iter()
+            .map(|record| (record.signal_id, record.grade))
+            .collect();
+
+        let mut signals: Vec<Signal> = self.store.read_jsonl(SIGNALS_FILE)?;
+        for signal in &mut signals {
+            signal.grade = grades.get(&signal.id).copied();
+        }
+
+        Ok(signals)
+    }
+
+    /// Wallets flagged by insider scans, in the order they were first flagged
+    pub fn flagged_wallets(&self) -> Result<Vec<String>> {
+        let mut wallets: Vec<String> = Vec::new();
+        for signal in self.store.read_jsonl::<Signal>(SIGNALS_FILE)? {
+            if let (SignalKind::InsiderEntry, Some(wallet)) = (signal.kind, signal.wallet) {
+                if !wallets.contains(&wallet) {
+                    wallets.push(wallet);
+                }
+            }
+        }
+
+        Ok(wallets)
+    }
+
+    /// Grades ungraded directional signals whose markets have resolved
+    pub async fn grade_resolved(&self, checker: &ResolutionChecker) -> Result<usize> {
+        let pending: Vec<Signal> = self
+            .load()?
+            .into_iter()
+            .filter(|s| s.grade.is_none() && s.outcome_index.is_some())
+            .collect();
+
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let mut condition_ids: Vec<String> = pending.iter().map(|s| s.condition_id.clone()).collect();
+        condition_ids.sort();
+        condition_ids.dedup();
+
+        let resolutions: HashMap<String, _> = checker
+            .check(&condition_ids)
+            .await?
+            .into_iter()
+            .map(|r| (r.condition_id.clone(), r))
+            .collect();
+
+        let grades: Vec<(String, SignalGrade)> = pending
+            .iter()
+            .filter_map(|signal| {
+                let resolution = resolutions.get(&signal.condition_id)?;
+                if resolution.status != ResolutionStatus::Resolved {
+                    return None;
+                }
+
+                let grade = if resolution.winning_outcome_index == signal.outcome_index {
+                    SignalGrade::Won
+                } else {
+                    SignalGrade::Lost
+                };
+                Some((signal.id.clone(), grade))
+            })
+            .collect();
+
+        self.record_grades(&grades)?;
+
+        Ok(grades.len())
+    }
+
+    /// Prints an accuracy report grouped by signal type
+    pub fn print_report(&self, signals: &[Signal]) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("SIGNAL ACCURACY REPORT");
+        outln!("{}", "=".repeat(80));
+
+        if signals.is_empty() {
+            outln!("\nNo signals recorded yet.");
+            return;
+        }
+
+        for kind in [SignalKind::Arbitrage, SignalKind::InsiderEntry, SignalKind::SmartMoney] {
+            let of_kind: Vec<&Signal> = signals.iter().filter(|s| s.kind == kind).collect();
+            if of_kind.is_empty() {
+                continue;
+            }
+
+            let graded: Vec<&Signal> = of_kind.iter().copied().filter(|s| s.grade.is_some()).collect();
+            let successes = graded
+                .iter()
+                .filter(|s| s.grade.map(|g| g.is_success()).unwrap_or(false))
+                .count();
+
+            outln!("\n--- {} ---", kind.label());
+            outln!("Signals emitted:      {}", of_kind.len());
+            outln!("Graded:               {}", graded.len());
+
+            if graded.is_empty() {
+                continue;
+            }
+
+            let accuracy = successes as f64 / graded.len() as f64 * 100.0;
+            match kind {
+                SignalKind::Arbitrage => {
+                    outln!("Persisted to next scan: {:.1}%", accuracy);
+                }
+                SignalKind::InsiderEntry | SignalKind::SmartMoney => {
+                    // Return per $1 staked at the signal's entry price
+                    let total_return: f64 = graded
+                        .iter()
+                        .filter(|s| s.price > 0.0)
+                        .map(|s| match s.grade {
+                            Some(SignalGrade::Won) => (1.0 - s.price) / s.price,
+                            _ => -1.0,
+                        })
+                        .sum();
+
+                    outln!("Win Rate:             {:.1}%", accuracy);
+                    outln!(
+                        "Avg Return per $1:    ${:.3}",
+                        total_return / graded.len() as f64
+                    );
+                }
+            }
+        }
+
+        outln!("\n{}", "=".repeat(80));
+    }
+}
+
+/// Builds insider-entry signals from a flagged wallet's open positions
+pub fn insider_entry_signals(wallet: &str, positions: &[Position]) -> Vec<Signal> {
+    let now = Utc::now().timestamp();
+
+    positions
+        .iter()
+        .map(|position| Signal {
+            // Deterministic ID so re-scanning the same wallet doesn't duplicate signals
+            id: format!("insider:{}:{}:{}", wallet, position.condition_id, position.outcome_index),
+            kind: SignalKind::InsiderEntry,
+            emitted_at: now,
+            condition_id: position.condition_id.clone(),
+            question: position.market_title.clone(),
+            outcome_index: Some(position.outcome_index),
+            price: position.avg_price,
+            wallet: Some(wallet.to_string()),
+            grade: None,
+        })
+        .collect()
+}
+
+/// Builds smart-money signals from the buys among copy-trading signals, graded like insider
+/// entries on whether the bought outcome wins
+pub fn smart_money_signals(copy_signals: &[CopySignal]) -> Vec<Signal> {
+    copy_signals
+        .iter()
+        .filter(|signal| signal.side == "BUY")
+        .map(|signal| Signal {
+            // Derived from the copy signal's ID, which is stable per trade
+            id: format!("smart-money:{}", signal.id),
+            kind: SignalKind::SmartMoney,
+            emitted_at: signal.generated_at,
+            condition_id: signal.condition_id.clone(),
+            question: signal.title.clone().unwrap_or_else(|| signal.condition_id.clone()),
+            outcome_index: Some(signal.outcome_index),
+            price: signal.entry_price,
+            wallet: Some(signal.wallet.clone()),
+            grade: None,
+        })
+        .collect()
+}
+
+/// Tracks arbitrage signals across scans so each opportunity is signalled once
+/// and graded on whether it was still there on the following scan
+#[derive(Default)]
+pub struct ArbitrageSignalTracker {
+    /// Keys of the arbitrages on the previous scan: condition IDs, and `link_key`s for linked pairs
+    live: HashSet<String>,
+    /// Signals emitted on the previous scan, awaiting their persistence check
+    awaiting_recheck: Vec<PendingRecheck>,
+    /// Each live opportunity's window so far, keyed by condition ID
+    windows: HashMap<String, OpportunityWindow>,
+    window_log: Option<OpportunityWindowLog>,
+}
+
+impl ArbitrageSignalTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records how long each opportunity stayed open once a scan no longer finds it
+    pub fn with_window_log(mut self, log: OpportunityWindowLog) -> Self {
+        self.window_log = Some(log);
+        self
+    }
+
+    /// The opportunities not seen on the previous scan, the ones `observe` emits signals for
+    pub fn new_opportunities<'o>(&self, opportunities: &'o [ArbitrageOpportunity]) -> Vec<&'o ArbitrageOpportunity> {
+        opportunities
+            .iter()
+            .filter(|o| o.condition_id.as_ref().is_some_and(|id| !self.live.contains(id)))
+            .collect()
+    }
+
+    /// The linked-pair opportunities not seen on the previous scan
+    pub fn new_links<'o>(&self, links: &'o [LinkArbitrageOpportunity]) -> Vec<&'o LinkArbitrageOpportunity> {
+        links.iter().filter(|l| !self.live.contains(&link_key(l))).collect()
+    }
+
+    /// Grades the previous scan's new signals and records signals for new opportunities,
+    /// binary and linked-pair alike
+    /// Returns the signals emitted for opportunities not seen on the previous scan
+    /// The tracker moves on to this scan even if the log can't be written, so a failed
+    /// write never makes the same opportunities count as new again
+    pub fn observe(
+        &mut self,
+        log: &SignalLog,
+        opportunities: &[ArbitrageOpportunity],
+        links: &[LinkArbitrageOpportunity],
+    ) -> Result<Vec<Signal>> {
+        let current: HashSet<String> = opportunities
+            .iter()
+            .filter_map(|o| o.condition_id.clone())
+            .chain(links.iter().map(link_key))
+            .collect();
+
+        let grades: Vec<(String, SignalGrade)> = self
+            .awaiting_recheck
+            .drain(..)
+            .map(|pending| pending.grade(&current))
+            .collect();
+
+        let now = Utc::now().timestamp();
+        let mut new_signals: Vec<(PendingRecheck, Signal)> = self
+            .new_opportunities(opportunities)
+            .into_iter()
+            .filter_map(|opp| {
+                let condition_id = opp.condition_id.clone()?;
+                let signal = Signal {
+                    id: format!("arb:{}:{}", condition_id, now),
+                    kind: SignalKind::Arbitrage,
+                    emitted_at: now,
+                    condition_id: condition_id.clone(),
+                    question: opp.question.clone(),
+                    outcome_index: None,
+                    price: opp.total_cost,
+                    wallet: None,
+                    grade: None,
+                };
+                let pending = PendingRecheck {
+                    signal_id: signal.id.clone(),
+                    key: condition_id.clone(),
+                    condition_ids: vec![condition_id],
+                };
+                Some((pending, signal))
+            })
+            .collect();
+        // A linked pair is signalled against the market whose YES it buys
+        new_signals.extend(self.new_links(links).into_iter().map(|link| {
+            let key = link_key(link);
+            let signal = Signal {
+                id: format!("{}:{}", key, now),
+                kind: SignalKind::Arbitrage,
+                emitted_at: now,
+                condition_id: link.implied_condition_id.clone(),
+                question: link
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| format!("{} => {}", link.implying_question, link.implied_question)),
+                outcome_index: None,
+                price: link.total_cost,
+                wallet: None,
+                grade: None,
+            };
+            let pending = PendingRecheck {
+                signal_id: signal.id.clone(),
+                key,
+                condition_ids: vec![link.implying_condition_id.clone(), link.implied_condition_id.clone()],
+            };
+            (pending, signal)
+        }));
+
+        let windows = self.track_windows(opportunities, &current, now);
+        let (awaiting_recheck, new_signals): (Vec<PendingRecheck>, Vec<Signal>) = new_signals.into_iter().unzip();
+        self.awaiting_recheck = awaiting_recheck;
+        self.live = current;
+
+        log.record_grades(&grades)?;
+        log.record(&new_signals)?;
+        windows?;
+        Ok(new_signals)
+    }
+
+    /// How many of the latest scan's signals are still awaiting their persistence check
+    pub fn pending_rechecks(&self) -> usize {
+        self.awaiting_recheck.len()
+    }
+
+    /// Grades the latest scan's signals against a fresh fetch of just their markets, for when
+    /// no further scan will run to grade them (the scanner stopping at its first hit)
+    /// Returns how many were graded
+    pub async fn recheck(
+        &mut self,
+        log: &SignalLog,
+        client: &impl ExchangeClient,
+        scanner: &ArbitrageScanner,
+    ) -> Result<usize> {
+        if self.awaiting_recheck.is_empty() {
+            return Ok(0);
+        }
+
+        let mut condition_ids: Vec<String> =
+            self.awaiting_recheck.iter().flat_map(|p| p.condition_ids.iter().cloned()).collect();
+        condition_ids.sort();
+        condition_ids.dedup();
+        let markets = client.fetch_markets_by_ids(&condition_ids).await?;
+        let current: HashSet<String> = scanner
+            .scan(&markets)
+            .into_iter()
+            .filter_map(|o| o.condition_id)
+            .chain(scanner.scan_links(&markets).iter().map(link_key))
+            .collect();
+
+        let grades: Vec<(String, SignalGrade)> =
+            self.awaiting_recheck.drain(..).map(|pending| pending.grade(&current)).collect();
+        log.record_grades(&grades)?;
+        Ok(grades.len())
+    }
+
+    /// Extends the windows of opportunities still open and logs those that closed
+    fn track_windows(&mut self, opportunities: &[ArbitrageOpportunity], current: &HashSet<String>, now: i64) -> Result<()> {
+        let Some(window_log) = &self.window_log else {
+            return Ok(());
+        };
+
+        for opp in opportunities {
+            let Some(condition_id) = &opp.condition_id else {
+                continue;
+            };
+            let window = self.windows.entry(condition_id.clone()).or_insert_with(|| OpportunityWindow {
+                condition_id: condition_id.clone(),
+                question: opp.question.clone(),
+                category: opp.category.clone(),
+                opened_at: now,
+                last_seen_at: now,
+                closed_at: now,
+                scans: 0,
+                opening_profit_percent: opp.profit_percent,
+                peak_profit_percent: opp.profit_percent,
+            });
+            window.last_seen_at = now;
+            window.scans += 1;
+            window.peak_profit_percent = window.peak_profit_percent.max(opp.profit_percent);
+        }
+
+        let closed_ids: Vec<String> = self.windows.keys().filter(|id| !current.contains(*id)).cloned().collect();
+        let closed: Vec<OpportunityWindow> = closed_ids
+            .iter()
+            .filter_map(|id| self.windows.remove(id))
+            .map(|window| OpportunityWindow { closed_at: now, ..window })
+            .collect();
+        window_log.record(&closed)
+    }
+}
+
+/// A signal awaiting its persistence check, with the markets it's rechecked against
+struct PendingRecheck {
+    signal_id: String,
+    /// The key its opportunity is tracked under
+    key: String,
+    condition_ids: Vec<String>,
+}
+
+impl PendingRecheck {
+    /// Persisted if `current` still holds its opportunity, Vanished otherwise
+    fn grade(self, current: &HashSet<String>) -> (String, SignalGrade) {
+        let grade = if current.contains(&self.key) {
+            SignalGrade::Persisted
+        } else {
+            SignalGrade::Vanished
+        };
+        (self.signal_id, grade)
+    }
+}
+
+/// Key a linked pair is tracked under across scans, kept apart from condition IDs
+fn link_key(link: &LinkArbitrageOpportunity) -> String {
+    format!("link:{}:{}", link.implying_condition_id, link.implied_condition_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal_log(name: &str) -> SignalLog {
+        let dir = std::env::temp_dir().join(format!("signals-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        SignalLog::new(Store::open(dir).unwrap())
+    }
+
+    fn link(implying: &str, implied: &str) -> LinkArbitrageOpportunity {
+        LinkArbitrageOpportunity {
+            label: None,
+            implying_condition_id: implying.to_string(),
+            implying_question: format!("{} happens", implying),
+            implied_condition_id: implied.to_string(),
+            implied_question: format!("{} happens", implied),
+            implying_yes: 0.55,
+            implied_yes: 0.50,
+            total_cost: 0.95,
+            profit_per_dollar: 0.05,
+            profit_percent: 5.26,
+            min_liquidity: 1000.0,
+            expected_profit: 50.0,
+        }
+    }
+
+    #[test]
+    fn linked_pairs_are_signalled_once_and_graded_on_the_next_scan() {
+        let log = signal_log("links");
+        let mut tracker = ArbitrageSignalTracker::new();
+        let first = [link("0xaaa", "0xbbb"), link("0xccc", "0xddd")];
+
+        assert_eq!(tracker.new_links(&first).len(), 2);
+        let signals = tracker.observe(&log, &[], &first).unwrap();
+        assert_eq!(signals.len(), 2);
+        assert!(signals.iter().all(|s| s.kind == SignalKind::Arbitrage));
+        assert_eq!(signals[0].condition_id, "0xbbb");
+
+        // One pair is still mispriced: no new signal, and the first scan's signals are graded
+        let second = [link("0xaaa", "0xbbb")];
+        assert!(tracker.new_links(&second).is_empty());
+        assert!(tracker.observe(&log, &[], &second).unwrap().is_empty());
+        assert_eq!(tracker.pending_rechecks(), 0);
+
+        let grades: HashMap<String, Option<SignalGrade>> =
+            log.load().unwrap().into_iter().map(|s| (s.condition_id, s.grade)).collect();
+        assert_eq!(grades["0xbbb"], Some(SignalGrade::Persisted));
+        assert_eq!(grades["0xddd"], Some(SignalGrade::Vanished));
+    }
+
+    #[test]
+    fn smart_money_signals_cover_buys_only() {
+        let copy = |side: &str, outcome_index: usize| CopySignal {
+            id: format!("copy:0xwallet:0xaaa:{}:{}", outcome_index, side),
+            generated_at: 1_700_000_000,
+            wallet: "0xwallet".to_string(),
+            wallet_name: "Sharp".to_string(),
+            condition_id: "0xaaa".to_string(),
+            title: Some("Will it rain?".to_string()),
+            outcome: "Yes".to_string(),
+            outcome_index,
+            side: side.to_string(),
+            entry_price: 0.40,
+            wallet_size: 100.0,
+            wallet_notional: 40.0,
+            size_proportion: 1.0,
+            suggested_stake: 10.0,
+            opened_position: true,
+            trade_timestamp: 1_700_000_000,
+            transaction_hash: None,
+        };
+
+        let signals = smart_money_signals(&[copy("BUY", 0), copy("SELL", 1)]);
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].kind, SignalKind::SmartMoney);
+        assert_eq!(signals[0].outcome_index, Some(0));
+        assert_eq!(signals[0].wallet.as_deref(), Some("0xwallet"));
+    }
+}
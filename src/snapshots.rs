@@ -0,0 +1,118 @@
+use crate::client::PolymarketClient;
+use crate::models::{ArbitrageOpportunity, Market, OrderBookSnapshot, SnapshotMarket, SnapshotOpportunity};
+use crate::store::Store;
+use anyhow::Result;
+use chrono::Utc;
+
+/// Append-only log of captured order book snapshots
+const SNAPSHOTS_FILE: &str = "orderbook_snapshots.jsonl";
+
+/// Captures and persists order books for flagged markets, so what was actually
+/// executable at that moment can be verified later
+#[derive(Clone)]
+pub struct OrderBookSnapshotter {
+    client: PolymarketClient,
+    store: Store,
+}
+
+impl OrderBookSnapshotter {
+    pub fn new(client: PolymarketClient, store: Store) -> Self {
+        Self { client, store }
+    }
+
+    /// Captures the order books of every outcome token in a market, with the opportunity
+    /// that flagged it if there was one
+    pub async fn capture_market(
+        &self,
+        market: &Market,
+        reason: &str,
+        opportunity: Option<SnapshotOpportunity>,
+    ) -> Result<OrderBookSnapshot> {
+        let token_ids = market.parsed_clob_token_ids().unwrap_or_default();
+
+        let books = futures::future::join_all(
+            token_ids.iter().map(|token_id| self.client.fetch_order_book(token_id)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+        let snapshot = OrderBookSnapshot {
+            captured_at: Utc::now().timestamp(),
+            reason: reason.to_string(),
+            condition_id: market.condition_id.clone().unwrap_or_default(),
+            question: market.question.clone(),
+            outcomes: market.parsed_outcomes().unwrap_or_default(),
+            outcome_prices: market.parsed_outcome_prices().unwrap_or_default(),
+            books,
+            market: Some(SnapshotMarket::from_market(market)),
+            opportunity,
+        };
+
+        self.store.append_jsonl(SNAPSHOTS_FILE, std::slice::from_ref(&snapshot))?;
+
+        Ok(snapshot)
+    }
+
+    /// Captures order books for markets identified by condition ID
+    pub async fn capture_condition_ids(&self, condition_ids: &[String], reason: &str) -> Result<Vec<OrderBookSnapshot>> {
+        let markets = self.client.fetch_markets_by_condition_ids(condition_ids).await?;
+
+        let mut snapshots = Vec::with_capacity(markets.len());
+        for market in &markets {
+            snapshots.push(self.capture_market(market, reason, None).await?);
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Captures snapshots for flagged markets, logging (not propagating) failures
+    pub async fn capture_flagged(&self, condition_ids: &[String], reason: &str) {
+        if condition_ids.is_empty() {
+            return;
+        }
+
+        match self.capture_condition_ids(condition_ids, reason).await {
+            Ok(snapshots) => {
+                for snapshot in &snapshots {
+                    snapshot.print_summary();
+                }
+            }
+            Err(e) => eoutln!("Warning: Failed to snapshot order books: {}", e),
+        }
+    }
+
+    /// Captures the books behind new arbitrage opportunities, keeping the prices each was
+    /// flagged at beside them so stale Gamma prices can be told from real edges later;
+    /// failures are logged, not propagated
+    pub async fn capture_opportunities(&self, opportunities: &[&ArbitrageOpportunity]) {
+        let condition_ids: Vec<String> = opportunities.iter().filter_map(|o| o.condition_id.clone()).collect();
+        if condition_ids.is_empty() {
+            return;
+        }
+
+        let markets = match self.client.fetch_markets_by_condition_ids(&condition_ids).await {
+            Ok(markets) => markets,
+            Err(e) => {
+                eoutln!("Warning: Failed to snapshot order books: {}", e);
+                return;
+            }
+        };
+
+        for market in &markets {
+            let opportunity = opportunities
+                .iter()
+                .find(|o| o.condition_id.is_some() && o.condition_id == market.condition_id)
+                .map(|o| SnapshotOpportunity::from_opportunity(o));
+            match self.capture_market(market, "arbitrage", opportunity).await {
+                Ok(snapshot) => snapshot.print_summary(),
+                Err(e) => eoutln!("Warning: Failed to snapshot order books for {}: {}", market.question, e),
+            }
+        }
+    }
+}
+
+/// Reads back the captured snapshots
+pub fn load_snapshots(store: &Store) -> Result<Vec<OrderBookSnapshot>> {
+    store.read_jsonl(SNAPSHOTS_FILE)
+}
@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Simple file-backed store for caches and persisted state
+#[derive(Clone)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    /// Opens a store rooted at the given directory, creating it if needed
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create data directory {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the full path of a file inside the store
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Loads a JSON document, returning None if it doesn't exist yet
+    pub fn load_json<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(value))
+    }
+
+    /// Saves a JSON document atomically (write to temp file, then rename)
+    pub fn save_json<T: Serialize>(&self, name: &str, value: &T) -> Result<()> {
+        let path = self.path(name);
+        let text = serde_json::to_string_pretty(value)?;
+        write_atomic(&path, text.as_bytes())
+    }
+
+    /// Saves raw bytes atomically
+    pub fn save_bytes(&self, name: &str, contents: &[u8]) -> Result<()> {
+        write_atomic(&self.path(name), contents)
+    }
+
+    /// Takes an exclusive lock on a lock file in the store, waiting while another process
+    /// holds it; the lock is released when the returned file is dropped
+    pub fn lock(&self, name: &str) -> Result<File> {
+        let path = self.path(name);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.lock().with_context(|| format!("Failed to lock {}", path.display()))?;
+        Ok(file)
+    }
+
+    /// Appends records to a JSON-lines log
+    pub fn append_jsonl<T: Serialize>(&self, name: &str, records: &[T]) -> Result<()> {
+        let path = self.path(name);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        for record in records {
+            let line = serde_json::to_string(record)?;
+            writeln!(file, "{}", line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads every record from a JSON-lines log (empty if the log doesn't exist)
+    pub fn read_jsonl<T: DeserializeOwned>(&self, name: &str) -> Result<Vec<T>> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("Failed to parse {} line {}", path.display(), i + 1))
+            })
+            .collect()
+    }
+}
+
+/// Writes a file by first writing a sibling temp file and renaming it into place
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    // Appended to the whole file name, so `state.json` and `state.jsonl` don't share a temp file
+    let mut file_name = path.file_name().context("Path has no file name")?.to_os_string();
+    file_name.push(".tmp");
+    let tmp_path = path.with_file_name(file_name);
+    fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move {} into place", path.display()))?;
+    Ok(())
+}
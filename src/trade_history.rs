@@ -0,0 +1,91 @@
+use crate::client::PolymarketClient;
+use crate::models::{Trade, TradeCursor};
+use crate::store::Store;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Directory in the store holding one trade history per wallet
+const HISTORY_DIR: &str = "trade_histories";
+
+/// When a wallet's history was last brought up to date, kept beside the history
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Watermark {
+    fetched_at: i64,
+}
+
+/// Local copy of each analyzed wallet's full trade history (JSON lines, oldest first)
+/// Later analyses fetch only the trades newer than the copy, so a wallet with tens of
+/// thousands of trades costs one request instead of dozens
+#[derive(Clone)]
+pub struct TradeHistoryCache {
+    store: Store,
+    /// Seconds a history is used as is after being brought up to date (0 = always check)
+    max_age_secs: i64,
+}
+
+impl TradeHistoryCache {
+    pub fn new(store: Store) -> Self {
+        Self { store, max_age_secs: 0 }
+    }
+
+    /// Uses a history without checking for new trades if it was brought up to date within
+    /// this many seconds, so wallets analyzed again soon after cost no requests at all
+    pub fn with_max_age_secs(mut self, secs: u64) -> Self {
+        self.max_age_secs = secs as i64;
+        self
+    }
+
+    /// Returns a wallet's full trade history, newest first, fetching only what's new since the
+    /// last call (or everything, the first time)
+    pub async fn fetch(&self, client: &PolymarketClient, wallet: &str) -> Result<Vec<Trade>> {
+        let name = history_file(wallet);
+        let watermark_name = watermark_file(wallet);
+        fs::create_dir_all(self.store.path(HISTORY_DIR))
+            .with_context(|| format!("Failed to create {}", HISTORY_DIR))?;
+
+        let cached: Vec<Trade> = match self.store.read_jsonl(&name) {
+            Ok(trades) => trades,
+            Err(e) => {
+                // An interrupted write leaves a partial last line; start the history over
+                eoutln!("Warning: Discarding cached trades for {}: {}", wallet, e);
+                fs::remove_file(self.store.path(&name)).ok();
+                Vec::new()
+            }
+        };
+
+        let now = Utc::now().timestamp();
+        let watermark: Option<Watermark> = self.store.load_json(&watermark_name).ok().flatten();
+        if !cached.is_empty() && watermark.is_some_and(|w| now - w.fetched_at < self.max_age_secs) {
+            let mut trades = cached;
+            trades.reverse();
+            return Ok(trades);
+        }
+
+        let mut new_trades = if cached.is_empty() {
+            client.fetch_wallet_trades(wallet).await?
+        } else {
+            client.fetch_wallet_trades_since(wallet, &TradeCursor::after(&cached)).await?
+        };
+        new_trades.sort_by_key(|trade| trade.timestamp);
+        self.store.append_jsonl(&name, &new_trades)?;
+        self.store.save_json(&watermark_name, &Watermark { fetched_at: now })?;
+
+        let mut trades = cached;
+        trades.extend(new_trades);
+        trades.reverse();
+        Ok(trades)
+    }
+}
+
+/// Cached history file of a wallet, inside the store
+fn history_file(wallet: &str) -> String {
+    format!("{}/{}.jsonl", HISTORY_DIR, wallet.to_lowercase())
+}
+
+/// When the wallet's history file was last brought up to date, inside the store
+fn watermark_file(wallet: &str) -> String {
+    format!("{}/{}.watermark.json", HISTORY_DIR, wallet.to_lowercase())
+}
@@ -1,92 +1,285 @@
-use crate::models::{Market, Position, ResolvedPosition, Trade, WalletPerformance};
-use std::collections::HashMap;
+use crate::cohort::CohortReport;
+use crate::config::SuspicionConfig;
+use crate::export::{Column, ColumnType, CsvExport};
+use crate::matching::NormalizedTitle;
+use crate::models::{
+    ActivityKind, ActivitySummary, AllInBet, ClosingLineValue, ConcentrationFlag, ConcentrationKind, Fill, FreshWalletWin,
+    LiquidityRole, Market, MarketMaking, OpenPosition, PeriodPerformance, Position, PricePoint, ResolvedPosition, StakeSizing,
+    SuspicionReport, TimelineAction, TimelineEvent, TimelineSeries, Trade, TradeCadence, WalletActivity, WalletPerformance,
+    WalletTimeline,
+};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use futures::{Stream, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Buys within this many hours of resolution count as "shortly before resolution"
+const LATE_BUY_WINDOW_HOURS: f64 = 24.0;
+/// Buys below this price were betting on an outcome the market considered unlikely
+const LATE_BUY_MAX_PRICE: f64 = 0.5;
+/// Wallets taking liquidity on at least this share of fills count as fully aggressive takers
+const AGGRESSIVE_TAKER_RATIO: f64 = 0.8;
+/// Minimum fills with a known role before the taker ratio is trusted
+const MIN_FILLS_FOR_TAKER_RATIO: usize = 10;
+/// The market's last view of an outcome is taken this long before resolution,
+/// so the price isn't already reflecting the announced result
+const PRE_RESOLUTION_HOURS: i64 = 1;
+/// A trade's timeline price change is measured this long after it
+const TIMELINE_LOOKAHEAD_HOURS: i64 = 24;
+/// Rise in an outcome's price after a buy that counts as a jump
+const TIMELINE_JUMP: f64 = 0.10;
+/// Typical gaps shorter than this are bursts of manual orders landing in consecutive
+/// blocks, not a timer, so they never classify a wallet as a bot
+const MIN_BOT_INTERVAL_SECS: f64 = 5.0;
+/// Title words too generic to tie markets together as one topic
+const GENERIC_TOPIC_WORDS: &[&str] = &[
+    "above", "after", "before", "below", "between", "close", "day", "down", "election", "end",
+    "first", "game", "hit", "higher", "less", "lower", "market", "match", "month", "more", "next",
+    "over", "price", "reach", "than", "under", "up", "vs", "week", "who", "win", "winner", "year",
+];
+
+/// Price lookup needed to split a resolved position's profit into drift and skill
+#[derive(Debug, Clone)]
+pub struct AttributionQuery {
+    pub condition_id: String,
+    pub outcome_index: usize,
+    pub token_id: String,
+    /// Timestamp of the pre-resolution price
+    pub at: i64,
+}
+
+/// Price history needed to place one traded outcome on a wallet's timeline
+#[derive(Debug, Clone)]
+pub struct TimelineQuery {
+    pub condition_id: String,
+    pub outcome_index: usize,
+    pub outcome: String,
+    pub token_id: String,
+    pub start: i64,
+    pub end: i64,
+}
 
 /// Analyzes wallet trading performance
-pub struct WalletAnalyzer;
+pub struct WalletAnalyzer {
+    suspicion: SuspicionConfig,
+    min_position_invested: f64,
+    /// Length of each performance period in days (0 = calendar months)
+    period_days: u32,
+    /// Only positions in markets resolved within this many days count (0 = all)
+    resolved_within_days: u32,
+}
 
 impl WalletAnalyzer {
     /// Creates a new wallet analyzer
     pub fn new() -> Self {
-        Self
+        Self {
+            suspicion: SuspicionConfig::default(),
+            min_position_invested: 0.0,
+            period_days: 0,
+            resolved_within_days: 0,
+        }
+    }
+
+    /// Uses the given thresholds and weights for the insider score
+    pub fn with_suspicion(mut self, suspicion: SuspicionConfig) -> Self {
+        self.suspicion = suspicion;
+        self
+    }
+
+    /// Ignores positions with less than this many dollars invested (e.g. airdrop-farming
+    /// dust bets) when computing win rate, ROI and the rest of the statistics
+    pub fn with_min_position_invested(mut self, dollars: f64) -> Self {
+        self.min_position_invested = dollars.max(0.0);
+        self
+    }
+
+    /// Breaks performance down into windows of this many days, counted back from now,
+    /// instead of calendar months (0 keeps calendar months)
+    pub fn with_period_days(mut self, days: u32) -> Self {
+        self.period_days = days;
+        self
+    }
+
+    /// Only counts positions in markets that resolved within this many days, or for positions
+    /// sold before resolution, entered within them (0 counts every position)
+    pub fn with_resolved_within_days(mut self, days: u32) -> Self {
+        self.resolved_within_days = days;
+        self
     }
 
     /// Analyzes a wallet's trading performance
     pub fn analyze(&self, trades: &[Trade], resolved_markets: &[Market]) -> WalletPerformance {
-        if trades.is_empty() {
+        self.analyze_ledger(&PositionLedger::from_trades(trades), resolved_markets)
+    }
+
+    /// Analyzes a wallet's trading performance with its activity feed, so splits, merges,
+    /// redemptions and rewards are accounted for rather than ignored
+    pub fn analyze_with_activity(&self, trades: &[Trade], activity: &[WalletActivity], resolved_markets: &[Market]) -> WalletPerformance {
+        let mut ledger = PositionLedger::from_trades(trades);
+        ledger.extend_activity(activity);
+        ledger.label_outcomes(resolved_markets);
+        self.analyze_ledger(&ledger, resolved_markets)
+    }
+
+    /// Analyzes a wallet's trading performance from a stream of trade pages (e.g.
+    /// `PolymarketClient::wallet_trade_pages`), updating positions as each page arrives
+    /// so huge wallets are processed while the rest of their history downloads
+    pub async fn analyze_stream<S>(&self, pages: S, resolved_markets: &[Market]) -> Result<WalletPerformance>
+    where
+        S: Stream<Item = Result<Vec<Trade>>>,
+    {
+        let ledger = PositionLedger::from_stream(pages).await?;
+        Ok(self.analyze_ledger(&ledger, resolved_markets))
+    }
+
+    /// Analyzes a wallet's trading performance from trades already accumulated in a ledger
+    pub fn analyze_ledger(&self, ledger: &PositionLedger, resolved_markets: &[Market]) -> WalletPerformance {
+        let Some(wallet_address) = &ledger.wallet_address else {
             return self.empty_performance(String::new());
+        };
+
+        // Build positions from trades, dropping dust before any matching or timing work
+        let mut positions = ledger.positions();
+        let before = positions.len();
+        positions.retain(|p| !self.is_dust(p));
+        let dust = before - positions.len();
+
+        // Match positions with resolved markets, keeping only recent ones if asked
+        let mut resolved_positions = self.match_resolved_positions(&positions, resolved_markets);
+        if self.resolved_within_days > 0 {
+            let cutoff = Utc::now().timestamp() - self.resolved_within_days as i64 * 86400;
+            resolved_positions.retain(|p| p.resolved_at.or(p.entered_at).is_none_or(|at| at >= cutoff));
         }
 
-        let wallet_address = trades[0].proxy_wallet.clone();
+        // Calculate performance metrics
+        let mut performance =
+            self.calculate_performance(wallet_address, ledger.total_trades, ledger.markets.len(), &resolved_positions);
+        performance.dust_positions = dust;
+        performance.volume = ledger.volume;
+        performance.cadence = self.cadence(&ledger.trade_timestamps());
+        performance.market_making = self.market_making(ledger);
+        performance.stake_sizing = self.stake_sizing(ledger, resolved_markets);
+        performance.first_trade_at = ledger.trade_timestamps().into_iter().min();
+        performance.fresh_wallet_wins = self.fresh_wallet_wins(&performance);
+        performance.activity = ledger.activity.clone();
+        performance
+    }
+
+    /// Positions held to a win with at least `fresh_wallet_min_stake` invested, entered within
+    /// `fresh_wallet_days` of the wallet's first trade, earliest first
+    pub fn fresh_wallet_wins(&self, performance: &WalletPerformance) -> Vec<FreshWalletWin> {
+        let Some(first_trade_at) = performance.first_trade_at else {
+            return Vec::new();
+        };
+
+        let mut wins: Vec<FreshWalletWin> = performance
+            .positions
+            .iter()
+            .filter(|p| p.won && !p.exited_early && p.total_invested >= self.suspicion.fresh_wallet_min_stake)
+            .filter_map(|p| {
+                let entered_at = p.entered_at?;
+                let wallet_age_days = (entered_at - first_trade_at) as f64 / 86_400.0;
+                (wallet_age_days <= self.suspicion.fresh_wallet_days).then(|| FreshWalletWin {
+                    condition_id: p.condition_id.clone(),
+                    market_title: p.market_title.clone(),
+                    outcome: p.bet_outcome.clone(),
+                    entered_at,
+                    wallet_age_days,
+                    stake: p.total_invested,
+                    profit: p.profit,
+                })
+            })
+            .collect();
+        wins.sort_by_key(|win| win.entered_at);
+        wins
+    }
 
-        // Build positions from trades
-        let positions = self.build_positions(trades);
+    /// Whether a position's lifetime capital is under the minimum invested
+    fn is_dust(&self, position: &Position) -> bool {
+        position.total_invested + position.cost_basis_sold < self.min_position_invested
+    }
 
-        // Match positions with resolved markets
-        let resolved_positions = self.match_resolved_positions(&positions, resolved_markets);
+    /// Analyzes several wallets (address and trades) and compares them side by side
+    pub fn compare(&self, wallets: Vec<(String, Vec<Trade>)>, resolved_markets: &[Market]) -> CohortReport {
+        let members: Vec<(Vec<Trade>, WalletPerformance)> = wallets
+            .into_iter()
+            .map(|(wallet, trades)| {
+                let mut performance = self.analyze(&trades, resolved_markets);
+                performance.wallet_address = wallet;
+                (trades, performance)
+            })
+            .collect();
+        CohortReport::build(&members, resolved_markets)
+    }
 
-        // Calculate performance metrics
-        self.calculate_performance(&wallet_address, trades, &resolved_positions)
+    /// Returns positions still held in markets that haven't resolved yet
+    pub fn open_positions(&self, trades: &[Trade], resolved_markets: &[Market]) -> Vec<Position> {
+        self.ledger_open_positions(&PositionLedger::from_trades(trades), resolved_markets)
     }
 
-    /// Builds positions from a list of trades
-    fn build_positions(&self, trades: &[Trade]) -> Vec<Position> {
-        let mut position_map: HashMap<(String, usize), Position> = HashMap::new();
+    /// Returns a ledger's positions still held in markets that haven't resolved yet
+    pub fn ledger_open_positions(&self, ledger: &PositionLedger, resolved_markets: &[Market]) -> Vec<Position> {
+        let resolved_ids: HashSet<&String> = resolved_markets
+            .iter()
+            .filter(|m| m.resolution_payouts().is_some())
+            .filter_map(|m| m.condition_id.as_ref())
+            .collect();
 
-        for trade in trades {
-            let key = (trade.condition_id.clone(), trade.outcome_index);
-
-            let position = position_map.entry(key.clone()).or_insert_with(|| Position {
-                condition_id: trade.condition_id.clone(),
-                outcome_index: trade.outcome_index,
-                net_shares: 0.0,
-                avg_price: 0.0,
-                total_invested: 0.0,
-                realized_profit: 0.0,
-                market_title: trade.title.clone().unwrap_or_else(|| "Unknown".to_string()),
-            });
+        ledger
+            .positions()
+            .into_iter()
+            .filter(|p| p.net_shares > 0.0 && !resolved_ids.contains(&p.condition_id) && !self.is_dust(p))
+            .collect()
+    }
 
-            match trade.side.as_str() {
-                "BUY" => {
-                    // Add to position
-                    let new_total_shares = position.net_shares + trade.size;
-                    let new_total_invested = position.total_invested + (trade.size * trade.price);
+    /// Marks a wallet's open positions to market using current market data
+    /// (`current_markets` should cover the condition IDs of `open_positions`)
+    pub fn value_open_positions(&self, performance: &mut WalletPerformance, open_positions: &[Position], current_markets: &[Market]) {
+        let markets: HashMap<&String, &Market> = current_markets
+            .iter()
+            .filter_map(|m| Some((m.condition_id.as_ref()?, m)))
+            .collect();
 
-                    position.net_shares = new_total_shares;
-                    position.total_invested = new_total_invested;
+        let mut valued: Vec<OpenPosition> = open_positions
+            .iter()
+            .map(|position| {
+                let market = markets.get(&position.condition_id);
+                // A market that settled since the resolved set was loaded is worth its payout;
+                // one still trading is worth its current price, however lopsided
+                let current_price = market.and_then(|m| {
+                    let prices = if m.closed == Some(true) {
+                        m.resolution_payouts().or_else(|| m.parsed_outcome_prices())?
+                    } else {
+                        m.parsed_outcome_prices()?
+                    };
+                    prices.get(position.outcome_index).copied()
+                });
+                let market_value = current_price.map(|price| position.net_shares * price);
 
-                    if new_total_shares > 0.0 {
-                        position.avg_price = new_total_invested / new_total_shares;
-                    }
+                OpenPosition {
+                    condition_id: position.condition_id.clone(),
+                    market_title: market.map(|m| m.question.clone()).unwrap_or_else(|| position.market_title.clone()),
+                    outcome_index: position.outcome_index,
+                    outcome: position.outcome.clone(),
+                    net_shares: position.net_shares,
+                    avg_price: position.avg_price,
+                    cost_basis: position.total_invested,
+                    current_price,
+                    market_value,
+                    unrealized_pnl: market_value.map(|value| value - position.total_invested),
                 }
-                "SELL" => {
-                    // Calculate realized profit from this sell
-                    // Profit = (sell_price - avg_buy_price) * shares_sold
-                    let realized_pnl = (trade.price - position.avg_price) * trade.size;
-                    position.realized_profit += realized_pnl;
-
-                    // Reduce position
-                    position.net_shares -= trade.size;
-
-                    // Reduce total invested proportionally
-                    let cost_basis_sold = trade.size * position.avg_price;
-                    position.total_invested -= cost_basis_sold;
-
-                    if position.net_shares <= 0.001 {
-                        // Position closed
-                        position.net_shares = 0.0;
-                        position.total_invested = 0.0;
-                        position.avg_price = 0.0;
-                    }
-                }
-                _ => {}
-            }
-        }
+            })
+            .collect();
+
+        // Biggest exposure first
+        valued.sort_by(|a, b| b.cost_basis.partial_cmp(&a.cost_basis).unwrap());
 
-        // Return all positions, including closed ones with realized profit
-        position_map.into_values().collect()
+        performance.unrealized_pnl = valued.iter().filter_map(|p| p.unrealized_pnl).sum();
+        performance.open_positions = valued;
     }
 
-    /// Matches positions with resolved markets to determine wins/losses
+    /// Determines the outcome of each position: either the market resolved, or
+    /// the wallet fully exited via sells (realized P&L only)
     fn match_resolved_positions(
         &self,
         positions: &[Position],
@@ -105,78 +298,144 @@ impl WalletAnalyzer {
             .collect();
 
         for position in positions {
-            if let Some(market) = market_map.get(&position.condition_id) {
-                if let Some(winning_index) = self.get_winning_outcome(market) {
-                    let won = position.outcome_index == winning_index;
-
-                    // Payout from remaining shares (if position still open)
-                    let payout = if won { position.net_shares } else { 0.0 };
-
-                    // Total profit = realized profit from sells + unrealized profit from remaining shares
-                    let unrealized_profit = payout - position.total_invested;
-                    let total_profit = position.realized_profit + unrealized_profit;
-
-                    resolved_positions.push(ResolvedPosition {
-                        condition_id: position.condition_id.clone(),
-                        market_title: market.question.clone(),
-                        bet_outcome_index: position.outcome_index,
-                        winning_outcome_index: winning_index,
-                        net_shares: position.net_shares,
-                        avg_price: position.avg_price,
-                        total_invested: position.total_invested,
-                        payout,
-                        profit: total_profit,  // Now includes realized + unrealized
-                        won,
-                    });
-                }
+            let resolution = market_map
+                .get(&position.condition_id)
+                .and_then(|market| market.resolution_payouts().map(|payouts| (market, payouts)));
+
+            // Capital deployed over the life of the position, and what came back from sells
+            let total_invested = position.total_invested + position.cost_basis_sold;
+            let sell_proceeds = position.cost_basis_sold + position.realized_profit;
+            let entry_price = if position.shares_bought > 0.0 {
+                total_invested / position.shares_bought
+            } else {
+                0.0
+            };
+            let entered_at = position.buys.iter().map(|b| b.timestamp).min();
+
+            if let Some((market, payouts)) = resolution {
+                // Remaining shares pay out at the settled price of the outcome held
+                let payout_per_share = payouts.get(position.outcome_index).copied().unwrap_or(0.0);
+                let resolution_payout = position.net_shares * payout_per_share;
+                let payout = resolution_payout + sell_proceeds;
+                let profit = payout - total_invested;
+
+                // Held to a single winner, the bet wins if it picked it; a position sold
+                // before resolution or a split resolution has no winner to pick, so judge
+                // by profit instead
+                let winning_index = market.winning_outcome_index();
+                let exited_early = position.net_shares == 0.0;
+                let won = match winning_index {
+                    Some(index) if !exited_early => position.outcome_index == index,
+                    _ => profit > 0.0,
+                };
+
+                let resolved_at = market.resolution_timestamp();
+                let hours_before_resolution =
+                    resolved_at.and_then(|at| self.hours_before_resolution(&position.buys, at));
+                let late_low_price_buy = resolved_at
+                    .map(|at| self.has_late_low_price_buy(&position.buys, at))
+                    .unwrap_or(false);
+
+                resolved_positions.push(ResolvedPosition {
+                    condition_id: position.condition_id.clone(),
+                    market_title: market.question.clone(),
+                    bet_outcome_index: position.outcome_index,
+                    bet_outcome: if position.outcome.is_empty() {
+                        market.outcome_name(position.outcome_index)
+                    } else {
+                        position.outcome.clone()
+                    },
+                    winning_outcome_index: winning_index,
+                    winning_outcome: winning_index.map(|index| market.outcome_name(index)),
+                    net_shares: position.net_shares,
+                    shares_bought: position.shares_bought,
+                    avg_price: entry_price,
+                    total_invested,
+                    payout,
+                    profit,  // Includes realized + unrealized
+                    realized_profit: position.realized_profit,
+                    exited_early,
+                    won,
+                    hours_before_resolution,
+                    late_low_price_buy,
+                    resolved_at,
+                    disputed: market.was_disputed(),
+                    category: market.primary_category(),
+                    event: market.event().map(|event| event.label().to_string()),
+                    entered_at,
+                    drift_pnl: None,
+                    skill_pnl: None,
+                    closing_price: None,
+                });
+            } else if position.net_shares == 0.0 && position.cost_basis_sold > 0.0 {
+                // Fully sold before resolution: the outcome is the realized P&L
+                resolved_positions.push(ResolvedPosition {
+                    condition_id: position.condition_id.clone(),
+                    market_title: position.market_title.clone(),
+                    bet_outcome_index: position.outcome_index,
+                    bet_outcome: position.outcome.clone(),
+                    winning_outcome_index: None,
+                    winning_outcome: None,
+                    net_shares: 0.0,
+                    shares_bought: position.shares_bought,
+                    avg_price: entry_price,
+                    total_invested,
+                    payout: sell_proceeds,
+                    profit: position.realized_profit,
+                    realized_profit: position.realized_profit,
+                    exited_early: true,
+                    won: position.realized_profit > 0.0,
+                    hours_before_resolution: None,
+                    late_low_price_buy: false,
+                    resolved_at: None,
+                    disputed: false,
+                    category: None,
+                    event: None,
+                    entered_at,
+                    drift_pnl: None,
+                    skill_pnl: None,
+                    closing_price: None,
+                });
             }
         }
 
         resolved_positions
     }
 
-    /// Determines the winning outcome from a market's outcome prices
-    /// Returns None if market is not resolved or outcome is ambiguous
-    fn get_winning_outcome(&self, market: &Market) -> Option<usize> {
-        let prices_str = market.outcome_prices.as_ref()?;
+    /// Cost-weighted average number of hours between a position's buys and resolution
+    fn hours_before_resolution(&self, buys: &[Fill], resolved_at: i64) -> Option<f64> {
+        let total_cost: f64 = buys.iter().map(|b| b.price * b.size).sum();
+        if total_cost <= 0.0 {
+            return None;
+        }
 
-        // Parse outcome prices - format: "[\"0.xxx\", \"1.xxx\"]"
-        let prices: Vec<f64> = serde_json::from_str::<Vec<String>>(prices_str)
-            .ok()?
+        let weighted_hours: f64 = buys
             .iter()
-            .filter_map(|s| s.parse().ok())
-            .collect();
+            .map(|b| {
+                let hours = (resolved_at - b.timestamp).max(0) as f64 / 3600.0;
+                hours * b.price * b.size
+            })
+            .sum();
 
-        if prices.len() != 2 {
-            return None;
-        }
+        Some(weighted_hours / total_cost)
+    }
 
-        // The winning outcome has a price close to 1.0
-        // The losing outcome has a price close to 0.0
-        if prices[0] > 0.9 {
-            Some(0)
-        } else if prices[1] > 0.9 {
-            Some(1)
-        } else {
-            // Market not yet resolved or ambiguous
-            None
-        }
+    /// Whether any buy happened at a low price shortly before the market resolved
+    fn has_late_low_price_buy(&self, buys: &[Fill], resolved_at: i64) -> bool {
+        buys.iter().any(|b| {
+            let hours = (resolved_at - b.timestamp) as f64 / 3600.0;
+            (0.0..=LATE_BUY_WINDOW_HOURS).contains(&hours) && b.price < LATE_BUY_MAX_PRICE
+        })
     }
 
     /// Calculates overall performance metrics
     fn calculate_performance(
         &self,
         wallet_address: &str,
-        trades: &[Trade],
+        total_trades: usize,
+        total_markets: usize,
         resolved_positions: &[ResolvedPosition],
     ) -> WalletPerformance {
-        let total_trades = trades.len();
-
-        // Count unique markets
-        let unique_markets: std::collections::HashSet<_> =
-            trades.iter().map(|t| &t.condition_id).collect();
-        let total_markets = unique_markets.len();
-
         let resolved_positions_count = resolved_positions.len();
         let wins = resolved_positions.iter().filter(|p| p.won).count();
         let losses = resolved_positions_count - wins;
@@ -190,6 +449,8 @@ impl WalletAnalyzer {
         let total_invested: f64 = resolved_positions.iter().map(|p| p.total_invested).sum();
         let total_payout: f64 = resolved_positions.iter().map(|p| p.payout).sum();
         let net_profit = total_payout - total_invested;
+        let realized_profit: f64 = resolved_positions.iter().map(|p| p.realized_profit).sum();
+        let closed_positions = resolved_positions.iter().filter(|p| p.exited_early).count();
 
         let roi = if total_invested > 0.0 {
             (net_profit / total_invested) * 100.0
@@ -212,10 +473,37 @@ impl WalletAnalyzer {
             0.0
         };
 
+        // Timing of winning bets relative to resolution
+        let late_winning_buys = winning_positions.iter().filter(|p| p.late_low_price_buy).count();
+        let winning_hours: Vec<f64> = winning_positions
+            .iter()
+            .filter_map(|p| p.hours_before_resolution)
+            .collect();
+        let avg_hours_before_resolution = if !winning_hours.is_empty() {
+            Some(winning_hours.iter().sum::<f64>() / winning_hours.len() as f64)
+        } else {
+            None
+        };
+
+        // A position bought at $0.80 wins 80% of the time if the market priced it right, so the
+        // mean entry price of positions held to resolution is the win rate to expect
+        let entry_prices: Vec<f64> = resolved_positions
+            .iter()
+            .filter(|p| !p.exited_early && p.avg_price > 0.0)
+            .map(|p| p.avg_price.min(1.0))
+            .collect();
+        let expected_win_rate = (!entry_prices.is_empty())
+            .then(|| entry_prices.iter().sum::<f64>() / entry_prices.len() as f64 * 100.0);
+
+        // Keep the per-market breakdown, biggest winners first
+        let mut positions = resolved_positions.to_vec();
+        positions.sort_by(|a, b| b.profit.partial_cmp(&a.profit).unwrap());
+
         WalletPerformance {
             wallet_address: wallet_address.to_string(),
             total_trades,
             total_markets,
+            volume: 0.0,
             resolved_positions: resolved_positions_count,
             wins,
             losses,
@@ -223,9 +511,551 @@ impl WalletAnalyzer {
             total_invested,
             total_payout,
             net_profit,
+            realized_profit,
+            closed_positions,
+            dust_positions: 0,
             roi,
             avg_profit_per_win,
             avg_loss_per_loss,
+            late_winning_buys,
+            avg_hours_before_resolution,
+            taker_fills: 0,
+            maker_fills: 0,
+            taker_ratio: None,
+            open_positions: Vec::new(),
+            unrealized_pnl: 0.0,
+            attributed_positions: 0,
+            drift_pnl: 0.0,
+            skill_pnl: 0.0,
+            periods: self.periods(resolved_positions),
+            cadence: None,
+            market_making: None,
+            stake_sizing: None,
+            first_trade_at: None,
+            fresh_wallet_wins: Vec::new(),
+            activity: None,
+            expected_win_rate,
+            closing_line: None,
+            positions,
+        }
+    }
+
+    /// Measures how regularly trades are spaced, from their timestamps in any order
+    /// Gaps are bucketed by `bot_tolerance_secs` either side of each gap; the densest
+    /// bucket gives the typical gap, and its share of all gaps the regularity
+    pub fn cadence(&self, timestamps: &[i64]) -> Option<TradeCadence> {
+        let mut timestamps = timestamps.to_vec();
+        timestamps.sort_unstable();
+        timestamps.dedup();
+
+        let mut gaps: Vec<f64> = timestamps.windows(2).map(|pair| (pair[1] - pair[0]) as f64).collect();
+        if gaps.len() < 2 {
+            return None;
+        }
+        gaps.sort_by(f64::total_cmp);
+
+        // Widest run of sorted gaps spanning at most twice the tolerance
+        let width = self.suspicion.bot_tolerance_secs.max(0.0) * 2.0;
+        let (mut best_start, mut best_len, mut end) = (0, 0, 0);
+        for start in 0..gaps.len() {
+            while end < gaps.len() && gaps[end] - gaps[start] <= width {
+                end += 1;
+            }
+            if end - start > best_len {
+                best_start = start;
+                best_len = end - start;
+            }
+        }
+        let typical_interval_secs = gaps[best_start + best_len / 2];
+        let regularity = best_len as f64 / gaps.len() as f64;
+
+        let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+        let variance = gaps.iter().map(|gap| (gap - mean).powi(2)).sum::<f64>() / gaps.len() as f64;
+        let variation = if mean > 0.0 { variance.sqrt() / mean } else { 0.0 };
+
+        let likely_bot = gaps.len() >= self.suspicion.bot_min_intervals
+            && regularity >= self.suspicion.bot_regularity
+            && typical_interval_secs >= MIN_BOT_INTERVAL_SECS;
+
+        Some(TradeCadence {
+            intervals: gaps.len(),
+            typical_interval_secs,
+            regularity,
+            variation,
+            likely_bot,
+        })
+    }
+
+    /// Measures how much the wallet trades like a market maker, from every fill in the ledger
+    /// Within a market, buying the first outcome or selling another counts as one side and the
+    /// reverse as the other, so quoting both outcomes nets out like buying and selling one
+    pub fn market_making(&self, ledger: &PositionLedger) -> Option<MarketMaking> {
+        if ledger.total_trades == 0 || ledger.volume <= 0.0 {
+            return None;
+        }
+        let config = &self.suspicion;
+
+        // condition ID -> (net dollars on the first outcome's side, outcomes bought, sold anything)
+        let mut markets: HashMap<&str, (f64, HashSet<usize>, bool)> = HashMap::new();
+        let mut sold = 0.0;
+        for ((condition_id, outcome_index), pending) in &ledger.positions {
+            let market = markets.entry(condition_id.as_str()).or_default();
+            let direction = if *outcome_index == 0 { 1.0 } else { -1.0 };
+            for fill in &pending.fills {
+                let dollars = fill.size * fill.price;
+                match fill.side {
+                    LedgerSide::Buy => {
+                        market.0 += direction * dollars;
+                        market.1.insert(*outcome_index);
+                    }
+                    LedgerSide::Sell => {
+                        market.0 -= direction * dollars;
+                        market.2 = true;
+                        sold += dollars;
+                    }
+                    LedgerSide::Split | LedgerSide::Merge | LedgerSide::Other => {}
+                }
+            }
+        }
+
+        let two_sided = markets
+            .values()
+            .filter(|(_, bought, sold)| bought.len() > 1 || (*sold && !bought.is_empty()))
+            .count();
+        let two_sided_share = two_sided as f64 / markets.len().max(1) as f64;
+        let net_exposure = (markets.values().map(|(net, ..)| net.abs()).sum::<f64>() / ledger.volume).min(1.0);
+        let sell_share = sold / ledger.volume;
+        let avg_trade_size = ledger.volume / ledger.total_trades as f64;
+
+        let likely_market_maker = ledger.total_trades >= config.market_maker_min_trades
+            && avg_trade_size <= config.market_maker_max_avg_trade
+            && two_sided_share >= config.market_maker_min_two_sided
+            && net_exposure <= config.market_maker_max_net_exposure
+            && (config.market_maker_min_sell_share..=1.0 - config.market_maker_min_sell_share).contains(&sell_share);
+
+        Some(MarketMaking {
+            trades: ledger.total_trades,
+            avg_trade_size,
+            sell_share,
+            two_sided_share,
+            net_exposure,
+            likely_market_maker,
+        })
+    }
+
+    /// Measures the wallet's bet sizes, and finds bets far larger than its estimated balance
+    /// on thinly traded markets
+    /// Fills are replayed oldest first to track the cost of positions still open; the most
+    /// ever open at once before a bet is a lower bound on the balance the wallet had to bet from.
+    /// Only resolved markets have a known volume, so unresolved bets are never all-in
+    pub fn stake_sizing(&self, ledger: &PositionLedger, resolved_markets: &[Market]) -> Option<StakeSizing> {
+        let config = &self.suspicion;
+        let market_map: HashMap<&str, &Market> = resolved_markets
+            .iter()
+            .filter_map(|m| m.condition_id.as_deref().map(|id| (id, m)))
+            .collect();
+
+        // Every fill of the positions that aren't dust, plus each market's resolution
+        let mut events: Vec<(i64, u8, &(String, usize), LedgerFill)> = Vec::new();
+        for (key, pending) in &ledger.positions {
+            if self.is_dust(&replay_position(&key.0, key.1, pending)) {
+                continue;
+            }
+            for fill in &pending.fills {
+                events.push((fill.timestamp, 0, key, *fill));
+            }
+            if let Some(resolved_at) = market_map.get(key.0.as_str()).and_then(|m| m.resolution_timestamp()) {
+                let release = LedgerFill { timestamp: resolved_at, side: LedgerSide::Other, size: 0.0, price: 0.0 };
+                events.push((resolved_at, 1, key, release));
+            }
+        }
+        events.sort_by_key(|(timestamp, order, ..)| (*timestamp, *order));
+
+        // (shares, cost) held per position, and each bet with the peak exposure before it
+        let mut held: HashMap<&(String, usize), (f64, f64)> = HashMap::new();
+        let mut bets: Vec<(&(String, usize), i64, f64, f64)> = Vec::new();
+        let mut bet_index: HashMap<(&(String, usize), i64), usize> = HashMap::new();
+        let (mut exposure, mut peak, mut peak_before) = (0.0_f64, 0.0_f64, 0.0_f64);
+        let mut current = None;
+        for (timestamp, order, key, fill) in events {
+            if current != Some(timestamp) {
+                peak_before = peak;
+                current = Some(timestamp);
+            }
+            let (shares, cost) = held.entry(key).or_default();
+            match (order, fill.side) {
+                (0, LedgerSide::Buy) => {
+                    let stake = fill.size * fill.price;
+                    *shares += fill.size;
+                    *cost += stake;
+                    exposure += stake;
+                    let index = *bet_index.entry((key, timestamp)).or_insert_with(|| {
+                        bets.push((key, timestamp, 0.0, peak_before));
+                        bets.len() - 1
+                    });
+                    bets[index].2 += stake;
+                }
+                (0, LedgerSide::Split) => {
+                    let cost_of_split = fill.size * fill.price;
+                    *shares += fill.size;
+                    *cost += cost_of_split;
+                    exposure += cost_of_split;
+                }
+                (0, LedgerSide::Sell | LedgerSide::Merge) => {
+                    let sold = fill.size.min(*shares);
+                    if sold > 0.0 {
+                        let released = *cost * sold / *shares;
+                        *shares -= sold;
+                        *cost -= released;
+                        exposure -= released;
+                    }
+                }
+                (1, _) => {
+                    exposure -= *cost;
+                    *shares = 0.0;
+                    *cost = 0.0;
+                }
+                _ => {}
+            }
+            peak = peak.max(exposure);
+        }
+        if bets.is_empty() {
+            return None;
+        }
+
+        let mut stakes: Vec<f64> = bets.iter().map(|bet| bet.2).collect();
+        stakes.sort_by(f64::total_cmp);
+        let median_stake = stakes[stakes.len() / 2];
+        let p90_stake = stakes[((stakes.len() as f64 * 0.9).ceil() as usize).clamp(1, stakes.len()) - 1];
+        let (max_key, _, max_stake, _) = *bets.iter().max_by(|a, b| a.2.total_cmp(&b.2))?;
+        let title = |key: &(String, usize)| {
+            ledger.positions[key].market_title.clone().unwrap_or_else(|| "Unknown".to_string())
+        };
+
+        let judged = bets.iter().skip(config.all_in_min_prior_bets).filter(|bet| bet.3 > 0.0);
+        let max_balance_multiple = judged.clone().map(|bet| bet.2 / bet.3).max_by(f64::total_cmp);
+        let all_in_bets = judged
+            .filter(|(_, _, stake, prior_balance)| {
+                *stake >= config.all_in_min_stake && stake / prior_balance >= config.all_in_balance_multiple
+            })
+            .filter_map(|&(key, timestamp, stake, prior_balance)| {
+                let market = market_map.get(key.0.as_str())?;
+                let market_volume = market.parsed_volume();
+                (market_volume <= config.all_in_max_market_volume).then(|| AllInBet {
+                    condition_id: key.0.clone(),
+                    market_title: market.question.clone(),
+                    outcome: ledger.positions[key].outcome.clone().unwrap_or_else(|| market.outcome_name(key.1)),
+                    timestamp,
+                    stake,
+                    prior_balance,
+                    balance_multiple: stake / prior_balance,
+                    median_multiple: if median_stake > 0.0 { stake / median_stake } else { 0.0 },
+                    market_volume,
+                    won: market.winning_outcome_index().map(|index| index == key.1),
+                })
+            })
+            .collect();
+
+        Some(StakeSizing {
+            bets: bets.len(),
+            median_stake,
+            p90_stake,
+            max_stake,
+            max_stake_market: title(max_key),
+            max_balance_multiple,
+            all_in_bets,
+        })
+    }
+
+    /// Groups resolved positions by when they were entered, oldest period first
+    /// Positions without a recorded buy can't be placed and are left out
+    fn periods(&self, resolved_positions: &[ResolvedPosition]) -> Vec<PeriodPerformance> {
+        let now = Utc::now().timestamp();
+        let mut periods: BTreeMap<i64, PeriodPerformance> = BTreeMap::new();
+
+        for position in resolved_positions {
+            let Some(entered_at) = position.entered_at else { continue };
+            let Some((start, label)) = self.period_of(entered_at, now) else { continue };
+
+            let period = periods.entry(start).or_insert_with(|| PeriodPerformance {
+                label,
+                start,
+                positions: 0,
+                wins: 0,
+                losses: 0,
+                invested: 0.0,
+                profit: 0.0,
+                roi: 0.0,
+            });
+            period.positions += 1;
+            if position.won {
+                period.wins += 1;
+            } else {
+                period.losses += 1;
+            }
+            period.invested += position.total_invested;
+            period.profit += position.profit;
+        }
+
+        periods
+            .into_values()
+            .map(|mut period| {
+                if period.invested > 0.0 {
+                    period.roi = period.profit / period.invested * 100.0;
+                }
+                period
+            })
+            .collect()
+    }
+
+    /// Start and label of the period containing `timestamp`: its calendar month, or the
+    /// `period_days` window it falls in, counting back from `now`
+    fn period_of(&self, timestamp: i64, now: i64) -> Option<(i64, String)> {
+        if self.period_days == 0 {
+            let date = DateTime::from_timestamp(timestamp, 0)?.date_naive();
+            let start = NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?;
+            Some((start.and_hms_opt(0, 0, 0)?.and_utc().timestamp(), start.format("%Y-%m").to_string()))
+        } else {
+            let length = self.period_days as i64 * 86400;
+            let start = now - ((now - timestamp).div_euclid(length) + 1) * length;
+            let label = DateTime::from_timestamp(start, 0)?.format("%Y-%m-%d").to_string();
+            Some((start, label))
+        }
+    }
+
+    /// Infers whether each trade took or added liquidity by checking it against the
+    /// wallet's taker-only fills; trades without a transaction hash can't be matched
+    pub fn liquidity_roles(&self, trades: &[Trade], taker_trades: &[Trade]) -> Vec<Option<LiquidityRole>> {
+        let taker_keys: HashSet<_> = taker_trades.iter().filter_map(fill_key).collect();
+
+        trades
+            .iter()
+            .map(|trade| {
+                fill_key(trade).map(|key| {
+                    if taker_keys.contains(&key) {
+                        LiquidityRole::Taker
+                    } else {
+                        LiquidityRole::Maker
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Fills in the taker/maker counts and taker ratio of a performance report
+    pub fn apply_liquidity_roles(&self, performance: &mut WalletPerformance, trades: &[Trade], taker_trades: &[Trade]) {
+        let roles = self.liquidity_roles(trades, taker_trades);
+        let taker_fills = roles.iter().filter(|r| **r == Some(LiquidityRole::Taker)).count();
+        let maker_fills = roles.iter().filter(|r| **r == Some(LiquidityRole::Maker)).count();
+
+        performance.taker_fills = taker_fills;
+        performance.maker_fills = maker_fills;
+        performance.taker_ratio = if taker_fills + maker_fills > 0 {
+            Some(taker_fills as f64 / (taker_fills + maker_fills) as f64)
+        } else {
+            None
+        };
+    }
+
+    /// Lists the pre-resolution prices needed to attribute each position held to resolution
+    pub fn attribution_queries(&self, performance: &WalletPerformance, resolved_markets: &[Market]) -> Vec<AttributionQuery> {
+        let token_ids: HashMap<&String, Vec<String>> = resolved_markets
+            .iter()
+            .filter_map(|m| Some((m.condition_id.as_ref()?, m.parsed_clob_token_ids()?)))
+            .collect();
+
+        performance
+            .positions
+            .iter()
+            .filter(|p| !p.exited_early)
+            .filter_map(|position| {
+                let token_id = token_ids.get(&position.condition_id)?.get(position.bet_outcome_index)?;
+                Some(AttributionQuery {
+                    condition_id: position.condition_id.clone(),
+                    outcome_index: position.bet_outcome_index,
+                    token_id: token_id.clone(),
+                    at: position.resolved_at? - PRE_RESOLUTION_HOURS * 3600,
+                })
+            })
+            .collect()
+    }
+
+    /// Splits each position's profit into market drift and skill, given the outcome's
+    /// price shortly before resolution (keyed by condition ID and outcome index)
+    ///
+    /// Drift is what the position earned from the market repricing the outcome between
+    /// entry and just before resolution - gains any trend-follower holding it shared.
+    /// Skill is the remainder: the part of the resolution the market never priced in.
+    ///
+    /// The same price is the position's closing line, so the entries are also scored
+    /// against it (closing line value)
+    pub fn apply_attribution(&self, performance: &mut WalletPerformance, pre_resolution_prices: &HashMap<(String, usize), f64>) {
+        let mut attributed = 0;
+        let mut total_drift = 0.0;
+        let mut total_skill = 0.0;
+        let mut beat_close = 0;
+        let mut shares = 0.0;
+        let mut invested = 0.0;
+        let mut edge = 0.0;
+
+        for position in &mut performance.positions {
+            let key = (position.condition_id.clone(), position.bet_outcome_index);
+            let Some(&price) = pre_resolution_prices.get(&key) else { continue };
+
+            let drift = position.net_shares * (price - position.avg_price) + position.realized_profit;
+            let skill = position.profit - drift;
+
+            position.drift_pnl = Some(drift);
+            position.skill_pnl = Some(skill);
+            position.closing_price = Some(price);
+            attributed += 1;
+            total_drift += drift;
+            total_skill += skill;
+
+            if price > position.avg_price {
+                beat_close += 1;
+            }
+            shares += position.shares_bought;
+            invested += position.total_invested;
+            edge += position.shares_bought * (price - position.avg_price);
+        }
+
+        performance.attributed_positions = attributed;
+        performance.drift_pnl = total_drift;
+        performance.skill_pnl = total_skill;
+        performance.closing_line = (attributed > 0).then(|| ClosingLineValue {
+            positions: attributed,
+            beat_close,
+            mean_edge: if shares > 0.0 { edge / shares } else { 0.0 },
+            roi: if invested > 0.0 { edge / invested * 100.0 } else { 0.0 },
+        });
+    }
+
+    /// Lists the price histories needed for a wallet's timeline: each traded outcome from a
+    /// day before its first trade until the lookahead window after its last
+    pub fn timeline_queries(&self, trades: &[Trade], markets: &[Market]) -> Vec<TimelineQuery> {
+        let token_ids: HashMap<&str, Vec<String>> = markets
+            .iter()
+            .filter_map(|m| Some((m.condition_id.as_deref()?, m.parsed_clob_token_ids()?)))
+            .collect();
+
+        let mut ranges: BTreeMap<(&str, usize), (&str, i64, i64)> = BTreeMap::new();
+        for trade in trades {
+            let range = ranges
+                .entry((&trade.condition_id, trade.outcome_index))
+                .or_insert((&trade.outcome, trade.timestamp, trade.timestamp));
+            range.1 = range.1.min(trade.timestamp);
+            range.2 = range.2.max(trade.timestamp);
+        }
+
+        let now = Utc::now().timestamp();
+        ranges
+            .into_iter()
+            .filter_map(|((condition_id, outcome_index), (outcome, first, last))| {
+                let token_id = token_ids.get(condition_id)?.get(outcome_index)?;
+                Some(TimelineQuery {
+                    condition_id: condition_id.to_string(),
+                    outcome_index,
+                    outcome: outcome.to_string(),
+                    token_id: token_id.clone(),
+                    start: first - 24 * 3600,
+                    end: (last + TIMELINE_LOOKAHEAD_HOURS * 3600).min(now),
+                })
+            })
+            .collect()
+    }
+
+    /// Builds a wallet's timeline: every trade in order, classified as opening, adding to,
+    /// reducing or closing its position, with the outcome's price at the trade and the
+    /// lookahead window later (histories keyed by condition ID and outcome index)
+    pub fn timeline(
+        &self,
+        wallet_address: &str,
+        trades: &[Trade],
+        queries: &[TimelineQuery],
+        histories: &HashMap<(String, usize), Vec<PricePoint>>,
+    ) -> WalletTimeline {
+        let now = Utc::now().timestamp();
+        let lookahead = TIMELINE_LOOKAHEAD_HOURS * 3600;
+        let price_at = |key: &(String, usize), timestamp: i64| {
+            histories
+                .get(key)?
+                .iter()
+                .filter(|point| point.timestamp <= timestamp)
+                .max_by_key(|point| point.timestamp)
+                .map(|point| point.price)
+        };
+
+        let mut ordered: Vec<&Trade> = trades.iter().collect();
+        ordered.sort_by_key(|trade| trade.timestamp);
+
+        let mut holdings: HashMap<(String, usize), f64> = HashMap::new();
+        let events: Vec<TimelineEvent> = ordered
+            .into_iter()
+            .map(|trade| {
+                let key = (trade.condition_id.clone(), trade.outcome_index);
+                let held = holdings.entry(key.clone()).or_insert(0.0);
+                let before = *held;
+                let action = if trade.side == "BUY" {
+                    *held += trade.size;
+                    if before <= f64::EPSILON { TimelineAction::Open } else { TimelineAction::Add }
+                } else {
+                    *held = (*held - trade.size).max(0.0);
+                    if *held <= f64::EPSILON { TimelineAction::Close } else { TimelineAction::Reduce }
+                };
+                let position_shares = *held;
+
+                let market_price = price_at(&key, trade.timestamp);
+                let price_after = (trade.timestamp + lookahead <= now)
+                    .then(|| price_at(&key, trade.timestamp + lookahead))
+                    .flatten();
+                TimelineEvent {
+                    timestamp: trade.timestamp,
+                    condition_id: trade.condition_id.clone(),
+                    market_title: trade.title.clone().unwrap_or_else(|| trade.condition_id.clone()),
+                    outcome: trade.outcome.clone(),
+                    outcome_index: trade.outcome_index,
+                    action,
+                    shares: trade.size,
+                    fill_price: trade.price,
+                    position_shares,
+                    market_price,
+                    price_after,
+                    price_change: market_price.zip(price_after).map(|(at, after)| after - at),
+                }
+            })
+            .collect();
+
+        let entry_changes: Vec<f64> = events
+            .iter()
+            .filter(|event| event.action.is_entry())
+            .filter_map(|event| event.price_change)
+            .collect();
+        let series = queries
+            .iter()
+            .filter_map(|query| {
+                let points = histories.get(&(query.condition_id.clone(), query.outcome_index))?;
+                Some(TimelineSeries {
+                    condition_id: query.condition_id.clone(),
+                    outcome_index: query.outcome_index,
+                    outcome: query.outcome.clone(),
+                    token_id: query.token_id.clone(),
+                    points: points.clone(),
+                })
+            })
+            .collect();
+
+        WalletTimeline {
+            wallet_address: wallet_address.to_string(),
+            generated_at: now,
+            lookahead_hours: TIMELINE_LOOKAHEAD_HOURS,
+            jump_threshold: TIMELINE_JUMP,
+            events,
+            series,
+            priced_entries: entry_changes.len(),
+            entries_before_jumps: entry_changes.iter().filter(|&&change| change >= TIMELINE_JUMP).count(),
+            mean_change_after_entry: (!entry_changes.is_empty())
+                .then(|| entry_changes.iter().sum::<f64>() / entry_changes.len() as f64),
         }
     }
 
@@ -235,6 +1065,7 @@ impl WalletAnalyzer {
             wallet_address,
             total_trades: 0,
             total_markets: 0,
+            volume: 0.0,
             resolved_positions: 0,
             wins: 0,
             losses: 0,
@@ -242,111 +1073,782 @@ impl WalletAnalyzer {
             total_invested: 0.0,
             total_payout: 0.0,
             net_profit: 0.0,
+            realized_profit: 0.0,
+            closed_positions: 0,
+            dust_positions: 0,
             roi: 0.0,
             avg_profit_per_win: 0.0,
             avg_loss_per_loss: 0.0,
+            late_winning_buys: 0,
+            avg_hours_before_resolution: None,
+            taker_fills: 0,
+            maker_fills: 0,
+            taker_ratio: None,
+            open_positions: Vec::new(),
+            unrealized_pnl: 0.0,
+            attributed_positions: 0,
+            drift_pnl: 0.0,
+            skill_pnl: 0.0,
+            positions: Vec::new(),
+            periods: Vec::new(),
+            cadence: None,
+            market_making: None,
+            stake_sizing: None,
+            first_trade_at: None,
+            fresh_wallet_wins: Vec::new(),
+            activity: None,
+            expected_win_rate: None,
+            closing_line: None,
         }
     }
 
-    /// Determines if a wallet's performance is suspicious (potential insider)
-    pub fn is_suspicious(&self, performance: &WalletPerformance) -> (bool, Vec<String>) {
-        let mut flags = Vec::new();
+    /// Scores how strongly a wallet's performance suggests insider knowledge (0-100)
+    /// The score is the weighted mean of the sub-scores, scaled down for small samples
+    pub fn suspicion(&self, performance: &WalletPerformance) -> SuspicionReport {
+        let config = &self.suspicion;
+        let mut reasons = Vec::new();
 
-        // Minimum thresholds to avoid false positives from small sample sizes
-        if performance.resolved_positions < 10 {
-            return (false, vec!["Insufficient data (less than 10 resolved positions)".to_string()]);
-        }
+        // Win rate: normal traders win ~50-60% of the time, but a bot's win rate measures
+        // its strategy (market making, arbitrage), not foreknowledge of outcomes
+        let bot_cadence = performance.cadence.as_ref().filter(|c| c.likely_bot);
+
+        // Tested against the win rate the entry prices implied, so backing heavy favorites
+        // doesn't pass for skill, and only counted when it's unlikely to be luck
+        let held: Vec<&ResolvedPosition> = performance.positions.iter().filter(|p| !p.exited_early).collect();
+        let (baseline, baseline_label) = match performance.expected_win_rate {
+            Some(expected) if config.price_implied_baseline => (expected, "from entry prices"),
+            _ => (50.0, "by chance"),
+        };
+        let held_wins = held.iter().filter(|p| p.won).count();
+        // The baseline only covers positions held to resolution, so compare their win rate with it
+        let (tested_win_rate, tested_positions) = if held.is_empty() {
+            (performance.win_rate, performance.resolved_positions)
+        } else {
+            (held_wins as f64 / held.len() as f64 * 100.0, held.len())
+        };
+        let p_value = (!held.is_empty()).then(|| binomial_tail(held.len(), held_wins, baseline / 100.0));
+        let significant = p_value.is_none_or(|p| p < config.win_rate_significance);
+        let test = match p_value {
+            Some(p) => format!("vs {:.1}% expected {}, p = {:.3}", baseline, baseline_label, p),
+            None => format!("vs {:.1}% expected {}", baseline, baseline_label),
+        };
 
-        // Flag 1: Abnormally high win rate (>75% is very suspicious)
-        if performance.win_rate > 75.0 {
-            flags.push(format!(
-                "Extremely high win rate: {:.1}% (normal is ~50-60%)",
+        let win_rate = match bot_cadence {
+            Some(_) => 0.0,
+            None if !significant => 0.0,
+            None => ramp(tested_win_rate - baseline + 50.0, config.win_rate_floor, config.win_rate_ceiling),
+        };
+        if let Some(cadence) = bot_cadence {
+            reasons.push(format!(
+                "Likely bot: {:.0}% of {} gaps between trades are {} ±{:.0}s, so the {:.1}% win rate is left out of the score",
+                cadence.regularity * 100.0,
+                cadence.intervals,
+                format_interval(cadence.typical_interval_secs),
+                config.bot_tolerance_secs,
                 performance.win_rate
             ));
-        } else if performance.win_rate > 65.0 {
-            flags.push(format!(
-                "Suspicious win rate: {:.1}% (normal is ~50-60%)",
-                performance.win_rate
+        } else if win_rate >= 0.5 {
+            reasons.push(format!(
+                "High win rate: {:.1}% over {} resolved positions ({})",
+                tested_win_rate, tested_positions, test
+            ));
+        } else if !significant && tested_win_rate >= config.win_rate_floor {
+            reasons.push(format!(
+                "Win rate within luck: {:.1}% over {} resolved positions ({}), left out of the score",
+                tested_win_rate, tested_positions, test
+            ));
+        }
+        if let Some(making) = performance.market_making.as_ref().filter(|m| m.likely_market_maker) {
+            reasons.push(format!(
+                "Likely market maker: {} trades averaging ${:.2}, {:.0}% of markets traded on both sides, {:.0}% net exposure",
+                making.trades,
+                making.avg_trade_size,
+                making.two_sided_share * 100.0,
+                making.net_exposure * 100.0
             ));
         }
 
-        // Flag 2: High ROI with significant capital deployed
-        if performance.roi > 50.0 && performance.total_invested > 1000.0 {
-            flags.push(format!(
-                "Very high ROI: {:.1}% with ${:.2} invested",
+        // ROI, discounted until enough capital is at stake to rule out small lucky bets
+        let capital_weight = if config.roi_full_capital > 0.0 {
+            (performance.total_invested / config.roi_full_capital).min(1.0)
+        } else {
+            1.0
+        };
+        let roi = ramp(performance.roi, 0.0, config.roi_ceiling) * capital_weight;
+        if roi >= 0.5 {
+            reasons.push(format!(
+                "High ROI: {:.1}% with ${:.2} invested",
                 performance.roi, performance.total_invested
             ));
         }
 
-        // Flag 3: Consistent profitability across many markets
-        if performance.wins > 15 && performance.win_rate > 70.0 {
-            flags.push(format!(
-                "Consistent high performance: {} wins out of {} resolved positions",
-                performance.wins, performance.resolved_positions
+        // Timing: cheap winning buys shortly before resolution, or winning while paying the spread
+        let late_buys = ramp(performance.late_winning_buys as f64, 0.0, config.late_buys_ceiling);
+        if performance.late_winning_buys > 0 {
+            reasons.push(format!(
+                "Bought shortly before resolution at low price: {} winning positions bought under ${:.2} within {:.0}h of resolution",
+                performance.late_winning_buys, LATE_BUY_MAX_PRICE, LATE_BUY_WINDOW_HOURS
             ));
         }
+        let taker_urgency = match performance.taker_ratio {
+            Some(taker_ratio) if performance.taker_fills + performance.maker_fills >= MIN_FILLS_FOR_TAKER_RATIO => {
+                ramp(taker_ratio, 0.5, AGGRESSIVE_TAKER_RATIO) * win_rate
+            }
+            _ => 0.0,
+        };
+        if taker_urgency >= 0.5 {
+            reasons.push(format!(
+                "Aggressive taker: {:.0}% of fills took liquidity with a {:.1}% win rate",
+                performance.taker_ratio.unwrap_or(0.0) * 100.0, performance.win_rate
+            ));
+        }
+        // Fresh wallets: a large winning bet within days of the wallet's first trade
+        let fresh_wallet = ramp(performance.fresh_wallet_wins.len() as f64, 0.0, config.fresh_wallet_ceiling);
+        if let Some(largest) = performance.fresh_wallet_wins.iter().max_by(|a, b| a.stake.total_cmp(&b.stake)) {
+            reasons.push(format!(
+                "Fresh wallet: {} large winning bet(s) within {:.0} days of its first trade, largest ${:.2} on \"{}\" {:.1} days in",
+                performance.fresh_wallet_wins.len(), config.fresh_wallet_days, largest.stake, largest.market_title, largest.wallet_age_days
+            ));
+        }
+        let timing = late_buys.max(taker_urgency).max(fresh_wallet);
 
-        // Flag 4: Large average win compared to average loss (asymmetric betting pattern)
-        if performance.avg_profit_per_win > performance.avg_loss_per_loss.abs() * 2.0
-            && performance.wins > 10
-        {
-            flags.push(format!(
-                "Asymmetric profit pattern: avg win ${:.2} vs avg loss ${:.2}",
-                performance.avg_profit_per_win, performance.avg_loss_per_loss
+        // Concentration: share of winnings coming from the three best positions
+        let mut winnings: Vec<f64> = performance.positions.iter()
+            .map(|p| p.profit)
+            .filter(|profit| *profit > 0.0)
+            .collect();
+        winnings.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        let total_winnings: f64 = winnings.iter().sum();
+        let top_share = if total_winnings > 0.0 {
+            winnings.iter().take(3).sum::<f64>() / total_winnings
+        } else {
+            0.0
+        };
+        let position_concentration = ramp(top_share, config.concentration_floor, 1.0);
+        if position_concentration >= 0.5 {
+            reasons.push(format!(
+                "Concentrated winnings: top 3 positions made {:.0}% of ${:.2} won",
+                top_share * 100.0, total_winnings
             ));
         }
 
-        let is_suspicious = !flags.is_empty();
+        // Domain concentration: winnings from one category, or from markets on one topic or event
+        let concentration_flags = self.concentration_flags(&performance.positions);
+        let mut domain_concentration: f64 = 0.0;
+        for flag in &concentration_flags {
+            domain_concentration = domain_concentration.max(ramp(flag.share / 100.0, config.domain_concentration_floor, 1.0));
+            reasons.push(format!(
+                "Domain concentration: {:.0}% of ${:.2} won came from {} \"{}\" ({} winning positions)",
+                flag.share, total_winnings, flag.kind.label(), flag.label, flag.positions
+            ));
+        }
+        let concentration = position_concentration.max(domain_concentration);
 
-        (is_suspicious, flags)
+        // Size asymmetry: staking more on bets that win suggests knowing which ones will
+        let average_stake = |won: bool| {
+            let stakes: Vec<f64> = held.iter().filter(|p| p.won == won).map(|p| p.total_invested).collect();
+            (!stakes.is_empty()).then(|| stakes.iter().sum::<f64>() / stakes.len() as f64)
+        };
+        let size_ratio = match (average_stake(true), average_stake(false)) {
+            (Some(win_stake), Some(loss_stake)) if loss_stake > 0.0 => win_stake / loss_stake,
+            _ => 0.0,
+        };
+        let stake_ratio = ramp(size_ratio, 1.0, config.size_ratio_ceiling);
+        if stake_ratio >= 0.5 {
+            reasons.push(format!(
+                "Asymmetric sizing: average winning stake is {:.1}x the average losing stake",
+                size_ratio
+            ));
+        }
+
+        // All-in bets: staking more than the wallet ever had at risk on a market few others traded, and winning
+        let winning_all_ins: Vec<&AllInBet> = performance
+            .stake_sizing
+            .iter()
+            .flat_map(|sizing| &sizing.all_in_bets)
+            .filter(|bet| bet.won == Some(true))
+            .collect();
+        let all_in = ramp(winning_all_ins.len() as f64, 0.0, config.all_in_ceiling);
+        if let Some(largest) = winning_all_ins.iter().max_by(|a, b| a.stake.total_cmp(&b.stake)) {
+            reasons.push(format!(
+                "All-in bets: {} winning bet(s) on thinly traded markets, largest ${:.2} on \"{}\" ({:.1}x its prior peak exposure, ${:.0} market volume)",
+                winning_all_ins.len(), largest.stake, largest.market_title, largest.balance_multiple, largest.market_volume
+            ));
+        }
+        let size_asymmetry = stake_ratio.max(all_in);
+
+        let weights = &config.weights;
+        let total_weight = weights.win_rate + weights.roi + weights.timing + weights.concentration + weights.size_asymmetry;
+        let weighted = win_rate * weights.win_rate
+            + roi * weights.roi
+            + timing * weights.timing
+            + concentration * weights.concentration
+            + size_asymmetry * weights.size_asymmetry;
+
+        // Small samples can't distinguish insiders from luck, except that a fresh wallet's
+        // big win is itself the pattern, and comes with little history by nature
+        let confidence = if !performance.fresh_wallet_wins.is_empty() {
+            1.0
+        } else if config.min_resolved_positions > 0 {
+            (performance.resolved_positions as f64 / config.min_resolved_positions as f64).min(1.0)
+        } else {
+            1.0
+        };
+        if confidence < 1.0 {
+            reasons.push(format!(
+                "Limited data: {} resolved positions (score scaled by {:.0}%)",
+                performance.resolved_positions, confidence * 100.0
+            ));
+        }
+
+        let score = if total_weight > 0.0 { weighted / total_weight * 100.0 * confidence } else { 0.0 };
+
+        SuspicionReport {
+            score,
+            flagged: score >= config.flag_score,
+            confidence,
+            win_rate: win_rate * 100.0,
+            roi: roi * 100.0,
+            timing: timing * 100.0,
+            concentration: concentration * 100.0,
+            size_asymmetry: size_asymmetry * 100.0,
+            win_rate_baseline: Some(baseline),
+            win_rate_p_value: p_value,
+            concentration_flags,
+            likely_bot: bot_cadence.is_some(),
+            likely_market_maker: performance.market_making.as_ref().is_some_and(|m| m.likely_market_maker),
+            reasons,
+        }
+    }
+
+    /// The category, the topic (a title keyword shared by several markets, like a team's
+    /// name) and the event with the most winnings, when any holds at least
+    /// `domain_concentration_floor` of them over `domain_min_positions` or more winning positions
+    pub fn concentration_flags(&self, positions: &[ResolvedPosition]) -> Vec<ConcentrationFlag> {
+        let winners: Vec<&ResolvedPosition> = positions.iter().filter(|p| p.profit > 0.0).collect();
+        let total_winnings: f64 = winners.iter().map(|p| p.profit).sum();
+        if total_winnings <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut categories: HashMap<String, (f64, usize)> = HashMap::new();
+        let mut topics: HashMap<String, (f64, usize)> = HashMap::new();
+        let mut events: HashMap<String, (f64, usize)> = HashMap::new();
+        for position in &winners {
+            if let Some(category) = &position.category {
+                let entry = categories.entry(category.clone()).or_default();
+                entry.0 += position.profit;
+                entry.1 += 1;
+            }
+            if let Some(event) = &position.event {
+                let entry = events.entry(event.clone()).or_default();
+                entry.0 += position.profit;
+                entry.1 += 1;
+            }
+
+            let words: HashSet<String> = NormalizedTitle::new(&position.market_title)
+                .tokens
+                .into_iter()
+                .filter(|word| word.len() >= 3 && word.chars().any(char::is_alphabetic))
+                .filter(|word| !GENERIC_TOPIC_WORDS.contains(&word.as_str()))
+                .collect();
+            for word in words {
+                let entry = topics.entry(word).or_default();
+                entry.0 += position.profit;
+                entry.1 += 1;
+            }
+        }
+
+        let min_positions = self.suspicion.domain_min_positions.max(1);
+        let floor = self.suspicion.domain_concentration_floor;
+        let mut flags: Vec<ConcentrationFlag> = [
+            (ConcentrationKind::Category, categories),
+            (ConcentrationKind::Topic, topics),
+            (ConcentrationKind::Event, events),
+        ]
+            .into_iter()
+            .filter_map(|(kind, groups)| {
+                let (label, (profit, count)) = groups
+                    .into_iter()
+                    .filter(|(_, (_, count))| *count >= min_positions)
+                    .max_by(|a, b| a.1 .0.total_cmp(&b.1 .0).then_with(|| b.0.cmp(&a.0)))?;
+                let share = profit / total_winnings;
+                (share >= floor).then_some(ConcentrationFlag {
+                    kind,
+                    label,
+                    share: share * 100.0,
+                    profit,
+                    positions: count,
+                })
+            })
+            .collect();
+        flags.sort_by(|a, b| b.share.total_cmp(&a.share));
+        flags
     }
 
     /// Prints wallet performance in a formatted way
-    pub fn print_performance(&self, performance: &WalletPerformance) {
-        println!("\n{}", "=".repeat(80));
-        println!("WALLET PERFORMANCE REPORT");
-        println!("{}", "=".repeat(80));
-        println!("\nWallet: {}", performance.wallet_address);
-        println!("\n--- Trading Activity ---");
-        println!("Total Trades:         {}", performance.total_trades);
-        println!("Unique Markets:       {}", performance.total_markets);
-        println!("Resolved Positions:   {}", performance.resolved_positions);
-
-        println!("\n--- Win/Loss Record ---");
-        println!("Wins:                 {}", performance.wins);
-        println!("Losses:               {}", performance.losses);
-        println!("Win Rate:             {:.1}%", performance.win_rate);
-
-        println!("\n--- Financial Performance ---");
-        println!("Total Invested:       ${:.2}", performance.total_invested);
-        println!("Total Payout:         ${:.2}", performance.total_payout);
-        println!("Net Profit:           ${:.2}", performance.net_profit);
-        println!("ROI:                  {:.1}%", performance.roi);
-        println!(
+    /// In detailed mode, every resolved position is listed after the summary
+    pub fn print_performance(&self, performance: &WalletPerformance, detailed: bool) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WALLET PERFORMANCE REPORT");
+        outln!("{}", "=".repeat(80));
+        outln!("\nWallet: {}", performance.wallet_address);
+        outln!("\n--- Trading Activity ---");
+        outln!("Total Trades:         {}", performance.total_trades);
+        outln!("Unique Markets:       {}", performance.total_markets);
+        outln!("Trading Volume:       ${:.2}", performance.volume);
+        if let Some(cadence) = &performance.cadence {
+            outln!(
+                "Trade Cadence:        {} ({:.0}% of {} gaps within ±{:.0}s, variation {:.2})",
+                if cadence.likely_bot {
+                    "likely bot"
+                } else if cadence.intervals < self.suspicion.bot_min_intervals {
+                    "too few trades to classify"
+                } else {
+                    "human-like"
+                },
+                cadence.regularity * 100.0,
+                cadence.intervals,
+                self.suspicion.bot_tolerance_secs,
+                cadence.variation
+            );
+            outln!("Typical Gap:          {}", format_interval(cadence.typical_interval_secs));
+        }
+        if let Some(making) = &performance.market_making {
+            outln!(
+                "Market Making:        {} ({:.0}% of markets two-sided, {:.0}% sells, net exposure {:.0}%, ${:.2} per trade)",
+                if making.likely_market_maker {
+                    "likely market maker"
+                } else if making.trades < self.suspicion.market_maker_min_trades {
+                    "too few trades to classify"
+                } else {
+                    "directional"
+                },
+                making.two_sided_share * 100.0,
+                making.sell_share * 100.0,
+                making.net_exposure * 100.0,
+                making.avg_trade_size
+            );
+        }
+        if let Some(activity) = &performance.activity {
+            outln!(
+                "Other Activity:       {} splits, {} merges, {} redemptions, {} conversions, ${:.2} rewards",
+                activity.splits, activity.merges, activity.redemptions, activity.conversions, activity.rewards
+            );
+        }
+        outln!("Resolved Positions:   {}", performance.resolved_positions);
+        outln!("Closed Early:         {}", performance.closed_positions);
+        if performance.dust_positions > 0 {
+            outln!(
+                "Dust Ignored:         {} (under ${:.2} invested)",
+                performance.dust_positions, self.min_position_invested
+            );
+        }
+
+        outln!("\n--- Win/Loss Record ---");
+        outln!("Wins:                 {}", performance.wins);
+        outln!("Losses:               {}", performance.losses);
+        outln!("Win Rate:             {:.1}%", performance.win_rate);
+        if let Some(expected) = performance.expected_win_rate {
+            outln!("Expected Win Rate:    {:.1}% (implied by entry prices)", expected);
+        }
+        if performance.cadence.as_ref().is_some_and(|c| c.likely_bot) {
+            outln!("                      (likely bot: reflects an automated strategy, not picks)");
+        }
+
+        outln!("\n--- Financial Performance ---");
+        outln!("Total Invested:       ${:.2}", performance.total_invested);
+        outln!("Total Payout:         ${:.2}", performance.total_payout);
+        outln!("Net Profit:           ${:.2}", performance.net_profit);
+        outln!("Realized (Sells):     ${:.2}", performance.realized_profit);
+        outln!("ROI:                  {:.1}%", performance.roi);
+        outln!(
             "Avg Profit per Win:   ${:.2}",
             performance.avg_profit_per_win
         );
-        println!(
+        outln!(
             "Avg Loss per Loss:    ${:.2}",
             performance.avg_loss_per_loss
         );
 
-        // Check if suspicious
-        let (is_suspicious, flags) = self.is_suspicious(performance);
+        outln!("\n--- Timing ---");
+        match performance.avg_hours_before_resolution {
+            Some(hours) => outln!("Avg Hours Before Resolution (wins): {:.1}", hours),
+            None => outln!("Avg Hours Before Resolution (wins): n/a"),
+        }
+        outln!("Late Low-Price Winning Buys:        {}", performance.late_winning_buys);
+        if let Some(first_trade_at) = performance.first_trade_at {
+            let age_days = (Utc::now().timestamp() - first_trade_at) as f64 / 86_400.0;
+            outln!(
+                "First Trade:                        {} ({:.0} days ago)",
+                DateTime::from_timestamp(first_trade_at, 0).map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                age_days
+            );
+        }
+        for win in &performance.fresh_wallet_wins {
+            outln!(
+                "Fresh-Wallet Win:                   ${:.2} on {} in \"{}\" {:.1} days after the first trade (profit ${:.2})",
+                win.stake, win.outcome, win.market_title, win.wallet_age_days, win.profit
+            );
+        }
+
+        if let Some(sizing) = &performance.stake_sizing {
+            outln!("\n--- Stake Sizing ({} bets) ---", sizing.bets);
+            outln!("Median Stake:         ${:.2}", sizing.median_stake);
+            outln!("P90 Stake:            ${:.2}", sizing.p90_stake);
+            outln!("Max Single Bet:       ${:.2} on {}", sizing.max_stake, sizing.max_stake_market);
+            if let Some(multiple) = sizing.max_balance_multiple {
+                outln!("Max vs Balance:       {:.1}x the most previously held in open positions", multiple);
+            }
+            for bet in &sizing.all_in_bets {
+                outln!(
+                    "All-In Bet:           ${:.2} on {} in \"{}\" ({:.1}x prior balance, {:.1}x median, ${:.0} volume) - {}",
+                    bet.stake,
+                    bet.outcome,
+                    bet.market_title,
+                    bet.balance_multiple,
+                    bet.median_multiple,
+                    bet.market_volume,
+                    match bet.won {
+                        Some(true) => "won",
+                        Some(false) => "lost",
+                        None => "split",
+                    }
+                );
+            }
+        }
+
+        if !performance.open_positions.is_empty() {
+            let cost: f64 = performance.open_positions.iter().map(|p| p.cost_basis).sum();
+            let value: f64 = performance.open_positions.iter().filter_map(|p| p.market_value).sum();
+            let unpriced = performance.open_positions.iter().filter(|p| p.current_price.is_none()).count();
+
+            outln!("\n--- Open Positions (marked to market) ---");
+            outln!("Open Positions:       {}", performance.open_positions.len());
+            outln!("Cost Basis:           ${:.2}", cost);
+            outln!("Market Value:         ${:.2}", value);
+            outln!("Unrealized P&L:       ${:.2}", performance.unrealized_pnl);
+            if unpriced > 0 {
+                outln!("Unpriced:             {} (no current price)", unpriced);
+            }
+        }
+
+        if performance.attributed_positions > 0 {
+            let total = performance.drift_pnl.abs() + performance.skill_pnl.abs();
+            let skill_share = if total > 0.0 { performance.skill_pnl.abs() / total * 100.0 } else { 0.0 };
+            outln!("\n--- Profit Attribution ({} positions) ---", performance.attributed_positions);
+            outln!("Market Drift:         ${:.2}", performance.drift_pnl);
+            outln!("Skill:                ${:.2}", performance.skill_pnl);
+            outln!("Skill Share:          {:.1}%", skill_share);
+        }
+
+        if let Some(closing_line) = &performance.closing_line {
+            outln!("\n--- Closing Line Value ({} positions) ---", closing_line.positions);
+            outln!("Beat the Close:       {:.1}% ({} positions)", closing_line.beat_rate(), closing_line.beat_close);
+            outln!("Mean Edge:            {:+.1}¢ per share", closing_line.mean_edge * 100.0);
+            outln!("Closing Line ROI:     {:+.1}%", closing_line.roi);
+        }
+
+        if let Some(taker_ratio) = performance.taker_ratio {
+            outln!("\n--- Order Flow ---");
+            outln!(
+                "Taker Ratio:          {:.1}% ({} taker / {} maker fills)",
+                taker_ratio * 100.0, performance.taker_fills, performance.maker_fills
+            );
+        }
+
+        if !performance.periods.is_empty() {
+            self.print_periods(performance, detailed);
+        }
+
+        if detailed {
+            self.print_position_breakdown(performance);
+            if !performance.open_positions.is_empty() {
+                self.print_open_positions(performance);
+            }
+        }
+
+        let report = self.suspicion(performance);
+
+        outln!("\n--- Insider Score ---");
+        outln!("Score:                {:.0}/100", report.score);
+        outln!("Win Rate:             {:.0}", report.win_rate);
+        if let (Some(baseline), Some(p_value)) = (report.win_rate_baseline, report.win_rate_p_value) {
+            outln!("Win Rate p-value:     {:.4} (against {:.1}%)", p_value, baseline);
+        }
+        outln!("ROI:                  {:.0}", report.roi);
+        outln!("Timing:               {:.0}", report.timing);
+        outln!("Concentration:        {:.0}", report.concentration);
+        outln!("Size Asymmetry:       {:.0}", report.size_asymmetry);
+        for flag in &report.concentration_flags {
+            outln!(
+                "Domain Concentration: {:.0}% of winnings from {} \"{}\" ({} positions, ${:.2})",
+                flag.share, flag.kind.label(), flag.label, flag.positions, flag.profit
+            );
+        }
+        if report.confidence < 1.0 {
+            outln!("Confidence:           {:.0}% (few resolved positions)", report.confidence * 100.0);
+        }
+
+        if report.flagged {
+            outln!("\n{}", "=".repeat(80));
+            outln!("⚠️  SUSPICIOUS ACTIVITY DETECTED (score {:.0}/100)", report.score);
+            outln!("{}", "=".repeat(80));
+            for reason in &report.reasons {
+                outln!("• {}", reason);
+            }
+            outln!("\nThis wallet shows patterns consistent with potential insider knowledge.");
+            outln!("{}", "=".repeat(80));
+        } else {
+            outln!("\n{}", "-".repeat(80));
+            outln!("No suspicious patterns detected.");
+            outln!("{}", "-".repeat(80));
+        }
+    }
+
+    /// Builds an export of a wallet's full trade history, oldest first, with running P&L
+    /// P&L is realized on sells against the position's average cost; resolution payouts aren't included
+    pub fn trades_export(&self, trades: &[Trade], decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("timestamp", ColumnType::Integer, "Trade time").unit("unix_seconds"),
+                Column::new("datetime", ColumnType::String, "Trade time (UTC, RFC 3339)"),
+                Column::new("proxy_wallet", ColumnType::String, "Wallet that traded"),
+                Column::new("name", ColumnType::String, "Wallet display name"),
+                Column::new("pseudonym", ColumnType::String, "Wallet pseudonym"),
+                Column::new("transaction_hash", ColumnType::String, "On-chain transaction hash"),
+                Column::new("condition_id", ColumnType::String, "Market condition ID"),
+                Column::new("title", ColumnType::String, "Market question"),
+                Column::new("outcome_index", ColumnType::Integer, "Index of the outcome traded"),
+                Column::new("outcome", ColumnType::String, "Name of the outcome traded"),
+                Column::new("side", ColumnType::String, "BUY or SELL"),
+                Column::new("size", ColumnType::Decimal, "Shares traded").unit("shares"),
+                Column::new("price", ColumnType::Decimal, "Price per share").unit("usd"),
+                Column::new("notional", ColumnType::Decimal, "Size x price").unit("usd"),
+                Column::new("cash_flow", ColumnType::Decimal, "Cash out (negative, buys) or in (positive, sells)").unit("usd"),
+                Column::new("position_key", ColumnType::String, "condition_id:outcome_index, identifying the position"),
+                Column::new("position_shares", ColumnType::Decimal, "Shares held in the position after this trade").unit("shares"),
+                Column::new("position_avg_price", ColumnType::Decimal, "Average cost of the shares held after this trade").unit("usd"),
+                Column::new("realized_pnl", ColumnType::Decimal, "P&L realized by this trade (sells only)").unit("usd"),
+                Column::new("position_realized_pnl", ColumnType::Decimal, "P&L realized in this position so far").unit("usd"),
+                Column::new("running_realized_pnl", ColumnType::Decimal, "P&L realized across the wallet so far").unit("usd"),
+            ],
+            decimal_places,
+        );
+
+        let mut sorted: Vec<&Trade> = trades.iter().collect();
+        sorted.sort_by_key(|t| t.timestamp);
+
+        // Position key -> (shares held, cost of shares held, realized P&L)
+        let mut positions: HashMap<String, (f64, f64, f64)> = HashMap::new();
+        let mut running_pnl = 0.0;
+
+        for trade in sorted {
+            let key = format!("{}:{}", trade.condition_id, trade.outcome_index);
+            let (shares, cost, position_pnl) = positions.entry(key.clone()).or_insert((0.0, 0.0, 0.0));
+            let notional = trade.size * trade.price;
 
-        if is_suspicious {
-            println!("\n{}", "=".repeat(80));
-            println!("⚠️  SUSPICIOUS ACTIVITY DETECTED");
-            println!("{}", "=".repeat(80));
-            for flag in flags {
-                println!("• {}", flag);
+            let (cash_flow, realized) = if trade.side == "SELL" {
+                // Sells beyond the tracked holding (e.g. merged or transferred shares) have no known
+                // cost basis, so like the position ledger only the shares held realize P&L
+                let avg_cost = if *shares > 0.0 { *cost / *shares } else { 0.0 };
+                let sold = trade.size.min(shares.max(0.0));
+                *cost -= avg_cost * sold;
+                *shares -= sold;
+                (notional, (trade.price - avg_cost) * sold)
+            } else {
+                *shares += trade.size;
+                *cost += notional;
+                (-notional, 0.0)
+            };
+            *position_pnl += realized;
+            running_pnl += realized;
+
+            let avg_price = (*shares > 0.0).then(|| *cost / *shares);
+            let datetime = chrono::DateTime::from_timestamp(trade.timestamp, 0).map(|t| t.to_rfc3339());
+
+            export.push_row(vec![
+                trade.timestamp.into(),
+                datetime.into(),
+                trade.proxy_wallet.as_str().into(),
+                trade.name.clone().into(),
+                trade.pseudonym.clone().into(),
+                trade.transaction_hash.clone().into(),
+                trade.condition_id.as_str().into(),
+                trade.title.clone().into(),
+                trade.outcome_index.into(),
+                trade.outcome.as_str().into(),
+                trade.side.as_str().into(),
+                trade.size.into(),
+                trade.price.into(),
+                notional.into(),
+                cash_flow.into(),
+                key.into(),
+                (*shares).into(),
+                avg_price.into(),
+                realized.into(),
+                (*position_pnl).into(),
+                running_pnl.into(),
+            ]);
+        }
+
+        export
+    }
+
+    /// Builds a CSV export of a wallet's resolved positions
+    pub fn positions_csv(&self, performance: &WalletPerformance, decimal_places: usize) -> CsvExport {
+        let mut export = CsvExport::new(
+            vec![
+                Column::new("condition_id", ColumnType::String, "Market condition ID"),
+                Column::new("market_title", ColumnType::String, "Market question"),
+                Column::new("bet_outcome_index", ColumnType::Integer, "Index of the outcome bought"),
+                Column::new("bet_outcome", ColumnType::String, "Name of the outcome bought"),
+                Column::new("winning_outcome_index", ColumnType::Integer, "Index of the winning outcome; empty if exited before resolution"),
+                Column::new("winning_outcome", ColumnType::String, "Name of the winning outcome; empty if exited before resolution"),
+                Column::new("shares_bought", ColumnType::Decimal, "Total shares bought").unit("shares"),
+                Column::new("net_shares", ColumnType::Decimal, "Shares still held at resolution").unit("shares"),
+                Column::new("avg_price", ColumnType::Decimal, "Average entry price per share").unit("usd"),
+                Column::new("total_invested", ColumnType::Decimal, "Full cost basis, including shares later sold").unit("usd"),
+                Column::new("payout", ColumnType::Decimal, "Resolution payout plus sell proceeds").unit("usd"),
+                Column::new("profit", ColumnType::Decimal, "Payout minus total invested").unit("usd"),
+                Column::new("realized_profit", ColumnType::Decimal, "Portion of profit realized via sells").unit("usd"),
+                Column::new("exited_early", ColumnType::Boolean, "Fully sold before resolution"),
+                Column::new("won", ColumnType::Boolean, "Position was profitable"),
+                Column::new("hours_before_resolution", ColumnType::Decimal, "Cost-weighted time from buys to resolution").unit("hours"),
+                Column::new("late_low_price_buy", ColumnType::Boolean, "Bought cheaply shortly before resolution"),
+                Column::new("drift_pnl", ColumnType::Decimal, "Profit from market repricing between entry and resolution (--attribution)").unit("usd"),
+                Column::new("skill_pnl", ColumnType::Decimal, "Profit from the resolution the market hadn't priced in (--attribution)").unit("usd"),
+                Column::new("closing_price", ColumnType::Decimal, "Outcome's price an hour before resolution (--attribution)").unit("usd"),
+                Column::new("closing_line_value", ColumnType::Decimal, "Closing price minus entry price (--attribution)").unit("usd"),
+            ],
+            decimal_places,
+        );
+
+        for position in &performance.positions {
+            export.push_row(vec![
+                position.condition_id.as_str().into(),
+                position.market_title.as_str().into(),
+                position.bet_outcome_index.into(),
+                position.bet_outcome.as_str().into(),
+                position.winning_outcome_index.into(),
+                position.winning_outcome.as_deref().into(),
+                position.shares_bought.into(),
+                position.net_shares.into(),
+                position.avg_price.into(),
+                position.total_invested.into(),
+                position.payout.into(),
+                position.profit.into(),
+                position.realized_profit.into(),
+                position.exited_early.into(),
+                position.won.into(),
+                position.hours_before_resolution.into(),
+                position.late_low_price_buy.into(),
+                position.drift_pnl.into(),
+                position.skill_pnl.into(),
+                position.closing_price.into(),
+                position.closing_line_value().into(),
+            ]);
+        }
+
+        export
+    }
+
+    /// Prints each open position with its current valuation
+    fn print_open_positions(&self, performance: &WalletPerformance) {
+        outln!("\n--- Open Positions (sorted by cost basis) ---");
+
+        for (i, position) in performance.open_positions.iter().enumerate() {
+            outln!("\n{}. {}", i + 1, position.market_title);
+            outln!(
+                "   Holding: {} | Shares: {:.2} @ ${:.3} | Cost: ${:.2}",
+                position.outcome, position.net_shares, position.avg_price, position.cost_basis
+            );
+            match (position.current_price, position.market_value, position.unrealized_pnl) {
+                (Some(price), Some(value), Some(pnl)) => outln!(
+                    "   Now: ${:.3} | Value: ${:.2} | Unrealized: ${:.2}",
+                    price, value, pnl
+                ),
+                _ => outln!("   Now: no current price"),
             }
-            println!("\nThis wallet shows patterns consistent with potential insider knowledge.");
-            println!("{}", "=".repeat(80));
+        }
+    }
+
+    /// Prints the per-period record, most recent periods only unless detailed
+    fn print_periods(&self, performance: &WalletPerformance, detailed: bool) {
+        const RECENT_PERIODS: usize = 6;
+
+        if self.period_days == 0 {
+            outln!("\n--- By Month (entry) ---");
         } else {
-            println!("\n{}", "-".repeat(80));
-            println!("No suspicious patterns detected.");
-            println!("{}", "-".repeat(80));
+            outln!("\n--- By {}-Day Window (entry) ---", self.period_days);
+        }
+
+        let skip = if detailed { 0 } else { performance.periods.len().saturating_sub(RECENT_PERIODS) };
+        outln!(
+            "{:<11} {:>9} {:>5} {:>6} {:>12} {:>12} {:>8}",
+            "Period", "Positions", "Wins", "Losses", "Invested", "Profit", "ROI"
+        );
+        for period in performance.periods.iter().skip(skip) {
+            outln!(
+                "{:<11} {:>9} {:>5} {:>6} {:>12} {:>12} {:>7.1}%",
+                period.label,
+                period.positions,
+                period.wins,
+                period.losses,
+                format!("${:.2}", period.invested),
+                format!("${:.2}", period.profit),
+                period.roi
+            );
+        }
+        if skip > 0 {
+            outln!("({} earlier period(s) shown with --detailed)", skip);
+        }
+    }
+
+    /// Prints each resolved position with entry, size, outcome and profit
+    fn print_position_breakdown(&self, performance: &WalletPerformance) {
+        outln!("\n--- Per-Market Breakdown (sorted by profit) ---");
+
+        if performance.positions.is_empty() {
+            outln!("No resolved positions.");
+            return;
+        }
+
+        for (i, position) in performance.positions.iter().enumerate() {
+            let result = if position.exited_early {
+                "SOLD"
+            } else if position.won {
+                "WON"
+            } else {
+                "LOST"
+            };
+
+            outln!("\n{}. {}", i + 1, position.market_title);
+            outln!(
+                "   Bet: {} @ ${:.3} | Shares: {:.2} | Invested: ${:.2}",
+                position.bet_outcome, position.avg_price, position.shares_bought, position.total_invested
+            );
+            outln!(
+                "   Result: {}{} | Payout: ${:.2} | Profit: ${:.2}{}",
+                result,
+                match &position.winning_outcome {
+                    Some(winner) if !position.won => format!(" ({} won)", winner),
+                    _ => String::new(),
+                },
+                position.payout,
+                position.profit,
+                if position.disputed { " | Oracle result disputed" } else { "" }
+            );
+            if let Some(hours) = position.hours_before_resolution {
+                let late = if position.late_low_price_buy { " (late low-price buy)" } else { "" };
+                outln!("   Entered: {:.1}h before resolution{}", hours, late);
+            }
+            if let (Some(drift), Some(skill)) = (position.drift_pnl, position.skill_pnl) {
+                outln!("   Attribution: drift ${:.2} | skill ${:.2}", drift, skill);
+            }
+            if let (Some(price), Some(clv)) = (position.closing_price, position.closing_line_value()) {
+                outln!("   Closing line: ${:.3} ({:+.1}¢ vs entry)", price, clv * 100.0);
+            }
         }
     }
 }
@@ -356,3 +1858,491 @@ impl Default for WalletAnalyzer {
         Self::new()
     }
 }
+
+/// One trade reduced to what position accounting needs
+#[derive(Debug, Clone, Copy)]
+struct LedgerFill {
+    timestamp: i64,
+    side: LedgerSide,
+    size: f64,
+    price: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LedgerSide {
+    Buy,
+    Sell,
+    /// Shares minted by splitting USDC into complete sets, at an even share of $1
+    Split,
+    /// Shares burned by merging complete sets back into USDC, at an even share of $1
+    Merge,
+    Other,
+}
+
+/// A position's fills, replayed into a `Position` once every trade has arrived
+#[derive(Debug, Clone)]
+struct PendingPosition {
+    /// Timestamp of the oldest trade seen, which supplies the outcome name and title
+    first_seen: i64,
+    /// None while the position has only been seen in splits and merges, which don't name it
+    outcome: Option<String>,
+    market_title: Option<String>,
+    fills: Vec<LedgerFill>,
+}
+
+/// Accumulates a wallet's trades, in any order and as they arrive, into per-position fills
+/// Only a compact fill is kept per trade, not the trade itself
+#[derive(Debug, Clone, Default)]
+pub struct PositionLedger {
+    wallet_address: Option<String>,
+    total_trades: usize,
+    /// Dollar value of every buy and sell
+    volume: f64,
+    markets: HashSet<String>,
+    positions: HashMap<(String, usize), PendingPosition>,
+    /// Activity outside the order book; None until the activity feed is added
+    activity: Option<ActivitySummary>,
+}
+
+impl PositionLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a ledger from a fully fetched trade history
+    pub fn from_trades(trades: &[Trade]) -> Self {
+        let mut ledger = Self::new();
+        ledger.extend(trades);
+        ledger
+    }
+
+    /// Builds a ledger from a stream of trade pages, adding each page as it arrives
+    pub async fn from_stream<S>(pages: S) -> Result<Self>
+    where
+        S: Stream<Item = Result<Vec<Trade>>>,
+    {
+        let mut ledger = Self::new();
+        let mut pages = std::pin::pin!(pages);
+        while let Some(page) = pages.next().await {
+            ledger.extend(&page?);
+        }
+        Ok(ledger)
+    }
+
+    /// Adds a trade
+    pub fn push(&mut self, trade: &Trade) {
+        if self.wallet_address.is_none() {
+            self.wallet_address = Some(trade.proxy_wallet.clone());
+        }
+        self.total_trades += 1;
+        self.volume += trade.size * trade.price;
+        if !self.markets.contains(&trade.condition_id) {
+            self.markets.insert(trade.condition_id.clone());
+        }
+
+        let key = (trade.condition_id.clone(), trade.outcome_index);
+        let pending = self.positions.entry(key).or_insert_with(|| PendingPosition {
+            first_seen: trade.timestamp,
+            outcome: Some(trade.outcome.clone()),
+            market_title: trade.title.clone(),
+            fills: Vec::new(),
+        });
+        if trade.timestamp < pending.first_seen {
+            pending.first_seen = trade.timestamp;
+            pending.outcome = Some(trade.outcome.clone());
+            pending.market_title = trade.title.clone();
+        } else if pending.outcome.is_none() {
+            pending.outcome = Some(trade.outcome.clone());
+        }
+
+        pending.fills.push(LedgerFill {
+            timestamp: trade.timestamp,
+            side: match trade.side.as_str() {
+                "BUY" => LedgerSide::Buy,
+                "SELL" => LedgerSide::Sell,
+                _ => LedgerSide::Other,
+            },
+            size: trade.size,
+            price: trade.price,
+        });
+    }
+
+    /// Adds several trades
+    pub fn extend<'a>(&mut self, trades: impl IntoIterator<Item = &'a Trade>) {
+        for trade in trades {
+            self.push(trade);
+        }
+    }
+
+    /// Adds a record from the wallet's activity feed
+    /// Splits and merges become fills of every outcome at $0.50 a share, so shares sold after
+    /// a split or merged after buying have a cost basis; redemptions, conversions and rewards
+    /// are only counted, since the resolution payout already covers what a redemption pays
+    pub fn push_activity(&mut self, activity: &WalletActivity) {
+        if self.wallet_address.is_none() {
+            self.wallet_address = Some(activity.proxy_wallet.clone());
+        }
+        let summary = self.activity.get_or_insert_with(ActivitySummary::default);
+        let side = match activity.kind {
+            ActivityKind::Split => {
+                summary.splits += 1;
+                LedgerSide::Split
+            }
+            ActivityKind::Merge => {
+                summary.merges += 1;
+                LedgerSide::Merge
+            }
+            ActivityKind::Redeem => {
+                summary.redemptions += 1;
+                return;
+            }
+            ActivityKind::Conversion => {
+                summary.conversions += 1;
+                return;
+            }
+            ActivityKind::Reward => {
+                summary.rewards += activity.usdc_size;
+                return;
+            }
+            ActivityKind::Trade | ActivityKind::Other => return,
+        };
+        if activity.condition_id.is_empty() || activity.size <= 0.0 {
+            return;
+        }
+
+        // The outcomes are named by the wallet's trades or the market (see `label_outcomes`)
+        for outcome_index in 0..2 {
+            let key = (activity.condition_id.clone(), outcome_index);
+            let pending = self.positions.entry(key).or_insert_with(|| PendingPosition {
+                first_seen: activity.timestamp,
+                outcome: None,
+                market_title: activity.title.clone(),
+                fills: Vec::new(),
+            });
+            pending.fills.push(LedgerFill { timestamp: activity.timestamp, side, size: activity.size, price: 0.5 });
+        }
+    }
+
+    /// Adds a wallet's activity feed; the ledger then reports an activity summary even when
+    /// the feed is empty
+    pub fn extend_activity<'a>(&mut self, activity: impl IntoIterator<Item = &'a WalletActivity>) {
+        self.activity.get_or_insert_with(ActivitySummary::default);
+        for record in activity {
+            self.push_activity(record);
+        }
+    }
+
+    /// Number of trades added so far
+    pub fn total_trades(&self) -> usize {
+        self.total_trades
+    }
+
+    /// Timestamp of every trade added, in no particular order
+    pub fn trade_timestamps(&self) -> Vec<i64> {
+        self.positions
+            .values()
+            .flat_map(|pending| pending.fills.iter())
+            .filter(|fill| !matches!(fill.side, LedgerSide::Split | LedgerSide::Merge))
+            .map(|fill| fill.timestamp)
+            .collect()
+    }
+
+    /// Names outcomes seen only in splits and merges from their markets' labels; any left
+    /// unnamed are reported as Yes/No
+    pub fn label_outcomes(&mut self, markets: &[Market]) {
+        let markets: HashMap<&String, &Market> = markets
+            .iter()
+            .filter_map(|m| Some((m.condition_id.as_ref()?, m)))
+            .collect();
+        for ((condition_id, outcome_index), pending) in &mut self.positions {
+            if pending.outcome.is_none() {
+                pending.outcome = markets
+                    .get(condition_id)
+                    .and_then(|m| m.parsed_outcomes()?.get(*outcome_index).cloned());
+            }
+        }
+    }
+
+    /// Replays each position's fills into positions, including closed ones with realized profit
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions
+            .iter()
+            .map(|((condition_id, outcome_index), pending)| replay_position(condition_id, *outcome_index, pending))
+            .collect()
+    }
+}
+
+/// Builds a position from its fills
+fn replay_position(condition_id: &str, outcome_index: usize, pending: &PendingPosition) -> Position {
+    let mut position = Position {
+        condition_id: condition_id.to_string(),
+        outcome_index,
+        outcome: pending.outcome.clone().unwrap_or_else(|| Market::default_outcome_name(outcome_index)),
+        net_shares: 0.0,
+        shares_bought: 0.0,
+        avg_price: 0.0,
+        total_invested: 0.0,
+        realized_profit: 0.0,
+        cost_basis_sold: 0.0,
+        market_title: pending.market_title.clone().unwrap_or_else(|| "Unknown".to_string()),
+        buys: Vec::new(),
+    };
+
+    // The API returns newest trades first; replay them oldest first so sells
+    // are matched against the buys that preceded them
+    let mut fills = pending.fills.clone();
+    fills.sort_by_key(|f| f.timestamp);
+
+    for fill in fills {
+        match fill.side {
+            LedgerSide::Buy | LedgerSide::Split => {
+                // Add to position
+                let new_total_shares = position.net_shares + fill.size;
+                let new_total_invested = position.total_invested + (fill.size * fill.price);
+
+                position.net_shares = new_total_shares;
+                position.shares_bought += fill.size;
+                position.total_invested = new_total_invested;
+                // Splits aren't bets on the outcome, so only buys feed timing and sizing
+                if fill.side == LedgerSide::Buy {
+                    position.buys.push(Fill {
+                        timestamp: fill.timestamp,
+                        price: fill.price,
+                        size: fill.size,
+                    });
+                }
+
+                if new_total_shares > 0.0 {
+                    position.avg_price = new_total_invested / new_total_shares;
+                }
+            }
+            LedgerSide::Sell | LedgerSide::Merge => {
+                // Only shares we saw being bought or split have a known cost basis;
+                // anything sold beyond that (e.g. splits when activity wasn't fetched) is ignored
+                let shares_sold = fill.size.min(position.net_shares);
+                if shares_sold <= 0.0 {
+                    continue;
+                }
+
+                // Calculate realized profit from this sell
+                // Profit = (sell_price - avg_buy_price) * shares_sold
+                let realized_pnl = (fill.price - position.avg_price) * shares_sold;
+                position.realized_profit += realized_pnl;
+
+                // Reduce position
+                position.net_shares -= shares_sold;
+
+                // Move the cost basis of the sold shares out of the open position
+                let cost_basis_sold = shares_sold * position.avg_price;
+                position.total_invested -= cost_basis_sold;
+                position.cost_basis_sold += cost_basis_sold;
+
+                if position.net_shares <= 0.001 {
+                    // Position closed
+                    position.net_shares = 0.0;
+                    position.total_invested = 0.0;
+                    position.avg_price = 0.0;
+                }
+            }
+            LedgerSide::Other => {}
+        }
+    }
+
+    position
+}
+
+/// Identifies a fill across the all-trades and taker-only trade feeds
+fn fill_key(trade: &Trade) -> Option<(String, String, usize, String, u64, u64)> {
+    Some((
+        trade.transaction_hash.clone()?,
+        trade.condition_id.clone(),
+        trade.outcome_index,
+        trade.side.clone(),
+        trade.size.to_bits(),
+        trade.price.to_bits(),
+    ))
+}
+
+/// One-sided binomial p-value: the chance of at least `successes` wins in `trials` bets each won with probability `p`
+fn binomial_tail(trials: usize, successes: usize, p: f64) -> f64 {
+    if successes == 0 {
+        return 1.0;
+    }
+    let p = p.clamp(1e-9, 1.0 - 1e-9);
+    let odds = (p / (1.0 - p)).ln();
+
+    // ln P(X = k), from P(X = 0) = (1 - p)^n by the ratio of consecutive terms
+    let mut log_pmf = trials as f64 * (1.0 - p).ln();
+    let mut tail = Vec::with_capacity(trials + 1 - successes.min(trials + 1));
+    for k in 0..=trials {
+        if k >= successes {
+            tail.push(log_pmf);
+        }
+        log_pmf += ((trials - k) as f64 / (k + 1) as f64).ln() + odds;
+    }
+
+    let Some(max) = tail.iter().copied().reduce(f64::max) else { return 0.0 };
+    (max + tail.iter().map(|l| (l - max).exp()).sum::<f64>().ln()).exp().min(1.0)
+}
+
+/// Maps `value` linearly onto 0-1 between `floor` and `ceiling`, clamped
+fn ramp(value: f64, floor: f64, ceiling: f64) -> f64 {
+    if ceiling <= floor {
+        return if value >= ceiling { 1.0 } else { 0.0 };
+    }
+    ((value - floor) / (ceiling - floor)).clamp(0.0, 1.0)
+}
+
+/// A gap between trades as seconds, minutes or hours
+fn format_interval(secs: f64) -> String {
+    if secs < 120.0 {
+        format!("{:.0}s", secs)
+    } else if secs < 7200.0 {
+        format!("{:.1}m", secs / 60.0)
+    } else {
+        format!("{:.1}h", secs / 3600.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn market(condition_id: &str, closed: bool, prices: [f64; 2]) -> Market {
+        serde_json::from_value(json!({
+            "question": format!("Market {}", condition_id),
+            "conditionId": condition_id,
+            "closed": closed,
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": format!("[\"{}\", \"{}\"]", prices[0], prices[1]),
+        }))
+        .unwrap()
+    }
+
+    fn trade(condition_id: &str, side: &str, outcome_index: usize, size: f64, price: f64, timestamp: i64) -> Trade {
+        serde_json::from_value(json!({
+            "proxyWallet": "0xwallet",
+            "side": side,
+            "conditionId": condition_id,
+            "size": size,
+            "price": price,
+            "timestamp": timestamp,
+            "outcome": if outcome_index == 0 { "Yes" } else { "No" },
+            "outcomeIndex": outcome_index,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn open_favourites_are_valued_at_their_price_not_a_payout() {
+        let analyzer = WalletAnalyzer::new();
+        let trades = [trade("0xaaa", "BUY", 0, 10.0, 0.50, 1_700_000_000)];
+        let mut performance = analyzer.analyze(&trades, &[]);
+        let open = analyzer.open_positions(&trades, &[]);
+
+        let live = market("0xaaa", false, [0.93, 0.07]);
+        assert_eq!(live.winning_outcome_index(), None);
+        assert_eq!(live.resolution_payouts(), None);
+        analyzer.value_open_positions(&mut performance, &open, &[live]);
+        assert_eq!(performance.open_positions[0].current_price, Some(0.93));
+        assert!((performance.unrealized_pnl - 4.3).abs() < 1e-9);
+
+        // Once the market closes, the same prices mean it resolved
+        analyzer.value_open_positions(&mut performance, &open, &[market("0xaaa", true, [0.93, 0.07])]);
+        assert_eq!(performance.open_positions[0].current_price, Some(1.0));
+    }
+
+    #[test]
+    fn exited_positions_are_judged_by_profit_and_held_ones_by_the_winner() {
+        let analyzer = WalletAnalyzer::new();
+        let resolved = [market("0xaaa", true, [1.0, 0.0]), market("0xbbb", true, [1.0, 0.0])];
+        let trades = [
+            // Bought the winner and sold it early at a loss
+            trade("0xaaa", "BUY", 0, 10.0, 0.60, 1_700_000_000),
+            trade("0xaaa", "SELL", 0, 10.0, 0.40, 1_700_000_100),
+            // Bought the loser and sold it early at a profit
+            trade("0xbbb", "BUY", 1, 10.0, 0.30, 1_700_000_000),
+            trade("0xbbb", "SELL", 1, 10.0, 0.50, 1_700_000_100),
+        ];
+        let performance = analyzer.analyze(&trades, &resolved);
+        let won = |condition_id: &str| {
+            let position = performance.positions.iter().find(|p| p.condition_id == condition_id).unwrap();
+            assert!(position.exited_early);
+            position.won
+        };
+        assert!(!won("0xaaa"));
+        assert!(won("0xbbb"));
+
+        // Held to resolution, picking the winner wins even if bought dear
+        let trades = [
+            trade("0xaaa", "BUY", 0, 10.0, 0.60, 1_700_000_000),
+            trade("0xbbb", "BUY", 1, 10.0, 0.30, 1_700_000_000),
+        ];
+        let performance = analyzer.analyze(&trades, &resolved);
+        let held = |condition_id: &str| performance.positions.iter().find(|p| p.condition_id == condition_id).unwrap();
+        assert!(!held("0xaaa").exited_early && held("0xaaa").won);
+        assert!(!held("0xbbb").won);
+    }
+
+    #[test]
+    fn exported_sells_beyond_the_holding_realize_only_the_shares_held() {
+        let analyzer = WalletAnalyzer::new();
+        let trades = [
+            trade("0xaaa", "BUY", 0, 10.0, 0.40, 1_700_000_000),
+            trade("0xaaa", "SELL", 0, 15.0, 0.60, 1_700_000_100),
+        ];
+        let path = std::env::temp_dir().join(format!("wallet-analyzer-trades-{}.csv", std::process::id()));
+        analyzer.trades_export(&trades, 4).write(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+
+        let mut lines = csv.lines();
+        let column = lines.next().unwrap().split(',').position(|name| name == "realized_pnl").unwrap();
+        let sell = lines.last().unwrap();
+        let realized: f64 = sell.split(',').nth(column).unwrap().parse().unwrap();
+        assert!((realized - 2.0).abs() < 1e-9);
+        assert!((analyzer.analyze(&trades, &[]).realized_profit - realized).abs() < 1e-9);
+    }
+
+    #[test]
+    fn split_outcomes_take_the_market_labels() {
+        let split = |condition_id: &str| -> WalletActivity {
+            serde_json::from_value(json!({
+                "proxyWallet": "0xwallet",
+                "type": "SPLIT",
+                "conditionId": condition_id,
+                "timestamp": 1_700_000_000,
+                "size": 10.0,
+                "usdcSize": 10.0,
+            }))
+            .unwrap()
+        };
+        let mut labelled = market("0xaaa", false, [0.5, 0.5]);
+        labelled.outcomes = Some(r#"["Chiefs", "Eagles"]"#.to_string());
+        let mut unlabelled = market("0xbbb", false, [0.5, 0.5]);
+        unlabelled.outcomes = None;
+
+        let mut ledger = PositionLedger::new();
+        ledger.extend_activity(&[split("0xaaa"), split("0xbbb"), split("0xccc")]);
+        // A later trade names its outcome itself
+        let mut sell = trade("0xccc", "SELL", 1, 5.0, 0.40, 1_700_000_100);
+        sell.outcome = "Under".to_string();
+        ledger.push(&sell);
+        ledger.label_outcomes(&[labelled, unlabelled]);
+
+        let mut outcomes: Vec<(String, usize, String)> =
+            ledger.positions().into_iter().map(|p| (p.condition_id, p.outcome_index, p.outcome)).collect();
+        outcomes.sort();
+        let expected = [
+            ("0xaaa", 0, "Chiefs"),
+            ("0xaaa", 1, "Eagles"),
+            ("0xbbb", 0, "Yes"),
+            ("0xbbb", 1, "No"),
+            ("0xccc", 0, "Yes"),
+            ("0xccc", 1, "Under"),
+        ];
+        let expected: Vec<(String, usize, String)> =
+            expected.iter().map(|(id, index, outcome)| (id.to_string(), *index, outcome.to_string())).collect();
+        assert_eq!(outcomes, expected);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::client::PolymarketClient;
+use crate::market_cache::ResolvedMarketCache;
+use crate::models::{Market, SuspicionReport, Trade, WalletPerformance};
+use crate::trade_history::TradeHistoryCache;
+use crate::wallet_analyzer::{AttributionQuery, WalletAnalyzer};
+use anyhow::Result;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// A wallet run through the whole analysis: its trades, the resolved markets they were settled
+/// against, the performance and the suspicion report
+pub struct WalletAnalysis {
+    pub trades: Vec<Trade>,
+    pub resolved_markets: Vec<Market>,
+    pub performance: WalletPerformance,
+    pub report: SuspicionReport,
+    /// Name or pseudonym the wallet trades under, if it has one
+    pub username: Option<String>,
+}
+
+/// Fetches a wallet's trades and the markets they're in, then analyzes, values and scores them:
+/// the pipeline behind the wallet report and the daemon's wallet endpoint
+pub struct WalletPipeline<'a> {
+    client: &'a PolymarketClient,
+    analyzer: &'a WalletAnalyzer,
+    trade_history: Option<&'a TradeHistoryCache>,
+    /// Settles against every resolved market through this cache; without it, only the markets
+    /// the wallet traded are fetched
+    resolved_cache: Option<&'a ResolvedMarketCache>,
+    wallet_activity: bool,
+    liquidity_roles: bool,
+    /// Concurrent price lookups for profit attribution, if attributed
+    attribution: Option<usize>,
+    progress: bool,
+}
+
+impl<'a> WalletPipeline<'a> {
+    pub fn new(client: &'a PolymarketClient, analyzer: &'a WalletAnalyzer) -> Self {
+        Self {
+            client,
+            analyzer,
+            trade_history: None,
+            resolved_cache: None,
+            wallet_activity: false,
+            liquidity_roles: false,
+            attribution: None,
+            progress: false,
+        }
+    }
+
+    /// Reads trades through the given cache, so wallets analyzed again only fetch new trades
+    pub fn with_trade_history(mut self, cache: Option<&'a TradeHistoryCache>) -> Self {
+        self.trade_history = cache;
+        self
+    }
+
+    /// Settles positions against every resolved market, loaded from the cache when it's fresh
+    pub fn with_resolved_cache(mut self, cache: &'a ResolvedMarketCache) -> Self {
+        self.resolved_cache = Some(cache);
+        self
+    }
+
+    /// Fetches the wallet's activity feed, so splits, merges and redemptions count
+    pub fn with_wallet_activity(mut self, enabled: bool) -> Self {
+        self.wallet_activity = enabled;
+        self
+    }
+
+    /// Fetches the wallet's taker fills to work out how often it takes liquidity
+    pub fn with_liquidity_roles(mut self, enabled: bool) -> Self {
+        self.liquidity_roles = enabled;
+        self
+    }
+
+    /// Splits resolved profit into drift and skill, looking up this many prices at once
+    pub fn with_attribution(mut self, max_concurrent: usize) -> Self {
+        self.attribution = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Prints each stage and how long it took
+    pub fn with_progress(mut self, enabled: bool) -> Self {
+        self.progress = enabled;
+        self
+    }
+
+    /// Runs the pipeline for one wallet, or returns None if it has no trades
+    pub async fn run(&self, wallet: &str) -> Result<Option<WalletAnalysis>> {
+        let client = self.client;
+        let analyzer = self.analyzer;
+        let cache_fresh = self.resolved_cache.is_some_and(ResolvedMarketCache::is_fresh);
+
+        // Resolved markets are fetched concurrently with the trades (unless the cache is fresh)
+        // so the market load overlaps trade pagination
+        self.report("📊 Fetching trade history and resolved markets...");
+        let fetch_start = Instant::now();
+        let ((trades, trades_duration), taker_trades, activity, (fetched_markets, markets_duration)) = tokio::join!(
+            async {
+                let trades = match self.trade_history {
+                    Some(cache) => cache.fetch(client, wallet).await,
+                    None => client.fetch_wallet_trades(wallet).await,
+                };
+                (trades, fetch_start.elapsed())
+            },
+            async {
+                if self.liquidity_roles {
+                    Some(client.fetch_wallet_taker_trades(wallet).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if self.wallet_activity {
+                    Some(client.fetch_wallet_activity(wallet).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                let markets = if self.resolved_cache.is_some() && !cache_fresh {
+                    client.fetch_resolved_markets().await.map(Some)
+                } else {
+                    Ok(None)
+                };
+                (markets, fetch_start.elapsed())
+            }
+        );
+        let trades = trades?;
+        self.report(&format!("✓ Fetched {} trades in {:.2}s", trades.len(), trades_duration.as_secs_f64()));
+        if trades.is_empty() {
+            return Ok(None);
+        }
+
+        let mut condition_ids: Vec<String> = trades.iter().map(|t| t.condition_id.clone()).collect();
+        condition_ids.sort();
+        condition_ids.dedup();
+        let (resolved_markets, open_markets) = match (self.resolved_cache, fetched_markets?) {
+            (Some(cache), Some(markets)) => {
+                self.report(&format!(
+                    "✓ Fetched {} resolved markets in {:.2}s",
+                    markets.len(),
+                    markets_duration.as_secs_f64()
+                ));
+                if let Err(e) = cache.save(&markets) {
+                    eoutln!("Warning: Failed to write resolved-market cache: {}", e);
+                }
+                (markets, None)
+            }
+            (Some(cache), None) => {
+                // Only the markets this wallet traded are read from the cache
+                let load_start = Instant::now();
+                let markets = match cache.load(&condition_ids)? {
+                    Some(markets) => markets,
+                    None => client.fetch_resolved_markets().await?,
+                };
+                self.report(&format!(
+                    "✓ Loaded {} resolved markets from cache in {:.3}s",
+                    markets.len(),
+                    load_start.elapsed().as_secs_f64()
+                ));
+                (markets, None)
+            }
+            (None, _) => {
+                // The traded markets, open or resolved, are enough to settle and value the wallet
+                let (resolved, open): (Vec<Market>, Vec<Market>) = client
+                    .fetch_markets_by_condition_ids(&condition_ids)
+                    .await?
+                    .into_iter()
+                    .partition(|m| m.closed == Some(true));
+                (resolved, Some(open))
+            }
+        };
+        self.report(&format!("✓ Total fetch time: {:.2}s\n", fetch_start.elapsed().as_secs_f64()));
+
+        self.report("📈 Analyzing performance...");
+        let analysis_start = Instant::now();
+        let mut performance = match activity {
+            Some(Ok(activity)) => analyzer.analyze_with_activity(&trades, &activity, &resolved_markets),
+            Some(Err(e)) => {
+                eoutln!("Warning: Failed to fetch wallet activity, ignoring splits and merges: {}", e);
+                analyzer.analyze(&trades, &resolved_markets)
+            }
+            None => analyzer.analyze(&trades, &resolved_markets),
+        };
+        match taker_trades {
+            Some(Ok(taker_trades)) => analyzer.apply_liquidity_roles(&mut performance, &trades, &taker_trades),
+            Some(Err(e)) => eoutln!("Warning: Failed to fetch taker fills, skipping taker ratio: {}", e),
+            None => {}
+        }
+        self.report(&format!("✓ Analysis completed in {:.3}s", analysis_start.elapsed().as_secs_f64()));
+
+        // Mark positions in unresolved markets to market at current prices
+        let open_positions = analyzer.open_positions(&trades, &resolved_markets);
+        if !open_positions.is_empty() {
+            self.report(&format!("💹 Valuing {} open positions at current prices...", open_positions.len()));
+            let current_markets = match open_markets {
+                Some(markets) => markets,
+                None => {
+                    let mut condition_ids: Vec<String> = open_positions.iter().map(|p| p.condition_id.clone()).collect();
+                    condition_ids.sort();
+                    condition_ids.dedup();
+                    client.fetch_markets_by_condition_ids(&condition_ids).await.unwrap_or_else(|e| {
+                        eoutln!("Warning: Failed to fetch current prices for open positions: {}", e);
+                        Vec::new()
+                    })
+                }
+            };
+            analyzer.value_open_positions(&mut performance, &open_positions, &current_markets);
+        }
+
+        if let Some(max_concurrent) = self.attribution {
+            self.report("📉 Fetching pre-resolution prices for profit attribution...");
+            let attribution_start = Instant::now();
+            let queries = analyzer.attribution_queries(&performance, &resolved_markets);
+            let prices = fetch_pre_resolution_prices(client, &queries, max_concurrent).await;
+            analyzer.apply_attribution(&mut performance, &prices);
+            self.report(&format!(
+                "✓ Attributed {} of {} positions in {:.2}s",
+                performance.attributed_positions,
+                queries.len(),
+                attribution_start.elapsed().as_secs_f64()
+            ));
+        }
+
+        let report = analyzer.suspicion(&performance);
+        let username = trades.iter().find_map(|t| t.name.as_ref().or(t.pseudonym.as_ref())).cloned();
+        Ok(Some(WalletAnalysis {
+            trades,
+            resolved_markets,
+            performance,
+            report,
+            username,
+        }))
+    }
+
+    fn report(&self, line: &str) {
+        if self.progress {
+            outln!("{}", line);
+        }
+    }
+}
+
+/// Fetches each queried outcome's price shortly before resolution, skipping failures
+async fn fetch_pre_resolution_prices(
+    client: &PolymarketClient,
+    queries: &[AttributionQuery],
+    max_concurrent: usize,
+) -> HashMap<(String, usize), f64> {
+    // Owned queries keep the futures free of borrowed closure arguments, so they're Send
+    // wherever the pipeline runs (the daemon's handlers need that)
+    futures::stream::iter(queries.iter().cloned())
+        .map(|query| async move {
+            let price = client.fetch_price_at(&query.token_id, query.at).await;
+            (query, price)
+        })
+        .buffer_unordered(max_concurrent)
+        .filter_map(|(query, price)| async move {
+            match price {
+                Ok(Some(price)) => Some(((query.condition_id, query.outcome_index), price)),
+                Ok(None) => None,
+                Err(e) => {
+                    eoutln!("Warning: Failed to fetch price history for {}: {}", query.condition_id, e);
+                    None
+                }
+            }
+        })
+        .collect()
+        .await
+}
@@ -0,0 +1,127 @@
+use crate::models::Trade;
+use chrono::Utc;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Share of a wallet's score carried into the next iteration if it isn't analyzed
+const CARRY_OVER: f64 = 0.5;
+/// Hours for the recency weight of a wallet's latest trade to halve
+const RECENCY_HALF_LIFE_HOURS: f64 = 6.0;
+/// Candidates whose score falls below this are dropped from the queue
+const MIN_SCORE: f64 = 0.01;
+
+/// Activity of a not-yet-analyzed wallet seen in recent trade samples
+#[derive(Debug, Default)]
+struct Candidate {
+    recent_volume: f64,
+    recent_trades: usize,
+    last_trade: i64,
+    /// Decayed score from earlier samples
+    carried_score: f64,
+}
+
+impl Candidate {
+    /// Expected informativeness: recent dollar volume and trade count, weighted by
+    /// how recently the wallet traded, plus what's left of earlier scores
+    fn score(&self, now: i64) -> f64 {
+        let activity = (1.0 + self.recent_volume).ln() + (1.0 + self.recent_trades as f64).ln();
+        let age_hours = (now - self.last_trade).max(0) as f64 / 3600.0;
+        let recency = 0.5f64.powf(age_hours / RECENCY_HALF_LIFE_HOURS);
+        activity * recency + self.carried_score
+    }
+}
+
+/// A wallet with its priority, ordered by score
+struct Prioritized {
+    wallet: String,
+    score: f64,
+}
+
+impl PartialEq for Prioritized {
+    fn eq(&self, other: &Self) -> bool {
+        self.score.total_cmp(&other.score) == Ordering::Equal
+    }
+}
+
+impl Eq for Prioritized {}
+
+impl PartialOrd for Prioritized {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Prioritized {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.total_cmp(&other.score)
+    }
+}
+
+/// Priority queue of wallets awaiting analysis in continuous scans, so each
+/// iteration's limited analysis budget goes to the most promising wallets first
+#[derive(Default)]
+pub struct WalletQueue {
+    candidates: HashMap<String, Candidate>,
+}
+
+impl WalletQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds a sample of recent trades into the queue
+    /// Wallets left over from earlier samples keep part of their score
+    pub fn observe(&mut self, trades: &[Trade]) {
+        let now = Utc::now().timestamp();
+
+        for candidate in self.candidates.values_mut() {
+            candidate.carried_score = candidate.score(now) * CARRY_OVER;
+            candidate.recent_volume = 0.0;
+            candidate.recent_trades = 0;
+        }
+
+        for trade in trades {
+            let candidate = self.candidates.entry(trade.proxy_wallet.clone()).or_default();
+            candidate.recent_volume += trade.price * trade.size;
+            candidate.recent_trades += 1;
+            candidate.last_trade = candidate.last_trade.max(trade.timestamp);
+        }
+
+        self.candidates.retain(|_, candidate| candidate.score(now) >= MIN_SCORE);
+    }
+
+    /// Removes and returns up to `count` of the highest-priority wallets, skipping
+    /// wallets already analyzed
+    pub fn pop(&mut self, count: usize, analyzed: &HashSet<String>) -> Vec<(String, f64)> {
+        let now = Utc::now().timestamp();
+        self.candidates.retain(|wallet, _| !analyzed.contains(wallet));
+
+        let mut heap: BinaryHeap<Prioritized> = self
+            .candidates
+            .iter()
+            .map(|(wallet, candidate)| Prioritized {
+                wallet: wallet.clone(),
+                score: candidate.score(now),
+            })
+            .collect();
+
+        let mut selected = Vec::new();
+        while selected.len() < count {
+            let Some(next) = heap.pop() else { break };
+            self.candidates.remove(&next.wallet);
+            selected.push((next.wallet, next.score));
+        }
+
+        selected
+    }
+
+    /// Number of wallets waiting to be analyzed
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Whether no wallets are waiting
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
@@ -1,12 +1,347 @@
+use crate::alerts::{Alert, AlertDispatcher};
 use crate::client::PolymarketClient;
+use crate::config::{CoordinationConfig, InsiderCriteria, ScheduleConfig, SuspicionConfig};
+use crate::identities::IdentityHistory;
+use crate::knowledge::{ScanSummary, WalletKnowledgeBase};
+use crate::market_cache::ResolvedMarketCache;
+use crate::models::{parse_api_timestamp, Market, SuspicionReport, Trade, WalletActivity, WalletPerformance};
+use crate::schedule::{format_hour, ScanMode, ScanScheduler};
+use crate::shutdown::is_cancelled;
+use crate::signals::{insider_entry_signals, SignalLog};
+use crate::snapshots::OrderBookSnapshotter;
+use crate::store::Store;
+use crate::trade_history::TradeHistoryCache;
 use crate::wallet_analyzer::WalletAnalyzer;
+use crate::wallet_queue::WalletQueue;
 use anyhow::Result;
+use chrono::Utc;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+/// Progress of `continuous_scan`, saved after every iteration
+const SCAN_STATE_FILE: &str = "scan_state.json";
+
+/// Accumulated results of a continuous scan, persisted so a restart resumes where it left off
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanState {
+    pub scan_count: usize,
+    /// Wallets already analyzed, never queued again
+    pub scanned_wallets: HashSet<String>,
+    /// (wallet, username, performance, insider score)
+    pub profitable_wallets: Vec<(String, Option<String>, WalletPerformance, SuspicionReport)>,
+    pub updated_at: i64,
+}
+
+impl ScanState {
+    /// Loads the saved state (empty if none)
+    pub fn load(store: &Store) -> Result<Self> {
+        Ok(store.load_json(SCAN_STATE_FILE)?.unwrap_or_default())
+    }
+
+    /// Saves the state
+    pub fn save(&mut self, store: &Store) -> Result<()> {
+        self.updated_at = Utc::now().timestamp();
+        store.save_json(SCAN_STATE_FILE, self)
+    }
+
+    /// Deletes the saved state, so the next continuous scan starts from scratch
+    pub fn clear(store: &Store) -> Result<()> {
+        let path = store.path(SCAN_STATE_FILE);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Two wallets that repeatedly bought the same outcomes close together
+#[derive(Debug, Clone, Serialize)]
+pub struct CoordinatedPair {
+    pub wallet_a: String,
+    pub wallet_b: String,
+    /// Distinct markets where both bought the same outcome within the window
+    pub shared_markets: usize,
+    /// `shared_markets` as a share of the less active wallet's markets
+    pub overlap: f64,
+    /// Median time between the two wallets' closest buys in each shared market
+    pub median_gap_secs: i64,
+}
+
+/// A group of wallets linked by coordinated trading, likely one trader's proxies
+#[derive(Debug, Clone, Serialize)]
+pub struct CoordinatedCluster {
+    pub wallets: Vec<String>,
+    /// Linked pairs, most coordinated first
+    pub pairs: Vec<CoordinatedPair>,
+    /// Condition ID and title of every market traded in coordination
+    pub markets: Vec<(String, Option<String>)>,
+    /// Mean overlap of the linked pairs
+    pub cohesion: f64,
+}
+
+/// Member indexes, linked pairs and coordinated markets of a cluster being assembled
+type ClusterParts<'a> = (HashSet<usize>, Vec<CoordinatedPair>, HashSet<&'a str>);
+
+/// Finds wallets that buy the same outcomes within a short window of each other,
+/// market after market, and groups them into clusters
+pub struct CoordinationDetector {
+    config: CoordinationConfig,
+    wallets: Vec<String>,
+    /// Distinct markets traded by each wallet
+    market_counts: Vec<usize>,
+    /// (condition ID, outcome index) -> buys as (wallet index, timestamp)
+    buys: HashMap<(String, usize), Vec<(usize, i64)>>,
+    titles: HashMap<String, String>,
+}
+
+impl CoordinationDetector {
+    pub fn new(config: CoordinationConfig) -> Self {
+        Self {
+            config,
+            wallets: Vec::new(),
+            market_counts: Vec::new(),
+            buys: HashMap::new(),
+            titles: HashMap::new(),
+        }
+    }
+
+    /// Adds a wallet's trades; only the timing of its buys is kept
+    pub fn add_wallet(&mut self, wallet: &str, trades: &[Trade]) {
+        let index = self.wallets.len();
+        self.wallets.push(wallet.to_lowercase());
+        self.market_counts
+            .push(trades.iter().map(|t| t.condition_id.as_str()).collect::<HashSet<_>>().len());
+
+        for trade in trades.iter().filter(|t| t.side == "BUY") {
+            self.buys
+                .entry((trade.condition_id.clone(), trade.outcome_index))
+                .or_default()
+                .push((index, trade.timestamp));
+            if let Some(title) = &trade.title {
+                self.titles.entry(trade.condition_id.clone()).or_insert_with(|| title.clone());
+            }
+        }
+    }
+
+    /// Linked pairs grouped into clusters, largest first
+    pub fn clusters(&self) -> Vec<CoordinatedCluster> {
+        let window = self.config.window_minutes as i64 * 60;
+
+        // (wallet a, wallet b) -> condition ID -> closest gap between their buys
+        let mut links: HashMap<(usize, usize), HashMap<&str, i64>> = HashMap::new();
+        for ((condition_id, _), buys) in &self.buys {
+            let mut buys = buys.clone();
+            buys.sort_by_key(|(_, timestamp)| *timestamp);
+
+            for (i, (wallet_i, time_i)) in buys.iter().enumerate() {
+                for (wallet_j, time_j) in buys[i + 1..].iter().take_while(|(_, t)| t - time_i <= window) {
+                    if wallet_i == wallet_j {
+                        continue;
+                    }
+                    let pair = (*wallet_i.min(wallet_j), *wallet_i.max(wallet_j));
+                    let gap = links.entry(pair).or_default().entry(condition_id.as_str()).or_insert(i64::MAX);
+                    *gap = (*gap).min(time_j - time_i);
+                }
+            }
+        }
+
+        let pairs: Vec<((usize, usize), CoordinatedPair, Vec<&str>)> = links
+            .into_iter()
+            .filter_map(|((a, b), markets)| {
+                let smaller = self.market_counts[a].min(self.market_counts[b]).max(1);
+                let overlap = markets.len() as f64 / smaller as f64;
+                if markets.len() < self.config.min_shared_markets || overlap < self.config.min_overlap {
+                    return None;
+                }
+
+                let mut gaps: Vec<i64> = markets.values().copied().collect();
+                gaps.sort_unstable();
+                let pair = CoordinatedPair {
+                    wallet_a: self.wallets[a].clone(),
+                    wallet_b: self.wallets[b].clone(),
+                    shared_markets: markets.len(),
+                    overlap,
+                    median_gap_secs: gaps[gaps.len() / 2],
+                };
+                Some(((a, b), pair, markets.into_keys().collect()))
+            })
+            .collect();
+
+        // Union-find over the linked pairs
+        let mut parent: Vec<usize> = (0..self.wallets.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for ((a, b), _, _) in &pairs {
+            let (root_a, root_b) = (root(&mut parent, *a), root(&mut parent, *b));
+            parent[root_a] = root_b;
+        }
+
+        let mut groups: HashMap<usize, ClusterParts> = HashMap::new();
+        for ((a, b), pair, markets) in pairs {
+            let group = groups.entry(root(&mut parent, a)).or_default();
+            group.0.extend([a, b]);
+            group.1.push(pair);
+            group.2.extend(markets);
+        }
+
+        let mut clusters: Vec<CoordinatedCluster> = groups
+            .into_values()
+            .map(|(members, mut pairs, markets)| {
+                let mut wallets: Vec<String> = members.into_iter().map(|i| self.wallets[i].clone()).collect();
+                wallets.sort();
+                pairs.sort_by(|a, b| b.overlap.total_cmp(&a.overlap).then(b.shared_markets.cmp(&a.shared_markets)));
+                let mut markets: Vec<(String, Option<String>)> = markets
+                    .into_iter()
+                    .map(|id| (id.to_string(), self.titles.get(id).cloned()))
+                    .collect();
+                markets.sort();
+
+                CoordinatedCluster {
+                    cohesion: pairs.iter().map(|p| p.overlap).sum::<f64>() / pairs.len() as f64,
+                    wallets,
+                    pairs,
+                    markets,
+                }
+            })
+            .collect();
+
+        clusters.sort_by(|a, b| {
+            b.wallets
+                .len()
+                .cmp(&a.wallets.len())
+                .then(b.cohesion.total_cmp(&a.cohesion))
+        });
+        clusters
+    }
+}
+
+/// How `find_active_wallets` samples recent trades and ranks the wallets behind them
+/// Ranking by trade count surfaces bots and market makers; the other strategies favour
+/// wallets putting real money on a view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DiscoveryStrategy {
+    /// Most trades in the recent sample (at least 3)
+    #[default]
+    TradeCount,
+    /// Largest single trade in the recent sample
+    LargestNotional,
+    /// Most dollars traded in active markets with this category or tag
+    Category(String),
+    /// Most dollars traded in active markets scheduled to resolve within this many days
+    ResolvingWithin(u32),
+}
+
+impl DiscoveryStrategy {
+    pub fn label(&self) -> String {
+        match self {
+            DiscoveryStrategy::TradeCount => "most trades".to_string(),
+            DiscoveryStrategy::LargestNotional => "largest trades".to_string(),
+            DiscoveryStrategy::Category(category) => format!("most traded in {}", category),
+            DiscoveryStrategy::ResolvingWithin(days) => format!("most traded in markets resolving within {} days", days),
+        }
+    }
+
+    /// Whether the strategy samples trades from chosen markets rather than all recent trades
+    fn targets_markets(&self) -> bool {
+        matches!(self, DiscoveryStrategy::Category(_) | DiscoveryStrategy::ResolvingWithin(_))
+    }
+
+    /// Whether an active market's trades belong in the sample
+    fn targets(&self, market: &Market, now: i64) -> bool {
+        match self {
+            DiscoveryStrategy::TradeCount | DiscoveryStrategy::LargestNotional => true,
+            DiscoveryStrategy::Category(category) => market.categories().contains(&category.trim().to_lowercase()),
+            DiscoveryStrategy::ResolvingWithin(days) => market
+                .end_date
+                .as_deref()
+                .and_then(parse_api_timestamp)
+                .is_some_and(|end| end >= now && end <= now + *days as i64 * 86400),
+        }
+    }
+
+    /// Wallets in the sample ranked by the strategy, best first
+    fn rank(&self, trades: &[Trade]) -> Vec<DiscoveredWallet> {
+        let mut wallets: HashMap<&str, DiscoveredWallet> = HashMap::new();
+        for trade in trades {
+            let notional = trade.size * trade.price;
+            let wallet = wallets.entry(trade.proxy_wallet.as_str()).or_insert_with(|| DiscoveredWallet {
+                wallet: trade.proxy_wallet.clone(),
+                trades: 0,
+                notional: 0.0,
+                largest_trade: 0.0,
+            });
+            wallet.trades += 1;
+            wallet.notional += notional;
+            wallet.largest_trade = wallet.largest_trade.max(notional);
+        }
+
+        let mut wallets: Vec<DiscoveredWallet> = wallets.into_values().collect();
+        match self {
+            DiscoveryStrategy::TradeCount => {
+                wallets.retain(|w| w.trades >= 3);
+                wallets.sort_by_key(|w| std::cmp::Reverse(w.trades));
+            }
+            DiscoveryStrategy::LargestNotional => wallets.sort_by(|a, b| b.largest_trade.total_cmp(&a.largest_trade)),
+            DiscoveryStrategy::Category(_) | DiscoveryStrategy::ResolvingWithin(_) => {
+                wallets.sort_by(|a, b| b.notional.total_cmp(&a.notional))
+            }
+        }
+        wallets
+    }
+}
+
+impl std::str::FromStr for DiscoveryStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once(':').map(|(k, v)| (k, Some(v.trim()))).unwrap_or((s, None));
+        match (kind.trim().to_ascii_lowercase().as_str(), value) {
+            ("count" | "trades", None) => Ok(DiscoveryStrategy::TradeCount),
+            ("notional" | "size", None) => Ok(DiscoveryStrategy::LargestNotional),
+            ("category", Some(category)) if !category.is_empty() => Ok(DiscoveryStrategy::Category(category.to_string())),
+            ("resolving", Some(days)) => match days.parse::<u32>() {
+                Ok(days) if days > 0 => Ok(DiscoveryStrategy::ResolvingWithin(days)),
+                _ => anyhow::bail!("Invalid days in discovery strategy {} (expected resolving:<days>)", s),
+            },
+            _ => anyhow::bail!(
+                "Unknown discovery strategy {} (expected count, notional, category:<name> or resolving:<days>)",
+                s
+            ),
+        }
+    }
+}
+
+/// A wallet found in a discovery sample, with its activity in the sample
+#[derive(Debug, Clone)]
+struct DiscoveredWallet {
+    wallet: String,
+    trades: usize,
+    notional: f64,
+    largest_trade: f64,
+}
+
 /// Scans for wallets with suspicious trading patterns
 pub struct WalletScanner {
     client: PolymarketClient,
     analyzer: WalletAnalyzer,
+    criteria: InsiderCriteria,
+    signals: Option<SignalLog>,
+    alerts: Option<AlertDispatcher>,
+    snapshots: Option<OrderBookSnapshotter>,
+    market_cache: Option<ResolvedMarketCache>,
+    trade_history: Option<TradeHistoryCache>,
+    state_store: Option<Store>,
+    resolved_refresh_iterations: usize,
+    coordination: Option<CoordinationConfig>,
+    knowledge_store: Option<Store>,
+    schedule: Option<ScheduleConfig>,
+    discovery: DiscoveryStrategy,
 }
 
 impl WalletScanner {
@@ -14,84 +349,374 @@ impl WalletScanner {
         Self {
             client: PolymarketClient::new(),
             analyzer: WalletAnalyzer::new(),
+            criteria: InsiderCriteria::default(),
+            signals: None,
+            alerts: None,
+            snapshots: None,
+            market_cache: None,
+            trade_history: None,
+            state_store: None,
+            resolved_refresh_iterations: 5,
+            coordination: None,
+            knowledge_store: None,
+            schedule: None,
+            discovery: DiscoveryStrategy::default(),
         }
     }
 
-    /// Scans recent trades to find wallets with high activity
-    pub async fn find_active_wallets(&self, sample_size: usize, max_wallets: usize) -> Result<Vec<String>> {
-        println!("🔍 Scanning recent trades to find active wallets...");
-        println!("  Fetching {} recent trades...", sample_size);
+    /// Uses the given API client (e.g. one built from the config)
+    pub fn with_client(mut self, client: PolymarketClient) -> Self {
+        self.client = client;
+        self
+    }
 
-        let trades = self.client.fetch_recent_trades(sample_size).await?;
+    /// Uses the given thresholds and weights for the insider score
+    pub fn with_suspicion(mut self, suspicion: SuspicionConfig) -> Self {
+        self.analyzer = std::mem::take(&mut self.analyzer).with_suspicion(suspicion);
+        self
+    }
 
-        println!("✓ Fetched {} trades", trades.len());
-        println!("  Analyzing wallet activity...");
+    /// Ignores positions with less than this many dollars invested when analyzing wallets
+    pub fn with_min_position_invested(mut self, dollars: f64) -> Self {
+        self.analyzer = std::mem::take(&mut self.analyzer).with_min_position_invested(dollars);
+        self
+    }
 
-        let mut wallet_trade_count: HashMap<String, usize> = HashMap::new();
+    /// Breaks each wallet's performance into windows of this many days (0 = calendar months)
+    pub fn with_period_days(mut self, days: u32) -> Self {
+        self.analyzer = std::mem::take(&mut self.analyzer).with_period_days(days);
+        self
+    }
 
-        for trade in trades {
-            *wallet_trade_count.entry(trade.proxy_wallet).or_insert(0) += 1;
+    /// Only counts positions in markets resolved within this many days (0 = all)
+    pub fn with_resolved_within_days(mut self, days: u32) -> Self {
+        self.analyzer = std::mem::take(&mut self.analyzer).with_resolved_within_days(days);
+        self
+    }
+
+    /// Uses the given criteria to decide which wallets count as profitable
+    pub fn with_criteria(mut self, criteria: InsiderCriteria) -> Self {
+        self.criteria = criteria;
+        self
+    }
+
+    /// Records insider-entry signals for flagged wallets into the given log
+    pub fn with_signal_log(mut self, signals: SignalLog) -> Self {
+        self.signals = Some(signals);
+        self
+    }
+
+    /// Sends alerts for newly flagged wallets through the given dispatcher
+    pub fn with_alerts(mut self, alerts: AlertDispatcher) -> Self {
+        if alerts.is_enabled() {
+            self.alerts = Some(alerts);
+        }
+        self
+    }
+
+    /// Snapshots the order books of a flagged wallet's open positions
+    pub fn with_snapshots(mut self, snapshots: OrderBookSnapshotter) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Loads resolved markets through the given cache instead of always refetching them
+    pub fn with_market_cache(mut self, cache: ResolvedMarketCache) -> Self {
+        self.market_cache = Some(cache);
+        self
+    }
+
+    /// Fetches wallet trades through the given cache, so rescanning a wallet only fetches its new trades
+    pub fn with_trade_history(mut self, cache: TradeHistoryCache) -> Self {
+        self.trade_history = Some(cache);
+        self
+    }
+
+    /// Saves continuous-scan progress to the given store and resumes from it on startup
+    pub fn with_state_store(mut self, store: Store) -> Self {
+        self.state_store = Some(store);
+        self
+    }
+
+    /// Refreshes resolved markets every `iterations` continuous-scan iterations (0 = never)
+    pub fn with_resolved_refresh(mut self, iterations: usize) -> Self {
+        self.resolved_refresh_iterations = iterations;
+        self
+    }
+
+    /// Times continuous-scan iterations to resolution bursts, if the schedule is enabled
+    pub fn with_schedule(mut self, schedule: ScheduleConfig) -> Self {
+        self.schedule = Some(schedule).filter(|schedule| schedule.enabled);
+        self
+    }
+
+    /// Samples and ranks wallets to scan with the given strategy
+    pub fn with_discovery(mut self, discovery: DiscoveryStrategy) -> Self {
+        self.discovery = discovery;
+        self
+    }
+
+    /// Looks for clusters of coordinated wallets among those scanned by `scan_for_insiders`
+    pub fn with_coordination(mut self, config: CoordinationConfig) -> Self {
+        self.coordination = Some(config);
+        self
+    }
+
+    /// Keeps the wallet knowledge base in the given store up to date: each scanned wallet's
+    /// display name (reporting renames) and latest results. Blacklisted wallets are skipped
+    pub fn with_knowledge_base(mut self, store: Store) -> Self {
+        self.knowledge_store = Some(store);
+        self
+    }
+
+    /// Loads the identity history if tracking is enabled, logging (not propagating) failures
+    fn load_identities(&self) -> Option<IdentityHistory> {
+        let store = self.knowledge_store.as_ref()?;
+        match IdentityHistory::load(store) {
+            Ok(history) => Some(history),
+            Err(e) => {
+                eoutln!("Warning: Failed to load wallet identities, not tracking them: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Saves identities and scan results to the knowledge base, logging (not propagating) failures
+    fn save_knowledge(&self, identities: &Option<IdentityHistory>, summaries: Vec<(String, ScanSummary)>) {
+        let Some(store) = &self.knowledge_store else {
+            return;
+        };
+        let saved = WalletKnowledgeBase::update(store, |knowledge| {
+            if let Some(history) = identities {
+                knowledge.set_identities(history);
+            }
+            for (wallet, summary) in summaries {
+                knowledge.record_scan(&wallet, summary);
+            }
+        });
+        if let Err(e) = saved {
+            eoutln!("Warning: Failed to save wallet knowledge: {}", e);
         }
+    }
+
+    /// Drops blacklisted wallets, if the knowledge base is enabled
+    fn without_blacklisted(&self, wallet_addresses: &[String]) -> Vec<String> {
+        let blacklisted = match self.knowledge_store.as_ref().map(WalletKnowledgeBase::load) {
+            Some(Ok(knowledge)) => knowledge.blacklisted(),
+            Some(Err(e)) => {
+                eoutln!("Warning: Failed to load the wallet blacklist: {}", e);
+                HashSet::new()
+            }
+            None => HashSet::new(),
+        };
+
+        let wallets: Vec<String> = wallet_addresses
+            .iter()
+            .filter(|w| !blacklisted.contains(&w.to_lowercase()))
+            .cloned()
+            .collect();
+        if wallets.len() < wallet_addresses.len() {
+            outln!("⏭️  Skipping {} blacklisted wallet(s)", wallet_addresses.len() - wallets.len());
+        }
+        wallets
+    }
+
+    /// Loads every resolved market, via the cache if one is configured
+    async fn load_resolved_markets(&self) -> Result<Vec<Market>> {
+        match &self.market_cache {
+            Some(cache) => cache.load_or_fetch_all(&self.client).await,
+            None => self.client.fetch_resolved_markets().await,
+        }
+    }
+
+    /// Fetches a wallet's splits, merges, redemptions and rewards if `wallet_activity` is set
+    /// None when disabled or the fetch failed, so the wallet is analyzed from its trades alone
+    async fn fetch_wallet_activity(&self, wallet: &str) -> Option<Vec<WalletActivity>> {
+        if !self.criteria.wallet_activity {
+            return None;
+        }
+        self.client.fetch_wallet_activity(wallet).await.ok()
+    }
+
+    /// Analyzes a wallet's trades, with its activity feed when that was fetched
+    fn analyze(&self, trades: &[Trade], activity: Option<&[WalletActivity]>, resolved_markets: &[Market]) -> WalletPerformance {
+        match activity {
+            Some(activity) => self.analyzer.analyze_with_activity(trades, activity, resolved_markets),
+            None => self.analyzer.analyze(trades, resolved_markets),
+        }
+    }
+
+    /// Fetches a wallet's trades, via the trade history cache if one is configured
+    async fn fetch_wallet_trades(&self, wallet: &str) -> Result<Vec<Trade>> {
+        match &self.trade_history {
+            Some(cache) => cache.fetch(&self.client, wallet).await,
+            None => self.client.fetch_wallet_trades(wallet).await,
+        }
+    }
+
+    /// Logs the open positions of a flagged wallet as insider-entry signals
+    /// and snapshots their order books for later audit
+    async fn record_insider_entries(&self, wallet: &str, trades: &[Trade], resolved_markets: &[Market]) {
+        if self.signals.is_none() && self.snapshots.is_none() {
+            return;
+        }
+
+        let positions = self.analyzer.open_positions(trades, resolved_markets);
+
+        if let Some(signals) = &self.signals {
+            if let Err(e) = signals.record(&insider_entry_signals(wallet, &positions)) {
+                eoutln!("\nWarning: Failed to record signals: {}", e);
+            }
+        }
+
+        if let Some(snapshots) = &self.snapshots {
+            let mut condition_ids: Vec<String> = positions.iter().map(|p| p.condition_id.clone()).collect();
+            condition_ids.sort();
+            condition_ids.dedup();
+            snapshots
+                .capture_flagged(&condition_ids, &format!("insider_entry:{}", wallet))
+                .await;
+        }
+    }
+
+    /// Adds the taker ratio to a profitable wallet's performance (one extra fetch,
+    /// so it's only done for wallets that already passed the criteria)
+    async fn infer_liquidity_roles(&self, wallet: &str, trades: &[Trade], performance: &mut WalletPerformance) {
+        match self.client.fetch_wallet_taker_trades(wallet).await {
+            Ok(taker_trades) => self.analyzer.apply_liquidity_roles(performance, trades, &taker_trades),
+            Err(e) => eoutln!("\nWarning: Failed to fetch taker fills for {}: {}", wallet, e),
+        }
+    }
 
-        // Get top wallets by trade count
-        let mut wallet_counts: Vec<_> = wallet_trade_count.into_iter().collect();
-        wallet_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    /// Samples recent trades with the discovery strategy: across all markets, or only in the
+    /// active markets the strategy targets
+    async fn discovery_trades(&self, sample_size: usize) -> Result<Vec<Trade>> {
+        if !self.discovery.targets_markets() {
+            outln!("  Fetching {} recent trades...", sample_size);
+            return self.client.fetch_recent_trades(sample_size).await;
+        }
+
+        let now = Utc::now().timestamp();
+        let condition_ids: Vec<String> = self
+            .client
+            .fetch_all_active_markets()
+            .await?
+            .into_iter()
+            .filter(|market| self.discovery.targets(market, now))
+            .filter_map(|market| market.condition_id)
+            .collect();
+        outln!("  Fetching {} recent trades in {} markets...", sample_size, condition_ids.len());
+        if condition_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.client.fetch_market_trades(&condition_ids, sample_size).await
+    }
+
+    /// Samples recent trades to find wallets worth analyzing, ranked by the discovery strategy
+    pub async fn find_active_wallets(&self, sample_size: usize, max_wallets: usize) -> Result<Vec<String>> {
+        outln!("🔍 Scanning recent trades to find active wallets ({})...", self.discovery.label());
 
-        println!("✓ Found {} unique wallets", wallet_counts.len());
+        let trades = self.discovery_trades(sample_size).await?;
 
-        // Take top N wallets with at least 3 trades
-        let top_wallets: Vec<String> = wallet_counts
+        outln!("✓ Fetched {} trades", trades.len());
+        outln!("  Analyzing wallet activity...");
+
+        let ranked = self.discovery.rank(&trades);
+        outln!("✓ Found {} unique wallets", trades.iter().map(|t| t.proxy_wallet.as_str()).collect::<HashSet<_>>().len());
+
+        let top_wallets: Vec<String> = ranked
             .into_iter()
-            .filter(|(_, count)| *count >= 3)
             .take(max_wallets)
-            .map(|(wallet, count)| {
-                println!("  {} ({} trades)", wallet, count);
-                wallet
+            .map(|wallet| {
+                outln!(
+                    "  {} ({} trades, ${:.0} traded, largest ${:.0})",
+                    wallet.wallet, wallet.trades, wallet.notional, wallet.largest_trade
+                );
+                wallet.wallet
             })
             .collect();
 
-        println!("\n✓ Selected {} wallets for analysis\n", top_wallets.len());
+        outln!("\n✓ Selected {} wallets for analysis\n", top_wallets.len());
 
         Ok(top_wallets)
     }
 
     /// Scans multiple wallets and identifies profitable ones
-    pub async fn scan_for_insiders(&self, wallet_addresses: &[String]) -> Result<()> {
-        println!("🎯 Scanning {} wallets for profitable traders...\n", wallet_addresses.len());
+    /// Returns the profitable wallets (wallet, username, performance, score), most suspicious first
+    pub async fn scan_for_insiders(
+        &self,
+        wallet_addresses: &[String],
+    ) -> Result<Vec<(String, Option<String>, WalletPerformance, SuspicionReport)>> {
+        let wallet_addresses = &self.without_blacklisted(wallet_addresses);
+        outln!("🎯 Scanning {} wallets for profitable traders...\n", wallet_addresses.len());
 
         // Fetch all resolved markets once (to avoid re-fetching for each wallet)
-        println!("📚 Loading resolved markets database...");
+        outln!("📚 Loading resolved markets database...");
         let start = std::time::Instant::now();
-        let resolved_markets = self.client.fetch_resolved_markets().await?;
-        println!("✓ Loaded {} resolved markets in {:.1}s\n", resolved_markets.len(), start.elapsed().as_secs_f64());
+        let resolved_markets = self.load_resolved_markets().await?;
+        outln!("✓ Loaded {} resolved markets in {:.1}s\n", resolved_markets.len(), start.elapsed().as_secs_f64());
 
         let mut profitable_wallets = Vec::new();
         let wallet_count = wallet_addresses.len();
+        let mut coordination = self.coordination.clone().map(CoordinationDetector::new);
+        let mut identities = self.load_identities();
+        let mut summaries = Vec::new();
+        let mut market_makers = 0;
 
-        for (index, wallet) in wallet_addresses.iter().enumerate() {
-            print!("\r[{}/{}] Analyzing wallets...", index + 1, wallet_count);
+        // Trades are fetched for several wallets at once, within the client's shared rate budget,
+        // and analyzed one wallet at a time in scan order
+        let mut fetches = futures::stream::iter(wallet_addresses)
+            .map(|wallet| async move {
+                let (trades, activity) = tokio::join!(self.fetch_wallet_trades(wallet), self.fetch_wallet_activity(wallet));
+                (wallet, trades, activity)
+            })
+            .buffered(self.client.max_concurrent_requests().max(1))
+            .enumerate();
+
+        while let Some((index, (wallet, fetched, activity))) = fetches.next().await {
+            // Keep what was analyzed so far rather than waiting out the rest of the batch
+            if self.client.cancellation().is_cancelled() {
+                out!("\r[{}/{}] Cancelled, keeping the wallets analyzed so far", index, wallet_count);
+                break;
+            }
+            out!("\r[{}/{}] Analyzing wallets...", index + 1, wallet_count);
             std::io::Write::flush(&mut std::io::stdout()).ok();
 
-            match self.client.fetch_wallet_trades(wallet).await {
+            match fetched {
                 Ok(trades) => {
                     if trades.is_empty() {
                         continue;
                     }
+                    if let Some(detector) = &mut coordination {
+                        detector.add_wallet(wallet, &trades);
+                    }
 
                     // Extract username from trades (prefer name over pseudonym)
                     let username = trades.iter()
                         .find_map(|t| t.name.as_ref().or(t.pseudonym.as_ref()))
                         .cloned();
 
-                    let performance = self.analyzer.analyze(&trades, &resolved_markets);
+                    let mut performance = self.analyze(&trades, activity.as_deref(), &resolved_markets);
+                    if self.criteria.excludes(&performance) {
+                        market_makers += 1;
+                    }
 
                     // Filter for genuinely profitable wallets
-                    // Require: 10+ resolved positions, ROI > 10%, net profit > $50
-                    if performance.resolved_positions >= 10
-                        && performance.roi > 10.0
-                        && performance.net_profit > 50.0 {
-                        let flags = self.analyzer.is_suspicious(&performance).1;
-                        profitable_wallets.push((wallet.clone(), username, performance, flags));
+                    if self.criteria.matches(&performance) {
+                        self.infer_liquidity_roles(wallet, &trades, &mut performance).await;
+                        let mut report = self.analyzer.suspicion(&performance);
+                        track_identity(&mut identities, wallet, &trades, Some(&mut report));
+                        summaries.push((wallet.clone(), ScanSummary::new(username.as_deref(), &performance, Some(&report))));
+                        if report.flagged {
+                            self.record_insider_entries(wallet, &trades, &resolved_markets).await;
+                            if let Some(alerts) = &self.alerts {
+                                alerts.send(&Alert::insider_wallet(wallet, username.as_deref(), &performance, &report)).await;
+                            }
+                        }
+                        profitable_wallets.push((wallet.clone(), username, performance, report));
+                    } else {
+                        track_identity(&mut identities, wallet, &trades, None);
+                        summaries.push((wallet.clone(), ScanSummary::new(username.as_deref(), &performance, None)));
                     }
                 }
                 Err(_e) => {
@@ -100,218 +725,421 @@ impl WalletScanner {
             }
         }
 
-        println!(); // New line after progress indicator
+        outln!(); // New line after progress indicator
+        self.save_knowledge(&identities, summaries);
 
         // Print summary
-        println!("\n{}", "=".repeat(80));
-        println!("SCAN SUMMARY");
-        println!("{}", "=".repeat(80));
-        println!("\nScanned wallets: {}", wallet_addresses.len());
-        println!("Profitable wallets found: {}\n", profitable_wallets.len());
+        outln!("\n{}", "=".repeat(80));
+        outln!("SCAN SUMMARY");
+        outln!("{}", "=".repeat(80));
+        outln!("\nScanned wallets: {}", wallet_addresses.len());
+        if market_makers > 0 {
+            outln!("Likely market makers excluded: {}", market_makers);
+        }
+        outln!("Profitable wallets found: {}\n", profitable_wallets.len());
 
-        if !profitable_wallets.is_empty() {
-            // Sort by ROI descending
-            profitable_wallets.sort_by(|a, b| b.2.roi.partial_cmp(&a.2.roi).unwrap());
+        // Most suspicious first
+        profitable_wallets.sort_by(|a, b| b.3.score.partial_cmp(&a.3.score).unwrap());
 
-            println!("{}", "=".repeat(80));
-            println!("PROFITABLE WALLETS (SORTED BY ROI)");
-            println!("{}", "=".repeat(80));
+        if !profitable_wallets.is_empty() {
+            outln!("{}", "=".repeat(80));
+            outln!("PROFITABLE WALLETS (SORTED BY INSIDER SCORE)");
+            outln!("{}", "=".repeat(80));
 
-            for (i, (wallet, username, perf, flags)) in profitable_wallets.iter().enumerate() {
+            for (i, (wallet, username, perf, report)) in profitable_wallets.iter().enumerate() {
                 // Display wallet with username if available
                 if let Some(user) = username {
-                    println!("\n{}. {} (@{})", i + 1, wallet, user);
+                    outln!("\n{}. {} (@{})", i + 1, wallet, user);
                 } else {
-                    println!("\n{}. {}", i + 1, wallet);
+                    outln!("\n{}. {}", i + 1, wallet);
                 }
 
-                println!("   Win Rate: {:.1}% | ROI: {:.1}% | Resolved Positions: {}",
+                outln!("   Win Rate: {:.1}% | ROI: {:.1}% | Resolved Positions: {}",
                     perf.win_rate, perf.roi, perf.resolved_positions);
-                println!("   Total Invested: ${:.2} | Net Profit: ${:.2}",
+                outln!("   Total Invested: ${:.2} | Net Profit: ${:.2}",
                     perf.total_invested, perf.net_profit);
 
-                if !flags.is_empty() {
-                    println!("   ⚠️  Red Flags:");
-                    for flag in flags {
-                        println!("     • {}", flag);
-                    }
-                }
+                print_suspicion(report);
             }
 
-            println!("\n{}", "=".repeat(80));
+            outln!("\n{}", "=".repeat(80));
         }
 
-        Ok(())
+        if let Some(detector) = &coordination {
+            print_clusters(&detector.clusters());
+        }
+
+        Ok(profitable_wallets)
     }
 
     /// Continuously scans for profitable wallets, accumulating results over time
+    /// Each iteration analyzes the highest-priority unscanned wallets from the queue
     pub async fn continuous_scan(&self, sample_size: usize, max_wallets: usize) -> Result<()> {
-        let mut all_profitable_wallets = Vec::new();
-        let mut scanned_wallets: HashSet<String> = HashSet::new();
-        let mut scan_count = 0;
-
-        // Setup Ctrl+C handler
-        let (shutdown_tx, mut shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
-        tokio::spawn(async move {
-            tokio::signal::ctrl_c().await.ok();
-            shutdown_tx.send(()).ok();
-        });
+        let mut state = match &self.state_store {
+            Some(store) => ScanState::load(store)?,
+            None => ScanState::default(),
+        };
+        if state.scan_count > 0 {
+            outln!(
+                "🔁 Resuming from scan #{}: {} wallets analyzed, {} profitable\n",
+                state.scan_count,
+                state.scanned_wallets.len(),
+                state.profitable_wallets.len()
+            );
+        }
+        let mut queue = WalletQueue::new();
+
+        // Stops between iterations, or mid-iteration once the client's requests are cancelled
+        let cancel = self.client.cancellation().clone();
 
         // Load resolved markets once
-        println!("📚 Loading resolved markets database...");
+        outln!("📚 Loading resolved markets database...");
         let start = std::time::Instant::now();
-        let resolved_markets = self.client.fetch_resolved_markets().await?;
-        println!("✓ Loaded {} resolved markets in {:.1}s\n", resolved_markets.len(), start.elapsed().as_secs_f64());
+        let mut resolved_markets = self.load_resolved_markets().await?;
+        outln!("✓ Loaded {} resolved markets in {:.1}s\n", resolved_markets.len(), start.elapsed().as_secs_f64());
+        // Overlap refreshes by an hour so markets closing mid-request aren't missed
+        let mut refreshed_at = Utc::now().timestamp() - 3600;
+        let mut iterations_since_refresh = 0;
+        let mut scheduler = self.schedule.clone().map(ScanScheduler::new);
 
         loop {
-            tokio::select! {
-                _ = shutdown_rx.recv() => {
-                    println!("\n\n🛑 Stopping scan...\n");
-                    break;
+            if cancel.is_cancelled() {
+                outln!("\n\n🛑 Stopping scan...\n");
+                break;
+            }
+
+            state.scan_count += 1;
+            outln!("🔄 Scan iteration #{}", state.scan_count);
+            outln!("{}", "=".repeat(80));
+
+            // With a schedule, spend requests in the hours after resolution bursts
+            let mut burst_started = false;
+            let mut mode = None;
+            if let Some(scheduler) = &mut scheduler {
+                scheduler.refresh_calendar(&self.client).await;
+                let current = scheduler.mode();
+                burst_started = scheduler.enter(current);
+                if let ScanMode::Intensive { until, markets } = current {
+                    if burst_started {
+                        outln!("⏰ Resolution burst: {} markets, scanning intensively until {} UTC", markets, format_hour(until));
+                    }
+                }
+                mode = Some(current);
+            }
+            let max_wallets = match (&scheduler, mode) {
+                (Some(scheduler), Some(mode)) => scheduler.max_wallets(mode, max_wallets),
+                _ => max_wallets,
+            };
+
+            // Pick up markets that resolved during the run, and right as a burst starts
+            iterations_since_refresh += 1;
+            if burst_started || (self.resolved_refresh_iterations > 0 && iterations_since_refresh >= self.resolved_refresh_iterations) {
+                iterations_since_refresh = 0;
+                let started_at = Utc::now().timestamp();
+                match self.client.fetch_recently_resolved_markets(refreshed_at).await {
+                    Ok(recent) => {
+                        let added = merge_resolved_markets(&mut resolved_markets, recent);
+                        outln!("📚 Refreshed resolved markets: {} newly resolved ({} total)", added, resolved_markets.len());
+                        refreshed_at = started_at - 3600;
+                        if let Some(scheduler) = &mut scheduler {
+                            scheduler.observe_resolved(added);
+                        }
+                    }
+                    Err(e) if is_cancelled(&e) => {}
+                    Err(e) => eoutln!("Warning: Failed to refresh resolved markets: {}", e),
                 }
-                _ = async {
-                    scan_count += 1;
-                    println!("🔄 Scan iteration #{}", scan_count);
-                    println!("{}", "=".repeat(80));
-
-                    // Find active wallets
-                    match self.find_active_wallets(sample_size, max_wallets).await {
-                        Ok(wallets) => {
-                            // Filter out already-scanned wallets
-                            let new_wallets: Vec<String> = wallets
-                                .into_iter()
-                                .filter(|w| !scanned_wallets.contains(w))
-                                .collect();
-
-                            println!("✓ Found {} new wallets to analyze (skipped {} already scanned)\n",
-                                new_wallets.len(),
-                                max_wallets.saturating_sub(new_wallets.len()));
-
-                            if new_wallets.is_empty() {
-                                println!("All wallets in this batch were already scanned. Waiting for new activity...\n");
-                            } else {
-                                // Scan new wallets
-                                let new_profitable = self.scan_wallets_internal(&new_wallets, &resolved_markets).await;
-
-                                // Mark as scanned
-                                for wallet in &new_wallets {
-                                    scanned_wallets.insert(wallet.clone());
-                                }
-
-                                // Add to cumulative results
-                                if !new_profitable.is_empty() {
-                                    println!("\n✨ Found {} new profitable wallet(s) in this iteration!", new_profitable.len());
-                                    all_profitable_wallets.extend(new_profitable);
-
-                                    // Print cumulative summary
-                                    self.print_cumulative_results(&all_profitable_wallets);
-                                } else {
-                                    println!("\n No profitable wallets found in this iteration.");
-                                }
+            }
+
+            // Queue wallets from a fresh sample of recent trades, most promising first
+            outln!("🔍 Sampling recent trades...");
+            match self.discovery_trades(sample_size).await {
+                Ok(trades) => {
+                    queue.observe(&trades);
+                    let selected = queue.pop(max_wallets, &state.scanned_wallets);
+                    let new_wallets: Vec<String> = selected.iter().map(|(w, _)| w.clone()).collect();
+
+                    outln!("✓ Selected {} wallets by priority ({} still queued)",
+                        new_wallets.len(),
+                        queue.len());
+                    for (wallet, score) in &selected {
+                        outln!("  {} (priority {:.2})", wallet, score);
+                    }
+                    outln!();
+
+                    if new_wallets.is_empty() {
+                        outln!("No unscanned wallets in the queue. Waiting for new activity...\n");
+                    } else {
+                        // Scan new wallets
+                        let new_profitable = self.scan_wallets_internal(&new_wallets, &resolved_markets).await;
+
+                        // Mark as scanned; a cancelled batch only counts the wallets it reported,
+                        // so the rest are analyzed again on resume
+                        if cancel.is_cancelled() {
+                            state.scanned_wallets.extend(new_profitable.iter().map(|(wallet, ..)| wallet.clone()));
+                        } else {
+                            for wallet in &new_wallets {
+                                state.scanned_wallets.insert(wallet.clone());
                             }
+                        }
+
+                        // Add to cumulative results
+                        if !new_profitable.is_empty() {
+                            outln!("\n✨ Found {} new profitable wallet(s) in this iteration!", new_profitable.len());
+                            state.profitable_wallets.extend(new_profitable);
 
-                            println!("\n📊 Total stats:");
-                            println!("   Scans completed: {}", scan_count);
-                            println!("   Wallets analyzed: {}", scanned_wallets.len());
-                            println!("   Profitable wallets found: {}", all_profitable_wallets.len());
-                            println!("\n🔄 Starting next scan... (Press Ctrl+C to stop)\n");
+                            // Print cumulative summary
+                            self.print_cumulative_results(&state.profitable_wallets);
+                        } else {
+                            outln!("\n No profitable wallets found in this iteration.");
                         }
-                        Err(e) => {
-                            println!("❌ Error finding wallets: {}\n", e);
-                            println!("Retrying immediately...\n");
+                    }
+
+                    if let Some(store) = &self.state_store {
+                        if let Err(e) = state.save(store) {
+                            eoutln!("Warning: Failed to save scan state: {}", e);
                         }
                     }
-                } => {}
+
+                    outln!("\n📊 Total stats:");
+                    outln!("   Scans completed: {}", state.scan_count);
+                    outln!("   Wallets analyzed: {}", state.scanned_wallets.len());
+                    outln!("   Profitable wallets found: {}", state.profitable_wallets.len());
+                    outln!("\n🔄 Starting next scan... (Press Ctrl+C to stop)\n");
+                }
+                Err(e) if is_cancelled(&e) => {}
+                Err(e) => {
+                    outln!("❌ Error fetching recent trades: {}\n", e);
+                    outln!("Retrying immediately...\n");
+                }
+            }
+
+            // Between bursts, wait before spending more requests
+            if let (Some(scheduler), Some(mode)) = (&scheduler, mode) {
+                let pause = scheduler.pause(mode);
+                if !pause.is_zero() {
+                    if let ScanMode::Idle { next_burst: Some(hour) } = mode {
+                        outln!("💤 Between resolution bursts (next at {} UTC): next scan in {}m\n", format_hour(hour), pause.as_secs() / 60);
+                    } else {
+                        outln!("💤 No resolution burst scheduled: next scan in {}m\n", pause.as_secs() / 60);
+                    }
+                    tokio::select! {
+                        _ = tokio::time::sleep(pause) => {}
+                        _ = cancel.cancelled() => {}
+                    }
+                }
             }
         }
 
         // Print final results
-        if !all_profitable_wallets.is_empty() {
-            println!("\n{}", "=".repeat(80));
-            println!("FINAL RESULTS");
-            println!("{}", "=".repeat(80));
-            println!("\nTotal scans: {}", scan_count);
-            println!("Total wallets analyzed: {}", scanned_wallets.len());
-            println!("Total profitable wallets found: {}\n", all_profitable_wallets.len());
+        if !state.profitable_wallets.is_empty() {
+            outln!("\n{}", "=".repeat(80));
+            outln!("FINAL RESULTS");
+            outln!("{}", "=".repeat(80));
+            outln!("\nTotal scans: {}", state.scan_count);
+            outln!("Total wallets analyzed: {}", state.scanned_wallets.len());
+            outln!("Total profitable wallets found: {}\n", state.profitable_wallets.len());
 
-            self.print_cumulative_results(&all_profitable_wallets);
+            self.print_cumulative_results(&state.profitable_wallets);
         }
 
         Ok(())
     }
 
     /// Internal method to scan wallets and return profitable ones
-    async fn scan_wallets_internal(&self, wallet_addresses: &[String], resolved_markets: &[crate::models::Market]) -> Vec<(String, Option<String>, crate::models::WalletPerformance, Vec<String>)> {
+    async fn scan_wallets_internal(&self, wallet_addresses: &[String], resolved_markets: &[Market]) -> Vec<(String, Option<String>, WalletPerformance, SuspicionReport)> {
+        let wallet_addresses = &self.without_blacklisted(wallet_addresses);
         let mut profitable_wallets = Vec::new();
         let wallet_count = wallet_addresses.len();
+        let mut identities = self.load_identities();
+        let mut summaries = Vec::new();
 
         for (index, wallet) in wallet_addresses.iter().enumerate() {
-            print!("\r[{}/{}] Analyzing wallets...", index + 1, wallet_count);
+            // Keep what was analyzed so far rather than waiting out the rest of the batch
+            if self.client.cancellation().is_cancelled() {
+                out!("\r[{}/{}] Cancelled, keeping the wallets analyzed so far", index, wallet_count);
+                break;
+            }
+            out!("\r[{}/{}] Analyzing wallets...", index + 1, wallet_count);
             std::io::Write::flush(&mut std::io::stdout()).ok();
 
-            if let Ok(trades) = self.client.fetch_wallet_trades(wallet).await {
+            if let Ok(trades) = self.fetch_wallet_trades(wallet).await {
                 if !trades.is_empty() {
                     let username = trades.iter()
                         .find_map(|t| t.name.as_ref().or(t.pseudonym.as_ref()))
                         .cloned();
 
-                    let performance = self.analyzer.analyze(&trades, resolved_markets);
+                    let activity = self.fetch_wallet_activity(wallet).await;
+                    let mut performance = self.analyze(&trades, activity.as_deref(), resolved_markets);
 
                     // Filter for genuinely profitable wallets
-                    // Require: 10+ resolved positions, ROI > 10%, net profit > $50
-                    if performance.resolved_positions >= 10
-                        && performance.roi > 10.0
-                        && performance.net_profit > 50.0 {
-                        let flags = self.analyzer.is_suspicious(&performance).1;
-                        profitable_wallets.push((wallet.clone(), username, performance, flags));
+                    if self.criteria.matches(&performance) {
+                        self.infer_liquidity_roles(wallet, &trades, &mut performance).await;
+                        let mut report = self.analyzer.suspicion(&performance);
+                        track_identity(&mut identities, wallet, &trades, Some(&mut report));
+                        summaries.push((wallet.clone(), ScanSummary::new(username.as_deref(), &performance, Some(&report))));
+                        if report.flagged {
+                            self.record_insider_entries(wallet, &trades, resolved_markets).await;
+                            if let Some(alerts) = &self.alerts {
+                                alerts.send(&Alert::insider_wallet(wallet, username.as_deref(), &performance, &report)).await;
+                            }
+                        }
+                        profitable_wallets.push((wallet.clone(), username, performance, report));
+                    } else {
+                        track_identity(&mut identities, wallet, &trades, None);
+                        summaries.push((wallet.clone(), ScanSummary::new(username.as_deref(), &performance, None)));
                     }
                 }
             }
         }
 
-        println!(); // New line after progress indicator
+        outln!(); // New line after progress indicator
+        self.save_knowledge(&identities, summaries);
         profitable_wallets
     }
 
-    /// Prints cumulative results sorted by ROI
-    fn print_cumulative_results(&self, profitable_wallets: &[(String, Option<String>, crate::models::WalletPerformance, Vec<String>)]) {
+    /// Prints cumulative results sorted by insider score
+    fn print_cumulative_results(&self, profitable_wallets: &[(String, Option<String>, WalletPerformance, SuspicionReport)]) {
         let mut sorted = profitable_wallets.to_vec();
-        sorted.sort_by(|a, b| b.2.roi.partial_cmp(&a.2.roi).unwrap());
+        sorted.sort_by(|a, b| b.3.score.partial_cmp(&a.3.score).unwrap());
 
-        println!("\n{}", "=".repeat(80));
-        println!("PROFITABLE WALLETS (SORTED BY ROI)");
-        println!("{}", "=".repeat(80));
+        outln!("\n{}", "=".repeat(80));
+        outln!("PROFITABLE WALLETS (SORTED BY INSIDER SCORE)");
+        outln!("{}", "=".repeat(80));
 
-        for (i, (wallet, username, perf, flags)) in sorted.iter().enumerate().take(20) {
+        for (i, (wallet, username, perf, report)) in sorted.iter().enumerate().take(20) {
             if let Some(user) = username {
-                println!("\n{}. {} (@{})", i + 1, wallet, user);
+                outln!("\n{}. {} (@{})", i + 1, wallet, user);
             } else {
-                println!("\n{}. {}", i + 1, wallet);
+                outln!("\n{}. {}", i + 1, wallet);
             }
 
-            println!("   Win Rate: {:.1}% | ROI: {:.1}% | Resolved Positions: {}",
+            outln!("   Win Rate: {:.1}% | ROI: {:.1}% | Resolved Positions: {}",
                 perf.win_rate, perf.roi, perf.resolved_positions);
-            println!("   Total Invested: ${:.2} | Net Profit: ${:.2}",
+            outln!("   Total Invested: ${:.2} | Net Profit: ${:.2}",
                 perf.total_invested, perf.net_profit);
 
-            if !flags.is_empty() {
-                println!("   ⚠️  Red Flags:");
-                for flag in flags {
-                    println!("     • {}", flag);
-                }
-            }
+            print_suspicion(report);
         }
 
         if sorted.len() > 20 {
-            println!("\n... and {} more", sorted.len() - 20);
+            outln!("\n... and {} more", sorted.len() - 20);
         }
 
-        println!("\n{}", "=".repeat(80));
+        outln!("\n{}", "=".repeat(80));
     }
 }
 
+/// Records a scanned wallet's display name, printing renames; a flagged wallet that
+/// has renamed itself since it was first flagged gets that added to its red flags
+fn track_identity(
+    identities: &mut Option<IdentityHistory>,
+    wallet: &str,
+    trades: &[Trade],
+    report: Option<&mut SuspicionReport>,
+) {
+    let Some(history) = identities else {
+        return;
+    };
+    let flagged = report.as_ref().is_some_and(|r| r.flagged);
+
+    if let Some(change) = history.observe(wallet, trades, flagged) {
+        outln!("\n🪪 {} renamed: {} → {}", change.wallet, change.previous.label(), change.current.label());
+    }
+
+    if let (Some(report), Some(identity)) = (report, history.get(wallet)) {
+        let renames = identity.changes_since_flagged();
+        if report.flagged && renames > 0 {
+            report.reasons.push(format!("Changed display name {} time(s) since first flagged", renames));
+        }
+    }
+}
+
+/// Prints a wallet's insider score and, if flagged, the reasons behind it
+fn print_suspicion(report: &SuspicionReport) {
+    outln!("   Insider Score: {:.0}/100 (win rate {:.0} | ROI {:.0} | timing {:.0} | concentration {:.0} | sizing {:.0})",
+        report.score, report.win_rate, report.roi, report.timing, report.concentration, report.size_asymmetry);
+    if report.likely_bot {
+        outln!("   🤖 Likely bot: machine-regular trade cadence, win rate not scored");
+    }
+    if report.likely_market_maker {
+        outln!("   🏦 Likely market maker: small two-sided trades, profit is mostly spread capture");
+    }
+    if report.flagged {
+        outln!("   ⚠️  Red Flags:");
+        for reason in &report.reasons {
+            outln!("     • {}", reason);
+        }
+    }
+}
+
+/// Prints coordinated wallet clusters found during a scan
+fn print_clusters(clusters: &[CoordinatedCluster]) {
+    outln!("\n{}", "=".repeat(80));
+    outln!("COORDINATED WALLET CLUSTERS");
+    outln!("{}", "=".repeat(80));
+
+    if clusters.is_empty() {
+        outln!("\nNo wallets repeatedly traded the same outcomes together.");
+        return;
+    }
+
+    for (i, cluster) in clusters.iter().enumerate() {
+        outln!(
+            "\n{}. {} wallets, {} markets traded together (cohesion {:.0}%)",
+            i + 1,
+            cluster.wallets.len(),
+            cluster.markets.len(),
+            cluster.cohesion * 100.0
+        );
+        for pair in &cluster.pairs {
+            outln!(
+                "   {} ~ {}: {} markets ({:.0}% overlap), median gap {}m",
+                pair.wallet_a,
+                pair.wallet_b,
+                pair.shared_markets,
+                pair.overlap * 100.0,
+                pair.median_gap_secs / 60
+            );
+        }
+        for (condition_id, title) in cluster.markets.iter().take(5) {
+            outln!("   • {}", title.as_deref().unwrap_or(condition_id));
+        }
+        if cluster.markets.len() > 5 {
+            outln!("   ... and {} more", cluster.markets.len() - 5);
+        }
+        outln!("   Combined report: cargo run -- cluster {}", cluster.wallets.join(" "));
+    }
+
+    outln!("\n{}", "=".repeat(80));
+}
+
 impl Default for WalletScanner {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Merges freshly fetched closed markets into the resolved set, replacing stale copies
+/// Returns how many markets weren't in the set before
+fn merge_resolved_markets(markets: &mut Vec<Market>, fresh: Vec<Market>) -> usize {
+    let mut index: HashMap<String, usize> = markets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| Some((m.condition_id.clone()?, i)))
+        .collect();
+
+    let mut added = 0;
+    for market in fresh {
+        let Some(condition_id) = market.condition_id.clone() else { continue };
+        match index.get(&condition_id) {
+            Some(&i) => markets[i] = market,
+            None => {
+                index.insert(condition_id, markets.len());
+                markets.push(market);
+                added += 1;
+            }
+        }
+    }
+
+    added
+}
@@ -0,0 +1,521 @@
+use crate::client::PolymarketClient;
+use crate::knowledge::WalletKnowledgeBase;
+use crate::models::{Market, Trade, TradeCursor};
+use crate::store::Store;
+use crate::wallet_analyzer::{PositionLedger, WalletAnalyzer};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// File in the store holding each watched wallet's notification cursor
+const WATCH_CURSORS_FILE: &str = "watch_cursors.json";
+/// Append-only log of the trades `watch` reported
+const WATCH_ACTIVITY_FILE: &str = "watch_activity.jsonl";
+/// Latest trades of a tailed wallet kept as context for new ones
+const RECENT_TRADES: usize = 100;
+
+/// A wallet being followed, optionally restricted to certain market categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    pub address: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Only alert on trades in these categories (empty = all categories)
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+impl WatchlistEntry {
+    /// Whether a trade in the given market should alert for this entry
+    pub fn matches_market(&self, market: Option<&Market>) -> bool {
+        if self.categories.is_empty() {
+            return true;
+        }
+
+        // A category filter can't be satisfied without market metadata
+        let market_categories = match market {
+            Some(market) => market.categories(),
+            None => return false,
+        };
+
+        self.categories
+            .iter()
+            .any(|wanted| market_categories.iter().any(|c| c == &wanted.to_lowercase()))
+    }
+
+    /// Display name: label if set, otherwise the address
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.address)
+    }
+}
+
+/// Followed wallets, kept in the wallet knowledge base
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub entries: Vec<WatchlistEntry>,
+}
+
+impl Watchlist {
+    /// Loads the watched wallets from the knowledge base (empty if none)
+    pub fn load(store: &Store) -> Result<Self> {
+        Ok(WalletKnowledgeBase::load(store)?.watchlist())
+    }
+
+    /// Saves the watchlist to the knowledge base, leaving other wallet knowledge alone
+    pub fn save(&self, store: &Store) -> Result<()> {
+        WalletKnowledgeBase::update(store, |knowledge| knowledge.set_watchlist(self))
+    }
+
+    /// Adds a wallet, replacing any existing entry for the same address
+    pub fn add(&mut self, entry: WatchlistEntry) {
+        self.remove(&entry.address);
+        self.entries.push(entry);
+    }
+
+    /// Removes a wallet, returning whether it was present
+    pub fn remove(&mut self, address: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| !e.address.eq_ignore_ascii_case(address));
+        self.entries.len() != before
+    }
+
+    /// Prints the watchlist
+    pub fn print(&self) {
+        outln!("\n{}", "=".repeat(80));
+        outln!("WATCHLIST");
+        outln!("{}", "=".repeat(80));
+
+        if self.entries.is_empty() {
+            outln!("\nNo wallets on the watchlist.");
+            return;
+        }
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            match &entry.label {
+                Some(label) => outln!("\n{}. {} ({})", i + 1, entry.address, label),
+                None => outln!("\n{}. {}", i + 1, entry.address),
+            }
+            if entry.categories.is_empty() {
+                outln!("   Categories: all");
+            } else {
+                outln!("   Categories: {}", entry.categories.join(", "));
+            }
+        }
+    }
+}
+
+/// A new trade seen while tailing a watched wallet
+pub struct TailedTrade {
+    pub trade: Trade,
+    /// Buy into a market outcome the wallet didn't hold before
+    pub opened_position: bool,
+}
+
+/// Notification cursors for every watched wallet, keyed by lowercase address
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchCursors {
+    pub cursors: HashMap<String, TradeCursor>,
+}
+
+impl WatchCursors {
+    /// Loads the `watch` cursors from the store (empty if none saved yet)
+    pub fn load(store: &Store) -> Result<Self> {
+        Self::load_from(store, WATCH_CURSORS_FILE)
+    }
+
+    /// Saves the `watch` cursors to the store
+    pub fn save(&self, store: &Store) -> Result<()> {
+        self.save_to(store, WATCH_CURSORS_FILE)
+    }
+
+    /// Loads cursors kept in another file, for tailers other than `watch`
+    pub fn load_from(store: &Store, name: &str) -> Result<Self> {
+        Ok(store.load_json(name)?.unwrap_or_default())
+    }
+
+    /// Saves cursors to another file
+    pub fn save_to(&self, store: &Store, name: &str) -> Result<()> {
+        store.save_json(name, self)
+    }
+
+    /// Returns a wallet's cursor, if it has been watched before
+    pub fn get(&self, address: &str) -> Option<&TradeCursor> {
+        self.cursors.get(&address.to_lowercase())
+    }
+
+    /// Records a wallet's cursor
+    pub fn set(&mut self, address: &str, cursor: TradeCursor) {
+        self.cursors.insert(address.to_lowercase(), cursor);
+    }
+}
+
+/// A trade `watch` reported, kept for digests of watched-wallet activity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedTradeRecord {
+    pub reported_at: i64,
+    pub wallet: String,
+    pub condition_id: String,
+    pub side: String,
+    pub notional: f64,
+    pub opened_position: bool,
+}
+
+/// Persists the trades `watch` reports
+#[derive(Clone)]
+pub struct WatchActivityLog {
+    store: Store,
+}
+
+impl WatchActivityLog {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Appends one reported trade
+    pub fn record(&self, wallet: &str, trade: &Trade, opened_position: bool, reported_at: i64) -> Result<()> {
+        let record = WatchedTradeRecord {
+            reported_at,
+            wallet: wallet.to_lowercase(),
+            condition_id: trade.condition_id.clone(),
+            side: trade.side.clone(),
+            notional: trade.size * trade.price,
+            opened_position,
+        };
+        self.store.append_jsonl(WATCH_ACTIVITY_FILE, &[record])
+    }
+
+    /// Loads the trades reported in `[since, until)`
+    pub fn load_between(&self, since: i64, until: i64) -> Result<Vec<WatchedTradeRecord>> {
+        Ok(self
+            .store
+            .read_jsonl::<WatchedTradeRecord>(WATCH_ACTIVITY_FILE)?
+            .into_iter()
+            .filter(|r| r.reported_at >= since && r.reported_at < until)
+            .collect())
+    }
+}
+
+/// Tracks which trades of a watched wallet have already been seen
+#[derive(Default)]
+pub struct TradeTail {
+    seen: HashSet<String>,
+    /// Market outcomes the wallet has bought into
+    held: HashSet<(String, usize)>,
+    /// Newest trade notified; None until the first poll of a wallet never watched before
+    cursor: Option<TradeCursor>,
+    /// The wallet's latest trades, newest first, as context for new ones
+    recent: Vec<Trade>,
+}
+
+impl TradeTail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resumes from a persisted cursor, so trades after it are reported on the first poll
+    pub fn with_cursor(cursor: TradeCursor) -> Self {
+        Self {
+            cursor: Some(cursor),
+            ..Self::default()
+        }
+    }
+
+    /// The newest trade notified so far, for persisting
+    pub fn cursor(&self) -> Option<&TradeCursor> {
+        self.cursor.as_ref()
+    }
+
+    /// Fetches the wallet's trades since the last poll: only those after the cursor once it has
+    /// one, however many there are, and otherwise a page of its latest trades to seed from
+    pub async fn poll(&self, client: &PolymarketClient, address: &str) -> Result<Vec<Trade>> {
+        match &self.cursor {
+            Some(cursor) => client.fetch_wallet_trades_since(address, cursor).await,
+            None => client.fetch_latest_wallet_trades(address, RECENT_TRADES).await,
+        }
+    }
+
+    /// The wallet's latest trades seen so far (up to 100), newest first
+    pub fn recent(&self) -> &[Trade] {
+        &self.recent
+    }
+
+    /// Returns trades not seen on earlier polls (or before the cursor), oldest first
+    /// Without a cursor the first poll only seeds the tail, so existing history isn't reported as new
+    pub fn new_trades(&mut self, mut trades: Vec<Trade>) -> Vec<TailedTrade> {
+        trades.sort_by_key(|t| t.timestamp);
+
+        let seeding = self.cursor.is_none();
+        let mut cursor = self.cursor.take().unwrap_or_default();
+
+        let mut new_trades = Vec::new();
+        for trade in trades {
+            if !self.seen.insert(trade.key()) {
+                continue;
+            }
+            self.recent.insert(0, trade.clone());
+
+            let opened_position = trade.side == "BUY"
+                && self.held.insert((trade.condition_id.clone(), trade.outcome_index));
+
+            if seeding || !cursor.covers(&trade) {
+                cursor.advance(&trade);
+                if !seeding {
+                    new_trades.push(TailedTrade { trade, opened_position });
+                }
+            }
+        }
+        self.recent.truncate(RECENT_TRADES);
+
+        self.cursor = Some(cursor);
+        new_trades
+    }
+}
+
+/// Looks up market metadata by condition ID, caching results for the session
+pub struct MarketMetadata {
+    client: PolymarketClient,
+    markets: HashMap<String, Market>,
+}
+
+impl MarketMetadata {
+    pub fn new(client: PolymarketClient) -> Self {
+        Self {
+            client,
+            markets: HashMap::new(),
+        }
+    }
+
+    /// Makes sure metadata for all given condition IDs is loaded
+    pub async fn ensure_loaded(&mut self, condition_ids: &[String]) -> Result<()> {
+        let mut missing: Vec<String> = condition_ids
+            .iter()
+            .filter(|id| !self.markets.contains_key(*id))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing.dedup();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        for market in self.client.fetch_markets_by_condition_ids(&missing).await? {
+            if let Some(id) = market.condition_id.clone() {
+                self.markets.insert(id, market);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns cached metadata for a market
+    pub fn get(&self, condition_id: &str) -> Option<&Market> {
+        self.markets.get(condition_id)
+    }
+}
+
+/// A watched wallet's record on resolved positions in one market category
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryRecord {
+    pub category: String,
+    pub resolved_positions: usize,
+    pub wins: usize,
+    /// Percent of resolved positions won
+    pub win_rate: f64,
+    pub net_profit: f64,
+    /// Net profit as a percent of the amount invested
+    pub roi: f64,
+}
+
+/// What's needed to judge a watched wallet's trade at a glance: how big it is for the
+/// wallet, how deep the market is, and how the wallet has done in the market's category
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TradeContext {
+    pub notional: f64,
+    /// Median dollar size of the wallet's recent trades
+    pub typical_notional: Option<f64>,
+    /// Trade size relative to the typical one (1.0 = typical, 3.0 = three times)
+    pub size_ratio: Option<f64>,
+    pub market_liquidity: Option<f64>,
+    pub category: Option<String>,
+    /// None when the category is unknown or the wallet has no resolved positions in it
+    pub category_record: Option<CategoryRecord>,
+}
+
+impl TradeContext {
+    /// One line per piece of context, for console output and alert messages
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        match (self.size_ratio, self.typical_notional) {
+            (Some(ratio), Some(typical)) => {
+                lines.push(format!("Size: {:.1}x the wallet's typical trade (${:.2})", ratio, typical))
+            }
+            _ => lines.push("Size: no recent trades to compare against".to_string()),
+        }
+
+        match self.market_liquidity {
+            Some(liquidity) => lines.push(format!(
+                "Market liquidity: ${:.0} (trade is {:.1}% of it)",
+                liquidity,
+                if liquidity > 0.0 { self.notional / liquidity * 100.0 } else { 0.0 }
+            )),
+            None => lines.push("Market liquidity: unknown".to_string()),
+        }
+
+        match (&self.category, &self.category_record) {
+            (Some(_), Some(record)) => lines.push(format!(
+                "In {}: {} resolved positions, {:.1}% won, ROI {:.1}%, net ${:.2}",
+                record.category, record.resolved_positions, record.win_rate, record.roi, record.net_profit
+            )),
+            (Some(category), None) => lines.push(format!("In {}: no resolved positions yet", category)),
+            (None, _) => lines.push("Category: unknown".to_string()),
+        }
+
+        lines
+    }
+}
+
+/// Builds trade context for watched wallets, computing each wallet's per-category
+/// record from its full history the first time it's needed and caching it for the session
+pub struct TradeContextBuilder {
+    client: PolymarketClient,
+    analyzer: WalletAnalyzer,
+    /// Lowercase wallet address -> category -> record
+    records: HashMap<String, HashMap<String, CategoryRecord>>,
+}
+
+impl TradeContextBuilder {
+    pub fn new(client: PolymarketClient) -> Self {
+        Self {
+            client,
+            analyzer: WalletAnalyzer::new(),
+            records: HashMap::new(),
+        }
+    }
+
+    /// Builds the context of a trade, sized against the wallet's `recent` trades
+    pub async fn context(&mut self, trade: &Trade, recent: &[Trade], metadata: &mut MarketMetadata) -> Result<TradeContext> {
+        metadata.ensure_loaded(std::slice::from_ref(&trade.condition_id)).await?;
+        let market = metadata.get(&trade.condition_id);
+
+        let notional = trade.size * trade.price;
+        let typical_notional = typical_notional(recent);
+        let category = market.and_then(Market::primary_category);
+        let market_liquidity = market.map(Market::parsed_liquidity);
+
+        let category_record = match &category {
+            Some(category) => self
+                .category_records(&trade.proxy_wallet, metadata)
+                .await?
+                .get(category)
+                .cloned(),
+            None => None,
+        };
+
+        Ok(TradeContext {
+            notional,
+            typical_notional,
+            size_ratio: typical_notional.filter(|t| *t > 0.0).map(|t| notional / t),
+            market_liquidity,
+            category,
+            category_record,
+        })
+    }
+
+    /// A wallet's record per category, computed on first use
+    async fn category_records(&mut self, wallet: &str, metadata: &mut MarketMetadata) -> Result<&HashMap<String, CategoryRecord>> {
+        let key = wallet.to_lowercase();
+        if !self.records.contains_key(&key) {
+            let records = self.compute_records(wallet, metadata).await?;
+            self.records.insert(key.clone(), records);
+        }
+        Ok(&self.records[&key])
+    }
+
+    async fn compute_records(&self, wallet: &str, metadata: &mut MarketMetadata) -> Result<HashMap<String, CategoryRecord>> {
+        let ledger = PositionLedger::from_stream(self.client.wallet_trade_pages(wallet)).await?;
+        let condition_ids: Vec<String> = ledger.positions().into_iter().map(|p| p.condition_id).collect();
+        metadata.ensure_loaded(&condition_ids).await?;
+
+        let markets: Vec<Market> = condition_ids.iter().filter_map(|id| metadata.get(id)).cloned().collect();
+        let performance = self.analyzer.analyze_ledger(&ledger, &markets);
+
+        // Category -> (positions, wins, invested, profit)
+        let mut totals: HashMap<String, (usize, usize, f64, f64)> = HashMap::new();
+        for position in &performance.positions {
+            let Some(category) = metadata.get(&position.condition_id).and_then(Market::primary_category) else { continue };
+            let entry = totals.entry(category).or_default();
+            entry.0 += 1;
+            entry.1 += position.won as usize;
+            entry.2 += position.total_invested;
+            entry.3 += position.profit;
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|(category, (positions, wins, invested, profit))| {
+                let record = CategoryRecord {
+                    category: category.clone(),
+                    resolved_positions: positions,
+                    wins,
+                    win_rate: wins as f64 / positions as f64 * 100.0,
+                    net_profit: profit,
+                    roi: if invested > 0.0 { profit / invested * 100.0 } else { 0.0 },
+                };
+                (category, record)
+            })
+            .collect())
+    }
+}
+
+/// Median dollar size of a wallet's trades, robust to the occasional whale fill
+pub fn typical_notional(trades: &[Trade]) -> Option<f64> {
+    let mut notionals: Vec<f64> = trades.iter().map(|t| t.size * t.price).filter(|n| *n > 0.0).collect();
+    if notionals.is_empty() {
+        return None;
+    }
+    notionals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mid = notionals.len() / 2;
+    Some(if notionals.len().is_multiple_of(2) {
+        (notionals[mid - 1] + notionals[mid]) / 2.0
+    } else {
+        notionals[mid]
+    })
+}
+
+/// Filters a wallet's trades down to those its watchlist entry should alert on
+/// Market metadata is fetched at evaluation time so category filters see current data
+pub async fn alertable_trades(
+    entry: &WatchlistEntry,
+    trades: Vec<Trade>,
+    metadata: &mut MarketMetadata,
+) -> Result<Vec<Trade>> {
+    if !entry.categories.is_empty() {
+        let condition_ids: Vec<String> = trades.iter().map(|t| t.condition_id.clone()).collect();
+        metadata.ensure_loaded(&condition_ids).await?;
+    }
+
+    Ok(trades
+        .into_iter()
+        .filter(|trade| entry.matches_market(metadata.get(&trade.condition_id)))
+        .collect())
+}
+
+/// Prints a single watched-wallet trade
+pub fn print_trade(entry: &WatchlistEntry, trade: &Trade, market: Option<&Market>) {
+    let time = chrono::DateTime::from_timestamp(trade.timestamp, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let title = trade.title.as_deref().unwrap_or(&trade.condition_id);
+
+    outln!("\n[{}] {} {} {}", time, entry.display_name(), trade.side, title);
+    outln!(
+        "   {} @ ${:.3} | {:.2} shares | ${:.2}",
+        trade.outcome, trade.price, trade.size, trade.price * trade.size
+    );
+    if let Some(category) = market.and_then(|m| m.category.as_ref()) {
+        outln!("   Category: {}", category);
+    }
+}